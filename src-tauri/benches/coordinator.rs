@@ -0,0 +1,120 @@
+// Coordinator Throughput Benchmarks
+//
+// Events/sec through the coordinator thread (queue -> SessionManager -> snapshot publish)
+// with 1 to 1000 live sessions, with and without threads reading the session store the way
+// get_miners and the tray do. Run with `cargo bench --bench coordinator`.
+//
+// The events are log updates and hook pings for hook sessions, which need no process
+// lookups, so the numbers reflect the pipeline itself rather than ps/sysinfo.
+//
+
+use claude_miner::coordinator::core::start_coordinator;
+use claude_miner::session::{event_queue, queue, HookEvent, LogEvent, MonitorEvent, SessionStore, SharedSessions};
+use claude_miner::types::WorkingState;
+use criterion::{BenchmarkId, Criterion, Throughput};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+
+const SESSION_COUNTS: &[usize] = &[1, 10, 100, 1000];
+const EVENTS: usize = 2_000;
+const START_TIME: u64 = 1_700_000_000;
+
+fn session_id(index: usize) -> String {
+    format!("bench-{:08}-0000-0000-0000-000000000000", index)
+}
+
+fn hook(index: usize, evt: &str, timestamp: u64) -> MonitorEvent {
+    MonitorEvent::Hook(HookEvent { sid: session_id(index), evt: evt.to_string(), profile: None, timestamp, pid: None })
+}
+
+/// Every fourth event a hook ping, the rest log updates, round-robin over the sessions
+fn workload(sessions: usize) -> Vec<MonitorEvent> {
+    (0..EVENTS).map(|i| {
+        let index = i % sessions;
+        let timestamp = START_TIME + 1 + i as u64;
+        if i % 4 == 0 {
+            hook(index, "working", timestamp)
+        } else {
+            MonitorEvent::Log(LogEvent {
+                session_id: session_id(index),
+                pid: None,
+                timestamp,
+                state: if i % 2 == 0 { WorkingState::ActivelyWorking } else { WorkingState::Idle },
+                has_approval_pending: false,
+                file_mtime: timestamp,
+                transcript: None,
+                profile: None,
+            })
+        }
+    }).collect()
+}
+
+/// Start a coordinator with `sessions` hook sessions, run the workload through it
+/// (while `readers` threads poll the session store) and time it until the queue drains
+fn run(sessions: usize, readers: usize) -> Duration {
+    let (sender, receiver) = event_queue(queue::DEFAULT_CAPACITY);
+    let shared_sessions: SharedSessions = Arc::new(SessionStore::default());
+    let coordinator = start_coordinator(receiver, Arc::new(Mutex::new(HashMap::new())), shared_sessions.clone());
+
+    for index in 0..sessions {
+        sender.send(hook(index, "start", START_TIME)).unwrap();
+    }
+    while shared_sessions.snapshot().len() < sessions {
+        thread::yield_now();
+    }
+
+    let stop = Arc::new(AtomicBool::new(false));
+    let reader_threads: Vec<_> = (0..readers).map(|_| {
+        let shared_sessions = shared_sessions.clone();
+        let stop = stop.clone();
+        thread::spawn(move || {
+            while !stop.load(Ordering::Relaxed) {
+                criterion::black_box(shared_sessions.snapshot().len());
+            }
+        })
+    }).collect();
+
+    let events = workload(sessions);
+    let started = Instant::now();
+    for event in events {
+        sender.send(event).unwrap();
+    }
+    drop(sender);
+    coordinator.join().unwrap();
+    let elapsed = started.elapsed();
+
+    stop.store(true, Ordering::Relaxed);
+    for reader in reader_threads {
+        reader.join().unwrap();
+    }
+    elapsed
+}
+
+fn coordinator_throughput(c: &mut Criterion) {
+    for readers in [0, 2] {
+        let mut group = c.benchmark_group(format!("coordinator/{}_readers", readers));
+        group.throughput(Throughput::Elements(EVENTS as u64));
+        group.sample_size(20);
+        for &sessions in SESSION_COUNTS {
+            group.bench_with_input(BenchmarkId::from_parameter(sessions), &sessions, |b, &sessions| {
+                b.iter_custom(|iters| (0..iters).map(|_| run(sessions, readers)).sum());
+            });
+        }
+        group.finish();
+    }
+}
+
+fn main() {
+    // The coordinator persists history and achievements under the config directory:
+    // point it at a scratch directory so benches never touch the real one
+    let data_dir = tempfile::tempdir().expect("scratch directory");
+    std::env::set_var("HOME", data_dir.path());
+    std::env::set_var("XDG_CONFIG_HOME", data_dir.path());
+
+    let mut criterion = Criterion::default().configure_from_args();
+    coordinator_throughput(&mut criterion);
+    criterion.final_summary();
+}
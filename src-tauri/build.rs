@@ -1,3 +1,36 @@
+#[path = "build/typescript.rs"]
+mod typescript;
+
+use std::path::Path;
+use std::process::Command;
+use std::time::{SystemTime, UNIX_EPOCH};
+
 fn main() {
+    // Build metadata for get_app_info
+    let commit = Command::new("git")
+        .args(["rev-parse", "--short", "HEAD"])
+        .output()
+        .ok()
+        .filter(|output| output.status.success())
+        .map(|output| String::from_utf8_lossy(&output.stdout).trim().to_string())
+        .unwrap_or_else(|| "unknown".to_string());
+    let built_at = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+
+    println!("cargo:rustc-env=CLAUDEMINER_GIT_COMMIT={}", commit);
+    println!("cargo:rustc-env=CLAUDEMINER_BUILD_TIMESTAMP={}", built_at);
+    println!("cargo:rerun-if-env-changed=CLAUDEMINER_UPDATE_PUBKEY");
+    println!("cargo:rerun-if-changed=../.git/HEAD");
+    println!("cargo:rerun-if-changed=../.git/refs/heads");
+
+    // TypeScript definitions of the event payloads for the frontend
+    if let Err(e) = typescript::generate(Path::new("src"), Path::new("../src/schema.d.ts")) {
+        panic!("Failed to generate schema.d.ts: {}", e);
+    }
+    println!("cargo:rerun-if-changed=src");
+    println!("cargo:rerun-if-changed=build");
+
     tauri_build::build()
 }
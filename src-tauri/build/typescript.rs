@@ -0,0 +1,342 @@
+// TypeScript Event Definitions
+//
+// Build-time generator for ../src/schema.d.ts: parses src/event/schema.rs, walks the types
+// EventPayload references (structs, enums and aliases anywhere under src/) and writes one
+// TypeScript declaration per type. Follows the serde attributes the crate uses: rename,
+// rename_all, tag, skip, skip_serializing_if (optional field) and flatten (intersection).
+// Types it can't see (external crates) become `unknown`. A referenced name that more than one
+// file defines is an error (it can't tell which one serde writes), which fails the build.
+//
+
+use std::collections::{BTreeSet, HashMap};
+use std::fs;
+use std::path::{Path, PathBuf};
+use syn::{Attribute, Fields, GenericArgument, Item, PathArguments, Type};
+
+const ROOT_TYPE: &str = "EventPayload";
+
+/// Regenerate `out` from the crate sources in `src_dir`; the file is only touched when it changes
+pub fn generate(src_dir: &Path, out: &Path) -> Result<(), String> {
+    let mut items = HashMap::new();
+    collect_items(src_dir, &mut items)?;
+
+    let mut generator = Generator { items: &items, emitted: BTreeSet::new(), queue: vec![ROOT_TYPE.to_string()], output: Vec::new() };
+    generator.run()?;
+
+    let mut ts = String::from("// Generated by src-tauri/build.rs from src-tauri/src/event/schema.rs. Do not edit.\n");
+    for declaration in generator.output {
+        ts.push('\n');
+        ts.push_str(&declaration);
+    }
+
+    if fs::read_to_string(out).ok().as_deref() != Some(ts.as_str()) {
+        fs::write(out, ts).map_err(|e| format!("{}: {}", out.display(), e))?;
+    }
+    Ok(())
+}
+
+/// Serializable structs, enums and type aliases by name, with the files that define them
+fn collect_items(dir: &Path, items: &mut HashMap<String, Vec<(PathBuf, Item)>>) -> Result<(), String> {
+    let mut entries: Vec<_> = fs::read_dir(dir).map_err(|e| e.to_string())?
+        .filter_map(Result::ok)
+        .map(|entry| entry.path())
+        .collect();
+    entries.sort();
+
+    for path in entries {
+        if path.is_dir() {
+            collect_items(&path, items)?;
+            continue;
+        }
+        if path.extension().and_then(|e| e.to_str()) != Some("rs") {
+            continue;
+        }
+        let source = fs::read_to_string(&path).map_err(|e| e.to_string())?;
+        let file = syn::parse_file(&source).map_err(|e| format!("{}: {}", path.display(), e))?;
+        for item in file.items {
+            let name = match &item {
+                Item::Struct(s) if derives_serialize(&s.attrs) => s.ident.to_string(),
+                Item::Enum(e) if derives_serialize(&e.attrs) => e.ident.to_string(),
+                Item::Type(t) if t.generics.params.is_empty() => t.ident.to_string(),
+                _ => continue,
+            };
+            items.entry(name).or_default().push((path.clone(), item));
+        }
+    }
+    Ok(())
+}
+
+fn derives_serialize(attrs: &[Attribute]) -> bool {
+    attrs.iter()
+        .filter(|a| a.path().is_ident("derive"))
+        .any(|a| a.meta.require_list().map(|l| l.tokens.to_string().contains("Serialize")).unwrap_or(false))
+}
+
+/// The subset of #[serde(...)] the generator understands
+#[derive(Default)]
+struct Serde {
+    rename: Option<String>,
+    rename_all: Option<String>,
+    tag: Option<String>,
+    skip: bool,
+    optional: bool,
+    flatten: bool,
+}
+
+fn serde_attrs(attrs: &[Attribute]) -> Serde {
+    let mut serde = Serde::default();
+    for attr in attrs.iter().filter(|a| a.path().is_ident("serde")) {
+        let _ = attr.parse_nested_meta(|meta| {
+            let key = meta.path.get_ident().map(|i| i.to_string()).unwrap_or_default();
+            let value = match meta.value() {
+                Ok(v) => v.parse::<syn::LitStr>().ok().map(|s| s.value()),
+                Err(_) => None,
+            };
+            match key.as_str() {
+                "rename" => serde.rename = value,
+                "rename_all" => serde.rename_all = value,
+                "tag" => serde.tag = value,
+                "skip" | "skip_serializing" => serde.skip = true,
+                "skip_serializing_if" => serde.optional = true,
+                "flatten" => serde.flatten = true,
+                _ => {}
+            }
+            Ok(())
+        });
+    }
+    serde
+}
+
+fn docs(attrs: &[Attribute], indent: &str) -> String {
+    let lines: Vec<String> = attrs.iter()
+        .filter(|a| a.path().is_ident("doc"))
+        .filter_map(|a| match &a.meta {
+            syn::Meta::NameValue(nv) => match &nv.value {
+                syn::Expr::Lit(syn::ExprLit { lit: syn::Lit::Str(s), .. }) => Some(s.value().trim().to_string()),
+                _ => None,
+            },
+            _ => None,
+        })
+        .collect();
+    match lines.len() {
+        0 => String::new(),
+        1 => format!("{}/** {} */\n", indent, lines[0]),
+        _ => {
+            let body: String = lines.iter().map(|l| format!("{} * {}\n", indent, l)).collect();
+            format!("{}/**\n{}{} */\n", indent, body, indent)
+        }
+    }
+}
+
+/// Apply a serde rename_all rule to a Rust identifier
+fn rename(ident: &str, rule: Option<&str>) -> String {
+    let words = || -> Vec<String> {
+        let mut words: Vec<String> = Vec::new();
+        for part in ident.split('_').filter(|p| !p.is_empty()) {
+            let mut word = String::new();
+            for c in part.chars() {
+                if c.is_uppercase() && !word.is_empty() {
+                    words.push(std::mem::take(&mut word));
+                }
+                word.extend(c.to_lowercase());
+            }
+            words.push(word);
+        }
+        words
+    };
+    match rule {
+        Some("lowercase") => ident.to_lowercase(),
+        Some("UPPERCASE") => ident.to_uppercase(),
+        Some("snake_case") => words().join("_"),
+        Some("kebab-case") => words().join("-"),
+        Some("SCREAMING_SNAKE_CASE") => words().join("_").to_uppercase(),
+        Some("camelCase") => {
+            let words = words();
+            let mut name = words.first().cloned().unwrap_or_default();
+            for word in &words[1.min(words.len())..] {
+                let mut chars = word.chars();
+                if let Some(first) = chars.next() {
+                    name.extend(first.to_uppercase());
+                    name.push_str(chars.as_str());
+                }
+            }
+            name
+        }
+        _ => ident.to_string(),
+    }
+}
+
+struct Generator<'a> {
+    items: &'a HashMap<String, Vec<(PathBuf, Item)>>,
+    emitted: BTreeSet<String>,
+    queue: Vec<String>,
+    output: Vec<String>,
+}
+
+impl Generator<'_> {
+    fn run(&mut self) -> Result<(), String> {
+        if !self.items.contains_key(ROOT_TYPE) {
+            return Err(format!("{} not found", ROOT_TYPE));
+        }
+        while let Some(name) = self.queue.pop() {
+            if !self.emitted.insert(name.clone()) {
+                continue;
+            }
+            let item = match self.items[&name].as_slice() {
+                [(_, item)] => item,
+                definitions => {
+                    let files: Vec<String> = definitions.iter().map(|(path, _)| path.display().to_string()).collect();
+                    return Err(format!("{} is defined more than once ({})", name, files.join(", ")));
+                }
+            };
+            let declaration = match item {
+                Item::Struct(s) => self.declare_struct(s),
+                Item::Enum(e) => self.declare_enum(e),
+                Item::Type(t) => format!("{}export type {} = {};\n", docs(&t.attrs, ""), name, self.ts_type(&t.ty)),
+                _ => continue,
+            };
+            self.output.push(declaration);
+        }
+        Ok(())
+    }
+
+    fn declare_struct(&mut self, item: &syn::ItemStruct) -> String {
+        let serde = serde_attrs(&item.attrs);
+        let name = item.ident.to_string();
+        let doc = docs(&item.attrs, "");
+        match &item.fields {
+            Fields::Named(_) => {
+                let (body, flattened) = self.object_fields(&item.fields, serde.rename_all.as_deref(), "    ");
+                if flattened.is_empty() {
+                    format!("{}export interface {} {{\n{}}}\n", doc, name, body)
+                } else {
+                    format!("{}export type {} = {{\n{}}} & {};\n", doc, name, body, flattened.join(" & "))
+                }
+            }
+            Fields::Unnamed(fields) if fields.unnamed.len() == 1 => {
+                format!("{}export type {} = {};\n", doc, name, self.ts_type(&fields.unnamed[0].ty))
+            }
+            Fields::Unnamed(fields) => {
+                let types: Vec<String> = fields.unnamed.iter().map(|f| self.ts_type(&f.ty)).collect();
+                format!("{}export type {} = [{}];\n", doc, name, types.join(", "))
+            }
+            Fields::Unit => format!("{}export type {} = null;\n", doc, name),
+        }
+    }
+
+    /// Object members for named fields, plus the types of flattened fields
+    fn object_fields(&mut self, fields: &Fields, rename_all: Option<&str>, indent: &str) -> (String, Vec<String>) {
+        let mut body = String::new();
+        let mut flattened = Vec::new();
+        for field in fields.iter() {
+            let serde = serde_attrs(&field.attrs);
+            if serde.skip {
+                continue;
+            }
+            let ty = self.ts_type(&field.ty);
+            if serde.flatten {
+                flattened.push(ty);
+                continue;
+            }
+            let ident = field.ident.as_ref().map(|i| i.to_string()).unwrap_or_default();
+            let key = serde.rename.unwrap_or_else(|| rename(ident.trim_start_matches("r#"), rename_all));
+            body.push_str(&docs(&field.attrs, indent));
+            body.push_str(&format!("{}{}{}: {};\n", indent, key, if serde.optional { "?" } else { "" }, ty));
+        }
+        (body, flattened)
+    }
+
+    fn declare_enum(&mut self, item: &syn::ItemEnum) -> String {
+        let serde = serde_attrs(&item.attrs);
+        let name = item.ident.to_string();
+        let mut variants = Vec::new();
+
+        for variant in &item.variants {
+            let variant_serde = serde_attrs(&variant.attrs);
+            if variant_serde.skip {
+                continue;
+            }
+            let tag_value = variant_serde.rename
+                .unwrap_or_else(|| rename(&variant.ident.to_string(), serde.rename_all.as_deref()));
+            let doc = docs(&variant.attrs, "    ");
+
+            let ts = match (&serde.tag, &variant.fields) {
+                (None, Fields::Unit) => format!("\"{}\"", tag_value),
+                (None, Fields::Unnamed(fields)) if fields.unnamed.len() == 1 => {
+                    format!("{{ \"{}\": {} }}", tag_value, self.ts_type(&fields.unnamed[0].ty))
+                }
+                (None, fields) => {
+                    let (body, _) = self.object_fields(fields, None, "        ");
+                    format!("{{\n        \"{}\": {{\n{}        }}\n    }}", tag_value, body)
+                }
+                (Some(tag), Fields::Unit) => format!("{{ {}: \"{}\" }}", tag, tag_value),
+                (Some(tag), Fields::Unnamed(fields)) => {
+                    let inner = fields.unnamed.first().map(|f| self.ts_type(&f.ty)).unwrap_or_default();
+                    format!("({{ {}: \"{}\" }} & {})", tag, tag_value, inner)
+                }
+                (Some(tag), fields) => {
+                    let (body, flattened) = self.object_fields(fields, None, "        ");
+                    let object = format!("{{\n        {}: \"{}\";\n{}    }}", tag, tag_value, body);
+                    std::iter::once(object).chain(flattened).collect::<Vec<_>>().join(" & ")
+                }
+            };
+            variants.push(format!("{}    | {}", doc, ts));
+        }
+
+        let mut declaration = docs(&item.attrs, "");
+        if name == ROOT_TYPE {
+            declaration.push_str("/** Every payload also carries the schema version it was emitted with */\n");
+            declaration.push_str(&format!("export type {} = {{ schema_version: number }} & (\n{}\n);\n", name, variants.join("\n")));
+            declaration.push_str("\n/** Tauri event names */\nexport type EventName = EventPayload[\"event\"];\n");
+        } else {
+            declaration.push_str(&format!("export type {} =\n{};\n", name, variants.join("\n")));
+        }
+        declaration
+    }
+
+    fn ts_type(&mut self, ty: &Type) -> String {
+        match ty {
+            Type::Reference(r) => self.ts_type(&r.elem),
+            Type::Slice(s) => format!("{}[]", self.element(&s.elem)),
+            Type::Array(a) => format!("{}[]", self.element(&a.elem)),
+            Type::Tuple(t) if t.elems.is_empty() => "null".to_string(),
+            Type::Tuple(t) => {
+                let types: Vec<String> = t.elems.iter().map(|e| self.ts_type(e)).collect();
+                format!("[{}]", types.join(", "))
+            }
+            Type::Path(p) => {
+                let Some(segment) = p.path.segments.last() else { return "unknown".to_string() };
+                let args: Vec<&Type> = match &segment.arguments {
+                    PathArguments::AngleBracketed(a) => a.args.iter()
+                        .filter_map(|arg| match arg { GenericArgument::Type(t) => Some(t), _ => None })
+                        .collect(),
+                    _ => Vec::new(),
+                };
+                let name = segment.ident.to_string();
+                match (name.as_str(), args.as_slice()) {
+                    ("u8" | "u16" | "u32" | "u64" | "u128" | "usize" | "i8" | "i16" | "i32" | "i64" | "i128" | "isize" | "f32" | "f64", _) => "number".to_string(),
+                    ("String" | "str" | "char" | "PathBuf" | "Path", _) => "string".to_string(),
+                    ("bool", _) => "boolean".to_string(),
+                    ("Value", _) => "unknown".to_string(),
+                    ("Map", []) => "Record<string, unknown>".to_string(),
+                    ("Option", [inner]) => format!("{} | null", self.ts_type(inner)),
+                    ("Vec" | "VecDeque" | "HashSet" | "BTreeSet", [inner]) => format!("{}[]", self.element(inner)),
+                    ("HashMap" | "BTreeMap" | "Map", [_, value]) => format!("Record<string, {}>", self.ts_type(value)),
+                    ("Box" | "Arc" | "Rc" | "Cow", [inner]) => self.ts_type(inner),
+                    _ if self.items.contains_key(&name) => {
+                        self.queue.push(name.clone());
+                        name
+                    }
+                    _ => "unknown".to_string(),
+                }
+            }
+            _ => "unknown".to_string(),
+        }
+    }
+
+    /// Array element type, parenthesized when it's a union
+    fn element(&mut self, ty: &Type) -> String {
+        let ts = self.ts_type(ty);
+        if ts.contains(" | ") { format!("({})", ts) } else { ts }
+    }
+}
@@ -0,0 +1,233 @@
+// Achievements
+//
+// Ore mined: every working -> resting completion counts as a mined task. The total,
+// per-day totals (local dates) and the best streak of consecutive days are kept in
+// <config dir>/achievements.json. Crossing a milestone (total, this week, streak)
+// triggers a notification; get_achievements serves the numbers to the UI. Counting happens
+// on the coordinator thread, the file is written by a background saver.
+//
+
+use crate::crash::spawn_named;
+use crate::persist::JsonStore;
+use chrono::{Datelike, Duration, Local, NaiveDate};
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::sync::mpsc::{self, Sender};
+use std::sync::Mutex;
+
+const ACHIEVEMENTS_FILE: &str = "achievements.json";
+const DATE_FORMAT: &str = "%Y-%m-%d";
+const KEEP_DAYS: i64 = 400;
+const RECENT_DAYS: i64 = 30;
+
+const TOTAL_MILESTONES: &[u64] = &[1, 10, 50, 100, 250, 500, 1000, 2500, 5000, 10000];
+const WEEK_MILESTONES: &[u64] = &[25, 50, 100, 250];
+const STREAK_MILESTONES: &[u64] = &[3, 7, 14, 30, 100];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum MilestoneKind {
+    Total,
+    Week,
+    Streak,
+}
+
+/// A milestone just reached
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Milestone {
+    pub kind: MilestoneKind,
+    pub count: u64,
+}
+
+impl Milestone {
+    pub fn title(&self) -> String {
+        match self.kind {
+            MilestoneKind::Total if self.count == 1 => "⛏️ First Ore Mined!".to_string(),
+            MilestoneKind::Total => format!("⛏️ {} Tasks Mined!", self.count),
+            MilestoneKind::Week => format!("💎 {} Tasks Mined This Week", self.count),
+            MilestoneKind::Streak => format!("🔥 {}-Day Mining Streak", self.count),
+        }
+    }
+
+    pub fn body(&self) -> String {
+        match self.kind {
+            MilestoneKind::Total if self.count == 1 => "Your miners completed their first task.".to_string(),
+            MilestoneKind::Total => format!("Your miners have completed {} tasks in total.", self.count),
+            MilestoneKind::Week => format!("{} tasks completed since Monday. The mine is busy!", self.count),
+            MilestoneKind::Streak => format!("Tasks completed {} days in a row.", self.count),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct DailyTotal {
+    pub date: String,  // YYYY-MM-DD (local)
+    pub tasks: u64,
+}
+
+/// Payload of get_achievements
+#[derive(Debug, Clone, Serialize)]
+pub struct Achievements {
+    pub total_tasks: u64,
+    pub today: u64,
+    pub this_week: u64,  // Since Monday
+    pub current_streak: u32,
+    pub best_streak: u32,
+    pub recent_days: Vec<DailyTotal>,  // Last 30 days, oldest first
+}
+
+/// Persisted counters
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+struct Tally {
+    total: u64,
+    daily: BTreeMap<String, u64>,  // Date -> tasks (ISO dates sort chronologically)
+    best_streak: u32,
+}
+
+impl Tally {
+    fn count_on(&self, date: NaiveDate) -> u64 {
+        self.daily.get(&date.format(DATE_FORMAT).to_string()).copied().unwrap_or(0)
+    }
+
+    /// Consecutive days with tasks, ending today (or yesterday if nothing was mined yet today)
+    fn streak(&self, today: NaiveDate) -> u32 {
+        let mut day = if self.count_on(today) > 0 { today } else { today - Duration::days(1) };
+        let mut streak = 0;
+        while self.count_on(day) > 0 {
+            streak += 1;
+            day -= Duration::days(1);
+        }
+        streak
+    }
+
+    fn week_total(&self, today: NaiveDate) -> u64 {
+        let since_monday = today.weekday().num_days_from_monday() as i64;
+        (0..=since_monday).map(|days| self.count_on(today - Duration::days(days))).sum()
+    }
+
+    /// Count a completed task; returns the milestones it reached
+    fn record(&mut self, today: NaiveDate) -> Vec<Milestone> {
+        self.total += 1;
+        *self.daily.entry(today.format(DATE_FORMAT).to_string()).or_default() += 1;
+
+        let cutoff = (today - Duration::days(KEEP_DAYS)).format(DATE_FORMAT).to_string();
+        self.daily.retain(|date, _| *date >= cutoff);
+
+        let mut reached = Vec::new();
+        if TOTAL_MILESTONES.contains(&self.total) {
+            reached.push(Milestone { kind: MilestoneKind::Total, count: self.total });
+        }
+        let week = self.week_total(today);
+        if WEEK_MILESTONES.contains(&week) {
+            reached.push(Milestone { kind: MilestoneKind::Week, count: week });
+        }
+        // Streaks grow with the first task of a day
+        if self.count_on(today) == 1 {
+            let streak = self.streak(today);
+            self.best_streak = self.best_streak.max(streak);
+            if STREAK_MILESTONES.contains(&(streak as u64)) {
+                reached.push(Milestone { kind: MilestoneKind::Streak, count: streak as u64 });
+            }
+        }
+        reached
+    }
+
+    fn report(&self, today: NaiveDate) -> Achievements {
+        Achievements {
+            total_tasks: self.total,
+            today: self.count_on(today),
+            this_week: self.week_total(today),
+            current_streak: self.streak(today),
+            best_streak: self.best_streak,
+            recent_days: (0..RECENT_DAYS).rev()
+                .map(|days| today - Duration::days(days))
+                .map(|date| DailyTotal { date: date.format(DATE_FORMAT).to_string(), tasks: self.count_on(date) })
+                .collect(),
+        }
+    }
+}
+
+static TALLY: Lazy<Mutex<Tally>> = Lazy::new(|| Mutex::new(store().load().unwrap_or_default()));
+
+fn store() -> JsonStore<Tally> {
+    JsonStore::new("Achievements", ACHIEVEMENTS_FILE)
+}
+
+/// Copies of the tally to write (saver thread started on first use)
+static SAVER: Lazy<Mutex<Sender<Tally>>> = Lazy::new(|| {
+    let (sender, receiver) = mpsc::channel::<Tally>();
+    spawn_named("achievements-saver", move || {
+        while let Ok(mut tally) = receiver.recv() {
+            // Only the newest of the queued copies is worth writing
+            while let Ok(newer) = receiver.try_recv() {
+                tally = newer;
+            }
+            store().save_or_log(&tally);
+        }
+    });
+    Mutex::new(sender)
+});
+
+fn save(tally: &Tally) {
+    let _ = SAVER.lock().unwrap().send(tally.clone());
+}
+
+/// Count a completed task (working -> resting); returns the milestones reached
+pub fn record_task_completion() -> Vec<Milestone> {
+    let mut tally = TALLY.lock().unwrap();
+    let reached = tally.record(Local::now().date_naive());
+    save(&tally);
+    for milestone in &reached {
+        println!("[Achievements] 🏆 {}", milestone.title());
+    }
+    reached
+}
+
+/// Totals, streaks and the last 30 days
+pub fn get() -> Achievements {
+    TALLY.lock().unwrap().report(Local::now().date_naive())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(s: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(s, DATE_FORMAT).unwrap()
+    }
+
+    #[test]
+    fn test_totals_and_milestones() {
+        let mut tally = Tally::default();
+        // 2024-01-01 is a Monday
+        assert_eq!(tally.record(date("2024-01-01")), vec![Milestone { kind: MilestoneKind::Total, count: 1 }]);
+        for _ in 0..8 {
+            tally.record(date("2024-01-01"));
+        }
+        assert_eq!(tally.record(date("2024-01-02")), vec![Milestone { kind: MilestoneKind::Total, count: 10 }]);
+
+        // Third day in a row: streak milestone
+        let reached = tally.record(date("2024-01-03"));
+        assert!(reached.contains(&Milestone { kind: MilestoneKind::Streak, count: 3 }));
+
+        for _ in 0..13 {
+            tally.record(date("2024-01-03"));
+        }
+        let reached = tally.record(date("2024-01-03"));
+        assert!(reached.contains(&Milestone { kind: MilestoneKind::Week, count: 25 }));
+
+        let report = tally.report(date("2024-01-04"));
+        assert_eq!(report.total_tasks, 25);
+        assert_eq!(report.today, 0);
+        assert_eq!(report.this_week, 25);
+        assert_eq!(report.current_streak, 3);
+        assert_eq!(report.best_streak, 3);
+        assert_eq!(report.recent_days.len(), RECENT_DAYS as usize);
+
+        // A new week starts from zero; a missed day breaks the streak
+        assert_eq!(tally.report(date("2024-01-08")).this_week, 0);
+        assert_eq!(tally.report(date("2024-01-06")).current_streak, 0);
+    }
+}
@@ -0,0 +1,81 @@
+// App Info
+//
+// Build metadata (version, git commit, build date, platform, cargo features) for the
+// About screen and for downstream consumers of webhooks, which get `app_version`
+// in every payload so they can check compatibility.
+// The commit and build time are captured by build.rs.
+//
+
+use crate::event::schema::EVENT_SCHEMA_VERSION;
+use chrono::{TimeZone, Utc};
+use serde::Serialize;
+
+pub const VERSION: &str = env!("CARGO_PKG_VERSION");
+const GIT_COMMIT: &str = env!("CLAUDEMINER_GIT_COMMIT");
+const BUILD_TIMESTAMP: &str = env!("CLAUDEMINER_BUILD_TIMESTAMP");
+
+#[derive(Debug, Clone, Serialize)]
+pub struct AppInfo {
+    pub name: &'static str,
+    pub version: &'static str,
+    pub git_commit: &'static str,
+    pub build_date: String,  // UTC, RFC 3339
+    pub os: &'static str,
+    pub arch: &'static str,
+    pub debug: bool,
+    pub features: Vec<&'static str>,
+    pub event_schema_version: u32,
+}
+
+pub fn get() -> AppInfo {
+    AppInfo {
+        name: env!("CARGO_PKG_NAME"),
+        version: VERSION,
+        git_commit: GIT_COMMIT,
+        build_date: build_date(BUILD_TIMESTAMP),
+        os: std::env::consts::OS,
+        arch: std::env::consts::ARCH,
+        debug: cfg!(debug_assertions),
+        features: enabled_features(),
+        event_schema_version: EVENT_SCHEMA_VERSION,
+    }
+}
+
+fn build_date(timestamp: &str) -> String {
+    timestamp.parse::<i64>().ok()
+        .and_then(|secs| Utc.timestamp_opt(secs, 0).single())
+        .map(|date| date.to_rfc3339())
+        .unwrap_or_else(|| "unknown".to_string())
+}
+
+/// Cargo features this binary was built with
+fn enabled_features() -> Vec<&'static str> {
+    let mut features = Vec::new();
+    if cfg!(feature = "custom-protocol") {
+        features.push("custom-protocol");
+    }
+    features
+}
+
+/// Add `app_version` to a JSON object payload (other values are left alone)
+pub fn tag_payload(mut payload: serde_json::Value) -> serde_json::Value {
+    if let Some(object) = payload.as_object_mut() {
+        object.entry("app_version").or_insert_with(|| VERSION.into());
+    }
+    payload
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_build_date_and_payload_tag() {
+        assert_eq!(build_date("0"), "1970-01-01T00:00:00+00:00");
+        assert_eq!(build_date(""), "unknown");
+
+        let payload = tag_payload(serde_json::json!({"pid": 42}));
+        assert_eq!(payload["app_version"], VERSION);
+        assert_eq!(tag_payload(serde_json::json!([1])), serde_json::json!([1]));
+    }
+}
@@ -0,0 +1,80 @@
+// Automation Actions
+//
+// Side effects run by rules: kill, notify, shell command, webhook.
+// Shell commands go through the script worker and webhooks run on their own thread,
+// so the coordinator never blocks.
+//
+
+use crate::app_info;
+use crate::automation::scripts;
+use crate::notification;
+use crate::session::{current_timestamp, SessionState};
+use crate::status::zombies::{self, ZombieResolution};
+use std::process::Command;
+use std::thread;
+use sysinfo::{Pid, System};
+
+/// Environment passed to shell commands and the webhook payload
+pub fn session_env(session: &SessionState) -> Vec<(&'static str, String)> {
+    vec![
+        ("CLAUDEMINER_SESSION_ID", session.session_id.clone()),
+        ("CLAUDEMINER_PID", session.pid.to_string()),
+        ("CLAUDEMINER_STATUS", session.current_status.to_string()),
+        ("CLAUDEMINER_PROJECT", session.project_path.clone().unwrap_or_default()),
+        ("CLAUDEMINER_STARTED_AT", session.started_at.to_string()),
+        ("CLAUDEMINER_STATUS_SINCE", session.status_since.to_string()),
+        ("CLAUDEMINER_WORKING_SECONDS", session.total_working_seconds.to_string()),
+    ]
+}
+
+/// Kill a session's process (foreign and container processes are left alone)
+pub fn kill(session: &SessionState) -> Result<(), String> {
+    if session.pid == 0 || session.container.is_some() {
+        return Err("no host process".to_string());
+    }
+    if crate::monitor::user::is_foreign_uid(crate::monitor::user::process_uid(session.pid)) {
+        return Err(format!("process {} belongs to another user", session.pid));
+    }
+
+    let mut sys = System::new();
+    let pid = Pid::from_u32(session.pid);
+    sys.refresh_process(pid);
+    match sys.process(pid) {
+        Some(process) if process.kill() => {
+            zombies::record_killed(session.pid, ZombieResolution::AutoKilled, current_timestamp());
+            Ok(())
+        }
+        Some(_) => Err(format!("failed to kill process {}", session.pid)),
+        None => Err(format!("process {} not found", session.pid)),
+    }
+}
+
+/// Show a system notification
+pub fn notify(title: &str, body: &str) {
+    notification::send_custom_notification(title, body);
+}
+
+/// Run a shell command in the background with the session environment
+pub fn run_shell(label: String, command: String, session: &SessionState) {
+    scripts::enqueue(label, command, session_env(session), session.project_path.clone());
+}
+
+/// POST a JSON payload with curl in the background (tagged with `app_version`)
+pub fn post_webhook(label: String, url: String, payload: serde_json::Value) {
+    let payload = app_info::tag_payload(payload);
+    thread::spawn(move || {
+        let output = Command::new("curl")
+            .args(["-sS", "-m", "10", "-X", "POST", "-H", "Content-Type: application/json"])
+            .arg("-d")
+            .arg(payload.to_string())
+            .arg(&url)
+            .output();
+
+        match output {
+            Ok(result) if result.status.success() => println!("[Automation] {} posted to {}", label, url),
+            Ok(result) => println!("[Automation] ⚠️ {} webhook failed: {}", label,
+                String::from_utf8_lossy(&result.stderr).trim()),
+            Err(e) => println!("[Automation] ⚠️ {} failed to run curl: {}", label, e),
+        }
+    });
+}
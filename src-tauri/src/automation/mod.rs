@@ -0,0 +1,9 @@
+// Automation Module
+//
+// User-configured reactions to session state (config.json "rules" and "scripts")
+
+pub mod actions;
+pub mod rules;
+pub mod scripts;
+
+pub use rules::RuleEngine;
@@ -0,0 +1,152 @@
+// Rule Engine
+//
+// Evaluates config.json "rules" against every session after each coordinator event
+// (so status changes are seen immediately and "for N seconds" conditions mature
+// on the next event). A rule fires once per session and re-arms when its condition
+// stops holding.
+//
+
+use crate::automation::actions;
+use crate::config::{self, RuleAction, RuleCondition, RuleConfig};
+use crate::session::SessionState;
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+
+/// (rule name, session ID)
+type RuleKey = (String, String);
+
+#[derive(Default)]
+pub struct RuleEngine {
+    matching_since: HashMap<RuleKey, u64>,  // When the condition started holding
+    fired: HashSet<RuleKey>,                // Rules already run for the current match
+}
+
+impl RuleEngine {
+    /// Evaluate all rules against all sessions
+    pub fn evaluate(&mut self, sessions: &HashMap<String, SessionState>, now: u64) {
+        let rules = config::get().rules;
+        if rules.is_empty() {
+            self.matching_since.clear();
+            self.fired.clear();
+            return;
+        }
+
+        for rule in rules.iter() {
+            for (session_id, session) in sessions.iter() {
+                let key = (rule.name.clone(), session_id.clone());
+
+                if session.ignored || !matches(&rule.when, session) {
+                    self.matching_since.remove(&key);
+                    self.fired.remove(&key);
+                    continue;
+                }
+
+                let since = *self.matching_since.entry(key.clone()).or_insert(now);
+                if now.saturating_sub(since) >= rule.when.for_secs && self.fired.insert(key) {
+                    run(rule, session);
+                }
+            }
+        }
+
+        self.matching_since.retain(|(_, id), _| sessions.contains_key(id));
+        self.fired.retain(|(_, id)| sessions.contains_key(id));
+    }
+}
+
+/// Check the instantaneous conditions of a rule (duration is tracked by the engine)
+pub fn matches(condition: &RuleCondition, session: &SessionState) -> bool {
+    if let Some(status) = &condition.status {
+        if session.current_status.as_str() != status {
+            return false;
+        }
+    }
+
+    if let Some(threshold) = condition.cpu_above {
+        match &session.last_cpu_event {
+            Some(cpu) if cpu.cpu_percent > threshold => {}
+            _ => return false,
+        }
+    }
+
+    if let Some(project) = &condition.project {
+        let Some(path) = session.project_path.as_deref() else {
+            return false;
+        };
+        let name = Path::new(path).file_name().and_then(|n| n.to_str());
+        if path != project && name != Some(project.as_str()) {
+            return false;
+        }
+    }
+
+    true
+}
+
+fn run(rule: &RuleConfig, session: &SessionState) {
+    let label = format!("Rule '{}'", rule.name);
+    println!("[RuleEngine] ⚡ {} matched session {} (PID: {})",
+        label, &session.session_id[..8.min(session.session_id.len())], session.pid);
+
+    match &rule.action {
+        RuleAction::Kill => {
+            if let Err(e) = actions::kill(session) {
+                println!("[RuleEngine] ⚠️ {} could not kill PID {}: {}", label, session.pid, e);
+            }
+        }
+        RuleAction::Notify { message } => {
+            let body = message.clone()
+                .unwrap_or_else(|| format!("Claude #{} is {}", session.pid, session.current_status));
+            actions::notify(&rule.name, &body);
+        }
+        RuleAction::Shell { command } => {
+            actions::run_shell(label, command.clone(), session);
+        }
+        RuleAction::Webhook { url } => {
+            let payload = serde_json::json!({
+                "rule": rule.name,
+                "session_id": session.session_id,
+                "pid": session.pid,
+                "status": session.current_status,
+                "project": session.project_path,
+                "started_at": session.started_at,
+                "status_since": session.status_since,
+                "total_working_seconds": session.total_working_seconds,
+            });
+            actions::post_webhook(label, url.clone(), payload);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::session::CpuEvent;
+    use crate::status::machine;
+
+    fn session() -> SessionState {
+        let mut session = SessionState::new_legacy(4242, "rules-test-session".to_string());
+        session.current_status = machine::ZOMBIE;
+        session.project_path = Some("/Users/dev/claudeminer".to_string());
+        session
+    }
+
+    #[test]
+    fn test_matches_conditions() {
+        let zombie = RuleCondition { status: Some("zombie".to_string()), ..Default::default() };
+        assert!(matches(&zombie, &session()));
+        assert!(!matches(&RuleCondition { status: Some("working".to_string()), ..Default::default() }, &session()));
+
+        let project = RuleCondition { project: Some("claudeminer".to_string()), ..zombie.clone() };
+        assert!(matches(&project, &session()));
+        let full_path = RuleCondition { project: Some("/Users/dev/claudeminer".to_string()), ..Default::default() };
+        assert!(matches(&full_path, &session()));
+        assert!(!matches(&RuleCondition { project: Some("other".to_string()), ..Default::default() }, &session()));
+
+        let hot = RuleCondition { cpu_above: Some(90.0), ..Default::default() };
+        assert!(!matches(&hot, &session()));
+        let mut busy = session();
+        busy.last_cpu_event = Some(CpuEvent {
+            pid: 4242, timestamp: 0, cpu_percent: 95.0, kind: "claude".to_string(), uid: None, container: None,
+        });
+        assert!(matches(&hot, &busy));
+    }
+}
@@ -0,0 +1,269 @@
+// Script Hooks
+//
+// Runs user commands (config.json "scripts") when a session is created, starts working,
+// goes to rest or terminates. Up to WORKERS commands run at once; one still running after
+// the timeout is killed with everything it spawned, so a hung script only holds up its own
+// worker. Commands run with the user's full permissions: the environment is merely trimmed
+// to the session variables plus a few basics such as PATH/HOME, and stdin is closed.
+// Their output goes to the app log.
+//
+
+use crate::automation::actions;
+use crate::config;
+use crate::crash::spawn_named;
+use crate::session::SessionState;
+use crate::status::machine;
+use crate::types::SessionStatus;
+use once_cell::sync::Lazy;
+use std::collections::HashMap;
+use std::io::Read;
+use std::path::Path;
+use std::process::{Command, Stdio};
+use std::sync::mpsc::{self, Sender};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// Variables inherited from the app environment (everything else is dropped)
+const INHERITED_ENV: &[&str] = &["PATH", "HOME", "USER", "LOGNAME", "SHELL", "LANG", "TMPDIR",
+    "SYSTEMROOT", "USERPROFILE", "TEMP", "COMSPEC"];
+const POLL_INTERVAL: Duration = Duration::from_millis(100);
+const WORKERS: usize = 4;
+
+/// Session lifecycle events scripts can be attached to
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ScriptEvent {
+    Created,
+    Working,
+    Resting,
+    Terminated,
+}
+
+impl ScriptEvent {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ScriptEvent::Created => "created",
+            ScriptEvent::Working => "working",
+            ScriptEvent::Resting => "resting",
+            ScriptEvent::Terminated => "terminated",
+        }
+    }
+}
+
+struct Job {
+    label: String,
+    command: String,
+    env: Vec<(&'static str, String)>,
+    cwd: Option<String>,
+    timeout: Duration,
+}
+
+/// Job queue shared by the workers (started on first use)
+static WORKER: Lazy<Mutex<Sender<Job>>> = Lazy::new(|| {
+    let (sender, receiver) = mpsc::channel::<Job>();
+    let receiver = Arc::new(Mutex::new(receiver));
+    for index in 0..WORKERS {
+        let receiver = receiver.clone();
+        spawn_named(&format!("script-worker-{}", index), move || loop {
+            // The lock is only held while waiting for the next job
+            let job = receiver.lock().unwrap().recv();
+            match job {
+                Ok(job) => run_job(job),
+                Err(_) => break,
+            }
+        });
+    }
+    println!("[ScriptHooks] {} workers started", WORKERS);
+    Mutex::new(sender)
+});
+
+/// Last status scripts ran for, per session (status-changed events repeat the same status)
+static LAST_STATUS: Lazy<Mutex<HashMap<String, SessionStatus>>> = Lazy::new(|| Mutex::new(HashMap::new()));
+
+/// Run the scripts registered for a session-created event
+pub fn on_session_created(session: &SessionState) {
+    LAST_STATUS.lock().unwrap().insert(session.session_id.clone(), session.current_status);
+    run_for_event(ScriptEvent::Created, session);
+}
+
+/// Run the working/resting scripts when the status actually changed
+pub fn on_status_changed(session: &SessionState) {
+    let previous = LAST_STATUS.lock().unwrap().insert(session.session_id.clone(), session.current_status);
+    if previous == Some(session.current_status) {
+        return;
+    }
+
+    match session.current_status {
+        machine::WORKING => run_for_event(ScriptEvent::Working, session),
+        machine::RESTING => run_for_event(ScriptEvent::Resting, session),
+        _ => {}
+    }
+}
+
+/// Run the scripts registered for a session-terminated event
+pub fn on_session_terminated(session: &SessionState) {
+    LAST_STATUS.lock().unwrap().remove(&session.session_id);
+    run_for_event(ScriptEvent::Terminated, session);
+}
+
+fn run_for_event(event: ScriptEvent, session: &SessionState) {
+    let scripts = config::get().scripts;
+    let commands = match event {
+        ScriptEvent::Created => scripts.on_created,
+        ScriptEvent::Working => scripts.on_working,
+        ScriptEvent::Resting => scripts.on_resting,
+        ScriptEvent::Terminated => scripts.on_terminated,
+    };
+
+    for (index, command) in commands.into_iter().enumerate() {
+        let mut env = actions::session_env(session);
+        env.push(("CLAUDEMINER_EVENT", event.as_str().to_string()));
+        enqueue(format!("on_{}[{}]", event.as_str(), index), command, env, session.project_path.clone());
+    }
+}
+
+/// Queue a command for the workers with the configured timeout
+pub fn enqueue(label: String, command: String, env: Vec<(&'static str, String)>, cwd: Option<String>) {
+    let timeout = Duration::from_secs(config::get().scripts.timeout_secs.max(1));
+    let job = Job { label, command, env, cwd, timeout };
+    if WORKER.lock().unwrap().send(job).is_err() {
+        eprintln!("[ScriptHooks] ⚠️ Worker is gone, dropping command");
+    }
+}
+
+fn run_job(job: Job) {
+    let mut cmd = shell_command(&job.command);
+    // Own process group, so a timeout also takes whatever the script left running
+    #[cfg(unix)]
+    std::os::unix::process::CommandExt::process_group(&mut cmd, 0);
+    cmd.env_clear()
+        .envs(INHERITED_ENV.iter().filter_map(|key| std::env::var(key).ok().map(|value| (*key, value))))
+        .envs(job.env)
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped());
+    if let Some(cwd) = job.cwd.filter(|dir| Path::new(dir).is_dir()) {
+        cmd.current_dir(cwd);
+    } else if let Some(home) = dirs::home_dir() {
+        cmd.current_dir(home);
+    }
+
+    let mut child = match cmd.spawn() {
+        Ok(child) => child,
+        Err(e) => {
+            println!("[ScriptHooks] ⚠️ {} failed to start: {}", job.label, e);
+            return;
+        }
+    };
+
+    // Drain pipes on their own threads so a chatty script can't block on a full pipe
+    let stdout = child.stdout.take().map(read_to_string_thread);
+    let stderr = child.stderr.take().map(read_to_string_thread);
+
+    let started = Instant::now();
+    let status = loop {
+        match child.try_wait() {
+            Ok(Some(status)) => break Some(status),
+            Ok(None) if started.elapsed() >= job.timeout => {
+                if let Err(e) = crate::kill::kill_tree(child.id()) {
+                    println!("[ScriptHooks] ⚠️ Failed to kill {}: {}", job.label, e);
+                }
+                let _ = child.kill();
+                let _ = child.wait();
+                println!("[ScriptHooks] ⏱️ {} timed out after {}s - killed", job.label, job.timeout.as_secs());
+                break None;
+            }
+            Ok(None) => thread::sleep(POLL_INTERVAL),
+            Err(e) => {
+                println!("[ScriptHooks] ⚠️ {} wait failed: {}", job.label, e);
+                break None;
+            }
+        }
+    };
+
+    // After a timeout, grandchildren may still hold the pipes open: don't wait for them
+    let Some(status) = status else { return };
+    println!("[ScriptHooks] {} exited with {} ({}ms)", job.label, status, started.elapsed().as_millis());
+    for (stream, handle) in [("stdout", stdout), ("stderr", stderr)] {
+        let output = handle.and_then(|h| h.join().ok()).unwrap_or_default();
+        if !output.trim().is_empty() {
+            println!("[ScriptHooks] {} {}: {}", job.label, stream, output.trim());
+        }
+    }
+}
+
+fn read_to_string_thread<R: Read + Send + 'static>(mut reader: R) -> thread::JoinHandle<String> {
+    thread::spawn(move || {
+        let mut bytes = Vec::new();
+        let _ = reader.read_to_end(&mut bytes);
+        String::from_utf8_lossy(&bytes).into_owned()
+    })
+}
+
+#[cfg(not(target_os = "windows"))]
+fn shell_command(command: &str) -> Command {
+    let mut cmd = Command::new("sh");
+    cmd.arg("-c").arg(command);
+    cmd
+}
+
+#[cfg(target_os = "windows")]
+fn shell_command(command: &str) -> Command {
+    let mut cmd = Command::new("cmd");
+    cmd.arg("/C").arg(command);
+    cmd
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_run_job_times_out() {
+        let started = Instant::now();
+        run_job(Job {
+            label: "test".to_string(),
+            command: "sleep 5".to_string(),
+            env: Vec::new(),
+            cwd: None,
+            timeout: Duration::from_millis(300),
+        });
+        assert!(started.elapsed() < Duration::from_secs(3));
+    }
+
+    #[test]
+    fn test_timeout_kills_background_children() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let marker = dir.path().join("survived");
+        run_job(Job {
+            label: "test".to_string(),
+            command: format!("(sleep 1; touch '{}') & sleep 5", marker.display()),
+            env: Vec::new(),
+            cwd: None,
+            timeout: Duration::from_millis(300),
+        });
+        thread::sleep(Duration::from_millis(1500));
+        assert!(!marker.exists());
+    }
+
+    #[test]
+    fn test_hung_script_does_not_block_others() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let marker = dir.path().join("ran");
+        let job = |command: String| Job {
+            label: "test".to_string(),
+            command,
+            env: Vec::new(),
+            cwd: None,
+            timeout: Duration::from_secs(3),
+        };
+        WORKER.lock().unwrap().send(job("sleep 10".to_string())).unwrap();
+        WORKER.lock().unwrap().send(job(format!("touch '{}'", marker.display()))).unwrap();
+
+        let deadline = Instant::now() + Duration::from_secs(2);
+        while !marker.exists() && Instant::now() < deadline {
+            thread::sleep(POLL_INTERVAL);
+        }
+        assert!(marker.exists());
+    }
+}
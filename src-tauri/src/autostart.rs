@@ -0,0 +1,154 @@
+// Autostart
+//
+// Start ClaudeMiner at login so monitoring resumes after a reboot:
+// - macOS:   LaunchAgent (~/Library/LaunchAgents/com.claudeminer.app.plist)
+// - Windows: HKCU\Software\Microsoft\Windows\CurrentVersion\Run value
+// - Linux:   XDG autostart entry (~/.config/autostart/claudeminer.desktop)
+// The OS registration is the source of truth; nothing is kept in config.json.
+//
+
+use std::path::PathBuf;
+
+#[cfg(target_os = "macos")]
+const LAUNCH_AGENT_LABEL: &str = "com.claudeminer.app";
+
+#[cfg(target_os = "windows")]
+const RUN_KEY: &str = r"HKCU\Software\Microsoft\Windows\CurrentVersion\Run";
+#[cfg(target_os = "windows")]
+const RUN_VALUE: &str = "ClaudeMiner";
+
+fn current_exe() -> Result<PathBuf, String> {
+    std::env::current_exe().map_err(|e| format!("Failed to locate executable: {}", e))
+}
+
+/// Is ClaudeMiner registered to start at login?
+#[cfg(any(target_os = "macos", target_os = "linux"))]
+pub fn is_enabled() -> Result<bool, String> {
+    Ok(entry_path()?.exists())
+}
+
+#[cfg(target_os = "windows")]
+pub fn is_enabled() -> Result<bool, String> {
+    std::process::Command::new("reg")
+        .args(["query", RUN_KEY, "/v", RUN_VALUE])
+        .output()
+        .map(|output| output.status.success())
+        .map_err(|e| format!("Failed to query registry: {}", e))
+}
+
+#[cfg(not(any(target_os = "macos", target_os = "linux", target_os = "windows")))]
+pub fn is_enabled() -> Result<bool, String> {
+    Err("Autostart is not supported on this platform".to_string())
+}
+
+/// Register or unregister ClaudeMiner as a login item
+pub fn set_enabled(enabled: bool) -> Result<(), String> {
+    let exe = current_exe()?;
+
+    #[cfg(any(target_os = "macos", target_os = "linux"))]
+    {
+        let path = entry_path()?;
+        if enabled {
+            if let Some(dir) = path.parent() {
+                std::fs::create_dir_all(dir)
+                    .map_err(|e| format!("Failed to create {}: {}", dir.display(), e))?;
+            }
+            std::fs::write(&path, entry_contents(&exe))
+                .map_err(|e| format!("Failed to write {}: {}", path.display(), e))?;
+        } else if path.exists() {
+            std::fs::remove_file(&path)
+                .map_err(|e| format!("Failed to remove {}: {}", path.display(), e))?;
+        }
+    }
+
+    #[cfg(target_os = "windows")]
+    {
+        let mut reg = std::process::Command::new("reg");
+        if enabled {
+            let command = format!("\"{}\"", exe.display());
+            reg.args(["add", RUN_KEY, "/v", RUN_VALUE, "/d", &command, "/f"]);
+        } else {
+            if !is_enabled()? {
+                return Ok(());
+            }
+            reg.args(["delete", RUN_KEY, "/v", RUN_VALUE, "/f"]);
+        }
+        let output = reg.output().map_err(|e| format!("Failed to run reg: {}", e))?;
+        if !output.status.success() {
+            return Err(format!("reg failed: {}", String::from_utf8_lossy(&output.stderr).trim()));
+        }
+    }
+
+    #[cfg(not(any(target_os = "macos", target_os = "linux", target_os = "windows")))]
+    return Err(format!("Autostart is not supported on this platform ({})", exe.display()));
+
+    #[cfg(any(target_os = "macos", target_os = "linux", target_os = "windows"))]
+    {
+        println!("[Autostart] {} ({})", if enabled { "Enabled" } else { "Disabled" }, exe.display());
+        Ok(())
+    }
+}
+
+#[cfg(target_os = "macos")]
+fn entry_path() -> Result<PathBuf, String> {
+    dirs::home_dir()
+        .map(|home| home.join("Library/LaunchAgents").join(format!("{}.plist", LAUNCH_AGENT_LABEL)))
+        .ok_or_else(|| "Failed to get home directory".to_string())
+}
+
+#[cfg(target_os = "linux")]
+fn entry_path() -> Result<PathBuf, String> {
+    dirs::config_dir()
+        .map(|config| config.join("autostart/claudeminer.desktop"))
+        .ok_or_else(|| "Failed to get config directory".to_string())
+}
+
+#[cfg(target_os = "macos")]
+fn entry_contents(exe: &std::path::Path) -> String {
+    format!(
+        r#"<?xml version="1.0" encoding="UTF-8"?>
+<!DOCTYPE plist PUBLIC "-//Apple//DTD PLIST 1.0//EN" "http://www.apple.com/DTDs/PropertyList-1.0.dtd">
+<plist version="1.0">
+<dict>
+    <key>Label</key>
+    <string>{}</string>
+    <key>ProgramArguments</key>
+    <array>
+        <string>{}</string>
+    </array>
+    <key>RunAtLoad</key>
+    <true/>
+    <key>ProcessType</key>
+    <string>Interactive</string>
+</dict>
+</plist>
+"#,
+        LAUNCH_AGENT_LABEL,
+        xml_escape(&exe.display().to_string())
+    )
+}
+
+#[cfg(target_os = "linux")]
+fn entry_contents(exe: &std::path::Path) -> String {
+    format!(
+        "[Desktop Entry]\nType=Application\nName=ClaudeMiner\nExec=\"{}\"\nX-GNOME-Autostart-enabled=true\n",
+        exe.display()
+    )
+}
+
+#[cfg(target_os = "macos")]
+fn xml_escape(value: &str) -> String {
+    value.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;")
+}
+
+#[cfg(all(test, target_os = "linux"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_desktop_entry() {
+        let entry = entry_contents(std::path::Path::new("/opt/ClaudeMiner/claude-miner"));
+        assert!(entry.starts_with("[Desktop Entry]\n"));
+        assert!(entry.contains("Exec=\"/opt/ClaudeMiner/claude-miner\"\n"));
+    }
+}
@@ -0,0 +1,199 @@
+// Claude CLI Version
+//
+// Detects the installed Claude Code version once at startup: `claude --version`, falling back
+// to package metadata (the npm package.json next to the resolved binary, or the versioned file
+// name of the native installer). Log and hook parsing is tuned against known releases, and a
+// newer CLI can change those formats without notice - so a version beyond TESTED_VERSION is
+// logged and reported by the doctor instead of letting detection silently degrade.
+// Bump TESTED_VERSION after checking the heuristics against a new release.
+//
+
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// Newest major.minor the log/hook heuristics were verified against
+pub const TESTED_VERSION: (u32, u32) = (2, 0);
+
+const NPM_PACKAGE: &str = "@anthropic-ai/claude-code";
+const VERSION_TIMEOUT: Duration = Duration::from_secs(5);
+
+static DETECTED: Mutex<Option<ClaudeCli>> = Mutex::new(None);
+
+/// Where the version came from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum VersionSource {
+    Cli,      // claude --version
+    Package,  // package.json / versioned install path
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Compatibility {
+    Supported,
+    Newer,    // Newer than TESTED_VERSION; detection may be unreliable
+    Unknown,  // CLI or version not found
+}
+
+/// The installed Claude Code CLI (get_claude_cli)
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ClaudeCli {
+    pub path: Option<String>,
+    pub version: Option<String>,
+    pub source: Option<VersionSource>,
+    pub compatibility: Compatibility,
+    pub tested_version: String,
+}
+
+/// Detected CLI (detected on first use, then cached)
+pub fn get() -> ClaudeCli {
+    let mut detected = DETECTED.lock().unwrap();
+    detected.get_or_insert_with(detect).clone()
+}
+
+/// Detect the CLI off the main thread and log compatibility warnings
+pub fn detect_in_background() {
+    crate::crash::spawn_named("claude-cli-detect", || {
+        let cli = get();
+        match (cli.compatibility, &cli.version) {
+            (Compatibility::Newer, Some(version)) => eprintln!(
+                "[ClaudeCli] ⚠️ Claude Code {} is newer than the formats ClaudeMiner was tested with (up to {}.x); status detection may be unreliable",
+                version, cli.tested_version),
+            (_, Some(version)) => println!("[ClaudeCli] Claude Code {} ({:?})", version, cli.source),
+            (_, None) => println!("[ClaudeCli] Claude Code version unknown (CLI: {:?})", cli.path),
+        }
+    });
+}
+
+fn detect() -> ClaudeCli {
+    let path = find_binary();
+    let detected = path.as_deref().and_then(|path| {
+        version_from_cli(path).map(|v| (v, VersionSource::Cli))
+            .or_else(|| version_from_package(path).map(|v| (v, VersionSource::Package)))
+    });
+    let (version, source) = detected.unzip();
+
+    ClaudeCli {
+        path: path.map(|p| p.display().to_string()),
+        compatibility: version.as_deref().map(compatibility).unwrap_or(Compatibility::Unknown),
+        version,
+        source,
+        tested_version: format!("{}.{}", TESTED_VERSION.0, TESTED_VERSION.1),
+    }
+}
+
+/// `claude` in PATH, then the usual install locations (apps launched from Finder get a bare PATH)
+fn find_binary() -> Option<PathBuf> {
+    let names: &[&str] = if cfg!(windows) { &["claude.exe", "claude.cmd"] } else { &["claude"] };
+    let path_dirs: Vec<PathBuf> = std::env::var_os("PATH").map(|p| std::env::split_paths(&p).collect()).unwrap_or_default();
+    let home = dirs::home_dir().unwrap_or_default();
+    let known_dirs = [
+        home.join(".claude").join("local"),
+        home.join(".local").join("bin"),
+        PathBuf::from("/opt/homebrew/bin"),
+        PathBuf::from("/usr/local/bin"),
+    ];
+
+    path_dirs.iter().chain(known_dirs.iter())
+        .flat_map(|dir| names.iter().map(move |name| dir.join(name)))
+        .find(|candidate| candidate.is_file())
+}
+
+/// Run `claude --version`, giving up after VERSION_TIMEOUT
+fn version_from_cli(path: &Path) -> Option<String> {
+    let mut child = Command::new(path)
+        .arg("--version")
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn()
+        .ok()?;
+
+    let started = Instant::now();
+    loop {
+        match child.try_wait() {
+            Ok(Some(_)) => break,
+            Ok(None) if started.elapsed() < VERSION_TIMEOUT => std::thread::sleep(Duration::from_millis(50)),
+            _ => {
+                let _ = child.kill();
+                let _ = child.wait();
+                return None;
+            }
+        }
+    }
+    let output = child.wait_with_output().ok()?;
+    parse_version(&String::from_utf8_lossy(&output.stdout))
+}
+
+/// Version from package metadata: the npm package.json above the resolved binary,
+/// or the file name of a native install (~/.local/share/claude/versions/2.0.14)
+fn version_from_package(path: &Path) -> Option<String> {
+    let resolved = std::fs::canonicalize(path).ok()?;
+
+    if let Some(version) = resolved.file_name().and_then(|name| parse_version(&name.to_string_lossy())) {
+        return Some(version);
+    }
+
+    resolved.ancestors().skip(1).find_map(|dir| {
+        let contents = std::fs::read_to_string(dir.join("package.json")).ok()?;
+        let package: serde_json::Value = serde_json::from_str(&contents).ok()?;
+        if package["name"] != NPM_PACKAGE {
+            return None;
+        }
+        package["version"].as_str().and_then(parse_version)
+    })
+}
+
+/// First x.y.z in `text` ("2.0.14 (Claude Code)" -> "2.0.14")
+fn parse_version(text: &str) -> Option<String> {
+    text.split(|c: char| c.is_whitespace() || c == '(' || c == ')' || c == 'v')
+        .find(|word| {
+            let parts: Vec<&str> = word.split('.').collect();
+            parts.len() >= 3 && parts[..3].iter().all(|part| !part.is_empty() && part.chars().all(|c| c.is_ascii_digit()))
+        })
+        .map(str::to_string)
+}
+
+fn compatibility(version: &str) -> Compatibility {
+    let mut parts = version.split('.').map(|part| part.parse::<u32>());
+    match (parts.next(), parts.next()) {
+        (Some(Ok(major)), Some(Ok(minor))) if (major, minor) > TESTED_VERSION => Compatibility::Newer,
+        (Some(Ok(_)), Some(Ok(_))) => Compatibility::Supported,
+        _ => Compatibility::Unknown,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_version() {
+        assert_eq!(parse_version("2.0.14 (Claude Code)\n").as_deref(), Some("2.0.14"));
+        assert_eq!(parse_version("claude v1.0.128").as_deref(), Some("1.0.128"));
+        assert_eq!(parse_version("command not found"), None);
+
+        assert_eq!(compatibility("1.0.128"), Compatibility::Supported);
+        assert_eq!(compatibility(&format!("{}.{}.99", TESTED_VERSION.0, TESTED_VERSION.1)), Compatibility::Supported);
+        assert_eq!(compatibility(&format!("{}.{}.0", TESTED_VERSION.0, TESTED_VERSION.1 + 1)), Compatibility::Newer);
+        assert_eq!(compatibility(&format!("{}.0.0", TESTED_VERSION.0 + 1)), Compatibility::Newer);
+    }
+
+    #[test]
+    fn test_version_from_package_json() {
+        let root = tempfile::tempdir().unwrap();
+        let package = root.path().join("node_modules").join("@anthropic-ai").join("claude-code");
+        std::fs::create_dir_all(&package).unwrap();
+        std::fs::write(package.join("package.json"), r#"{"name":"@anthropic-ai/claude-code","version":"1.0.98"}"#).unwrap();
+        std::fs::write(package.join("cli.js"), "").unwrap();
+        assert_eq!(version_from_package(&package.join("cli.js")).as_deref(), Some("1.0.98"));
+
+        let versions = root.path().join("versions");
+        std::fs::create_dir_all(&versions).unwrap();
+        std::fs::write(versions.join("2.0.14"), "").unwrap();
+        assert_eq!(version_from_package(&versions.join("2.0.14")).as_deref(), Some("2.0.14"));
+    }
+}
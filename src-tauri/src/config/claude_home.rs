@@ -0,0 +1,97 @@
+// Claude Home Directories (Profiles)
+//
+// Resolves which Claude config directories to monitor and hook. Each one is a profile:
+// "default" is $CLAUDE_CONFIG_DIR (or ~/.claude), the rest are listed in config.json
+// (e.g. separate work/personal configs with their own settings.json and debug dirs).
+//
+
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+
+pub const DEFAULT_PROFILE: &str = "default";
+
+/// A Claude config directory being monitored
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ClaudeProfile {
+    pub name: String,
+    pub home: PathBuf,
+}
+
+/// Default Claude home: $CLAUDE_CONFIG_DIR, falling back to ~/.claude
+pub fn default_claude_home() -> Option<PathBuf> {
+    if let Ok(dir) = std::env::var("CLAUDE_CONFIG_DIR") {
+        if !dir.trim().is_empty() {
+            return Some(expand_home(dir.trim()));
+        }
+    }
+    dirs::home_dir().map(|home| home.join(".claude"))
+}
+
+/// All profiles to monitor (default first, duplicate homes removed)
+pub fn claude_profiles() -> Vec<ClaudeProfile> {
+    let mut profiles: Vec<ClaudeProfile> = Vec::new();
+
+    let default = default_claude_home().map(|home| ClaudeProfile {
+        name: DEFAULT_PROFILE.to_string(),
+        home,
+    });
+    let configured = super::get().profiles.into_iter().map(|p| ClaudeProfile {
+        name: p.name,
+        home: expand_home(&p.path),
+    });
+
+    for profile in default.into_iter().chain(configured) {
+        if !profiles.iter().any(|p| p.home == profile.home) {
+            profiles.push(profile);
+        }
+    }
+    profiles
+}
+
+/// All Claude homes to monitor (default first)
+pub fn claude_homes() -> Vec<PathBuf> {
+    claude_profiles().into_iter().map(|p| p.home).collect()
+}
+
+/// Name of the profile whose home contains `path`
+pub fn profile_for_path(path: &Path) -> Option<String> {
+    find_profile(&claude_profiles(), path)
+}
+
+fn find_profile(profiles: &[ClaudeProfile], path: &Path) -> Option<String> {
+    profiles.iter()
+        .find(|p| path.starts_with(&p.home))
+        .map(|p| p.name.clone())
+}
+
+/// Expand a leading "~" to the user's home directory
+fn expand_home(path: &str) -> PathBuf {
+    match (path.strip_prefix('~'), dirs::home_dir()) {
+        (Some(rest), Some(home)) => home.join(rest.trim_start_matches('/')),
+        _ => PathBuf::from(path),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_expand_home() {
+        let home = dirs::home_dir().unwrap();
+        assert_eq!(expand_home("~/.claude-work"), home.join(".claude-work"));
+        assert_eq!(expand_home("/opt/claude"), PathBuf::from("/opt/claude"));
+    }
+
+    #[test]
+    fn test_find_profile() {
+        let profiles = vec![
+            ClaudeProfile { name: "default".to_string(), home: PathBuf::from("/home/me/.claude") },
+            ClaudeProfile { name: "work".to_string(), home: PathBuf::from("/home/me/.claude-work") },
+        ];
+
+        assert_eq!(find_profile(&profiles, Path::new("/home/me/.claude-work/debug/a.txt")).as_deref(), Some("work"));
+        assert_eq!(find_profile(&profiles, Path::new("/home/me/.claude/projects/x/a.jsonl")).as_deref(), Some("default"));
+        assert_eq!(find_profile(&profiles, Path::new("/tmp/a.txt")), None);
+    }
+}
@@ -0,0 +1,9 @@
+// Config Module - User configuration
+//
+// Persistent ClaudeMiner settings stored as JSON in the app config directory
+
+pub mod claude_home;
+pub mod store;
+
+pub use claude_home::{ClaudeProfile, claude_homes, claude_profiles, profile_for_path};
+pub use store::{HotkeyConfig, IgnoreConfig, NotificationChannelConfig, NotificationChannelKind, NotificationConfig, NotificationOverride, NotificationSoundsConfig, OverlayConfig, OverlayPosition, ProcessMatcherConfig, ProfileConfig, ProjectNotificationOverride, ProjectHooksConfig, QuietHoursConfig, RuleAction, RuleCondition, RuleConfig, TerminalApp, TrayTitleMode, WindowBounds, WindowConfig, get, update};
@@ -0,0 +1,710 @@
+// Config Store
+//
+// Loads config.json once (lazily) and keeps it in a singleton.
+// Missing fields fall back to defaults so older config files keep working.
+//
+
+use crate::notification::sender::NotificationKind;
+use crate::persist::{self, JsonStore};
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::io;
+use std::path::PathBuf;
+use std::sync::RwLock;
+
+const CONFIG_FILE: &str = "config.json";
+
+/// ClaudeMiner configuration (~/Library/Application Support/com.claudeminer.app/config.json)
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AppConfig {
+    pub ignore: IgnoreConfig,
+    pub launcher: LauncherConfig,
+    /// Extra Claude profiles (besides the default $CLAUDE_CONFIG_DIR / ~/.claude)
+    #[serde(alias = "claude_homes")]
+    pub profiles: Vec<ProfileConfig>,
+    /// Which processes are miners (empty = built-in Claude rule)
+    pub process_matchers: Vec<ProcessMatcherConfig>,
+    pub users: UserFilterConfig,
+    pub energy: EnergyConfig,
+    /// Automation rules, evaluated by the coordinator
+    pub rules: Vec<RuleConfig>,
+    pub scripts: ScriptHooksConfig,
+    pub notifications: NotificationConfig,
+    pub quiet_hours: QuietHoursConfig,
+    pub sounds: NotificationSoundsConfig,
+    pub tray: TrayConfig,
+    pub hotkeys: HotkeyConfig,
+    pub window: WindowConfig,
+    pub overlay: OverlayConfig,
+    pub hooks: HooksConfig,
+    pub crash_reports: CrashReportConfig,
+    pub archive: ArchiveConfig,
+    pub watch: WatchConfig,
+}
+
+/// Auto-archive of idle sessions (hidden from the miner list, see session/archive.rs)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ArchiveConfig {
+    /// Archive sessions resting this long (0 = never)
+    pub idle_secs: u64,
+}
+
+impl Default for ArchiveConfig {
+    fn default() -> Self {
+        Self { idle_secs: 4 * 3600 }
+    }
+}
+
+/// Log and transcript directory watching (see monitor/watch.rs)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct WatchConfig {
+    /// Also watch subdirectories of the debug directories
+    pub recursive: bool,
+    /// Watch <claude home>/projects recursively for transcript updates
+    pub projects: bool,
+    /// Poll instead of using native file events (network home directories that aren't detected)
+    pub force_polling: bool,
+    pub poll_interval_secs: u64,
+}
+
+impl Default for WatchConfig {
+    fn default() -> Self {
+        Self { recursive: false, projects: true, force_polling: false, poll_interval_secs: 2 }
+    }
+}
+
+/// Crash report submission (reports are always written locally)
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CrashReportConfig {
+    /// Opt-in: POST each crash report to `endpoint`
+    pub submit: bool,
+    pub endpoint: Option<String>,
+}
+
+/// Claude Code hook registration in settings.json
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct HooksConfig {
+    /// false = hooks are removed and not re-added at startup (CPU/log detection only)
+    pub enabled: bool,
+    /// Projects that also get the hooks in their own .claude/ settings
+    pub projects: Vec<ProjectHooksConfig>,
+    /// A Hook session silent this long falls back to CPU/log detection (long tool calls,
+    /// e.g. builds or test runs, send no hooks until they finish)
+    pub stale_secs: u64,
+}
+
+impl Default for HooksConfig {
+    fn default() -> Self {
+        Self { enabled: true, projects: Vec::new(), stale_secs: 60 * 60 }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ProjectHooksConfig {
+    /// Project directory (the one containing .claude/)
+    pub path: String,
+    /// settings.local.json (not committed) instead of the shared settings.json
+    #[serde(default = "default_true")]
+    pub local: bool,
+}
+
+/// Main window behavior and last state (restored at startup)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct WindowConfig {
+    /// Closing the window hides it to the tray instead of quitting
+    pub close_to_tray: bool,
+    /// Last size/position (logical pixels); None = tauri.conf.json defaults
+    pub bounds: Option<WindowBounds>,
+    pub last_tab: Option<String>,
+}
+
+impl Default for WindowConfig {
+    fn default() -> Self {
+        Self { close_to_tray: true, bounds: None, last_tab: None }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct WindowBounds {
+    pub x: f64,
+    pub y: f64,
+    pub width: f64,
+    pub height: f64,
+}
+
+/// Always-on-top status strip (overlay.rs)
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct OverlayConfig {
+    /// Shown at startup (the overlay was open when the app last quit)
+    pub visible: bool,
+    /// Mouse events pass through to the apps underneath
+    pub click_through: bool,
+    /// Last top-left position (logical pixels); None = top-right corner of the main monitor
+    pub position: Option<OverlayPosition>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct OverlayPosition {
+    pub x: f64,
+    pub y: f64,
+}
+
+/// Global shortcuts (Tauri accelerator syntax, e.g. "Alt+CmdOrCtrl+M"; null = disabled)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct HotkeyConfig {
+    pub toggle_window: Option<String>,
+    pub kill_zombies: Option<String>,
+}
+
+impl Default for HotkeyConfig {
+    fn default() -> Self {
+        Self {
+            toggle_window: Some("Alt+CmdOrCtrl+M".to_string()),
+            // Killing is destructive: only bound when the user picks a shortcut
+            kill_zombies: None,
+        }
+    }
+}
+
+impl HotkeyConfig {
+    /// Every binding with its action name
+    pub fn bindings(&self) -> [(&'static str, Option<&str>); 2] {
+        [
+            ("toggle window", self.toggle_window.as_deref()),
+            ("kill zombies", self.kill_zombies.as_deref()),
+        ]
+    }
+
+    /// Check the accelerators before they are saved (one message per bad binding)
+    pub fn validate(&self) -> Result<(), Vec<String>> {
+        let mut errors: Vec<String> = self.bindings().into_iter()
+            .filter_map(|(action, accelerator)| {
+                let accelerator = accelerator?;
+                validate_accelerator(accelerator).err().map(|e| format!("{} ({}): {}", accelerator, action, e))
+            })
+            .collect();
+        if let (Some(a), Some(b)) = (&self.toggle_window, &self.kill_zombies) {
+            if a.eq_ignore_ascii_case(b) {
+                errors.push(format!("{} is bound to two actions", a));
+            }
+        }
+        if errors.is_empty() { Ok(()) } else { Err(errors) }
+    }
+}
+
+const ACCELERATOR_MODIFIERS: &[&str] = &["shift", "ctrl", "control", "alt", "option", "cmd", "command",
+    "super", "meta", "cmdorctrl", "commandorcontrol", "altgr"];
+const ACCELERATOR_KEYS: &[&str] = &["space", "tab", "enter", "return", "escape", "esc", "backspace", "delete",
+    "insert", "home", "end", "pageup", "pagedown", "up", "down", "left", "right", "plus", "minus",
+    "comma", "period", "slash", "backslash", "semicolon", "quote", "backquote", "bracketleft", "bracketright"];
+
+/// "Modifier+...+Key": known modifiers, then one key; a modifier is required unless the key is F1-F24
+fn validate_accelerator(accelerator: &str) -> Result<(), String> {
+    let parts: Vec<String> = accelerator.split('+').map(|p| p.trim().to_ascii_lowercase()).collect();
+    let (key, modifiers) = parts.split_last().ok_or("empty shortcut")?;
+
+    for modifier in modifiers {
+        if !ACCELERATOR_MODIFIERS.contains(&modifier.as_str()) {
+            return Err(format!("unknown modifier '{}'", modifier));
+        }
+    }
+
+    let function_key = key.strip_prefix('f')
+        .and_then(|n| n.parse::<u8>().ok())
+        .is_some_and(|n| (1..=24).contains(&n));
+    let single = key.len() == 1 && key.chars().all(|c| c.is_ascii_alphanumeric() || "=-[];',./\\`".contains(c));
+    if !(function_key || single || ACCELERATOR_KEYS.contains(&key.as_str())) {
+        return Err(format!("unknown key '{}'", key));
+    }
+    if modifiers.is_empty() && !function_key {
+        return Err("needs a modifier (Alt, Shift, CmdOrCtrl, ...)".to_string());
+    }
+    Ok(())
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TrayConfig {
+    /// What the macOS menu bar title shows next to the icon
+    pub title: TrayTitleMode,
+    /// Switch the icon with session state (working animation, idle, zombie)
+    pub dynamic_icon: bool,
+    /// "default" (bundled) or a folder name under <config dir>/tray-themes/
+    pub icon_theme: String,
+    /// Working count on the Dock icon badge (macOS)
+    pub dock_badge: bool,
+}
+
+impl Default for TrayConfig {
+    fn default() -> Self {
+        Self { title: TrayTitleMode::default(), dynamic_icon: true, icon_theme: "default".to_string(), dock_badge: true }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TrayTitleMode {
+    #[default]
+    Count,  // "⛏️ 2"
+    Time,   // "⛏️ 2 · 14m" (longest-running working session)
+    Off,
+}
+
+/// Notification sound per event: "default" (system default), a system sound name
+/// (e.g. "Glass" on macOS, "Reminder" on Windows, "complete" on Linux) or none (silent)
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct NotificationSoundsConfig {
+    pub completion: Option<String>,
+    pub approval_pending: Option<String>,
+    pub zombie: Option<String>,
+}
+
+/// Do Not Disturb schedule in local time ("HH:MM"; start > end wraps past midnight)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct QuietHoursConfig {
+    pub enabled: bool,
+    pub start: String,
+    pub end: String,
+    /// Quiet all day on Saturday and Sunday
+    pub weekends: bool,
+}
+
+impl Default for QuietHoursConfig {
+    fn default() -> Self {
+        Self { enabled: false, start: "22:00".to_string(), end: "08:00".to_string(), weekends: false }
+    }
+}
+
+/// Per-event notification toggles (all on by default)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct NotificationConfig {
+    pub task_completion: bool,
+    pub session_created: bool,
+    pub zombie_killed: bool,
+    pub zombie_detected: bool,
+    pub approval_pending: bool,
+    pub long_task: bool,
+    pub milestones: bool,
+    pub skip_when_terminal_focused: bool,  // No task completion notification while its terminal is in front
+    /// Where notifications go (empty = native notifications only; see notification::channel)
+    pub channels: Vec<NotificationChannelConfig>,
+    /// Mute or always notify for specific projects (first match wins; session flags take precedence)
+    pub project_overrides: Vec<ProjectNotificationOverride>,
+}
+
+/// Mute a project's notifications, or deliver them regardless of the per-event
+/// toggles, quiet hours and focus mode
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum NotificationOverride {
+    Mute,
+    Always,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ProjectNotificationOverride {
+    /// Project path, or a glob pattern matched against it (e.g. "/Users/me/scratch/**")
+    pub project: String,
+    pub mode: NotificationOverride,
+}
+
+/// A notification delivery channel and the notification kinds routed to it
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct NotificationChannelConfig {
+    #[serde(flatten)]
+    pub channel: NotificationChannelKind,
+    /// Notification kinds sent here ("task_completion", "zombie_detected", ...); empty = all
+    #[serde(default)]
+    pub events: Vec<String>,
+}
+
+impl NotificationChannelConfig {
+    /// Check every `events` entry names a notification kind
+    pub fn validate(&self) -> Result<(), String> {
+        let unknown: Vec<&str> = self.events.iter()
+            .filter(|e| serde_json::from_value::<NotificationKind>(serde_json::Value::String(e.to_string())).is_err())
+            .map(String::as_str)
+            .collect();
+        if unknown.is_empty() {
+            Ok(())
+        } else {
+            Err(format!("unknown notification kind(s) in \"events\": {}", unknown.join(", ")))
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum NotificationChannelKind {
+    /// System notification (implicit for every kind unless configured explicitly)
+    Native,
+    /// POST {kind, title, body, session_id, pid} as JSON
+    Webhook { url: String },
+    /// Slack incoming webhook
+    Slack { webhook_url: String },
+    /// Read the notification aloud (macOS `say`, Linux `spd-say`)
+    Tts {
+        #[serde(default)]
+        voice: Option<String>,
+    },
+}
+
+impl Default for NotificationConfig {
+    fn default() -> Self {
+        Self {
+            task_completion: true,
+            session_created: true,
+            zombie_killed: true,
+            zombie_detected: true,
+            approval_pending: true,
+            long_task: true,
+            milestones: true,
+            skip_when_terminal_focused: true,
+            channels: Vec::new(),
+            project_overrides: Vec::new(),
+        }
+    }
+}
+
+/// Shell commands run on session lifecycle events (see automation::scripts)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ScriptHooksConfig {
+    pub on_created: Vec<String>,
+    pub on_working: Vec<String>,
+    pub on_resting: Vec<String>,
+    pub on_terminated: Vec<String>,
+    /// Commands still running after this are killed (also applies to rule shell actions)
+    pub timeout_secs: u64,
+}
+
+impl Default for ScriptHooksConfig {
+    fn default() -> Self {
+        Self {
+            on_created: Vec::new(),
+            on_working: Vec::new(),
+            on_resting: Vec::new(),
+            on_terminated: Vec::new(),
+            timeout_secs: 30,
+        }
+    }
+}
+
+/// Automation rule: once every condition has held for `for_secs`, run the action
+/// (once per session, re-armed when the condition stops holding)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RuleConfig {
+    pub name: String,
+    #[serde(default)]
+    pub when: RuleCondition,
+    pub action: RuleAction,
+}
+
+/// Rule conditions (all set conditions must match)
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RuleCondition {
+    pub status: Option<String>,    // e.g. "zombie"
+    pub cpu_above: Option<f32>,    // Percent
+    pub project: Option<String>,   // Project path or its directory name
+    pub for_secs: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "lowercase")]
+pub enum RuleAction {
+    Kill,
+    Notify {
+        #[serde(default)]
+        message: Option<String>,
+    },
+    Shell { command: String },
+    Webhook { url: String },
+}
+
+/// Per-process energy impact sampling (macOS only, off by default)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct EnergyConfig {
+    pub enabled: bool,
+    pub interval_secs: u64,
+}
+
+impl Default for EnergyConfig {
+    fn default() -> Self {
+        Self { enabled: false, interval_secs: 30 }
+    }
+}
+
+/// Whose processes are monitored (always includes the current user)
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct UserFilterConfig {
+    /// Monitor every user's processes (shared machines, read-only)
+    pub all_users: bool,
+    /// Additional UIDs to monitor
+    pub extra_uids: Vec<u32>,
+}
+
+/// Process matching rule: regexes on the executable name and/or full command line
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ProcessMatcherConfig {
+    pub kind: String,             // Type tag shown in the UI, e.g. "aider"
+    #[serde(default)]
+    pub label: Option<String>,    // Display name, e.g. "Aider"
+    #[serde(default)]
+    pub name: Option<String>,     // Regex on the executable name
+    #[serde(default)]
+    pub args: Option<String>,     // Regex on the full command line
+    #[serde(default)]
+    pub exclude: Option<String>,  // Regex; matching command lines are skipped
+}
+
+impl ProcessMatcherConfig {
+    /// Check the rule is usable; the error names the rule and the offending field
+    pub fn validate(&self) -> Result<(), String> {
+        if self.kind.trim().is_empty() {
+            return Err("rule without a \"kind\" type tag".to_string());
+        }
+        if self.name.is_none() && self.args.is_none() {
+            return Err(format!("'{}' rule needs a \"name\" or \"args\" pattern", self.kind));
+        }
+        for (field, pattern) in [("name", &self.name), ("args", &self.args), ("exclude", &self.exclude)] {
+            if let Some(pattern) = pattern {
+                regex::Regex::new(pattern).map_err(|e| {
+                    format!("'{}' rule has an invalid \"{}\" pattern '{}': {}", self.kind, field, pattern, e)
+                })?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// A named Claude config directory ("~" allowed in path)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(from = "ProfileEntry")]
+pub struct ProfileConfig {
+    pub name: String,
+    pub path: String,
+}
+
+/// A profile as written in config.json: {"name", "path"}, or a bare path from the older
+/// "claude_homes" list (named after its directory)
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum ProfileEntry {
+    Named { name: String, path: String },
+    Path(String),
+}
+
+impl From<ProfileEntry> for ProfileConfig {
+    fn from(entry: ProfileEntry) -> Self {
+        match entry {
+            ProfileEntry::Named { name, path } => ProfileConfig { name, path },
+            ProfileEntry::Path(path) => {
+                let name = std::path::Path::new(&path).file_name()
+                    .map(|name| name.to_string_lossy().trim_start_matches('.').to_string())
+                    .filter(|name| !name.is_empty())
+                    .unwrap_or_else(|| path.clone());
+                ProfileConfig { name, path }
+            }
+        }
+    }
+}
+
+/// Sessions matching any of these rules are never tracked as miners
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct IgnoreConfig {
+    /// Glob patterns matched against the process working directory (e.g. "/tmp/ci-*/**")
+    pub cwd_globs: Vec<String>,
+    /// Regex patterns matched against the full process command line
+    pub command_patterns: Vec<String>,
+}
+
+/// How start_miner opens new sessions
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct LauncherConfig {
+    pub terminal: TerminalApp,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TerminalApp {
+    #[default]
+    Terminal,
+    Iterm,
+    Tmux,
+}
+
+// Tests run against the defaults, not the developer's config.json
+static CONFIG: Lazy<RwLock<AppConfig>> = Lazy::new(|| RwLock::new(load()));
+
+fn default_true() -> bool {
+    true
+}
+
+/// Get config.json path
+pub fn get_config_path() -> PathBuf {
+    persist::data_dir().join(CONFIG_FILE)
+}
+
+fn store() -> JsonStore<AppConfig> {
+    JsonStore::new("Config", CONFIG_FILE)
+}
+
+fn load() -> AppConfig {
+    let store = store();
+    match store.load() {
+        Some(config) => {
+            println!("[Config] Loaded {:?}", store.path());
+            report_problems(&config);
+            config
+        }
+        None => {
+            println!("[Config] No usable config at {:?}, using defaults", store.path());
+            AppConfig::default()
+        }
+    }
+}
+
+/// Log settings that parse but can't be used (the affected rules are skipped)
+fn report_problems(config: &AppConfig) {
+    for (index, matcher) in config.process_matchers.iter().enumerate() {
+        if let Err(e) = matcher.validate() {
+            eprintln!("[Config] ⚠️ process_matchers[{}]: {}", index, e);
+        }
+    }
+    for (index, channel) in config.notifications.channels.iter().enumerate() {
+        if let Err(e) = channel.validate() {
+            eprintln!("[Config] ⚠️ notifications.channels[{}]: {}", index, e);
+        }
+    }
+}
+
+fn save(config: &AppConfig) -> io::Result<()> {
+    let store = store();
+    store.save(config)?;
+    println!("[Config] Saved {:?}", store.path());
+    Ok(())
+}
+
+/// Get a snapshot of the current config
+pub fn get() -> AppConfig {
+    CONFIG.read().unwrap().clone()
+}
+
+/// Modify the config and persist it to disk
+pub fn update<F: FnOnce(&mut AppConfig)>(f: F) -> io::Result<()> {
+    let mut config = CONFIG.write().unwrap();
+    f(&mut config);
+    save(&config)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_hotkeys() {
+        assert_eq!(HotkeyConfig::default().validate(), Ok(()));
+        assert_eq!(HotkeyConfig::default().kill_zombies, None);
+
+        let hotkeys = HotkeyConfig {
+            toggle_window: Some("Alt+CmdOrCtrl+M".to_string()),
+            kill_zombies: Some("Shift+F13".to_string()),
+        };
+        assert_eq!(hotkeys.validate(), Ok(()));
+
+        let hotkeys = HotkeyConfig {
+            toggle_window: Some("Hyper+M".to_string()),
+            kill_zombies: Some("Z".to_string()),
+        };
+        let errors = hotkeys.validate().unwrap_err();
+        assert_eq!(errors.len(), 2);
+        assert!(errors[0].contains("unknown modifier 'hyper'"));
+        assert!(errors[1].contains("needs a modifier"));
+
+        assert!(validate_accelerator("CmdOrCtrl+").is_err());
+        assert!(validate_accelerator("F5").is_ok());
+        let same = HotkeyConfig { toggle_window: Some("Alt+M".to_string()), kill_zombies: Some("alt+m".to_string()) };
+        assert_eq!(same.validate().unwrap_err().len(), 1);
+    }
+
+    #[test]
+    fn test_missing_fields_use_defaults() {
+        let config: AppConfig = serde_json::from_str("{}").unwrap();
+        assert_eq!(config, AppConfig::default());
+
+        let config: AppConfig = serde_json::from_str(r#"{"ignore":{"cwd_globs":["/tmp/ci-*"]}}"#).unwrap();
+        assert_eq!(config.ignore.cwd_globs, vec!["/tmp/ci-*".to_string()]);
+        assert!(config.ignore.command_patterns.is_empty());
+    }
+
+    #[test]
+    fn test_parse_rules() {
+        let config: AppConfig = serde_json::from_str(r#"{"rules":[
+            {"name":"reap zombies","when":{"status":"zombie","for_secs":300},"action":{"type":"kill"}},
+            {"name":"hot","when":{"cpu_above":90,"for_secs":600},"action":{"type":"webhook","url":"http://localhost/hook"}}
+        ]}"#).unwrap();
+        assert_eq!(config.rules.len(), 2);
+        assert_eq!(config.rules[0].when.status.as_deref(), Some("zombie"));
+        assert_eq!(config.rules[0].action, RuleAction::Kill);
+        assert_eq!(config.rules[1].when.cpu_above, Some(90.0));
+    }
+
+    #[test]
+    fn test_validate_process_matchers() {
+        let config: AppConfig = serde_json::from_str(r#"{"process_matchers":[
+            {"kind":"claude","args":"\\bclaude\\b","exclude":"claude-miner"},
+            {"kind":"aider","name":"(aider"},
+            {"kind":"codex"},
+            {"kind":"","args":"codex"}
+        ]}"#).unwrap();
+        let errors: Vec<_> = config.process_matchers.iter().map(|m| m.validate().err()).collect();
+
+        assert_eq!(errors[0], None);
+        assert!(errors[1].as_deref().unwrap().starts_with("'aider' rule has an invalid \"name\" pattern '(aider'"));
+        assert_eq!(errors[2].as_deref(), Some("'codex' rule needs a \"name\" or \"args\" pattern"));
+        assert!(errors[3].is_some());
+    }
+
+    #[test]
+    fn test_claude_homes_become_profiles() {
+        let config: AppConfig = serde_json::from_str(r#"{"claude_homes":["~/.claude-work","/opt/claude"]}"#).unwrap();
+        assert_eq!(config.profiles, vec![
+            ProfileConfig { name: "claude-work".to_string(), path: "~/.claude-work".to_string() },
+            ProfileConfig { name: "claude".to_string(), path: "/opt/claude".to_string() },
+        ]);
+
+        let config: AppConfig = serde_json::from_str(r#"{"profiles":[{"name":"work","path":"~/.claude-work"}]}"#).unwrap();
+        assert_eq!(config.profiles[0].name, "work");
+        let saved = serde_json::to_value(&config).unwrap();
+        assert_eq!(saved["profiles"][0], serde_json::json!({"name": "work", "path": "~/.claude-work"}));
+    }
+
+    #[test]
+    fn test_validate_notification_channels() {
+        let config: AppConfig = serde_json::from_str(r#"{"notifications":{"channels":[
+            {"type":"tts","events":["task_completion","zombie_detected"]},
+            {"type":"slack","webhook_url":"https://hooks.slack.test/x","events":["zombie_detected","task_complete"]}
+        ]}}"#).unwrap();
+        let channels = &config.notifications.channels;
+
+        assert_eq!(channels[0].validate(), Ok(()));
+        assert_eq!(channels[1].validate(),
+            Err("unknown notification kind(s) in \"events\": task_complete".to_string()));
+    }
+}
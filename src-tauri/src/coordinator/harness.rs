@@ -0,0 +1,230 @@
+// Coordinator Test Harness
+//
+// Drives a Coordinator synchronously with scripted Log/Cpu/Hook events, on a mocked
+// clock and with fake processes (see crate::testing), then checks the resulting session
+// statuses, the events emitted to the frontend, cleanup requests and notifications.
+// Everything runs on the test's thread, so harness tests can run in parallel.
+//
+
+use crate::coordinator::core::Coordinator;
+use crate::event::emitter::{take_emitted, EmittedEvent};
+use crate::monitor::matcher::CLAUDE_KIND;
+use crate::notification::history::{self as notification_history, Outcome};
+use crate::notification::sender::NotificationKind;
+use crate::session::{CachedSession, CleanupEvent, PidEvidence, CpuEvent, HookEvent, LogEvent, MonitorEvent, ProcessExitEvent, SessionCache, SessionStore, SharedSessions};
+use crate::status::machine;
+use crate::testing::{self, MockProcess};
+use crate::types::{SessionStatus, WorkingState};
+use std::collections::HashMap;
+use std::sync::mpsc::{channel, Receiver};
+use std::sync::{Arc, Mutex};
+
+struct Harness {
+    coordinator: Coordinator,
+    sessions: SharedSessions,
+    session_cache: SessionCache,
+    cleanup: Receiver<CleanupEvent>,
+    now: u64,
+}
+
+impl Harness {
+    fn new(now: u64) -> Self {
+        testing::set_now(now);
+        let sessions: SharedSessions = Arc::new(SessionStore::default());
+        let session_cache = Arc::new(Mutex::new(HashMap::new()));
+        let (cleanup_sender, cleanup) = channel();
+        let coordinator = Coordinator::new(sessions.clone(), session_cache.clone(), Some(cleanup_sender));
+        take_emitted();
+        Self { coordinator, sessions, session_cache, cleanup, now }
+    }
+
+    fn advance(&mut self, secs: u64) {
+        self.now += secs;
+        testing::set_now(self.now);
+    }
+
+    /// A running Claude process whose debug log belongs to `session_id`
+    fn spawn(&self, pid: u32, session_id: &str) {
+        testing::set_process(pid, MockProcess::Running);
+        self.session_cache.lock().unwrap().insert(pid, CachedSession { session_id: session_id.to_string(), start_time: None, evidence: PidEvidence::DebugFile });
+    }
+
+    fn send(&mut self, event: MonitorEvent) {
+        self.coordinator.handle(event);
+    }
+
+    fn cpu(&mut self, pid: u32, cpu_percent: f32) {
+        let event = CpuEvent { pid, timestamp: self.now, cpu_percent, kind: CLAUDE_KIND.to_string(), uid: None, container: None };
+        self.send(MonitorEvent::Cpu(event));
+    }
+
+    fn log(&mut self, session_id: &str, pid: u32, state: WorkingState) {
+        self.log_with_approval(session_id, pid, state, false);
+    }
+
+    fn log_with_approval(&mut self, session_id: &str, pid: u32, state: WorkingState, has_approval_pending: bool) {
+        self.send(MonitorEvent::Log(LogEvent {
+            session_id: session_id.to_string(),
+            pid: Some(pid),
+            timestamp: self.now,
+            state,
+            has_approval_pending,
+            file_mtime: self.now,
+            transcript: None,
+            profile: None,
+        }));
+    }
+
+    fn hook(&mut self, session_id: &str, evt: &str) {
+        let event = HookEvent { sid: session_id.to_string(), evt: evt.to_string(), profile: None, timestamp: self.now, pid: None };
+        self.send(MonitorEvent::Hook(event));
+    }
+
+    fn status(&self, session_id: &str) -> Option<SessionStatus> {
+        self.sessions.get(session_id).map(|s| s.current_status)
+    }
+
+    /// (event name, status) of the session events emitted for `session_id` since the last call
+    fn emitted(&self, session_id: &str) -> Vec<(&'static str, SessionStatus)> {
+        take_emitted().into_iter()
+            .filter(|e: &EmittedEvent| e.session_id == session_id)
+            .map(|e| (e.name, e.status))
+            .collect()
+    }
+
+    fn notified(&self, session_id: &str, kind: NotificationKind) -> bool {
+        notification_history::get(None).iter()
+            .any(|n| n.session_id.as_deref() == Some(session_id) && n.kind == kind && n.outcome == Outcome::Delivered)
+    }
+}
+
+#[test]
+fn test_legacy_working_to_resting_debounce() {
+    let id = "harness-legacy-debounce";
+    let pid = 80_000_101;  // Above any OS pid_max: never a real process
+    let mut h = Harness::new(1_000_000);
+    h.spawn(pid, id);
+
+    // Discovered by CPU, streaming a tool call: working
+    h.cpu(pid, 55.0);
+    assert_eq!(h.status(id), Some(machine::WORKING));
+    h.advance(2);
+    h.log(id, pid, WorkingState::ActivelyWorking);
+    assert_eq!(h.status(id), Some(machine::WORKING));
+    h.emitted(id);
+
+    // CPU goes idle while the log is still fresh: thinking, not done
+    h.advance(20);
+    h.cpu(pid, 0.1);
+    assert_eq!(h.status(id), Some(machine::WORKING));
+    assert!(h.emitted(id).is_empty());
+
+    // Idle CPU and a log quiet for longer than the debounce: the task is over
+    h.advance(40);
+    h.cpu(pid, 0.1);
+    assert_eq!(h.status(id), Some(machine::RESTING));
+    assert_eq!(h.emitted(id), vec![("session-status-changed", machine::RESTING)]);
+    assert!(h.notified(id, NotificationKind::TaskCompletion));
+}
+
+#[test]
+fn test_legacy_zombie_then_exit() {
+    let id = "harness-legacy-zombie";
+    let pid = 80_000_102;
+    let mut h = Harness::new(2_000_000);
+    h.spawn(pid, id);
+    h.cpu(pid, 30.0);
+    assert_eq!(h.status(id), Some(machine::WORKING));
+
+    // The terminal window closes: zombie, and the cleaner is asked to check the process
+    testing::set_process(pid, MockProcess::TtyLost);
+    h.advance(5);
+    h.cpu(pid, 0.0);
+    assert_eq!(h.status(id), Some(machine::ZOMBIE));
+    assert!(matches!(h.cleanup.try_recv(), Ok(CleanupEvent::SessionBecameZombie(s)) if s == id));
+    assert!(h.notified(id, NotificationKind::ZombieDetected));
+    h.emitted(id);
+
+    // The process dies: the session goes away and later log lines don't resurrect it
+    testing::set_process(pid, MockProcess::Exited);
+    h.advance(5);
+    h.send(MonitorEvent::ProcessExit(ProcessExitEvent { pid, timestamp: h.now }));
+    assert_eq!(h.status(id), None);
+    assert_eq!(h.emitted(id), vec![("session-terminated", machine::ZOMBIE)]);
+
+    h.advance(1);
+    h.log(id, pid, WorkingState::ActivelyWorking);
+    assert_eq!(h.status(id), None);
+}
+
+#[test]
+fn test_hook_session_lifecycle() {
+    let id = "harness-hook-lifecycle";
+    let mut h = Harness::new(3_000_000);
+
+    h.hook(id, "start");
+    h.advance(1);
+    h.hook(id, "working");
+    h.advance(30);
+    h.hook(id, "resting");
+    assert_eq!(h.status(id), Some(machine::RESTING));
+    assert!(h.notified(id, NotificationKind::TaskCompletion));
+
+    h.advance(5);
+    h.hook(id, "end");
+    assert_eq!(h.status(id), None);
+    assert_eq!(h.emitted(id), vec![
+        ("session-created", machine::RESTING),
+        ("session-status-changed", machine::WORKING),
+        ("session-status-changed", machine::RESTING),
+        ("session-terminated", machine::RESTING),
+    ]);
+
+    // A late hook from before the end doesn't bring it back
+    h.send(MonitorEvent::Hook(HookEvent { sid: id.to_string(), evt: "start".to_string(), profile: None, timestamp: h.now - 10, pid: None }));
+    assert_eq!(h.status(id), None);
+}
+
+#[test]
+fn test_stale_hooks_fall_back_to_legacy() {
+    let id = "harness-hook-stale";
+    let mut h = Harness::new(4_000_000);
+    h.hook(id, "start");
+    h.hook(id, "working");
+
+    // Hooks go silent while the session shows no activity: the periodic sweep decides it's done
+    h.advance(crate::config::get().hooks.stale_secs);
+    h.hook("harness-hook-stale-other", "start");
+    assert_eq!(h.status(id), Some(machine::RESTING));
+    assert!(h.sessions.get(id).unwrap().hooks_stale);
+    assert!(h.notified(id, NotificationKind::HooksStale));
+
+    // Hooks are back
+    h.advance(1);
+    h.hook(id, "working");
+    assert_eq!(h.status(id), Some(machine::WORKING));
+    assert!(!h.sessions.get(id).unwrap().hooks_stale);
+}
+
+#[test]
+fn test_legacy_approval_needs_attention() {
+    let id = "harness-legacy-attention";
+    let pid = 80_000_104;
+    let mut h = Harness::new(4_000_000);
+    h.spawn(pid, id);
+    h.cpu(pid, 30.0);
+    h.emitted(id);
+
+    // The debug log shows a permission prompt: flag, event and notification
+    h.advance(2);
+    h.log_with_approval(id, pid, WorkingState::Unknown, true);
+    assert!(h.sessions.get(id).unwrap().needs_attention);
+    assert!(h.emitted(id).iter().any(|(name, _)| *name == "session-needs-attention"));
+    assert!(h.notified(id, NotificationKind::NeedsAttention));
+
+    // Approved: the tool runs and the flag clears
+    h.advance(5);
+    h.log(id, pid, WorkingState::ActivelyWorking);
+    assert!(!h.sessions.get(id).unwrap().needs_attention);
+    assert!(h.emitted(id).iter().any(|(name, _)| *name == "session-needs-attention"));
+}
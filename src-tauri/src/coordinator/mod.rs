@@ -1,8 +1,10 @@
 // Coordinator Module - Central event coordination
 //
-// This module handles event routing and session state decisions
+// This module routes events to the session manager and publishes the resulting changes
 
 pub mod core;
+#[cfg(test)]
+mod harness;
 
 pub use core::start_coordinator_with_cleanup;
 // pub use core::start_coordinator; // Unused - use start_coordinator_with_cleanup instead
\ No newline at end of file
@@ -0,0 +1,172 @@
+// Crash Reporter
+//
+// A panic in a monitor thread (CPU monitor, coordinator, hook receiver, ...) used to
+// kill that thread silently while the UI kept showing stale state. The panic hook:
+// - writes a crash report (thread, message, location, backtrace) to <config dir>/crashes/
+// - emits "app-crash" so the UI can tell the user
+// - if enabled in config.json "crash_reports", POSTs the report to the configured endpoint
+// The default hook still runs afterwards (stderr output is unchanged).
+//
+
+use crate::config;
+use chrono::Local;
+use serde::{Deserialize, Serialize};
+use std::backtrace::Backtrace;
+use std::fs;
+use std::any::Any;
+use std::panic::{self, Location, PanicHookInfo};
+use std::path::PathBuf;
+use std::process::{Command, Stdio};
+use std::thread;
+
+/// Reports kept on disk
+const MAX_REPORTS: usize = 10;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CrashReport {
+    pub thread: String,
+    pub message: String,
+    pub location: Option<String>,  // "src/monitor/cpu.rs:120:9"
+    pub backtrace: String,
+    pub timestamp: String,         // Local time, "2026-10-16 14:22:33"
+    pub version: String,
+}
+
+/// Install the panic hook (called once, first thing in main)
+pub fn install() {
+    let default_hook = panic::take_hook();
+    panic::set_hook(Box::new(move |info| {
+        let report = build_report(info, Backtrace::force_capture().to_string());
+        eprintln!("[Crash] ❌ Thread '{}' panicked: {}", report.thread, report.message);
+
+        match save(&report) {
+            Ok(path) => eprintln!("[Crash] Report written to {:?}", path),
+            Err(e) => eprintln!("[Crash] Failed to write report: {}", e),
+        }
+        crate::event::emitter::emit_app_crash(&report);
+
+        // Off the panicking thread: it may hold the config lock until it unwinds
+        let _ = thread::Builder::new()
+            .name("crash-report".to_string())
+            .spawn(move || submit(&report));
+
+        default_hook(info);
+    }));
+}
+
+/// thread::spawn with a name, so crash reports say which thread died
+pub fn spawn_named<F>(name: &str, f: F) -> thread::JoinHandle<()>
+where
+    F: FnOnce() + Send + 'static,
+{
+    thread::Builder::new()
+        .name(name.to_string())
+        .spawn(f)
+        .unwrap_or_else(|e| panic!("Failed to spawn {} thread: {}", name, e))
+}
+
+fn build_report(info: &PanicHookInfo, backtrace: String) -> CrashReport {
+    let thread = thread::current().name().unwrap_or("unnamed").to_string();
+    report_from(thread, info.payload(), info.location(), backtrace)
+}
+
+/// The report for a panic payload (a &str or String for panic!/expect messages)
+fn report_from(thread: String, payload: &(dyn Any + Send), location: Option<&Location>, backtrace: String) -> CrashReport {
+    let message = payload.downcast_ref::<&str>().map(|s| s.to_string())
+        .or_else(|| payload.downcast_ref::<String>().cloned())
+        .unwrap_or_else(|| "Unknown panic".to_string());
+
+    CrashReport {
+        thread,
+        message,
+        location: location.map(|l| format!("{}:{}:{}", l.file(), l.line(), l.column())),
+        backtrace,
+        timestamp: Local::now().format("%Y-%m-%d %H:%M:%S").to_string(),
+        version: crate::app_info::VERSION.to_string(),
+    }
+}
+
+fn crash_dir() -> PathBuf {
+    config::store::get_config_path()
+        .parent()
+        .map(|dir| dir.join("crashes"))
+        .unwrap_or_else(|| std::env::temp_dir().join("claudeminer-crashes"))
+}
+
+fn save(report: &CrashReport) -> std::io::Result<PathBuf> {
+    let dir = crash_dir();
+    fs::create_dir_all(&dir)?;
+
+    let path = dir.join(format!("crash-{}.json", Local::now().format("%Y%m%d-%H%M%S-%3f")));
+    fs::write(&path, serde_json::to_string_pretty(report).unwrap_or_default())?;
+
+    for old in list().into_iter().skip(MAX_REPORTS) {
+        let _ = fs::remove_file(old);
+    }
+    Ok(path)
+}
+
+/// Crash report files, newest first (timestamps sort lexicographically)
+fn list() -> Vec<PathBuf> {
+    let mut reports: Vec<PathBuf> = fs::read_dir(crash_dir())
+        .map(|entries| {
+            entries.flatten()
+                .map(|e| e.path())
+                .filter(|p| p.file_name().and_then(|n| n.to_str()).is_some_and(|n| n.starts_with("crash-")))
+                .collect()
+        })
+        .unwrap_or_default();
+    reports.sort();
+    reports.reverse();
+    reports
+}
+
+/// The most recent crash report, if any
+pub fn last_crash() -> Option<CrashReport> {
+    list().into_iter()
+        .find_map(|path| fs::read_to_string(path).ok())
+        .and_then(|contents| serde_json::from_str(&contents).ok())
+}
+
+/// POST the report if the user opted in
+fn submit(report: &CrashReport) {
+    let settings = config::get().crash_reports;
+    let Some(endpoint) = settings.endpoint.filter(|_| settings.submit) else { return };
+
+    let payload = crate::app_info::tag_payload(serde_json::to_value(report).unwrap_or_default()).to_string();
+    let result = Command::new("curl")
+        .args(["-sS", "-m", "10", "-X", "POST", "-H", "Content-Type: application/json"])
+        .arg("-d")
+        .arg(payload)
+        .arg(&endpoint)
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn();
+
+    match result {
+        Ok(_) => eprintln!("[Crash] Submitting report to {}", endpoint),
+        Err(e) => eprintln!("[Crash] Failed to run curl: {}", e),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_report_from_panic() {
+        // The payloads panic! produces: a String when formatted, a &str when literal
+        let formatted: Box<dyn Any + Send> = Box::new(format!("scan failed: {}", 42));
+        let report = report_from("test-monitor".to_string(), formatted.as_ref(), Some(Location::caller()), String::new());
+        assert_eq!(report.thread, "test-monitor");
+        assert_eq!(report.message, "scan failed: 42");
+        assert!(report.location.unwrap().starts_with("src/crash.rs:"));
+
+        let literal: Box<dyn Any + Send> = Box::new("lock poisoned");
+        assert_eq!(report_from(String::new(), literal.as_ref(), None, String::new()).message, "lock poisoned");
+
+        let other: Box<dyn Any + Send> = Box::new(7_u32);
+        let report = report_from(String::new(), other.as_ref(), None, String::new());
+        assert_eq!((report.message.as_str(), report.location), ("Unknown panic", None));
+    }
+}
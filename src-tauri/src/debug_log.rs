@@ -0,0 +1,125 @@
+// Debug Log
+//
+// Verbose diagnostics (debouncer decisions, session ID lookups, ...) that used to be appended
+// forever to files in /tmp. Written as JSON lines ({"ts", "module", "msg"}) to debug.log next
+// to config.json and echoed to stdout; the file is rotated by size (debug.log -> debug.log.1
+// -> debug.log.2, the oldest is dropped). Off by default and free when off: messages are only
+// formatted while enabled. Toggled with set_debug_logging, or CLAUDEMINER_DEBUG=1 at startup.
+//
+
+use once_cell::sync::Lazy;
+use serde::Serialize;
+use std::fs::{self, File, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+const MAX_FILE_BYTES: u64 = 5 * 1024 * 1024;
+const KEEP_ROTATED: usize = 2;
+
+static ENABLED: Lazy<AtomicBool> = Lazy::new(|| {
+    AtomicBool::new(std::env::var("CLAUDEMINER_DEBUG").is_ok_and(|value| value == "1"))
+});
+static FILE: Mutex<Option<LogFile>> = Mutex::new(None);
+
+/// Debug logging state (set_debug_logging)
+#[derive(Debug, Clone, Serialize)]
+pub struct DebugLogStatus {
+    pub enabled: bool,
+    pub path: String,
+}
+
+/// The open log file and its current size
+struct LogFile {
+    file: File,
+    size: u64,
+}
+
+/// Location of the debug log
+pub fn log_path() -> PathBuf {
+    crate::persist::data_dir().join("debug.log")
+}
+
+pub fn is_enabled() -> bool {
+    ENABLED.load(Ordering::Relaxed)
+}
+
+/// Turn debug logging on or off
+pub fn set_enabled(enabled: bool) -> DebugLogStatus {
+    let was_enabled = ENABLED.swap(enabled, Ordering::Relaxed);
+    if was_enabled && !enabled {
+        *FILE.lock().unwrap() = None;  // Close the file; reopened when re-enabled
+    }
+    if was_enabled != enabled {
+        println!("[DebugLog] Debug logging {}", if enabled { "enabled" } else { "disabled" });
+    }
+    DebugLogStatus { enabled, path: log_path().display().to_string() }
+}
+
+/// Log a message from `module`; `message` only runs while debug logging is enabled
+pub fn log(module: &str, message: impl FnOnce() -> String) {
+    if !is_enabled() {
+        return;
+    }
+    let message = message();
+    println!("[{}] {}", module, message);
+
+    let timestamp = SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_millis() as u64).unwrap_or(0);
+    let line = serde_json::json!({ "ts": timestamp, "module": module, "msg": message });
+    append(&format!("{}\n", line));
+}
+
+fn append(line: &str) {
+    let path = log_path();
+    let mut file = FILE.lock().unwrap();
+
+    if file.as_ref().is_some_and(|log| log.size + line.len() as u64 > MAX_FILE_BYTES) {
+        *file = None;
+        rotate(&path, KEEP_ROTATED);
+    }
+    if file.is_none() {
+        *file = OpenOptions::new().create(true).append(true).open(&path).ok().map(|opened| {
+            let size = opened.metadata().map(|m| m.len()).unwrap_or(0);
+            LogFile { file: opened, size }
+        });
+    }
+    if let Some(log) = file.as_mut() {
+        if log.file.write_all(line.as_bytes()).is_ok() {
+            log.size += line.len() as u64;
+        }
+    }
+}
+
+/// Shift `path` to `path.1`, `path.1` to `path.2`, ... keeping `keep` rotated files
+fn rotate(path: &Path, keep: usize) {
+    let rotated = |index: usize| PathBuf::from(format!("{}.{}", path.display(), index));
+    let _ = fs::remove_file(rotated(keep));
+    for index in (1..keep).rev() {
+        let _ = fs::rename(rotated(index), rotated(index + 1));
+    }
+    let _ = fs::rename(path, rotated(1));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rotate_keeps_newest_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("debug.log");
+        let read = |name: &str| fs::read_to_string(dir.path().join(name)).ok();
+
+        for generation in ["first", "second", "third"] {
+            fs::write(&path, generation).unwrap();
+            rotate(&path, 2);
+        }
+
+        assert_eq!(read("debug.log"), None);
+        assert_eq!(read("debug.log.1").as_deref(), Some("third"));
+        assert_eq!(read("debug.log.2").as_deref(), Some("second"));
+        assert_eq!(read("debug.log.3"), None);
+    }
+}
@@ -0,0 +1,233 @@
+// Deep Links
+//
+// claudeminer:// URLs, so notifications, chat messages and scripts can point at the app:
+// - claudeminer://session/<id>  show the window and focus that session's card (id prefix ok)
+// - claudeminer://kill/<pid>    show the window and ask before killing the process
+// How URLs arrive:
+// - macOS: Apple Event (kAEGetURL), registered before the event loop starts; the scheme
+//   itself is declared in Info.plist (CFBundleURLTypes)
+// - Linux/Windows: the OS starts a second instance with the URL as an argument; it is
+//   forwarded to the running instance over the hook pipe and the second instance exits
+//   (no pipe on Windows yet, so there the URL is only handled at startup)
+// Links that arrive before the frontend listens are kept until it asks for them.
+//
+
+use once_cell::sync::{Lazy, OnceCell};
+use serde::Serialize;
+use std::sync::Mutex;
+use tauri::Manager;
+
+pub const SCHEME: &str = "claudeminer";
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "action", rename_all = "lowercase")]
+pub enum DeepLink {
+    Session { session_id: String },
+    Kill { pid: u32 },
+}
+
+static APP_HANDLE: OnceCell<tauri::AppHandle> = OnceCell::new();
+
+/// Link received before the frontend was ready (startup argument, launch Apple Event)
+static PENDING: Lazy<Mutex<Option<DeepLink>>> = Lazy::new(|| Mutex::new(None));
+
+/// Initialize with the AppHandle (called once during app setup)
+pub fn init(app_handle: tauri::AppHandle) {
+    if APP_HANDLE.set(app_handle).is_err() {
+        eprintln!("[DeepLink] Warning: AppHandle already initialized");
+    }
+}
+
+/// Parse a claudeminer:// URL
+pub fn parse(url: &str) -> Option<DeepLink> {
+    let (scheme, rest) = url.trim().split_once("://")?;
+    if !scheme.eq_ignore_ascii_case(SCHEME) {
+        return None;
+    }
+
+    // Drop query/fragment and trailing slashes
+    let rest = rest.split(['?', '#']).next().unwrap_or("").trim_end_matches('/');
+    let (action, argument) = rest.split_once('/')?;
+
+    match action.to_ascii_lowercase().as_str() {
+        "session" => {
+            let valid = !argument.is_empty()
+                && argument.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+            valid.then(|| DeepLink::Session { session_id: argument.to_string() })
+        }
+        "kill" => argument.parse::<u32>().ok()
+            .filter(|pid| *pid > 0)
+            .map(|pid| DeepLink::Kill { pid }),
+        _ => None,
+    }
+}
+
+/// First claudeminer:// URL among the command line arguments
+pub fn find_in_args(args: impl IntoIterator<Item = String>) -> Option<String> {
+    let prefix = format!("{}://", SCHEME);
+    args.into_iter().find(|arg| arg.to_ascii_lowercase().starts_with(&prefix))
+}
+
+/// Handle a URL: bring the window forward and hand the link to the frontend
+pub fn handle(url: &str) {
+    let Some(link) = parse(url) else {
+        println!("[DeepLink] ⚠️ Ignoring unsupported URL: {}", url);
+        return;
+    };
+    println!("[DeepLink] 🔗 {:?}", link);
+
+    let Some(handle) = APP_HANDLE.get() else {
+        // Launch URL on macOS: the app isn't set up yet
+        *PENDING.lock().unwrap() = Some(link);
+        return;
+    };
+
+    if let Some(window) = handle.get_window("main") {
+        let _ = window.show();
+        let _ = window.unminimize();
+        let _ = window.set_focus();
+    }
+
+    if let Err(e) = crate::event::schema::emit(handle, crate::event::schema::EventPayload::DeepLink(&link)) {
+        eprintln!("[DeepLink] Failed to emit deep-link: {}", e);
+    }
+}
+
+/// Keep a link for the frontend to pick up once it has loaded
+pub fn set_pending(url: &str) {
+    if let Some(link) = parse(url) {
+        *PENDING.lock().unwrap() = Some(link);
+    }
+}
+
+/// Take the link that arrived before the frontend was ready
+pub fn take_pending() -> Option<DeepLink> {
+    PENDING.lock().unwrap().take()
+}
+
+/// Register the URL scheme with the OS (Linux: xdg desktop entry, Windows: HKCU class)
+/// macOS reads it from Info.plist
+pub fn register_scheme() {
+    let Ok(exe) = std::env::current_exe() else { return };
+
+    #[cfg(target_os = "linux")]
+    {
+        let Some(applications) = dirs::data_dir().map(|d| d.join("applications")) else { return };
+        let desktop_file = applications.join("claudeminer-url-handler.desktop");
+        let entry = format!(
+            "[Desktop Entry]\nType=Application\nName=ClaudeMiner\nExec=\"{}\" %u\nNoDisplay=true\nMimeType=x-scheme-handler/{};\n",
+            exe.display(),
+            SCHEME
+        );
+        if std::fs::read_to_string(&desktop_file).ok().as_deref() == Some(entry.as_str()) {
+            return;
+        }
+        if let Err(e) = std::fs::create_dir_all(&applications).and_then(|_| std::fs::write(&desktop_file, entry)) {
+            eprintln!("[DeepLink] Failed to write {}: {}", desktop_file.display(), e);
+            return;
+        }
+        let _ = std::process::Command::new("xdg-mime")
+            .args(["default", "claudeminer-url-handler.desktop", &format!("x-scheme-handler/{}", SCHEME)])
+            .status();
+        println!("[DeepLink] Registered {}:// handler", SCHEME);
+    }
+
+    #[cfg(target_os = "windows")]
+    {
+        let key = format!("HKCU\\Software\\Classes\\{}", SCHEME);
+        let command = format!("\"{}\" \"%1\"", exe.display());
+        let entries: [(String, Option<&str>, String); 3] = [
+            (key.clone(), None, "URL:ClaudeMiner".to_string()),
+            (key.clone(), Some("URL Protocol"), String::new()),
+            (format!("{}\\shell\\open\\command", key), None, command),
+        ];
+        for (key, name, value) in entries {
+            let mut reg = std::process::Command::new("reg");
+            reg.args(["add", &key, "/f", "/d", &value]);
+            match name {
+                Some(name) => reg.args(["/v", name]),
+                None => reg.arg("/ve"),
+            };
+            if let Err(e) = reg.output() {
+                eprintln!("[DeepLink] Failed to register {}:// handler: {}", SCHEME, e);
+                return;
+            }
+        }
+        println!("[DeepLink] Registered {}:// handler", SCHEME);
+    }
+
+    #[cfg(not(any(target_os = "linux", target_os = "windows")))]
+    let _ = exe;
+}
+
+/// Receive URLs sent to the running app (kAEGetURL)
+/// Must be called on the main thread before the event loop starts to catch the launch URL
+#[cfg(target_os = "macos")]
+pub fn register_url_event_handler() {
+    use cocoa::base::{id, nil};
+    use objc::declare::ClassDecl;
+    use objc::runtime::{Object, Sel};
+    use objc::{class, msg_send, sel, sel_impl};
+
+    const INTERNET_EVENT_CLASS: u32 = u32::from_be_bytes(*b"GURL");
+    const GET_URL_EVENT: u32 = u32::from_be_bytes(*b"GURL");
+    const DIRECT_OBJECT_KEYWORD: u32 = u32::from_be_bytes(*b"----");
+
+    extern "C" fn handle_get_url_event(_this: &Object, _cmd: Sel, event: id, _reply: id) {
+        unsafe {
+            let descriptor: id = msg_send![event, paramDescriptorForKeyword: DIRECT_OBJECT_KEYWORD];
+            if descriptor == nil {
+                return;
+            }
+            let string: id = msg_send![descriptor, stringValue];
+            if string == nil {
+                return;
+            }
+            let utf8: *const std::os::raw::c_char = msg_send![string, UTF8String];
+            let url = std::ffi::CStr::from_ptr(utf8).to_string_lossy().into_owned();
+            handle(&url);
+        }
+    }
+
+    unsafe {
+        let Some(mut decl) = ClassDecl::new("ClaudeMinerURLEventHandler", class!(NSObject)) else { return };
+        decl.add_method(
+            sel!(handleGetURLEvent:withReplyEvent:),
+            handle_get_url_event as extern "C" fn(&Object, Sel, id, id),
+        );
+        let handler_class = decl.register();
+        let handler: id = msg_send![handler_class, new];
+
+        let manager: id = msg_send![class!(NSAppleEventManager), sharedAppleEventManager];
+        let _: () = msg_send![manager,
+            setEventHandler: handler
+            andSelector: sel!(handleGetURLEvent:withReplyEvent:)
+            forEventClass: INTERNET_EVENT_CLASS
+            andEventID: GET_URL_EVENT];
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(
+            parse("claudeminer://session/3f2a9c1e-77b0"),
+            Some(DeepLink::Session { session_id: "3f2a9c1e-77b0".to_string() })
+        );
+        assert_eq!(parse("ClaudeMiner://kill/4242/"), Some(DeepLink::Kill { pid: 4242 }));
+        assert_eq!(parse("claudeminer://kill/4242?from=slack"), Some(DeepLink::Kill { pid: 4242 }));
+
+        assert_eq!(parse("claudeminer://kill/0"), None);
+        assert_eq!(parse("claudeminer://kill/abc"), None);
+        assert_eq!(parse("claudeminer://session/"), None);
+        assert_eq!(parse("claudeminer://session/../etc"), None);
+        assert_eq!(parse("claudeminer://open/1"), None);
+        assert_eq!(parse("https://session/abc"), None);
+
+        let args = vec!["claude-miner".to_string(), "claudeminer://kill/1".to_string()];
+        assert_eq!(find_in_args(args), Some("claudeminer://kill/1".to_string()));
+    }
+}
@@ -0,0 +1,270 @@
+// Demo Mode
+//
+// `--demo` (or CLAUDEMINER_DEMO=1) replaces the process, log, transcript and network
+// monitors with a synthetic event generator: fake sessions appear, flap between working
+// and resting, ask for permission, turn into zombies and end, all through the normal
+// MonitorEvent queue. Useful for UI work and screenshots without real Claude sessions.
+//
+// Synthetic sessions get PIDs from DEMO_PID_BASE up, which no OS hands out; liveness and
+// TTY probes (and kill_miner) ask this module about them instead of the OS.
+//
+
+use crate::crash::spawn_named;
+use crate::session::{current_timestamp, ApprovalEvent, EventSender, HookEvent, LogEvent, MonitorEvent, TranscriptStats};
+use crate::types::WorkingState;
+use once_cell::sync::Lazy;
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::thread;
+use std::time::Duration;
+
+const DEMO_PID_BASE: u32 = 90_000_000;
+const TICK: Duration = Duration::from_secs(3);
+const TARGET_SESSIONS: usize = 6;
+const MAX_ZOMBIES: usize = 2;
+const ZOMBIE_EXIT_SECS: u64 = 180;  // Unkilled zombies end by themselves after this
+
+const PROJECTS: &[&str] = &[
+    "~/code/claudeminer",
+    "~/code/webapp",
+    "~/code/api-server",
+    "~/code/docs-site",
+    "~/code/ml-pipeline",
+    "~/code/infra",
+];
+const TOOLS: &[&str] = &["Bash", "Edit", "Write", "WebFetch"];
+
+/// Is demo mode on? (`--demo` argument or CLAUDEMINER_DEMO=1)
+pub fn enabled() -> bool {
+    static ENABLED: Lazy<bool> = Lazy::new(|| {
+        std::env::args().any(|arg| arg == "--demo")
+            || std::env::var("CLAUDEMINER_DEMO").is_ok_and(|v| v == "1" || v == "true")
+    });
+    *ENABLED
+}
+
+/// Synthetic processes: PID -> state
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Process {
+    Running,
+    TtyLost,  // Zombie: alive without a terminal
+    Dead,
+}
+
+static PROCESSES: Lazy<Mutex<HashMap<u32, Process>>> = Lazy::new(|| Mutex::new(HashMap::new()));
+
+pub fn is_synthetic(pid: u32) -> bool {
+    pid >= DEMO_PID_BASE && PROCESSES.lock().unwrap().contains_key(&pid)
+}
+
+pub fn is_alive(pid: u32) -> bool {
+    matches!(PROCESSES.lock().unwrap().get(&pid), Some(Process::Running | Process::TtyLost))
+}
+
+pub fn is_tty_lost(pid: u32) -> bool {
+    PROCESSES.lock().unwrap().get(&pid) == Some(&Process::TtyLost)
+}
+
+/// "Kill" a synthetic process; the generator ends its session on the next tick
+pub fn kill(pid: u32) -> bool {
+    match PROCESSES.lock().unwrap().get_mut(&pid) {
+        Some(process) if *process != Process::Dead => {
+            *process = Process::Dead;
+            println!("[Demo] Killed synthetic process {}", pid);
+            true
+        }
+        _ => false,
+    }
+}
+
+fn set_process(pid: u32, process: Process) {
+    PROCESSES.lock().unwrap().insert(pid, process);
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Phase {
+    Resting,
+    Working,
+    Approval,
+    Zombie { since: u64 },
+}
+
+#[derive(Debug)]
+struct FakeSession {
+    session_id: String,
+    pid: u32,
+    phase: Phase,
+}
+
+/// Scripted sessions; each step returns the events to send
+struct Script {
+    sessions: Vec<FakeSession>,
+    next_pid: u32,
+    rng: u64,
+}
+
+impl Script {
+    fn new(seed: u64) -> Self {
+        Self { sessions: Vec::new(), next_pid: DEMO_PID_BASE, rng: seed | 1 }
+    }
+
+    /// xorshift64: deterministic for a seed, good enough for a demo
+    fn roll(&mut self, percent: u64) -> bool {
+        self.rng ^= self.rng << 13;
+        self.rng ^= self.rng >> 7;
+        self.rng ^= self.rng << 17;
+        self.rng % 100 < percent
+    }
+
+    fn pick<'a>(&mut self, items: &[&'a str]) -> &'a str {
+        self.roll(0);
+        items[(self.rng % items.len() as u64) as usize]
+    }
+
+    fn step(&mut self, now: u64) -> Vec<MonitorEvent> {
+        let mut events = Vec::new();
+
+        // Killed from the UI (or ended by themselves): end the session
+        let mut ended = Vec::new();
+        for (index, session) in self.sessions.iter().enumerate() {
+            let expired = matches!(session.phase, Phase::Zombie { since } if now.saturating_sub(since) >= ZOMBIE_EXIT_SECS);
+            if !is_alive(session.pid) || expired {
+                set_process(session.pid, Process::Dead);
+                events.push(hook(&session.session_id, "end", now));
+                ended.push(index);
+            }
+        }
+        for index in ended.into_iter().rev() {
+            self.sessions.remove(index);
+        }
+
+        // Keep the mine populated, one newcomer per step
+        if self.sessions.len() < TARGET_SESSIONS {
+            events.extend(self.spawn(now));
+        }
+
+        let mut zombies = self.sessions.iter().filter(|s| matches!(s.phase, Phase::Zombie { .. })).count();
+        for index in 0..self.sessions.len() {
+            let phase = self.sessions[index].phase;
+            let next = match phase {
+                Phase::Resting if zombies < MAX_ZOMBIES && self.roll(3) => Phase::Zombie { since: now },
+                Phase::Resting if self.roll(35) => Phase::Working,
+                Phase::Working if self.roll(6) => Phase::Approval,
+                Phase::Working if self.roll(25) => Phase::Resting,
+                Phase::Approval if self.roll(40) => Phase::Working,
+                _ => phase,
+            };
+            if next == phase {
+                continue;
+            }
+            if matches!(next, Phase::Zombie { .. }) {
+                zombies += 1;
+            }
+
+            let tool = self.pick(TOOLS);
+            let session = &mut self.sessions[index];
+            session.phase = next;
+            let id = session.session_id.clone();
+            match next {
+                Phase::Working => events.push(hook(&id, "working", now)),
+                Phase::Resting => events.push(hook(&id, "resting", now)),
+                Phase::Approval => events.push(MonitorEvent::Approval(ApprovalEvent {
+                    sid: id,
+                    message: format!("Claude needs your permission to use {}", tool),
+                    profile: None,
+                    timestamp: now,
+                })),
+                Phase::Zombie { .. } => {
+                    // The terminal goes away; the next log event makes the manager notice
+                    set_process(session.pid, Process::TtyLost);
+                    events.push(log(&id, session.pid, now, None));
+                }
+            }
+        }
+        events
+    }
+
+    fn spawn(&mut self, now: u64) -> Vec<MonitorEvent> {
+        let project = self.pick(PROJECTS);
+        let session_id = uuid::Uuid::new_v4().to_string();
+        let pid = self.next_pid;
+        self.next_pid += 1;
+        set_process(pid, Process::Running);
+        println!("[Demo] New synthetic session {} (PID {}) in {}", &session_id[..8], pid, project);
+
+        self.sessions.push(FakeSession { session_id: session_id.clone(), pid, phase: Phase::Resting });
+        // A transcript line introduces the PID and project, the start hook makes it hook-managed
+        vec![log(&session_id, pid, now, Some(project)), hook(&session_id, "start", now)]
+    }
+}
+
+fn hook(session_id: &str, evt: &str, now: u64) -> MonitorEvent {
+    MonitorEvent::Hook(HookEvent { sid: session_id.to_string(), evt: evt.to_string(), profile: None, timestamp: now, pid: None })
+}
+
+fn log(session_id: &str, pid: u32, now: u64, project: Option<&str>) -> MonitorEvent {
+    MonitorEvent::Log(LogEvent {
+        session_id: session_id.to_string(),
+        pid: Some(pid),
+        timestamp: now,
+        state: WorkingState::Idle,
+        has_approval_pending: false,
+        file_mtime: now,
+        transcript: project.map(|cwd| TranscriptStats { cwd: Some(cwd.to_string()), ..Default::default() }),
+        profile: None,
+    })
+}
+
+/// Start the synthetic event generator
+pub fn start_generator(event_sender: EventSender) -> thread::JoinHandle<()> {
+    println!("[Demo] 🎬 Demo mode: generating synthetic sessions");
+    spawn_named("demo-generator", move || {
+        let mut script = Script::new(current_timestamp());
+        loop {
+            for event in script.step(current_timestamp()) {
+                if event_sender.send(event).is_err() {
+                    println!("[Demo] Event queue closed, stopping");
+                    return;
+                }
+            }
+            thread::sleep(TICK);
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_script_lifecycle() {
+        let mut script = Script::new(42);
+        let mut now = 1_000;
+        let mut hooks: HashMap<String, Vec<String>> = HashMap::new();
+        for _ in 0..200 {
+            for event in script.step(now) {
+                if let MonitorEvent::Hook(hook) = event {
+                    hooks.entry(hook.sid).or_default().push(hook.evt);
+                }
+            }
+            assert!(script.sessions.len() <= TARGET_SESSIONS);
+            assert!(script.sessions.iter().filter(|s| matches!(s.phase, Phase::Zombie { .. })).count() <= MAX_ZOMBIES);
+            now += 3;
+        }
+
+        // Every session starts first; ended ones end last
+        assert!(hooks.len() >= TARGET_SESSIONS);
+        for evts in hooks.values() {
+            assert_eq!(evts[0], "start");
+            assert!(!evts[..evts.len() - 1].contains(&"end".to_string()));
+        }
+        assert!(hooks.values().flatten().any(|e| e == "working"));
+
+        // A killed session ends on the next step
+        let victim = script.sessions[0].pid;
+        assert!(is_synthetic(victim) && kill(victim));
+        let events = script.step(now);
+        assert!(events.iter().any(|e| matches!(e, MonitorEvent::Hook(h) if h.evt == "end")));
+        assert!(!is_alive(victim));
+    }
+}
@@ -0,0 +1,227 @@
+// Doctor
+//
+// Environment diagnostics for "why doesn't ClaudeMiner see my sessions?":
+// debug directories, hook registration, the hook pipe, notification permission,
+// access to ~/.claude (macOS Full Disk Access), the Claude Code version and the ps/lsof
+// tools detection uses.
+// Each check carries an optional fix-it action; the UI runs it through run_doctor_fix.
+//
+
+use crate::claude_cli::{self, Compatibility};
+use crate::config;
+use crate::hooks::{manager, pipe};
+use crate::permissions::{self, PermissionAction, PermissionState, PermissionStatus};
+use crate::session::finder::get_claude_debug_dirs;
+use serde::Serialize;
+use std::fs;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CheckStatus {
+    Ok,
+    Warning,
+    Error,
+}
+
+/// Fix-it action offered with a failed check
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct FixAction {
+    pub id: &'static str,
+    pub label: &'static str,
+}
+
+pub const FIX_REGISTER_HOOKS: FixAction = FixAction { id: "register_hooks", label: "Register hooks" };
+pub const FIX_RECREATE_PIPE: FixAction = FixAction { id: "recreate_pipe", label: "Recreate pipe" };
+pub const FIX_REQUEST_NOTIFICATIONS: FixAction = FixAction { id: "request_notification_permission", label: "Ask for permission" };
+pub const FIX_NOTIFICATION_SETTINGS: FixAction = FixAction { id: "open_notification_settings", label: "Open Notification Settings" };
+pub const FIX_FULL_DISK_ACCESS: FixAction = FixAction { id: "open_full_disk_access", label: "Open Privacy Settings" };
+
+#[derive(Debug, Clone, Serialize)]
+pub struct DoctorCheck {
+    pub id: &'static str,
+    pub label: &'static str,
+    pub status: CheckStatus,
+    pub detail: String,
+    pub fix: Option<FixAction>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct DoctorReport {
+    pub checks: Vec<DoctorCheck>,
+    pub healthy: bool,  // No errors (warnings allowed)
+}
+
+fn check(id: &'static str, label: &'static str, status: CheckStatus, detail: String, fix: Option<FixAction>) -> DoctorCheck {
+    DoctorCheck { id, label, status, detail, fix }
+}
+
+/// Run every check
+pub fn run() -> DoctorReport {
+    let permissions = permissions::get();
+    let mut checks = vec![
+        check_debug_dirs(),
+        check_hooks(),
+        check_pipe(),
+        from_permission("notifications", "Notification permission", permissions.notifications, CheckStatus::Warning),
+        from_permission("claude_access", "Access to Claude home", permissions.claude_dir, CheckStatus::Error),
+        check_claude_version(),
+    ];
+    checks.extend(check_tools());
+
+    let healthy = checks.iter().all(|c| c.status != CheckStatus::Error);
+    println!("[Doctor] {} checks, healthy: {}", checks.len(), healthy);
+    DoctorReport { checks, healthy }
+}
+
+fn check_debug_dirs() -> DoctorCheck {
+    let dirs = get_claude_debug_dirs();
+    let readable: Vec<String> = dirs.iter()
+        .filter(|dir| fs::read_dir(dir).is_ok())
+        .map(|dir| dir.display().to_string())
+        .collect();
+
+    if readable.is_empty() {
+        let searched: Vec<String> = dirs.iter().map(|d| d.display().to_string()).collect();
+        check("debug_dir", "Claude debug logs", CheckStatus::Warning,
+            format!("No readable debug directory ({}); log-based detection is off", searched.join(", ")), None)
+    } else {
+        check("debug_dir", "Claude debug logs", CheckStatus::Ok, readable.join(", "), None)
+    }
+}
+
+fn check_hooks() -> DoctorCheck {
+    if !config::get().hooks.enabled {
+        return check("hooks", "Claude Code hooks", CheckStatus::Warning,
+            "Disabled in settings; status updates rely on CPU/log detection".to_string(), None);
+    }
+
+    let mut missing = Vec::new();
+    for (i, (_, path)) in manager::get_settings_paths().iter().enumerate() {
+        if i > 0 && !path.parent().is_some_and(|p| p.is_dir()) {
+            continue;
+        }
+        match manager::read_settings(path) {
+            Ok(settings) if manager::hooks_up_to_date(&settings) => {}
+            Ok(_) => missing.push(path.display().to_string()),
+            Err(e) => missing.push(format!("{} ({})", path.display(), e)),
+        }
+    }
+
+    if missing.is_empty() {
+        check("hooks", "Claude Code hooks", CheckStatus::Ok, "Registered in every Claude home".to_string(), None)
+    } else {
+        check("hooks", "Claude Code hooks", CheckStatus::Error,
+            format!("Missing or outdated in {}", missing.join(", ")), Some(FIX_REGISTER_HOOKS))
+    }
+}
+
+fn check_pipe() -> DoctorCheck {
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::FileTypeExt;
+
+        match fs::metadata(pipe::path()) {
+            Ok(metadata) if metadata.file_type().is_fifo() => {
+                check("pipe", "Hook pipe", CheckStatus::Ok, pipe::path().to_string(), None)
+            }
+            Ok(_) => check("pipe", "Hook pipe", CheckStatus::Error,
+                format!("{} exists but is not a named pipe", pipe::path()), Some(FIX_RECREATE_PIPE)),
+            Err(e) => check("pipe", "Hook pipe", CheckStatus::Error,
+                format!("{}: {}", pipe::path(), e), Some(FIX_RECREATE_PIPE)),
+        }
+    }
+
+    #[cfg(not(unix))]
+    check("pipe", "Hook pipe", CheckStatus::Warning,
+        format!("Named pipes are not supported on this platform ({})", pipe::path()), None)
+}
+
+fn check_claude_version() -> DoctorCheck {
+    let cli = claude_cli::get();
+    match (cli.compatibility, cli.version) {
+        (Compatibility::Supported, Some(version)) => check("claude_version", "Claude Code version", CheckStatus::Ok, version, None),
+        (Compatibility::Newer, Some(version)) => check("claude_version", "Claude Code version", CheckStatus::Warning,
+            format!("{} is newer than the versions ClaudeMiner was tested with (up to {}.x); status detection may be unreliable",
+                version, cli.tested_version), None),
+        (_, _) => check("claude_version", "Claude Code version", CheckStatus::Warning,
+            match cli.path {
+                Some(path) => format!("Could not read the version of {}", path),
+                None => "Claude Code CLI not found".to_string(),
+            }, None),
+    }
+}
+
+/// Permission check; a denied permission counts as `denied_status`
+fn from_permission(id: &'static str, label: &'static str, permission: PermissionStatus, denied_status: CheckStatus) -> DoctorCheck {
+    let status = match permission.state {
+        PermissionState::Granted => CheckStatus::Ok,
+        PermissionState::Denied => denied_status,
+        PermissionState::Unknown => CheckStatus::Warning,
+    };
+    let fix = permission.action.map(|action| match action {
+        PermissionAction::RequestNotifications => FIX_REQUEST_NOTIFICATIONS,
+        PermissionAction::OpenNotificationSettings => FIX_NOTIFICATION_SETTINGS,
+        PermissionAction::OpenFullDiskAccess => FIX_FULL_DISK_ACCESS,
+    });
+    check(id, label, status, permission.detail, fix)
+}
+
+/// External tools used for process/terminal detection
+fn check_tools() -> Vec<DoctorCheck> {
+    if cfg!(windows) {
+        return Vec::new();
+    }
+
+    [("ps", "ps command"), ("lsof", "lsof command")].into_iter()
+        .map(|(tool, label)| match find_in_path(tool) {
+            Some(path) => check(tool, label, CheckStatus::Ok, path, None),
+            None => check(tool, label, CheckStatus::Warning, format!("{} not found in PATH", tool), None),
+        })
+        .collect()
+}
+
+fn find_in_path(tool: &str) -> Option<String> {
+    let path = std::env::var_os("PATH")?;
+    std::env::split_paths(&path)
+        .map(|dir| dir.join(tool))
+        .find(|candidate| candidate.is_file())
+        .map(|candidate| candidate.display().to_string())
+}
+
+/// Run a fix-it action that the backend can perform
+pub fn fix(action_id: &str) -> Result<(), String> {
+    match action_id {
+        "register_hooks" => {
+            config::update(|c| c.hooks.enabled = true)
+                .map_err(|e| format!("Failed to save config: {}", e))?;
+            crate::hooks::ensure_hooks_registered()
+                .map_err(|e| format!("Failed to register hooks: {}", e))
+        }
+        "recreate_pipe" => crate::hooks::receiver::recreate_pipe(pipe::path())
+            .map_err(|e| format!("Failed to recreate pipe: {}", e)),
+        "request_notification_permission" => permissions::run_action(PermissionAction::RequestNotifications),
+        "open_notification_settings" => permissions::run_action(PermissionAction::OpenNotificationSettings),
+        "open_full_disk_access" => permissions::run_action(PermissionAction::OpenFullDiskAccess),
+        other => Err(format!("Unknown fix: {}", other)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_permission_check() {
+        let granted = PermissionStatus { state: PermissionState::Granted, detail: String::new(), action: None };
+        assert_eq!(from_permission("x", "x", granted, CheckStatus::Error).status, CheckStatus::Ok);
+
+        let denied = PermissionStatus {
+            state: PermissionState::Denied,
+            detail: String::new(),
+            action: Some(PermissionAction::OpenFullDiskAccess),
+        };
+        let check = from_permission("x", "x", denied, CheckStatus::Error);
+        assert_eq!(check.status, CheckStatus::Error);
+        assert_eq!(check.fix, Some(FIX_FULL_DISK_ACCESS));
+    }
+}
@@ -1,17 +1,84 @@
 // Event Emitter Module
 //
 // Centralized Tauri event emission using singleton pattern
-// - Session lifecycle events (created, status changed, terminated)
-// - Tray menu updates
+// - Session lifecycle events (created, status changed, terminated), which also trigger script hooks
+// - Needs-attention changes (blocked on a permission prompt)
+// - Compact session diffs (only changed fields)
+// - Miner list updates
+// - Live session log lines
+// - Focus mode changes
+// - Tray menu updates and Dock badge
+// Payloads are defined in schema.rs
 //
 
+use crate::automation::scripts;
+use crate::config;
+use crate::crash::CrashReport;
+use crate::event::schema::{self, EventPayload};
+use crate::logs::tail::LogLines;
+use crate::notification::focus::{self, FocusStatus};
 use crate::session::SessionState;
-use once_cell::sync::OnceCell;
-use tauri::Manager;
+use crate::status::machine;
+use crate::types::{Miner, SessionStatus};
+use once_cell::sync::{Lazy, OnceCell};
+use serde_json::{Map, Value};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::Mutex;
 
 /// Global AppHandle singleton for event emission
 static APP_HANDLE: OnceCell<tauri::AppHandle> = OnceCell::new();
 
+/// Last emitted state per session (session_id -> serialized SessionState), used to compute diffs
+static LAST_EMITTED: Lazy<Mutex<HashMap<String, Value>>> = Lazy::new(|| Mutex::new(HashMap::new()));
+
+/// (total, working, resting, zombie, needs attention) shown in the tray menu
+type TrayCounts = (u32, u32, u32, u32, u32);
+
+/// Last counts shown in the tray menu
+static TRAY_COUNTS: Lazy<Mutex<TrayCounts>> = Lazy::new(|| Mutex::new((0, 0, 0, 0, 0)));
+
+/// Session actions last shown in the tray menu
+static TRAY_ACTIONS: Lazy<Mutex<Vec<TrayAction>>> = Lazy::new(|| Mutex::new(Vec::new()));
+
+/// At most this many session actions in the tray menu
+const MAX_TRAY_ACTIONS: usize = 5;
+
+/// A session waiting on the user, offered as a tray menu item: the in-app toast with the
+/// notification's buttons can't be seen while the window is hidden in the tray
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TrayAction {
+    Focus { pid: u32, session_id: String },  // Blocked on a permission prompt
+    Kill { pid: u32 },                       // Zombie
+}
+
+impl TrayAction {
+    /// "focus-session:<pid>:<session id>" / "kill-zombie:<pid>"
+    fn menu_id(&self) -> String {
+        match self {
+            TrayAction::Focus { pid, session_id } => format!("focus-session:{}:{}", pid, session_id),
+            TrayAction::Kill { pid } => format!("kill-zombie:{}", pid),
+        }
+    }
+
+    /// The action of a clicked tray menu item, if it is one
+    pub fn from_menu_id(id: &str) -> Option<TrayAction> {
+        if let Some(rest) = id.strip_prefix("focus-session:") {
+            let (pid, session_id) = rest.split_once(':')?;
+            return Some(TrayAction::Focus { pid: pid.parse().ok()?, session_id: session_id.to_string() });
+        }
+        let pid = id.strip_prefix("kill-zombie:")?.parse().ok()?;
+        Some(TrayAction::Kill { pid })
+    }
+
+    fn label(&self) -> String {
+        match self {
+            TrayAction::Focus { pid, .. } => format!("✋ Focus Claude #{} (needs approval)", pid),
+            TrayAction::Kill { pid } => format!("🧟 Kill Zombie #{}", pid),
+        }
+    }
+}
+
 /// Initialize the event emitter with AppHandle
 /// This should be called once during app setup
 pub fn init(app_handle: tauri::AppHandle) {
@@ -26,10 +93,120 @@ fn get_handle() -> Option<&'static tauri::AppHandle> {
     APP_HANDLE.get()
 }
 
-/// Emit session-created event to frontend
+/// Compute changed top-level fields between two serialized sessions
+/// With no previous state every field counts as changed
+fn diff_fields(old: Option<&Value>, new: &Value) -> Map<String, Value> {
+    let mut changes = Map::new();
+
+    if let Some(new_fields) = new.as_object() {
+        let old_fields = old.and_then(|v| v.as_object());
+        for (key, value) in new_fields {
+            if old_fields.and_then(|o| o.get(key)) != Some(value) {
+                changes.insert(key.clone(), value.clone());
+            }
+        }
+    }
+
+    changes
+}
+
+/// Make `session` the state later diffs are computed against
+fn remember_emitted(session: &SessionState) {
+    match serde_json::to_value(session) {
+        Ok(state) => {
+            LAST_EMITTED.lock().unwrap().insert(session.session_id.clone(), state);
+        }
+        Err(e) => eprintln!("[EventEmitter] Failed to serialize session for diff: {}", e),
+    }
+}
+
+/// Full state of the listed sessions for a frontend (re)starting to apply session-diff events;
+/// later diffs are computed against exactly these states
+pub fn resync_sessions(sessions: &HashMap<String, SessionState>) -> Vec<SessionState> {
+    let mut last = LAST_EMITTED.lock().unwrap();
+    let mut listed: Vec<SessionState> = listed_sessions(sessions).into_iter().cloned().collect();
+    listed.sort_by_key(|s| s.started_at);
+    for session in &listed {
+        if let Ok(state) = serde_json::to_value(session) {
+            last.insert(session.session_id.clone(), state);
+        }
+    }
+    listed
+}
+
+/// Emit session-diff event to frontend (changed fields only)
+fn emit_session_diff(session: &SessionState) {
+    let new_state = match serde_json::to_value(session) {
+        Ok(v) => v,
+        Err(e) => {
+            eprintln!("[EventEmitter] Failed to serialize session for diff: {}", e);
+            return;
+        }
+    };
+
+    let changes = {
+        let mut last = LAST_EMITTED.lock().unwrap();
+        let changes = diff_fields(last.get(&session.session_id), &new_state);
+        last.insert(session.session_id.clone(), new_state);
+        changes
+    };
+
+    if changes.is_empty() {
+        return;
+    }
+
+    if let Some(handle) = get_handle() {
+        let diff = EventPayload::SessionDiff { session_id: &session.session_id, changes: &changes };
+        if let Err(e) = schema::emit(handle, diff) {
+            eprintln!("[EventEmitter] Failed to emit session-diff: {}", e);
+        } else {
+            println!("[EventEmitter] 📡 Emitted session-diff for session {} ({} fields)",
+                &session.session_id[..8.min(session.session_id.len())],
+                changes.len());
+        }
+    }
+}
+
+/// A session event as emitted (tests have no AppHandle to listen on)
+#[cfg(test)]
+#[derive(Debug, Clone, PartialEq)]
+pub struct EmittedEvent {
+    pub name: &'static str,
+    pub session_id: String,
+    pub status: SessionStatus,
+}
+
+#[cfg(test)]
+thread_local! {
+    static EMITTED: std::cell::RefCell<Vec<EmittedEvent>> = const { std::cell::RefCell::new(Vec::new()) };
+}
+
+/// Session events emitted on this thread since the last call
+#[cfg(test)]
+pub fn take_emitted() -> Vec<EmittedEvent> {
+    EMITTED.with(|e| e.take())
+}
+
+#[cfg(test)]
+fn record_emitted(name: &'static str, session: &SessionState) {
+    EMITTED.with(|e| e.borrow_mut().push(EmittedEvent {
+        name,
+        session_id: session.session_id.clone(),
+        status: session.current_status,
+    }));
+}
+
+#[cfg(not(test))]
+fn record_emitted(_name: &'static str, _session: &SessionState) {}
+
+/// Emit session-created event to frontend (the full state; later changes arrive as session-diff)
 pub fn emit_session_created(session: &SessionState) {
+    record_emitted("session-created", session);
+    remember_emitted(session);
+    scripts::on_session_created(session);
+
     if let Some(handle) = get_handle() {
-        if let Err(e) = handle.emit_all("session-created", session) {
+        if let Err(e) = schema::emit(handle, EventPayload::SessionCreated(session)) {
             eprintln!("[EventEmitter] Failed to emit session-created: {}", e);
         } else {
             println!("[EventEmitter] 📡 Emitted session-created for session {}",
@@ -40,25 +217,21 @@ pub fn emit_session_created(session: &SessionState) {
     }
 }
 
-/// Emit session-status-changed event to frontend
+/// A session's status changed: emit what changed since its last event as session-diff
 pub fn emit_session_status_changed(session: &SessionState) {
-    if let Some(handle) = get_handle() {
-        if let Err(e) = handle.emit_all("session-status-changed", session) {
-            eprintln!("[EventEmitter] Failed to emit session-status-changed: {}", e);
-        } else {
-            println!("[EventEmitter] 📡 Emitted session-status-changed for session {} (status: {})",
-                &session.session_id[..8.min(session.session_id.len())],
-                session.current_status);
-        }
-    } else {
-        eprintln!("[EventEmitter] ⚠️ Cannot emit session-status-changed: AppHandle not initialized");
-    }
+    record_emitted("session-status-changed", session);
+    emit_session_diff(session);
+    scripts::on_status_changed(session);
 }
 
 /// Emit session-terminated event to frontend
 pub fn emit_session_terminated(session: &SessionState) {
+    record_emitted("session-terminated", session);
+    LAST_EMITTED.lock().unwrap().remove(&session.session_id);
+    scripts::on_session_terminated(session);
+
     if let Some(handle) = get_handle() {
-        if let Err(e) = handle.emit_all("session-terminated", session) {
+        if let Err(e) = schema::emit(handle, EventPayload::SessionTerminated(session)) {
             eprintln!("[EventEmitter] Failed to emit session-terminated: {}", e);
         } else {
             println!("[EventEmitter] 📡 Emitted session-terminated for session {}",
@@ -69,8 +242,200 @@ pub fn emit_session_terminated(session: &SessionState) {
     }
 }
 
+/// Emit session-needs-attention event to frontend (permission prompt raised or answered)
+pub fn emit_session_attention(session: &SessionState) {
+    record_emitted("session-needs-attention", session);
+    emit_session_diff(session);
+
+    let payload = EventPayload::SessionNeedsAttention {
+        session_id: &session.session_id,
+        pid: session.pid,
+        status: session.current_status,
+        needs_attention: session.needs_attention,
+        started_at: session.started_at,
+        status_since: session.status_since,
+        total_working_seconds: session.total_working_seconds,
+    };
+    if let Some(handle) = get_handle() {
+        if let Err(e) = schema::emit(handle, payload) {
+            eprintln!("[EventEmitter] Failed to emit session-needs-attention: {}", e);
+        } else {
+            println!("[EventEmitter] 📡 Emitted session-needs-attention for session {} ({})",
+                &session.session_id[..8.min(session.session_id.len())], session.needs_attention);
+        }
+    }
+}
+
+/// Set the tray title next to the icon (macOS only)
+pub fn set_tray_title(title: &str) {
+    #[cfg(target_os = "macos")]
+    if let Some(handle) = get_handle() {
+        let _ = handle.tray_handle().set_title(title);
+    }
+    #[cfg(not(target_os = "macos"))]
+    let _ = title;
+}
+
+/// Last badge shown on the Dock icon (u32::MAX = none yet)
+static LAST_DOCK_BADGE: AtomicU32 = AtomicU32::new(u32::MAX);
+
+/// Mirror the working count onto the Dock icon badge (macOS; 0 clears it)
+pub fn set_dock_badge(count: u32) {
+    let count = if config::get().tray.dock_badge { count } else { 0 };
+    if LAST_DOCK_BADGE.swap(count, Ordering::Relaxed) != count {
+        show_dock_badge(count);
+    }
+}
+
+#[cfg(target_os = "macos")]
+fn show_dock_badge(count: u32) {
+    if let Some(handle) = get_handle() {
+        let result = handle.run_on_main_thread(move || unsafe {
+            use cocoa::appkit::NSApp;
+            use cocoa::base::{id, nil};
+            use cocoa::foundation::NSString;
+            use objc::{msg_send, sel, sel_impl};
+
+            let dock_tile: id = msg_send![NSApp(), dockTile];
+            if count > 0 {
+                let label = NSString::alloc(nil).init_str(&count.to_string());
+                let _: () = msg_send![dock_tile, setBadgeLabel: label];
+                let _: () = msg_send![label, release];
+            } else {
+                let _: () = msg_send![dock_tile, setBadgeLabel: nil];
+            }
+        });
+        if let Err(e) = result {
+            eprintln!("[EventEmitter] Failed to update Dock badge: {}", e);
+        }
+    }
+}
+
+#[cfg(not(target_os = "macos"))]
+fn show_dock_badge(_count: u32) {}
+
+/// Replace the tray icon; template icons are recolored by macOS to match the menu bar
+pub fn set_tray_icon(icon: tauri::Icon, template: bool) {
+    if let Some(handle) = get_handle() {
+        let tray = handle.tray_handle();
+        #[cfg(target_os = "macos")]
+        let _ = tray.set_icon_as_template(template);
+        #[cfg(not(target_os = "macos"))]
+        let _ = template;
+        if let Err(e) = tray.set_icon(icon) {
+            eprintln!("[EventEmitter] Failed to set tray icon: {}", e);
+        }
+    }
+}
+
+/// Emit app-crash event to frontend (a thread panicked; see crash::last_crash)
+pub fn emit_app_crash(report: &CrashReport) {
+    if let Some(handle) = get_handle() {
+        if let Err(e) = schema::emit(handle, EventPayload::AppCrash(report)) {
+            eprintln!("[EventEmitter] Failed to emit app-crash: {}", e);
+        }
+    }
+}
+
+/// Emit miners-updated event to frontend (full Miner list, same as get_miners)
+pub fn emit_miners_updated(miners: &[Miner]) {
+    if let Some(handle) = get_handle() {
+        if let Err(e) = schema::emit(handle, EventPayload::MinersUpdated { miners }) {
+            eprintln!("[EventEmitter] Failed to emit miners-updated: {}", e);
+        } else {
+            println!("[EventEmitter] 📡 Emitted miners-updated ({} miners)", miners.len());
+        }
+    } else {
+        eprintln!("[EventEmitter] ⚠️ Cannot emit miners-updated: AppHandle not initialized");
+    }
+}
+
+/// Emit session-log-lines event to frontend (lines from a tailed session log)
+pub fn emit_session_log_lines(lines: &LogLines) {
+    if let Some(handle) = get_handle() {
+        if let Err(e) = schema::emit(handle, EventPayload::SessionLogLines(lines)) {
+            eprintln!("[EventEmitter] Failed to emit session-log-lines: {}", e);
+        }
+    }
+}
+
+/// Emit focus-changed event to frontend (focus mode started, ended or queued a completion)
+pub fn emit_focus_changed(status: &FocusStatus) {
+    if let Some(handle) = get_handle() {
+        if let Err(e) = schema::emit(handle, EventPayload::FocusChanged(status)) {
+            eprintln!("[EventEmitter] Failed to emit focus-changed: {}", e);
+        }
+    }
+}
+
+/// Rebuild the tray menu with the last session counts (after focus mode changes)
+pub fn refresh_tray_menu() {
+    let (total, working, resting, zombie, attention) = *TRAY_COUNTS.lock().unwrap();
+    if get_handle().is_some() {
+        let _ = update_tray_menu(total, working, resting, zombie, attention);
+    }
+}
+
+/// Rebuild the tray menu when the listed sessions' counts changed (called by the coordinator
+/// after every sync, so the tray stays current while the window is closed)
+pub fn sync_tray_menu(sessions: &HashMap<String, SessionState>) {
+    let counts = tray_counts(sessions);
+    let actions = tray_actions(sessions);
+    if (*TRAY_COUNTS.lock().unwrap() == counts && *TRAY_ACTIONS.lock().unwrap() == actions) || get_handle().is_none() {
+        return;
+    }
+    *TRAY_ACTIONS.lock().unwrap() = actions;
+    let (total, working, resting, zombie, attention) = counts;
+    if let Err(e) = update_tray_menu(total, working, resting, zombie, attention) {
+        eprintln!("[EventEmitter] Failed to update tray menu: {}", e);
+    }
+}
+
+/// Counts of the sessions in the miner list
+/// Needs attention overlaps the others: a resting session can be blocked on a permission prompt
+fn tray_counts(sessions: &HashMap<String, SessionState>) -> TrayCounts {
+    let listed = listed_sessions(sessions);
+    let count = |status: SessionStatus| listed.iter().filter(|s| s.current_status == status).count() as u32;
+    let attention = listed.iter().filter(|s| s.needs_attention).count() as u32;
+    (listed.len() as u32, count(machine::WORKING), count(machine::RESTING), count(machine::ZOMBIE), attention)
+}
+
+/// Listed sessions blocked on a permission prompt (with a terminal to focus), then killable zombies
+fn tray_actions(sessions: &HashMap<String, SessionState>) -> Vec<TrayAction> {
+    let mut listed = listed_sessions(sessions);
+    listed.sort_by_key(|s| s.pid);
+
+    let focus = listed.iter()
+        .filter(|s| s.needs_attention && s.tty.is_some())
+        .map(|s| TrayAction::Focus { pid: s.pid, session_id: s.session_id.clone() });
+    // Read-only sessions (containers, other users) can't be killed from here
+    let kill = listed.iter()
+        .filter(|s| s.current_status == machine::ZOMBIE && s.container.is_none()
+            && !crate::monitor::user::is_foreign_uid(s.last_cpu_event.as_ref().and_then(|c| c.uid)))
+        .map(|s| TrayAction::Kill { pid: s.pid });
+    focus.chain(kill).take(MAX_TRAY_ACTIONS).collect()
+}
+
+fn listed_sessions(sessions: &HashMap<String, SessionState>) -> Vec<&SessionState> {
+    sessions.iter()
+        .filter(|(id, s)| crate::event::is_listed(id, s, false))
+        .map(|(_, s)| s)
+        .collect()
+}
+
+/// Tray menu label of the focus mode toggle
+fn focus_label(status: &FocusStatus) -> String {
+    match (status.active, status.queued) {
+        (false, _) => format!("🍅 Start Focus ({} min)", focus::DEFAULT_MINUTES),
+        (true, 0) => "🍅 End Focus".to_string(),
+        (true, queued) => format!("🍅 End Focus ({} finished)", queued),
+    }
+}
+
 /// Update tray menu with session statistics
-pub fn update_tray_menu(total: u32, working: u32, resting: u32, zombie: u32) -> Result<(), String> {
+pub fn update_tray_menu(total: u32, working: u32, resting: u32, zombie: u32, attention: u32) -> Result<(), String> {
+    *TRAY_COUNTS.lock().unwrap() = (total, working, resting, zombie, attention);
+    crate::event::status_line::write(working, resting, zombie);
     if let Some(handle) = get_handle() {
         use tauri::{SystemTrayMenu, SystemTrayMenuItem, CustomMenuItem};
 
@@ -79,13 +444,10 @@ pub fn update_tray_menu(total: u32, working: u32, resting: u32, zombie: u32) ->
         // Update tray icon title with working count (macOS only)
         #[cfg(target_os = "macos")]
         {
-            let title = if working > 0 {
-                format!("⛏️ {}", working)
-            } else {
-                String::new() // Empty when no working sessions
-            };
+            let title = crate::event::tray::current_title(Some(working)); // Empty when no working sessions
             let _ = tray.set_title(&title); // Ignore errors on other platforms
         }
+        set_dock_badge(working);
 
         // Update tooltip
         tray.set_tooltip(&format!("ClaudeMiner - {} sessions", total))
@@ -100,27 +462,41 @@ pub fn update_tray_menu(total: u32, working: u32, resting: u32, zombie: u32) ->
             format!("😴 Resting: {}", resting)).disabled();
         let zombie_label = CustomMenuItem::new("zombie".to_string(),
             format!("🧟 Zombie: {}", zombie)).disabled();
+        let attention_label = CustomMenuItem::new("attention".to_string(),
+            format!("✋ Needs Approval: {}", attention)).disabled();
+
+        let focus_item = CustomMenuItem::new("focus".to_string(), focus_label(&focus::status()));
 
         let separator1 = SystemTrayMenuItem::Separator;
         let show = CustomMenuItem::new("show".to_string(), "Show Window");
+        let widget = CustomMenuItem::new("widget".to_string(), "Mini Widget");
+        let overlay = CustomMenuItem::new("overlay".to_string(), "Status Overlay");
         let separator2 = SystemTrayMenuItem::Separator;
         let quit = CustomMenuItem::new("quit".to_string(), "Quit");
 
-        let tray_menu = SystemTrayMenu::new()
+        let mut tray_menu = SystemTrayMenu::new()
             .add_item(stats_label)
             .add_item(working_label)
             .add_item(resting_label)
             .add_item(zombie_label)
+            .add_item(attention_label);
+        for action in TRAY_ACTIONS.lock().unwrap().iter() {
+            tray_menu = tray_menu.add_item(CustomMenuItem::new(action.menu_id(), action.label()));
+        }
+        let tray_menu = tray_menu
             .add_native_item(separator1)
+            .add_item(focus_item)
             .add_item(show)
+            .add_item(widget)
+            .add_item(overlay)
             .add_native_item(separator2)
             .add_item(quit);
 
         tray.set_menu(tray_menu)
             .map_err(|e| e.to_string())?;
 
-        println!("[EventEmitter] 🎯 Updated tray menu: {} sessions (working: {}, resting: {}, zombie: {})",
-            total, working, resting, zombie);
+        println!("[EventEmitter] 🎯 Updated tray menu: {} sessions (working: {}, resting: {}, zombie: {}, needs approval: {})",
+            total, working, resting, zombie, attention);
 
         Ok(())
     } else {
@@ -135,9 +511,57 @@ mod tests {
     #[test]
     fn test_event_emitter_without_init() {
         // Should not panic, just print warnings
-        let session = SessionState::default();
+        let session = SessionState::new_legacy(0, "test-session".to_string());
         emit_session_created(&session);
         emit_session_status_changed(&session);
         emit_session_terminated(&session);
     }
+
+    #[test]
+    fn test_tray_counts() {
+        let mut sessions = HashMap::new();
+        for (id, pid, status) in [("a", 1, machine::WORKING), ("b", 2, machine::RESTING), ("c", 3, machine::ZOMBIE), ("d", 0, machine::RESTING)] {
+            let mut session = SessionState::new_legacy(pid, id.to_string());
+            session.current_status = status;
+            sessions.insert(id.to_string(), session);
+        }
+        sessions.get_mut("b").unwrap().ignored = true;
+
+        // Same sessions as the miner list: ignored and PID-less resting sessions don't count
+        assert_eq!(tray_counts(&sessions), (2, 1, 0, 1, 0));
+
+        // Blocked sessions are counted on top of their status
+        let mut blocked = SessionState::new_legacy(4, "e".to_string());
+        blocked.current_status = machine::RESTING;
+        blocked.needs_attention = true;
+        sessions.insert("e".to_string(), blocked);
+        assert_eq!(tray_counts(&sessions), (3, 1, 1, 1, 1));
+
+        // The zombie can be killed from the tray; the blocked session needs a terminal to focus
+        assert_eq!(tray_actions(&sessions), [TrayAction::Kill { pid: 3 }]);
+        sessions.get_mut("e").unwrap().tty = Some("/dev/ttys004".to_string());
+        let actions = tray_actions(&sessions);
+        assert_eq!(actions, [TrayAction::Focus { pid: 4, session_id: "e".to_string() }, TrayAction::Kill { pid: 3 }]);
+        for action in actions {
+            assert_eq!(TrayAction::from_menu_id(&action.menu_id()), Some(action));
+        }
+        assert_eq!(TrayAction::from_menu_id("quit"), None);
+    }
+
+    #[test]
+    fn test_diff_fields() {
+        let old = serde_json::json!({"pid": 1, "current_status": "resting", "has_terminal": true});
+        let new = serde_json::json!({"pid": 1, "current_status": "working", "has_terminal": true});
+
+        // Only the changed field is included
+        let changes = diff_fields(Some(&old), &new);
+        assert_eq!(changes.len(), 1);
+        assert_eq!(changes.get("current_status"), Some(&serde_json::json!("working")));
+
+        // No previous state: everything is a change
+        assert_eq!(diff_fields(None, &new).len(), 3);
+
+        // Identical state: nothing changed
+        assert!(diff_fields(Some(&new), &new).is_empty());
+    }
 }
@@ -3,6 +3,11 @@
 // This module handles all Tauri event emission using singleton pattern
 
 pub mod emitter;
+pub mod publisher;
+pub mod schema;
+pub mod status_line;
+pub mod tray;
+pub mod tray_icon;
 
 // Re-export public API
 pub use emitter::{
@@ -10,5 +15,11 @@ pub use emitter::{
     emit_session_created,
     emit_session_status_changed,
     emit_session_terminated,
+    emit_session_attention,
+    emit_focus_changed,
+    refresh_tray_menu,
+    sync_tray_menu,
     update_tray_menu,
+    TrayAction,
 };
+pub use publisher::{start_miners_publisher, notify_miners_changed, set_miners_interval, build_miners, is_listed};
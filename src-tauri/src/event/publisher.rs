@@ -0,0 +1,246 @@
+// Miners Publisher Thread
+//
+// Pushes the Miner list to the frontend ("miners-updated") periodically
+// and whenever the coordinator reports a change, so the UI doesn't have to poll get_miners
+//
+
+use crate::config::NotificationOverride;
+use crate::event::emitter::emit_miners_updated;
+use crate::monitor::{energy, matcher, user};
+use crate::session::{current_timestamp, SessionState, SharedSessions};
+use crate::status::machine;
+use crate::types::{Miner, SessionStatus};
+use once_cell::sync::OnceCell;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::mpsc::{channel, Receiver, RecvTimeoutError, Sender};
+use std::thread;
+use std::time::{Duration, Instant};
+use sysinfo::{Pid, System};
+
+const DEFAULT_INTERVAL_MS: u64 = 3000;  // Same cadence as the old frontend polling
+const MIN_INTERVAL_MS: u64 = 500;
+const CHANGE_COALESCE: Duration = Duration::from_millis(100);  // Batch bursts of coordinator updates
+const IDLE_WAIT: Duration = Duration::from_secs(3600);  // Periodic publishing disabled
+
+/// (session_id, pid, status, has_terminal, pinned, ignored, archived, notification override) per session, sorted
+type SessionsSignature = Vec<(String, u32, SessionStatus, bool, bool, bool, bool, Option<NotificationOverride>)>;
+
+/// Periodic publish interval (0 = publish on change only)
+static PUBLISH_INTERVAL_MS: AtomicU64 = AtomicU64::new(DEFAULT_INTERVAL_MS);
+
+/// Change notification channel into the publisher thread
+static CHANGE_SENDER: OnceCell<Sender<()>> = OnceCell::new();
+
+/// Start miners publisher thread
+pub fn start_miners_publisher(
+    shared_sessions: SharedSessions,
+) -> thread::JoinHandle<()> {
+    let (change_sender, change_receiver) = channel();
+    if CHANGE_SENDER.set(change_sender).is_err() {
+        eprintln!("[MinersPublisher] Warning: publisher already started");
+    }
+
+    thread::spawn(move || {
+        run_miners_publisher(shared_sessions, change_receiver);
+    })
+}
+
+/// Tell the publisher that session state changed
+pub fn notify_miners_changed() {
+    if let Some(sender) = CHANGE_SENDER.get() {
+        let _ = sender.send(());
+    }
+}
+
+/// Set the periodic publish interval and trigger an immediate publish
+/// Returns the effective interval (0 = on change only)
+pub fn set_miners_interval(interval_ms: u64) -> u64 {
+    let effective = if interval_ms == 0 { 0 } else { interval_ms.max(MIN_INTERVAL_MS) };
+    PUBLISH_INTERVAL_MS.store(effective, Ordering::Relaxed);
+    println!("[MinersPublisher] Publish interval set to {}ms", effective);
+
+    // Push the current list right away so a new subscriber doesn't wait a full interval
+    notify_miners_changed();
+    effective
+}
+
+fn run_miners_publisher(
+    shared_sessions: SharedSessions,
+    change_receiver: Receiver<()>,
+) {
+    println!("[MinersPublisher] Started");
+
+    let mut sys = System::new();
+    let mut last_signature: Option<SessionsSignature> = None;
+    let mut last_publish = Instant::now();
+
+    loop {
+        let interval_ms = PUBLISH_INTERVAL_MS.load(Ordering::Relaxed);
+        let timeout = if interval_ms == 0 {
+            IDLE_WAIT
+        } else {
+            Duration::from_millis(interval_ms).saturating_sub(last_publish.elapsed())
+        };
+
+        let changed = match change_receiver.recv_timeout(timeout) {
+            Ok(()) => {
+                thread::sleep(CHANGE_COALESCE);
+                while change_receiver.try_recv().is_ok() {}
+                true
+            }
+            Err(RecvTimeoutError::Timeout) => false,
+            Err(RecvTimeoutError::Disconnected) => break,
+        };
+
+        if !changed && interval_ms == 0 {
+            continue;
+        }
+
+        // Snapshot sessions so the store isn't read while refreshing process info
+        let sessions = shared_sessions.snapshot();
+
+        // Change notifications only publish when something visible changed
+        let signature = sessions_signature(&sessions);
+        if changed && last_signature.as_ref() == Some(&signature) {
+            continue;
+        }
+
+        let miners = build_miners(&sessions, &mut sys, false);
+        emit_miners_updated(&miners);
+
+        last_signature = Some(signature);
+        last_publish = Instant::now();
+    }
+
+    println!("[MinersPublisher] Channel disconnected, shutting down");
+}
+
+/// Visible state of all sessions (ignores CPU/memory noise)
+fn sessions_signature(sessions: &HashMap<String, SessionState>) -> SessionsSignature {
+    let mut signature: Vec<_> = sessions.iter()
+        .map(|(id, s)| (id.clone(), s.pid, s.current_status, s.has_terminal, s.pinned, s.ignored, s.archived, s.notification_override))
+        .collect();
+    signature.sort();
+    signature
+}
+
+/// Is the session shown in the miner list? (archived sessions only if asked for)
+pub fn is_listed(session_id: &str, session: &SessionState, include_archived: bool) -> bool {
+    // Skip only truly invalid sessions ($SESSION_ID or sessions with PID=0 that never got a real PID)
+    if session_id == "$SESSION_ID" {
+        return false;
+    }
+
+    // Ignored sessions are tracked but never shown
+    if session.ignored || (session.archived && !include_archived) {
+        return false;
+    }
+
+    // Skip sessions with PID=0 only if they're not working (PID=0 means we haven't discovered the PID yet)
+    session.pid != 0 || session.current_status == machine::WORKING
+}
+
+/// Convert sessions to the Miner list shown in the UI (archived sessions only if asked for)
+/// Only the tracked PIDs are refreshed in `sys`, not the whole process table
+pub fn build_miners(sessions: &HashMap<String, SessionState>, sys: &mut System, include_archived: bool) -> Vec<Miner> {
+    let mut miners = Vec::new();
+
+    for (session_id, session_state) in sessions.iter() {
+        if !is_listed(session_id, session_state, include_archived) {
+            continue;
+        }
+
+        let pid = Pid::from_u32(session_state.pid);
+
+        // Get memory from sysinfo (containerized PIDs aren't host processes)
+        let memory = if session_state.container.is_some() {
+            0
+        } else {
+            sys.refresh_process(pid);
+            sys.process(pid)
+                .map(|p| p.memory())
+                .unwrap_or(0)
+        };
+
+        // Get CPU from last CPU event
+        let cpu = session_state.last_cpu_event.as_ref()
+            .map(|e| e.cpu_percent)
+            .unwrap_or(0.0);
+
+        miners.push(Miner {
+            session_id: session_id.clone(),
+            pid: session_state.pid,
+            cpu_usage: cpu,
+            energy_impact: if session_state.container.is_some() { None } else { energy::energy_impact(session_state.pid) },
+            memory,
+            status: session_state.current_status.to_string(),
+            has_terminal: session_state.has_terminal,
+            name: matcher::label_for(&session_state.kind),
+            pinned: session_state.pinned,
+            archived: session_state.archived,
+            project_path: session_state.project_path.clone(),
+            profile: session_state.profile.clone(),
+            kind: session_state.kind.clone(),
+            read_only: session_state.container.is_some()
+                || user::is_foreign_uid(session_state.last_cpu_event.as_ref().and_then(|e| e.uid)),
+            container: session_state.container.clone(),
+            activity: session_state.activity().map(str::to_string),
+            needs_attention: session_state.needs_attention,
+            idle_secs: session_state.idle_secs(current_timestamp()),
+            started_at: session_state.started_at,
+            status_since: session_state.status_since,
+            total_working_seconds: session_state.working_seconds(current_timestamp()),
+            notification_override: session_state.notification_override,
+        });
+    }
+
+    // Pinned sessions first
+    miners.sort_by_key(|m| !m.pinned);
+
+    miners
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_build_miners_skips_invalid_sessions() {
+        let mut sessions = HashMap::new();
+        sessions.insert("$SESSION_ID".to_string(), SessionState::new_legacy(1234, "$SESSION_ID".to_string()));
+
+        let mut no_pid = SessionState::new_hook("no-pid".to_string());
+        no_pid.current_status = machine::RESTING;
+        sessions.insert("no-pid".to_string(), no_pid);
+
+        let mut working = SessionState::new_legacy(999_999_001, "working".to_string());
+        working.current_status = machine::WORKING;
+        sessions.insert("working".to_string(), working);
+
+        let miners = build_miners(&sessions, &mut System::new(), false);
+        assert_eq!(miners.len(), 1);
+        assert_eq!(miners[0].pid, 999_999_001);
+        assert_eq!(miners[0].status, "working");
+    }
+
+    #[test]
+    fn test_signature_ignores_cpu() {
+        let mut sessions = HashMap::new();
+        sessions.insert("a".to_string(), SessionState::new_legacy(1, "a".to_string()));
+        let before = sessions_signature(&sessions);
+
+        sessions.get_mut("a").unwrap().last_cpu_event = Some(crate::session::CpuEvent {
+            pid: 1,
+            timestamp: 0,
+            cpu_percent: 42.0,
+            kind: "claude".to_string(),
+            uid: None,
+            container: None,
+        });
+        assert_eq!(before, sessions_signature(&sessions));
+
+        sessions.get_mut("a").unwrap().current_status = machine::WORKING;
+        assert_ne!(before, sessions_signature(&sessions));
+    }
+}
@@ -0,0 +1,133 @@
+// Event Payload Schema
+//
+// Every event the backend emits to the webviews, as one internally tagged enum: the Tauri
+// event name doubles as the `event` tag, and emit() adds `schema_version` to the payload,
+// so listeners receive { schema_version, event, ...fields }. Payloads that are structs keep
+// their fields at the top level; lists and plain values sit in a named field.
+//
+// build.rs turns this enum and the types it references into TypeScript definitions
+// (../src/schema.d.ts). Bump EVENT_SCHEMA_VERSION when a payload changes incompatibly.
+//
+
+use crate::crash::CrashReport;
+use crate::deeplink::DeepLink;
+use crate::logs::tail::LogLines;
+use crate::notification::focus::FocusStatus;
+use crate::notification::sender::{NotificationAction, NotificationKind};
+use crate::session::SessionState;
+use crate::types::{Miner, SessionStatus};
+use crate::updater::{StagedUpdate, UpdateProgress};
+use serde::Serialize;
+use serde_json::{Map, Value};
+use tauri::Manager;
+
+/// Version of the event payload format
+/// 2: payloads are tagged with `event`; miners-updated and update-failed wrap their values
+/// 3: status changes arrive as session-diff only (no full session-status-changed payload)
+pub const EVENT_SCHEMA_VERSION: u32 = 3;
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "event", rename_all = "kebab-case")]
+pub enum EventPayload<'a> {
+    /// A session was detected
+    SessionCreated(&'a SessionState),
+    /// A session ended (process exited, killed or removed)
+    SessionTerminated(&'a SessionState),
+    /// Only the session fields that changed since its last event (session-created or
+    /// get_session_states has the full state to apply it to)
+    SessionDiff {
+        session_id: &'a str,
+        changes: &'a Map<String, Value>,
+    },
+    /// Blocked on a permission prompt, or the prompt was answered
+    SessionNeedsAttention {
+        session_id: &'a str,
+        pid: u32,
+        status: SessionStatus,
+        needs_attention: bool,
+        started_at: u64,
+        status_since: u64,
+        total_working_seconds: u64,
+    },
+    /// The full miner list (same as get_miners)
+    MinersUpdated { miners: &'a [Miner] },
+    /// New lines from a tailed session log
+    SessionLogLines(&'a LogLines),
+    /// Focus mode started, ended or queued a completion
+    FocusChanged(&'a FocusStatus),
+    /// A notification with buttons, rendered as an in-app toast
+    NotificationActions {
+        kind: NotificationKind,
+        session_id: Option<&'a str>,
+        pid: Option<u32>,
+        title: &'a str,
+        body: &'a str,
+        actions: &'a [NotificationAction],
+    },
+    /// claudeminer:// link opened while the app runs
+    DeepLink(&'a DeepLink),
+    /// A backend thread panicked (report saved, see get_last_crash)
+    AppCrash(&'a CrashReport),
+    UpdateProgress(&'a UpdateProgress),
+    /// Update downloaded and verified; restart_to_update installs it
+    UpdateReady(&'a StagedUpdate),
+    UpdateFailed { error: &'a str },
+}
+
+impl EventPayload<'_> {
+    /// Tauri event name (same as the `event` tag)
+    pub fn name(&self) -> &'static str {
+        match self {
+            EventPayload::SessionCreated(_) => "session-created",
+            EventPayload::SessionTerminated(_) => "session-terminated",
+            EventPayload::SessionDiff { .. } => "session-diff",
+            EventPayload::SessionNeedsAttention { .. } => "session-needs-attention",
+            EventPayload::MinersUpdated { .. } => "miners-updated",
+            EventPayload::SessionLogLines(_) => "session-log-lines",
+            EventPayload::FocusChanged(_) => "focus-changed",
+            EventPayload::NotificationActions { .. } => "notification-actions",
+            EventPayload::DeepLink(_) => "deep-link",
+            EventPayload::AppCrash(_) => "app-crash",
+            EventPayload::UpdateProgress(_) => "update-progress",
+            EventPayload::UpdateReady(_) => "update-ready",
+            EventPayload::UpdateFailed { .. } => "update-failed",
+        }
+    }
+
+    /// JSON sent to the webviews: the tagged payload plus schema_version
+    pub fn to_json(&self) -> Result<Value, String> {
+        let mut value = serde_json::to_value(self).map_err(|e| e.to_string())?;
+        let fields = value.as_object_mut().ok_or("event payload is not an object")?;
+        fields.insert("schema_version".to_string(), Value::from(EVENT_SCHEMA_VERSION));
+        Ok(value)
+    }
+}
+
+/// Emit an event to every window
+pub fn emit(handle: &tauri::AppHandle, payload: EventPayload) -> Result<(), String> {
+    let json = payload.to_json()?;
+    handle.emit_all(payload.name(), json).map_err(|e| e.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_payload_json() {
+        let session = SessionState::new_legacy(42, "schema-test".to_string());
+        let json = EventPayload::SessionCreated(&session).to_json().unwrap();
+        assert_eq!(json["event"], "session-created");
+        assert_eq!(json["schema_version"], EVENT_SCHEMA_VERSION);
+        assert_eq!(json["session_id"], "schema-test");
+        assert_eq!(json["pid"], 42);
+
+        let json = EventPayload::MinersUpdated { miners: &[] }.to_json().unwrap();
+        assert_eq!(json["event"], "miners-updated");
+        assert_eq!(json["miners"], serde_json::json!([]));
+
+        // The tag is the event name
+        let failed = EventPayload::UpdateFailed { error: "checksum mismatch" };
+        assert_eq!(failed.to_json().unwrap()["event"], failed.name());
+    }
+}
@@ -0,0 +1,53 @@
+// Status Line File
+//
+// One-line miner summary ("⛏2 😴1 🧟0") for external status bars (SketchyBar, Übersicht,
+// Polybar, ...) to poll: <config dir>/status.txt, rewritten atomically on every tray update
+// and removed when the app exits so bars don't show stale counts.
+//
+
+use crate::hooks::backup::write_atomic;
+use std::path::PathBuf;
+use std::sync::Mutex;
+
+const STATUS_FILE: &str = "status.txt";
+
+/// Last line written (skip rewriting an unchanged file)
+static LAST_LINE: Mutex<Option<String>> = Mutex::new(None);
+
+pub fn path() -> PathBuf {
+    crate::persist::data_dir().join(STATUS_FILE)
+}
+
+pub fn format(working: u32, resting: u32, zombie: u32) -> String {
+    format!("⛏{} 😴{} 🧟{}", working, resting, zombie)
+}
+
+/// Write the status line if it changed
+pub fn write(working: u32, resting: u32, zombie: u32) {
+    let line = format(working, resting, zombie);
+    let mut last = LAST_LINE.lock().unwrap();
+    if last.as_deref() == Some(line.as_str()) {
+        return;
+    }
+    match write_atomic(&path(), &format!("{}\n", line)) {
+        Ok(()) => *last = Some(line),
+        Err(e) => eprintln!("[StatusLine] Failed to write {}: {}", path().display(), e),
+    }
+}
+
+/// Remove the status file (app exit)
+pub fn clear() {
+    let _ = std::fs::remove_file(path());
+    *LAST_LINE.lock().unwrap() = None;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format() {
+        assert_eq!(format(2, 1, 0), "⛏2 😴1 🧟0");
+        assert!(!format(10, 20, 30).contains('\n'));
+    }
+}
@@ -0,0 +1,104 @@
+// Tray Title
+//
+// macOS menu bar title next to the tray icon (config.json "tray.title"):
+// - count: "⛏️ N" working sessions
+// - time:  "⛏️ N · 14m", elapsed time of the longest-running working session
+// - off:   no title
+// A light timer refreshes it so the elapsed time keeps ticking between events.
+//
+
+use crate::config::{self, TrayTitleMode};
+use crate::event::emitter;
+use crate::session::{current_timestamp, SessionState, SharedSessions};
+use crate::status::{history, machine};
+use once_cell::sync::OnceCell;
+use std::collections::HashMap;
+use std::thread;
+use std::time::Duration;
+
+const REFRESH_INTERVAL: Duration = Duration::from_secs(15);
+
+/// Sessions the title is computed from (set when the timer starts)
+static SESSIONS: OnceCell<SharedSessions> = OnceCell::new();
+
+/// Start the tray title timer (macOS only; other platforms have no tray title)
+pub fn start_tray_title_timer(shared_sessions: SharedSessions) -> thread::JoinHandle<()> {
+    let _ = SESSIONS.set(shared_sessions);
+    thread::spawn(move || {
+        println!("[TrayTitle] Started");
+        loop {
+            thread::sleep(REFRESH_INTERVAL);
+            emitter::set_tray_title(&current_title(None));
+        }
+    })
+}
+
+/// Refresh the title right away (after a mode change)
+pub fn set_title_now() {
+    emitter::set_tray_title(&current_title(None));
+}
+
+/// Title for the current sessions; `working` is the caller's count when sessions aren't available
+pub fn current_title(working: Option<u32>) -> String {
+    match SESSIONS.get() {
+        Some(sessions) => title_for_sessions(&sessions.snapshot(), current_timestamp()),
+        None => format_title(config::get().tray.title, working.unwrap_or(0), None),
+    }
+}
+
+/// Title for the current sessions
+pub fn title_for_sessions(sessions: &HashMap<String, SessionState>, now: u64) -> String {
+    let working: Vec<&SessionState> = sessions.values()
+        .filter(|s| s.current_status == machine::WORKING && !s.ignored)
+        .collect();
+
+    let longest = working.iter()
+        .filter_map(|s| working_since(s))
+        .min()
+        .map(|since| now.saturating_sub(since));
+
+    format_title(config::get().tray.title, working.len() as u32, longest)
+}
+
+/// When the session's current working stretch started (from the status history)
+fn working_since(session: &SessionState) -> Option<u64> {
+    history::get(&session.session_id).iter()
+        .rev()
+        .find(|t| t.from != Some(machine::WORKING) && t.to == machine::WORKING)
+        .map(|t| t.timestamp)
+}
+
+pub fn format_title(mode: TrayTitleMode, working: u32, longest_secs: Option<u64>) -> String {
+    if working == 0 || mode == TrayTitleMode::Off {
+        return String::new();
+    }
+
+    match (mode, longest_secs) {
+        (TrayTitleMode::Time, Some(secs)) => format!("⛏️ {} · {}", working, format_elapsed(secs)),
+        _ => format!("⛏️ {}", working),
+    }
+}
+
+/// "45s", "14m", "2h05m"
+fn format_elapsed(secs: u64) -> String {
+    match secs {
+        0..=59 => format!("{}s", secs),
+        60..=3599 => format!("{}m", secs / 60),
+        _ => format!("{}h{:02}m", secs / 3600, secs % 3600 / 60),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_title() {
+        assert_eq!(format_title(TrayTitleMode::Count, 2, Some(840)), "⛏️ 2");
+        assert_eq!(format_title(TrayTitleMode::Time, 2, Some(840)), "⛏️ 2 · 14m");
+        assert_eq!(format_title(TrayTitleMode::Time, 1, Some(7500)), "⛏️ 1 · 2h05m");
+        assert_eq!(format_title(TrayTitleMode::Time, 1, None), "⛏️ 1");
+        assert_eq!(format_title(TrayTitleMode::Off, 3, Some(60)), "");
+        assert_eq!(format_title(TrayTitleMode::Time, 0, None), "");
+    }
+}
@@ -0,0 +1,156 @@
+// Tray Icon State
+//
+// Switches the tray icon with the aggregate session state published by the coordinator:
+// red when a zombie exists, animated frames while any session works, gray when idle.
+// Icons come from a theme (config.json "tray.icon_theme"): "default" is bundled, other
+// names are loaded from <config dir>/tray-themes/<name>/ with the same file names
+// (idle.png, working-0..3.png, zombie.png); missing files fall back to the bundled ones.
+//
+
+use crate::config;
+use crate::event::emitter;
+use crate::session::SessionState;
+use crate::status::machine;
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicU8, Ordering};
+use std::thread;
+use std::time::Duration;
+
+const FRAME_INTERVAL: Duration = Duration::from_millis(400);
+const IDLE_POLL: Duration = Duration::from_secs(1);
+const WORKING_FRAMES: usize = 4;
+
+const APP_ICON: &[u8] = include_bytes!("../../icons/icon.png");  // Static template icon (tauri.conf.json)
+const DEFAULT_IDLE: &[u8] = include_bytes!("../../icons/tray/default/idle.png");
+const DEFAULT_ZOMBIE: &[u8] = include_bytes!("../../icons/tray/default/zombie.png");
+const DEFAULT_WORKING: [&[u8]; WORKING_FRAMES] = [
+    include_bytes!("../../icons/tray/default/working-0.png"),
+    include_bytes!("../../icons/tray/default/working-1.png"),
+    include_bytes!("../../icons/tray/default/working-2.png"),
+    include_bytes!("../../icons/tray/default/working-3.png"),
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TrayIconState {
+    Idle = 0,
+    Working = 1,
+    Zombie = 2,
+}
+
+static STATE: AtomicU8 = AtomicU8::new(TrayIconState::Idle as u8);
+
+/// Aggregate state of the visible sessions (a zombie outranks work)
+pub fn aggregate_state<'a>(sessions: impl IntoIterator<Item = &'a SessionState>) -> TrayIconState {
+    let mut state = TrayIconState::Idle;
+    for session in sessions.into_iter().filter(|s| !s.ignored) {
+        match session.current_status {
+            machine::ZOMBIE => return TrayIconState::Zombie,
+            machine::WORKING => state = TrayIconState::Working,
+            _ => {}
+        }
+    }
+    state
+}
+
+/// Publish the coordinator's sessions (called after every sync)
+pub fn update_state(sessions: &HashMap<String, SessionState>) {
+    STATE.store(aggregate_state(sessions.values()) as u8, Ordering::Relaxed);
+}
+
+fn current_state() -> TrayIconState {
+    match STATE.load(Ordering::Relaxed) {
+        1 => TrayIconState::Working,
+        2 => TrayIconState::Zombie,
+        _ => TrayIconState::Idle,
+    }
+}
+
+/// Start the tray icon animator (no-op while "tray.dynamic_icon" is off)
+pub fn start_tray_icon_animator() -> thread::JoinHandle<()> {
+    thread::spawn(|| {
+        println!("[TrayIcon] Started");
+        let mut shown: Option<(TrayIconState, usize, String)> = None;
+        let mut frame = 0;
+
+        loop {
+            let tray_config = config::get().tray;
+            if !tray_config.dynamic_icon {
+                if shown.take().is_some() {
+                    emitter::set_tray_icon(tauri::Icon::Raw(APP_ICON.to_vec()), true);
+                }
+                thread::sleep(IDLE_POLL);
+                continue;
+            }
+
+            let state = current_state();
+            frame = if state == TrayIconState::Working { (frame + 1) % WORKING_FRAMES } else { 0 };
+
+            let key = (state, frame, tray_config.icon_theme.clone());
+            if shown.as_ref() != Some(&key) {
+                emitter::set_tray_icon(load_icon(&tray_config.icon_theme, state, frame), false);
+                shown = Some(key);
+            }
+
+            thread::sleep(if state == TrayIconState::Working { FRAME_INTERVAL } else { IDLE_POLL });
+        }
+    })
+}
+
+fn icon_file_name(state: TrayIconState, frame: usize) -> String {
+    match state {
+        TrayIconState::Idle => "idle.png".to_string(),
+        TrayIconState::Zombie => "zombie.png".to_string(),
+        TrayIconState::Working => format!("working-{}.png", frame),
+    }
+}
+
+/// Themed icon file, falling back to the bundled default
+fn load_icon(theme: &str, state: TrayIconState, frame: usize) -> tauri::Icon {
+    if theme != "default" {
+        let path = theme_dir(theme).join(icon_file_name(state, frame));
+        if path.is_file() {
+            return tauri::Icon::File(path);
+        }
+    }
+
+    let bytes = match state {
+        TrayIconState::Idle => DEFAULT_IDLE,
+        TrayIconState::Zombie => DEFAULT_ZOMBIE,
+        TrayIconState::Working => DEFAULT_WORKING[frame % WORKING_FRAMES],
+    };
+    tauri::Icon::Raw(bytes.to_vec())
+}
+
+fn theme_dir(theme: &str) -> PathBuf {
+    config::store::get_config_path()
+        .parent()
+        .map(|dir| dir.join("tray-themes").join(theme))
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::SessionStatus;
+
+    fn session(id: &str, status: SessionStatus) -> SessionState {
+        let mut session = SessionState::new_legacy(1, id.to_string());
+        session.current_status = status;
+        session
+    }
+
+    #[test]
+    fn test_aggregate_state() {
+        let resting = session("session-a", machine::RESTING);
+        let working = session("session-b", machine::WORKING);
+        let mut zombie = session("session-c", machine::ZOMBIE);
+
+        assert_eq!(aggregate_state([&resting]), TrayIconState::Idle);
+        assert_eq!(aggregate_state([&resting, &working]), TrayIconState::Working);
+        assert_eq!(aggregate_state([&working, &zombie]), TrayIconState::Zombie);
+
+        zombie.ignored = true;
+        assert_eq!(aggregate_state([&working, &zombie]), TrayIconState::Working);
+    }
+}
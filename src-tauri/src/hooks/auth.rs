@@ -0,0 +1,100 @@
+// Hook Pipe Authentication
+//
+// Anything that can open the pipe could forge session events, so every message must carry a
+// shared-secret token: the hook commands registered in settings.json embed it, and
+// the receiver drops messages without it before they reach the coordinator
+// (checked when decoding the envelope, see protocol.rs).
+// The token is generated at startup when none exists and kept in a private file
+// (<config dir>/pipe_token, 0600). It isn't regenerated on every launch: Claude Code
+// snapshots hooks when a session starts, so running sessions would keep sending the
+// old token after a restart. Delete the file to rotate it.
+//
+
+use once_cell::sync::Lazy;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+const TOKEN_FILE: &str = "pipe_token";
+
+static TOKEN: Lazy<String> = Lazy::new(|| {
+    let path = token_path();
+    load_or_create(&path).unwrap_or_else(|e| {
+        // Still authenticate this run; hooks get re-registered with the new token
+        eprintln!("[HookAuth] Failed to persist token at {:?}: {}", path, e);
+        new_token()
+    })
+});
+
+/// The shared secret embedded in hook commands
+pub fn token() -> &'static str {
+    &TOKEN
+}
+
+fn token_path() -> PathBuf {
+    crate::persist::data_dir().join(TOKEN_FILE)
+}
+
+fn new_token() -> String {
+    uuid::Uuid::new_v4().simple().to_string()
+}
+
+fn load_or_create(path: &Path) -> io::Result<String> {
+    if let Ok(existing) = fs::read_to_string(path) {
+        let existing = existing.trim();
+        if is_valid_token(existing) {
+            return Ok(existing.to_string());
+        }
+    }
+
+    let token = new_token();
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    fs::write(path, &token)?;
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        fs::set_permissions(path, fs::Permissions::from_mode(0o600))?;
+    }
+    println!("[HookAuth] Generated new pipe token at {:?}", path);
+    Ok(token)
+}
+
+/// Tokens are embedded in single-quoted shell JSON, so only hex is accepted
+fn is_valid_token(token: &str) -> bool {
+    token.len() >= 32 && token.chars().all(|c| c.is_ascii_hexdigit())
+}
+
+/// Is this the current token?
+pub fn verify(candidate: &str) -> bool {
+    constant_time_eq(candidate.as_bytes(), token().as_bytes())
+}
+
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0u8, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_token_file_and_verify() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join(TOKEN_FILE);
+
+        let created = load_or_create(&path).unwrap();
+        assert!(is_valid_token(&created));
+        assert_eq!(load_or_create(&path).unwrap(), created);
+
+        // A tampered file is replaced
+        fs::write(&path, "x' ; rm -rf ~").unwrap();
+        assert_ne!(load_or_create(&path).unwrap(), created);
+
+        assert!(verify(token()));
+        assert!(!verify(&created));
+        assert!(!verify(""));
+    }
+}
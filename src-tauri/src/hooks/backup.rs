@@ -0,0 +1,195 @@
+// settings.json Writes and Backups
+//
+// Claude's settings.json belongs to the user, so every write ClaudeMiner makes is
+// atomic (temp file + rename in the same directory, so a crash never leaves a
+// truncated file; ClaudeMiner's own config.json is written the same way) and preceded by
+// a timestamped backup next to it:
+//   settings.json.claudeminer-backup-20261016-142233-123
+// Only the newest MAX_BACKUPS are kept. Backups can be listed and restored.
+//
+
+use chrono::{Local, NaiveDateTime};
+use serde::Serialize;
+use std::fs;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+
+pub const MAX_BACKUPS: usize = 5;
+const BACKUP_MARKER: &str = ".claudeminer-backup-";
+const TIMESTAMP_FORMAT: &str = "%Y%m%d-%H%M%S-%3f";
+
+/// Distinguishes the temp files of concurrent writes from this process
+static TEMP_COUNTER: AtomicU64 = AtomicU64::new(0);
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SettingsBackup {
+    pub path: String,
+    pub settings_path: String,
+    pub created: String,  // Local time, "2026-10-16 14:22:33"
+}
+
+/// Write a file atomically: temp file in the same directory, fsync, rename over the target
+/// A symlinked target (dotfiles repos) is resolved first, so the link stays and its file changes
+pub fn write_atomic(path: &Path, contents: &str) -> io::Result<()> {
+    let resolved = fs::canonicalize(path).ok();
+    let path = resolved.as_deref().unwrap_or(path);
+    let dir = path.parent().unwrap_or(Path::new("."));
+    fs::create_dir_all(dir)?;
+
+    let file_name = path.file_name().and_then(|n| n.to_str()).unwrap_or("settings.json");
+    let temp_path = dir.join(format!(".{}.claudeminer-tmp-{}-{}",
+        file_name, std::process::id(), TEMP_COUNTER.fetch_add(1, Ordering::Relaxed)));
+
+    let result = (|| {
+        let mut file = fs::File::create(&temp_path)?;
+        file.write_all(contents.as_bytes())?;
+        file.sync_all()?;
+        // Keep the original permissions (settings.json may be private)
+        if let Ok(metadata) = fs::metadata(path) {
+            fs::set_permissions(&temp_path, metadata.permissions())?;
+        }
+        fs::rename(&temp_path, path)
+    })();
+
+    if result.is_err() {
+        let _ = fs::remove_file(&temp_path);
+    }
+    result
+}
+
+/// Copy the current file to a new timestamped backup and drop the oldest ones
+pub fn backup(path: &Path) -> io::Result<Option<PathBuf>> {
+    if !path.exists() {
+        return Ok(None);
+    }
+
+    let backup_path = backup_path_for(path, &Local::now().format(TIMESTAMP_FORMAT).to_string());
+    fs::copy(path, &backup_path)?;
+    println!("[HookManager] Created backup at {:?}", backup_path);
+
+    rotate(path, MAX_BACKUPS)?;
+    Ok(Some(backup_path))
+}
+
+fn backup_path_for(path: &Path, timestamp: &str) -> PathBuf {
+    let file_name = path.file_name().and_then(|n| n.to_str()).unwrap_or("settings.json");
+    path.with_file_name(format!("{}{}{}", file_name, BACKUP_MARKER, timestamp))
+}
+
+/// Backups of a settings file, newest first
+pub fn list(path: &Path) -> Vec<PathBuf> {
+    let Some(dir) = path.parent() else { return Vec::new() };
+    let Some(file_name) = path.file_name().and_then(|n| n.to_str()) else { return Vec::new() };
+    let prefix = format!("{}{}", file_name, BACKUP_MARKER);
+
+    let mut backups: Vec<PathBuf> = fs::read_dir(dir)
+        .map(|entries| {
+            entries.flatten()
+                .map(|e| e.path())
+                .filter(|p| p.file_name().and_then(|n| n.to_str()).is_some_and(|n| n.starts_with(&prefix)))
+                .collect()
+        })
+        .unwrap_or_default();
+
+    // Timestamps sort lexicographically
+    backups.sort();
+    backups.reverse();
+    backups
+}
+
+/// Delete all but the newest `keep` backups
+fn rotate(path: &Path, keep: usize) -> io::Result<()> {
+    for old in list(path).into_iter().skip(keep) {
+        fs::remove_file(&old)?;
+        println!("[HookManager] Removed old backup {:?}", old);
+    }
+    Ok(())
+}
+
+/// Describe the backups of a settings file (for the UI)
+pub fn describe(path: &Path) -> Vec<SettingsBackup> {
+    let Some(file_name) = path.file_name().and_then(|n| n.to_str()) else { return Vec::new() };
+    let prefix = format!("{}{}", file_name, BACKUP_MARKER);
+
+    list(path).into_iter()
+        .map(|backup| {
+            let created = backup.file_name()
+                .and_then(|n| n.to_str())
+                .and_then(|n| n.strip_prefix(&prefix))
+                .and_then(|ts| NaiveDateTime::parse_from_str(ts, TIMESTAMP_FORMAT).ok())
+                .map(|ts| ts.format("%Y-%m-%d %H:%M:%S").to_string())
+                .unwrap_or_default();
+            SettingsBackup {
+                path: backup.display().to_string(),
+                settings_path: path.display().to_string(),
+                created,
+            }
+        })
+        .collect()
+}
+
+/// Restore a backup over its settings file (the current file is backed up first)
+/// `backup_path` must be one of `list(settings_path)` and contain valid JSON
+pub fn restore(settings_path: &Path, backup_path: &Path) -> Result<(), String> {
+    if !list(settings_path).iter().any(|b| b == backup_path) {
+        return Err(format!("Not a backup of {}: {}", settings_path.display(), backup_path.display()));
+    }
+
+    let contents = fs::read_to_string(backup_path)
+        .map_err(|e| format!("Failed to read {}: {}", backup_path.display(), e))?;
+    serde_json::from_str::<serde_json::Value>(&contents)
+        .map_err(|e| format!("Backup is not valid JSON: {}", e))?;
+
+    backup(settings_path).map_err(|e| format!("Failed to back up current settings: {}", e))?;
+    write_atomic(settings_path, &contents)
+        .map_err(|e| format!("Failed to write {}: {}", settings_path.display(), e))?;
+
+    println!("[HookManager] Restored {:?} from {:?}", settings_path, backup_path);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_backup_rotation_and_restore() {
+        let temp_dir = TempDir::new().unwrap();
+        let settings_path = temp_dir.path().join("settings.json");
+
+        write_atomic(&settings_path, r#"{"version": 0}"#).unwrap();
+        for i in 0..MAX_BACKUPS + 2 {
+            fs::copy(&settings_path, backup_path_for(&settings_path, &format!("20260101-000000-{:03}", i))).unwrap();
+        }
+        rotate(&settings_path, MAX_BACKUPS).unwrap();
+
+        let backups = list(&settings_path);
+        assert_eq!(backups.len(), MAX_BACKUPS);
+        assert!(backups[0].to_str().unwrap().ends_with("-006"));
+        assert_eq!(describe(&settings_path)[0].created, "2026-01-01 00:00:00");
+
+        write_atomic(&settings_path, r#"{"version": 1}"#).unwrap();
+        restore(&settings_path, &backups[0]).unwrap();
+        assert_eq!(fs::read_to_string(&settings_path).unwrap(), r#"{"version": 0}"#);
+
+        // Only listed backups can be restored
+        assert!(restore(&settings_path, &temp_dir.path().join("other.json")).is_err());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_write_atomic_keeps_symlink() {
+        let temp_dir = TempDir::new().unwrap();
+        let target = temp_dir.path().join("dotfiles").join("settings.json");
+        fs::create_dir_all(target.parent().unwrap()).unwrap();
+        fs::write(&target, "{}").unwrap();
+        let link = temp_dir.path().join("settings.json");
+        std::os::unix::fs::symlink(&target, &link).unwrap();
+
+        write_atomic(&link, r#"{"version": 1}"#).unwrap();
+        assert!(fs::symlink_metadata(&link).unwrap().file_type().is_symlink());
+        assert_eq!(fs::read_to_string(&target).unwrap(), r#"{"version": 1}"#);
+    }
+}
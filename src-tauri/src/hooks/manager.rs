@@ -1,16 +1,15 @@
 // Hook Manager
 //
-// Manages Claude Code hook registration in settings.json
+// Manages Claude Code hook registration in settings.json of every Claude home
 // Automatically registers hooks on app startup
 //
 
+use crate::hooks::{auth, backup, pipe, project, protocol};
 use serde::{Deserialize, Serialize};
 use serde_json::{json, Value};
 use std::fs;
 use std::io;
-use std::path::PathBuf;
-
-const PIPE_PATH: &str = "/tmp/claudeminer_pipe";
+use std::path::{Path, PathBuf};
 
 /// Hook configuration for Claude Code
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -37,8 +36,10 @@ pub struct ClaudeSettings {
 
 #[derive(Debug, Serialize, Deserialize, Default)]
 pub struct HookEvents {
+    #[serde(default)]
     #[serde(rename = "UserPromptSubmit")]
     pub user_prompt_submit: Vec<HookConfig>,
+    #[serde(default)]
     #[serde(rename = "Stop")]
     pub stop: Vec<HookConfig>,
     #[serde(skip_serializing_if = "Vec::is_empty", default)]
@@ -47,37 +48,81 @@ pub struct HookEvents {
     #[serde(skip_serializing_if = "Vec::is_empty", default)]
     #[serde(rename = "SessionEnd")]
     pub session_end: Vec<HookConfig>,
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    #[serde(rename = "Notification")]
+    pub notification: Vec<HookConfig>,
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    #[serde(rename = "PostToolUse")]
+    pub post_tool_use: Vec<HookConfig>,
+}
+
+impl HookEvents {
+    /// Every event ClaudeMiner registers (or older versions registered) hooks on
+    fn all(&self) -> [&Vec<HookConfig>; 6] {
+        [
+            &self.session_start,
+            &self.user_prompt_submit,
+            &self.stop,
+            &self.session_end,
+            &self.notification,
+            &self.post_tool_use,
+        ]
+    }
+
+    fn all_mut(&mut self) -> [&mut Vec<HookConfig>; 6] {
+        [
+            &mut self.session_start,
+            &mut self.user_prompt_submit,
+            &mut self.stop,
+            &mut self.session_end,
+            &mut self.notification,
+            &mut self.post_tool_use,
+        ]
+    }
+
+    /// Events apply_hooks registers on
+    fn registered(&self) -> [&Vec<HookConfig>; 4] {
+        [&self.user_prompt_submit, &self.stop, &self.notification, &self.post_tool_use]
+    }
 }
 
-/// Get Claude settings.json path
-pub fn get_settings_path() -> PathBuf {
-    dirs::home_dir()
-        .expect("Failed to get home directory")
-        .join(".claude")
-        .join("settings.json")
+/// Get (profile name, settings.json path) of every Claude profile (default first)
+pub fn get_settings_paths() -> Vec<(String, PathBuf)> {
+    crate::config::claude_profiles()
+        .into_iter()
+        .map(|profile| (profile.name, profile.home.join("settings.json")))
+        .collect()
 }
 
 /// Check if ClaudeMiner hooks are already registered
 pub fn has_claudeminer_hooks(settings: &ClaudeSettings) -> bool {
-    // Check if any hook contains our pipe path
-    let check_hooks = |configs: &[HookConfig]| {
-        configs.iter().any(|config| {
-            config.hooks.iter().any(|hook| {
-                hook.command.contains(PIPE_PATH)
-            })
+    // Check if any hook writes to our pipe (current or legacy path)
+    settings.hooks.all().iter().any(|configs| has_our_hook(configs))
+}
+
+fn has_our_hook(configs: &[HookConfig]) -> bool {
+    configs.iter().any(|config| {
+        config.hooks.iter().any(|hook| {
+            pipe::is_claudeminer_command(&hook.command)
         })
-    };
+    })
+}
 
-    check_hooks(&settings.hooks.session_start) ||
-    check_hooks(&settings.hooks.user_prompt_submit) ||
-    check_hooks(&settings.hooks.stop) ||
-    check_hooks(&settings.hooks.session_end)
+/// Are the ClaudeMiner hooks registered on every event, all with the current pipe path,
+/// protocol and token?
+pub fn hooks_up_to_date(settings: &ClaudeSettings) -> bool {
+    let envelope = format!("{{\"v\":{},\"token\":\"{}\"", protocol::PROTOCOL_VERSION, auth::token());
+
+    settings.hooks.registered().iter().all(|configs| has_our_hook(configs)) && settings.hooks.all().iter()
+        .flat_map(|configs| configs.iter().flat_map(|config| config.hooks.iter()))
+        .filter(|hook| pipe::is_claudeminer_command(&hook.command))
+        .all(|hook| hook.command.contains(pipe::path()) && hook.command.contains(&envelope)
+            && hook.command.contains(TIMESTAMP_FIELD)
+            && (hook.command.contains("\"evt\":\"approval\"") || hook.command.contains(PID_FIELD)))
 }
 
 /// Read Claude settings.json
-pub fn read_settings() -> io::Result<ClaudeSettings> {
-    let path = get_settings_path();
-
+pub fn read_settings(path: &Path) -> io::Result<ClaudeSettings> {
     if !path.exists() {
         // Create default settings if not exists
         let default_settings = ClaudeSettings {
@@ -87,7 +132,7 @@ pub fn read_settings() -> io::Result<ClaudeSettings> {
         return Ok(default_settings);
     }
 
-    let contents = fs::read_to_string(&path)?;
+    let contents = fs::read_to_string(path)?;
 
     // Parse JSON, preserving unknown fields
     let settings: ClaudeSettings = serde_json::from_str(&contents)
@@ -96,46 +141,68 @@ pub fn read_settings() -> io::Result<ClaudeSettings> {
     Ok(settings)
 }
 
-/// Write Claude settings.json with backup
-pub fn write_settings(settings: &ClaudeSettings) -> io::Result<()> {
-    let path = get_settings_path();
-
-    // Create backup if file exists
-    if path.exists() {
-        let backup_path = path.with_extension("json.backup");
-        fs::copy(&path, &backup_path)?;
-        println!("[HookManager] Created backup at {:?}", backup_path);
-    }
-
-    // Ensure .claude directory exists
-    if let Some(parent) = path.parent() {
-        fs::create_dir_all(parent)?;
-    }
-
+/// Write Claude settings.json atomically, after a timestamped backup
+pub fn write_settings(path: &Path, settings: &ClaudeSettings) -> io::Result<()> {
     // Write settings with pretty formatting
     let json_str = serde_json::to_string_pretty(&settings)
         .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
 
-    fs::write(&path, json_str)?;
+    backup::backup(path)?;
+    backup::write_atomic(path, &json_str)?;
     println!("[HookManager] Updated settings.json at {:?}", path);
 
     Ok(())
 }
 
-/// Create ClaudeMiner hook commands
-fn create_hook_command(event_name: &str) -> String {
+/// Create ClaudeMiner hook commands: one protocol envelope (version + pipe token) per line,
+/// tagged with the profile so sessions can be told apart, and the PID of the Claude process
+/// running the hook ($PPID, spliced outside the single quotes so the shell expands it)
+/// and when it ran
+fn create_hook_command(event_name: &str, profile: &str) -> String {
+    format!(
+        "echo '{{\"v\":{},\"token\":\"{}\",\"sid\":\"$SESSION_ID\",\"evt\":\"{}\",\"profile\":\"{}\",{},{}}}' > {}",
+        protocol::PROTOCOL_VERSION, auth::token(), event_name, profile_tag(profile), PID_FIELD, TIMESTAMP_FIELD, pipe::shell_quoted()
+    )
+}
+
+/// "pid" field of the lifecycle hook commands (older registrations lack it)
+const PID_FIELD: &str = "\"pid\":'\"$PPID\"'";
+
+/// "timestamp" field of the hook commands: when the hook ran, not when the message was read
+const TIMESTAMP_FIELD: &str = "\"timestamp\":'\"$(date +%s)\"'";
+
+/// Notification hook command: Claude Code passes the notification ({"session_id","message",...})
+/// on stdin, which is forwarded whole as the "hook" field of an "approval" message
+fn create_notification_hook_command(profile: &str) -> String {
     format!(
-        "echo '{{\"sid\":\"$SESSION_ID\",\"evt\":\"{}\"}}' > {}",
-        event_name, PIPE_PATH
+        "printf '{{\"v\":{},\"token\":\"{}\",\"evt\":\"approval\",\"profile\":\"{}\",{},\"hook\":%s}}\\n' \"$(sed -e 's/\"transcript_path\":\"[^\"]*\",//' -e 's/\"cwd\":\"[^\"]*\",//' | tr -d '\\r\\n')\" > {}",
+        protocol::PROTOCOL_VERSION, auth::token(), profile_tag(profile), TIMESTAMP_FIELD, pipe::shell_quoted()
     )
 }
 
+/// Profile name safe to embed in the single-quoted JSON of a hook command
+fn profile_tag(profile: &str) -> String {
+    profile.chars()
+        .filter(|c| c.is_ascii_alphanumeric() || "-_. ".contains(*c))
+        .collect()
+}
+
 /// Register ClaudeMiner hooks
-pub fn register_hooks() -> io::Result<()> {
-    println!("[HookManager] Registering ClaudeMiner hooks...");
+pub fn register_hooks(path: &Path, profile: &str) -> io::Result<()> {
+    println!("[HookManager] Registering ClaudeMiner hooks in {:?} (profile: {})...", path, profile);
 
-    let mut settings = read_settings()?;
+    let mut settings = read_settings(path)?;
+    apply_hooks(&mut settings, profile);
+
+    // Write updated settings
+    write_settings(path, &settings)?;
+
+    println!("[HookManager] Successfully registered ClaudeMiner hooks");
+    Ok(())
+}
 
+/// Add (or replace) the ClaudeMiner hooks in parsed settings
+fn apply_hooks(settings: &mut ClaudeSettings, profile: &str) {
     // Create our hook config
     let claudeminer_hooks = vec![
         Hook {
@@ -145,15 +212,15 @@ pub fn register_hooks() -> io::Result<()> {
     ];
 
     // Helper to add or update hook
-    let mut add_hook = |configs: &mut Vec<HookConfig>, event_name: &str| {
+    let mut add_hook = |configs: &mut Vec<HookConfig>, command: String| {
         // Remove existing ClaudeMiner hooks if any
         configs.retain(|config| {
-            !config.hooks.iter().any(|h| h.command.contains(PIPE_PATH))
+            !config.hooks.iter().any(|h| pipe::is_claudeminer_command(&h.command))
         });
 
         // Add new hook
         let mut hook = claudeminer_hooks[0].clone();
-        hook.command = create_hook_command(event_name);
+        hook.command = command;
 
         configs.push(HookConfig {
             matcher: "*".to_string(), // Apply to all tools
@@ -163,56 +230,209 @@ pub fn register_hooks() -> io::Result<()> {
 
     // Register hooks for each event
     // Note: SessionStart and SessionEnd hooks removed to avoid slowing down Claude Code startup
-    add_hook(&mut settings.hooks.user_prompt_submit, "working");
-    add_hook(&mut settings.hooks.stop, "resting");
+    add_hook(&mut settings.hooks.user_prompt_submit, create_hook_command("working", profile));
+    add_hook(&mut settings.hooks.stop, create_hook_command("resting", profile));
+    // Permission requests ("Claude needs your permission to use Bash") -> waiting_approval,
+    // and back to working once the tool has run
+    add_hook(&mut settings.hooks.notification, create_notification_hook_command(profile));
+    add_hook(&mut settings.hooks.post_tool_use, create_hook_command("working", profile));
+}
 
-    // Write updated settings
-    write_settings(&settings)?;
+/// One difference between two JSON documents (path in JSON Pointer form)
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct JsonChange {
+    pub path: String,
+    pub op: &'static str,  // add | remove | replace
+    pub old: Option<Value>,
+    pub new: Option<Value>,
+}
 
-    println!("[HookManager] Successfully registered ClaudeMiner hooks");
-    Ok(())
+/// What register_hooks would do to one settings.json (nothing is written)
+#[derive(Debug, Clone, Serialize)]
+pub struct HookChangePreview {
+    pub profile: String,
+    pub settings_path: String,
+    pub exists: bool,
+    pub changes: Vec<JsonChange>,
+    pub before: String,
+    pub after: String,
+}
+
+/// Dry run of register_hooks for one settings.json
+pub fn preview_hooks(path: &Path, profile: &str) -> io::Result<HookChangePreview> {
+    let to_io = |e: serde_json::Error| io::Error::new(io::ErrorKind::InvalidData, e);
+
+    let exists = path.exists();
+    // Compare against the file as it is on disk (not as we'd re-serialize it)
+    let before: Value = if exists { serde_json::from_str(&fs::read_to_string(path)?).map_err(to_io)? } else { json!({}) };
+
+    let mut settings = read_settings(path)?;
+    apply_hooks(&mut settings, profile);
+    let after = serde_json::to_value(&settings).map_err(to_io)?;
+
+    let mut changes = Vec::new();
+    json_diff(&before, &after, "", &mut changes);
+
+    Ok(HookChangePreview {
+        profile: profile.to_string(),
+        settings_path: path.display().to_string(),
+        exists,
+        changes,
+        before: serde_json::to_string_pretty(&before).map_err(to_io)?,
+        after: serde_json::to_string_pretty(&after).map_err(to_io)?,
+    })
+}
+
+/// Structural diff: objects by key, arrays by index, anything else replaced whole
+fn json_diff(old: &Value, new: &Value, path: &str, changes: &mut Vec<JsonChange>) {
+    let change = |op, old: Option<&Value>, new: Option<&Value>, path: String| JsonChange {
+        path,
+        op,
+        old: old.cloned(),
+        new: new.cloned(),
+    };
+
+    match (old, new) {
+        (Value::Object(old_map), Value::Object(new_map)) => {
+            for (key, old_value) in old_map {
+                let child = format!("{}/{}", path, key.replace('~', "~0").replace('/', "~1"));
+                match new_map.get(key) {
+                    Some(new_value) => json_diff(old_value, new_value, &child, changes),
+                    None => changes.push(change("remove", Some(old_value), None, child)),
+                }
+            }
+            for (key, new_value) in new_map.iter().filter(|(key, _)| !old_map.contains_key(*key)) {
+                let child = format!("{}/{}", path, key.replace('~', "~0").replace('/', "~1"));
+                changes.push(change("add", None, Some(new_value), child));
+            }
+        }
+        (Value::Array(old_items), Value::Array(new_items)) => {
+            for i in 0..old_items.len().max(new_items.len()) {
+                let child = format!("{}/{}", path, i);
+                match (old_items.get(i), new_items.get(i)) {
+                    (Some(o), Some(n)) => json_diff(o, n, &child, changes),
+                    (Some(o), None) => changes.push(change("remove", Some(o), None, child)),
+                    (None, Some(n)) => changes.push(change("add", None, Some(n), child)),
+                    (None, None) => {}
+                }
+            }
+        }
+        _ if old != new => changes.push(change("replace", Some(old), Some(new), path.to_string())),
+        _ => {}
+    }
 }
 
 /// Unregister ClaudeMiner hooks (for cleanup)
-pub fn unregister_hooks() -> io::Result<()> {
-    println!("[HookManager] Unregistering ClaudeMiner hooks...");
+pub fn unregister_hooks(path: &Path) -> io::Result<()> {
+    println!("[HookManager] Unregistering ClaudeMiner hooks from {:?}...", path);
 
-    let mut settings = read_settings()?;
+    let mut settings = read_settings(path)?;
 
     // Helper to remove ClaudeMiner hooks
     let remove_hooks = |configs: &mut Vec<HookConfig>| {
         configs.retain(|config| {
-            !config.hooks.iter().any(|h| h.command.contains(PIPE_PATH))
+            !config.hooks.iter().any(|h| pipe::is_claudeminer_command(&h.command))
         });
     };
 
-    // Remove hooks from each event
-    remove_hooks(&mut settings.hooks.user_prompt_submit);
-    remove_hooks(&mut settings.hooks.stop);
+    // Remove hooks from each event (including ones older versions registered)
+    for configs in settings.hooks.all_mut() {
+        remove_hooks(configs);
+    }
 
     // Write updated settings
-    write_settings(&settings)?;
+    write_settings(path, &settings)?;
 
     println!("[HookManager] Successfully unregistered ClaudeMiner hooks");
     Ok(())
 }
 
-/// Ensure hooks are registered (idempotent)
+/// Remove ClaudeMiner hooks from every Claude home that has them
+/// (settings.json files without our hooks are left untouched)
+pub fn unregister_all_hooks() -> io::Result<()> {
+    let mut result = Ok(());
+
+    for (_, path) in get_settings_paths() {
+        if !path.exists() {
+            continue;
+        }
+        let unregistered = read_settings(&path).and_then(|settings| {
+            if has_claudeminer_hooks(&settings) { unregister_hooks(&path) } else { Ok(()) }
+        });
+        if let Err(e) = unregistered {
+            eprintln!("[HookManager] Failed to unregister hooks in {:?}: {}", path, e);
+            result = Err(e);
+        }
+    }
+
+    for project in crate::config::get().hooks.projects {
+        if let Err(e) = project::unregister_project_hooks(&project) {
+            eprintln!("[HookManager] Failed to unregister hooks in project {}: {}", project.path, e);
+            result = Err(e);
+        }
+    }
+
+    result
+}
+
+/// Ensure hooks are registered in every Claude home (idempotent)
+/// The default home is always hooked; extra homes only if their directory exists
+/// No-op while hooks are disabled in config.json
 pub fn ensure_hooks_registered() -> io::Result<()> {
-    let settings = read_settings()?;
+    if !crate::config::get().hooks.enabled {
+        println!("[HookManager] Hooks disabled in config.json, not registering");
+        return Ok(());
+    }
+
+    let mut result = Ok(());
+
+    for (i, (profile, path)) in get_settings_paths().iter().enumerate() {
+        let home_exists = path.parent().map(|p| p.is_dir()).unwrap_or(false);
+        if i > 0 && !home_exists {
+            println!("[HookManager] Skipping missing Claude home {:?}", path.parent());
+            continue;
+        }
 
-    if has_claudeminer_hooks(&settings) {
-        println!("[HookManager] ClaudeMiner hooks already registered");
+        // Keep going so one broken settings.json doesn't leave other homes unhooked
+        if let Err(e) = ensure_hooks_registered_at(path, profile) {
+            eprintln!("[HookManager] Failed to register hooks in {:?}: {}", path, e);
+            result = Err(e);
+        }
+    }
+
+    // Opted-in projects (.claude/settings.local.json)
+    if let Err(e) = project::ensure_project_hooks_registered() {
+        result = Err(e);
+    }
+
+    result
+}
+
+/// Dry run of hook registration for every Claude home ensure_hooks_registered would touch
+pub fn preview_hook_changes() -> io::Result<Vec<HookChangePreview>> {
+    get_settings_paths()
+        .iter()
+        .enumerate()
+        .filter(|(i, (_, path))| *i == 0 || path.parent().is_some_and(|p| p.is_dir()))
+        .map(|(_, (profile, path))| preview_hooks(path, profile))
+        .collect()
+}
+
+fn ensure_hooks_registered_at(path: &Path, profile: &str) -> io::Result<()> {
+    let settings = read_settings(path)?;
+
+    if hooks_up_to_date(&settings) {
+        println!("[HookManager] ClaudeMiner hooks already registered in {:?}", path);
         Ok(())
     } else {
-        println!("[HookManager] ClaudeMiner hooks not found, registering...");
-        register_hooks()
+        println!("[HookManager] ClaudeMiner hooks missing or outdated in {:?}, registering...", path);
+        register_hooks(path, profile)
     }
 }
 
 /// Verify hook registration by checking settings
-pub fn verify_hooks() -> io::Result<bool> {
-    let settings = read_settings()?;
+pub fn verify_hooks(path: &Path) -> io::Result<bool> {
+    let settings = read_settings(path)?;
     let registered = has_claudeminer_hooks(&settings);
 
     if registered {
@@ -237,9 +457,114 @@ mod tests {
 
         // Test with mock settings path (would need to refactor to accept custom path)
         // For now, just test the hook command creation
-        let cmd = create_hook_command("start");
+        let cmd = create_hook_command("start", "work");
         assert!(cmd.contains("\"evt\":\"start\""));
-        assert!(cmd.contains(PIPE_PATH));
+        assert!(cmd.contains("\"profile\":\"work\""));
+        assert!(cmd.contains(pipe::path()));
+        assert!(cmd.contains(&format!("\"token\":\"{}\"", auth::token())));
+
+        // The echoed JSON (with the shell's $PPID and date expanded) is a valid protocol envelope
+        let expanded = cmd.replace("'\"$PPID\"'", "4242").replace("'\"$(date +%s)\"'", "1700000000");
+        let json = expanded.split('\'').nth(1).unwrap();
+        let envelope = protocol::decode(json.as_bytes()).unwrap();
+        assert_eq!(envelope.event(), Some("start"));
+        assert_eq!(envelope.body["pid"], 4242);
+        assert_eq!(envelope.body["timestamp"], 1700000000);
+
+        // Quotes in profile names can't break out of the echo
+        assert!(!create_hook_command("start", "it's").contains("it's"));
+
+        // The notification hook wraps Claude Code's stdin JSON in an approval envelope
+        let cmd = create_notification_hook_command("work").replace("'\"$(date +%s)\"'", "1700000000");
+        let format = cmd.split('\'').nth(1).unwrap();
+        let line = format.replace("%s", r#"{"session_id":"abc","message":"Claude needs your permission to use Bash"}"#);
+        let envelope = protocol::decode(line.trim_end_matches("\\n").as_bytes()).unwrap();
+        assert_eq!(envelope.event(), Some("approval"));
+        assert_eq!(envelope.body["hook"]["session_id"], "abc");
+    }
+
+    /// Run a hook command with its pipe redirect dropped, returning what it would write
+    #[cfg(unix)]
+    fn run_hook_command(cmd: &str, stdin: &str) -> String {
+        use std::io::Write;
+        use std::process::{Command, Stdio};
+
+        let cmd = cmd.replace(&format!(" > {}", pipe::shell_quoted()), "");
+        let mut child = Command::new("sh").arg("-c").arg(&cmd)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .spawn()
+            .unwrap();
+        child.stdin.take().unwrap().write_all(stdin.as_bytes()).unwrap();
+        String::from_utf8(child.wait_with_output().unwrap().stdout).unwrap()
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_hook_commands_carry_timestamp() {
+        let before = crate::session::current_timestamp();
+
+        let output = run_hook_command(&create_hook_command("working", "default"), "");
+        let envelope = protocol::decode(output.trim_end().as_bytes()).unwrap();
+        let hook = match protocol::PipeEvent::from_envelope(envelope).unwrap() {
+            protocol::PipeEvent::Hook(hook) => hook,
+            other => panic!("unexpected {:?}", other),
+        };
+        assert_eq!(hook.evt, "working");
+        assert!(hook.timestamp >= before && hook.timestamp <= crate::session::current_timestamp());
+        assert!(hook.pid.is_some());
+
+        // Paths the receiver doesn't use are dropped, so the line fits in one atomic pipe write
+        let input = format!("{{\"session_id\":\"abc\",\"transcript_path\":\"{0}/t.jsonl\",\"cwd\":\"{0}\",\"message\":\"hi\"}}\n", "/p".repeat(200));
+        let output = run_hook_command(&create_notification_hook_command("default"), &input);
+        let envelope = protocol::decode(output.trim_end().as_bytes()).unwrap();
+        let timestamp = envelope.body["timestamp"].as_u64().unwrap();
+        assert!(timestamp >= before && timestamp <= crate::session::current_timestamp());
+        assert_eq!(envelope.body["hook"], json!({"session_id": "abc", "message": "hi"}));
+    }
+
+    #[test]
+    fn test_unregister_keeps_other_settings() {
+        let temp_dir = TempDir::new().unwrap();
+        let settings_path = temp_dir.path().join("settings.json");
+        fs::write(&settings_path, r#"{"model": "opus", "hooks": {"Stop": [{"matcher": "*", "hooks": [{"type": "command", "command": "say done"}]}]}}"#).unwrap();
+
+        register_hooks(&settings_path, "default").unwrap();
+        assert!(hooks_up_to_date(&read_settings(&settings_path).unwrap()));
+
+        unregister_hooks(&settings_path).unwrap();
+        let settings = read_settings(&settings_path).unwrap();
+        assert!(!has_claudeminer_hooks(&settings));
+        assert_eq!(settings.hooks.stop.len(), 1);
+        assert_eq!(settings.other["model"], "opus");
+    }
+
+    #[test]
+    fn test_preview_hooks_writes_nothing() {
+        let temp_dir = TempDir::new().unwrap();
+        let settings_path = temp_dir.path().join("settings.json");
+        let original = r#"{"model": "opus", "hooks": {"Stop": []}}"#;
+        fs::write(&settings_path, original).unwrap();
+
+        let preview = preview_hooks(&settings_path, "default").unwrap();
+        assert_eq!(fs::read_to_string(&settings_path).unwrap(), original);
+
+        let paths: Vec<&str> = preview.changes.iter().map(|c| c.path.as_str()).collect();
+        assert_eq!(paths, ["/hooks/Stop/0", "/hooks/UserPromptSubmit", "/hooks/Notification", "/hooks/PostToolUse"]);
+        assert!(preview.changes.iter().all(|c| c.op == "add"));
+
+        // Registering for real, then previewing again: nothing left to change
+        register_hooks(&settings_path, "default").unwrap();
+        assert!(preview_hooks(&settings_path, "default").unwrap().changes.is_empty());
+
+        // Only another tool's Notification hook: the missing events are added whole
+        let original = r#"{"hooks": {"Notification": [{"matcher": "*", "hooks": [{"type": "command", "command": "say hi"}]}]}}"#;
+        fs::write(&settings_path, original).unwrap();
+        let preview = preview_hooks(&settings_path, "default").unwrap();
+        assert_eq!(fs::read_to_string(&settings_path).unwrap(), original);
+        let paths: Vec<&str> = preview.changes.iter().map(|c| c.path.as_str()).collect();
+        assert!(paths.contains(&"/hooks/UserPromptSubmit"));
+        assert!(paths.contains(&"/hooks/Stop"));
     }
 
     #[test]
@@ -257,7 +582,7 @@ mod tests {
             matcher: "*".to_string(),
             hooks: vec![Hook {
                 hook_type: "command".to_string(),
-                command: format!("echo 'test' > {}", PIPE_PATH),
+                command: format!("echo 'test' > {}", pipe::LEGACY_PIPE_PATH),
             }],
         });
 
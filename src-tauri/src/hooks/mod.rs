@@ -2,11 +2,17 @@
 //
 // This module handles registration and receiving of Claude Code hooks
 
+pub mod auth;
+pub mod backup;
 pub mod manager;
+pub mod pipe;
+pub mod project;
+pub mod protocol;
 pub mod receiver;
 pub mod sender;
+pub mod watcher;
 
-pub use manager::ensure_hooks_registered;
+pub use manager::{ensure_hooks_registered, unregister_all_hooks};
 pub use receiver::start_hook_receiver;
-// pub use receiver::{start_hook_receiver_with_config, ReceiverConfig}; // Unused
-// pub use sender::send_process_killed_event; // Unused
\ No newline at end of file
+pub use watcher::start_settings_watcher;
+// pub use receiver::{start_hook_receiver_with_config, ReceiverConfig}; // Unused
\ No newline at end of file
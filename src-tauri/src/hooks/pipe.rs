@@ -0,0 +1,79 @@
+// Hook Pipe Path
+//
+// The named pipe lives in $TMPDIR and carries the UID in its name:
+//   macOS: /var/folders/xx/.../T/claudeminer-501.pipe ($TMPDIR is already per-user)
+//   Linux: /tmp/claudeminer-1000.pipe
+// so several users on one machine each get their own pipe, and it goes away with the
+// temp directory on reboot. The path is embedded in the hook commands at registration.
+// Older versions used a fixed /tmp/claudeminer_pipe: hooks pointing there are still
+// recognized as ours (and rewritten at startup), and the old FIFO is removed.
+//
+
+use once_cell::sync::Lazy;
+use std::path::Path;
+
+pub const LEGACY_PIPE_PATH: &str = "/tmp/claudeminer_pipe";
+
+static PIPE_PATH: Lazy<String> = Lazy::new(|| pipe_path_in(&std::env::temp_dir(), current_uid()));
+
+/// This user's hook pipe
+pub fn path() -> &'static str {
+    &PIPE_PATH
+}
+
+fn pipe_path_in(temp_dir: &Path, uid: u32) -> String {
+    temp_dir.join(format!("claudeminer-{}.pipe", uid)).display().to_string()
+}
+
+#[cfg(unix)]
+pub fn current_uid() -> u32 {
+    // SAFETY: getuid has no preconditions and cannot fail
+    unsafe { libc::getuid() }
+}
+
+#[cfg(not(unix))]
+pub fn current_uid() -> u32 {
+    0
+}
+
+/// The pipe path as a single-quoted shell word (for hook commands)
+pub fn shell_quoted() -> String {
+    format!("'{}'", path().replace('\'', r"'\''"))
+}
+
+/// Does a hook command write to a ClaudeMiner pipe (current or legacy)?
+pub fn is_claudeminer_command(command: &str) -> bool {
+    command.contains(path()) || command.contains(LEGACY_PIPE_PATH)
+}
+
+/// Remove the pre-$TMPDIR pipe if it's ours (another user's can't be removed from /tmp anyway)
+pub fn remove_legacy_pipe() {
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::{FileTypeExt, MetadataExt};
+
+        let Ok(metadata) = std::fs::symlink_metadata(LEGACY_PIPE_PATH) else { return };
+        if !metadata.file_type().is_fifo() || metadata.uid() != current_uid() {
+            return;
+        }
+        match std::fs::remove_file(LEGACY_PIPE_PATH) {
+            Ok(()) => println!("[HookReceiver] Removed legacy pipe {}", LEGACY_PIPE_PATH),
+            Err(e) => eprintln!("[HookReceiver] Failed to remove legacy pipe {}: {}", LEGACY_PIPE_PATH, e),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pipe_path_per_user() {
+        assert_eq!(pipe_path_in(Path::new("/tmp"), 1000), "/tmp/claudeminer-1000.pipe");
+        assert_ne!(pipe_path_in(Path::new("/tmp"), 1000), pipe_path_in(Path::new("/tmp"), 1001));
+
+        assert!(is_claudeminer_command(&format!("echo '{{}}' > {}", shell_quoted())));
+        assert!(is_claudeminer_command("echo '{}' > /tmp/claudeminer_pipe"));
+        assert!(!is_claudeminer_command("echo '{}' > /tmp/other_pipe"));
+    }
+}
@@ -0,0 +1,180 @@
+// Project-Level Hooks
+//
+// Besides the user settings in each Claude home, Claude Code reads per-project
+// <project>/.claude/settings.json (shared) and settings.local.json (personal).
+// - Projects listed in config.json "hooks.projects" get the ClaudeMiner hooks there too
+//   (settings.local.json by default, so they aren't committed by accident)
+// - For the projects of monitored sessions, hooks defined in those files are reported:
+//   extra ClaudeMiner hooks and other tools' hooks on the events ClaudeMiner uses
+//
+
+use crate::config::{self, ProjectHooksConfig};
+use crate::hooks::manager;
+use crate::hooks::watcher::{self, HookConflict};
+use serde::Serialize;
+use std::collections::BTreeSet;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// Hook tag for project-level hooks (project files aren't tied to a Claude profile)
+const PROJECT_PROFILE: &str = "default";
+
+/// Hooks found in a project's Claude settings
+#[derive(Debug, Clone, Serialize)]
+pub struct ProjectHookReport {
+    pub project_path: String,
+    pub settings_path: String,
+    pub claudeminer_hooks: bool,
+    pub conflicts: Vec<HookConflict>,
+}
+
+/// <project>/.claude/settings.json or settings.local.json
+pub fn settings_path(project: &ProjectHooksConfig) -> PathBuf {
+    let file_name = if project.local { "settings.local.json" } else { "settings.json" };
+    Path::new(&project.path).join(".claude").join(file_name)
+}
+
+/// Both project settings files (shared, local)
+fn project_settings_paths(project_path: &Path) -> [PathBuf; 2] {
+    let claude_dir = project_path.join(".claude");
+    [claude_dir.join("settings.json"), claude_dir.join("settings.local.json")]
+}
+
+/// Register the hooks in every configured project (no-op while hooks are disabled)
+pub fn ensure_project_hooks_registered() -> io::Result<()> {
+    if !config::get().hooks.enabled {
+        return Ok(());
+    }
+
+    let mut result = Ok(());
+    for project in config::get().hooks.projects {
+        if !Path::new(&project.path).is_dir() {
+            println!("[HookManager] Skipping missing project {}", project.path);
+            continue;
+        }
+
+        let path = settings_path(&project);
+        let registered = manager::read_settings(&path).map(|s| manager::hooks_up_to_date(&s));
+        let outcome = match registered {
+            Ok(true) => Ok(()),
+            Ok(false) => manager::register_hooks(&path, PROJECT_PROFILE),
+            Err(e) => Err(e),
+        };
+        if let Err(e) = outcome {
+            eprintln!("[HookManager] Failed to register hooks in {:?}: {}", path, e);
+            result = Err(e);
+        }
+    }
+    result
+}
+
+/// Remove the hooks from a project's settings file (only if it has them)
+pub fn unregister_project_hooks(project: &ProjectHooksConfig) -> io::Result<()> {
+    let path = settings_path(project);
+    if !path.exists() || !manager::has_claudeminer_hooks(&manager::read_settings(&path)?) {
+        return Ok(());
+    }
+    manager::unregister_hooks(&path)
+}
+
+/// Replace the configured projects: removed ones are unhooked, the rest (re)hooked
+pub fn set_projects(projects: Vec<ProjectHooksConfig>) -> Result<(), String> {
+    if let Some(p) = projects.iter().find(|p| !Path::new(&p.path).is_dir()) {
+        return Err(format!("Not a directory: {}", p.path));
+    }
+
+    let previous = config::get().hooks.projects;
+    config::update(|c| c.hooks.projects = projects.clone())
+        .map_err(|e| format!("Failed to save config: {}", e))?;
+
+    for removed in previous.iter().filter(|p| !projects.contains(p)) {
+        unregister_project_hooks(removed)
+            .map_err(|e| format!("Failed to unregister hooks in {}: {}", removed.path, e))?;
+    }
+    ensure_project_hooks_registered()
+        .map_err(|e| format!("Failed to register hooks: {}", e))
+}
+
+/// Hooks defined in the project settings of the given project directories
+pub fn report<'a>(project_paths: impl IntoIterator<Item = &'a str>) -> Vec<ProjectHookReport> {
+    let projects: BTreeSet<&str> = project_paths.into_iter().collect();
+
+    let mut reports = Vec::new();
+    for project in projects {
+        for path in project_settings_paths(Path::new(project)) {
+            if !path.exists() {
+                continue;
+            }
+            let settings = match manager::read_settings(&path) {
+                Ok(settings) => settings,
+                Err(e) => {
+                    eprintln!("[HookManager] Cannot parse {:?}: {}", path, e);
+                    continue;
+                }
+            };
+
+            let claudeminer_hooks = manager::has_claudeminer_hooks(&settings);
+            let conflicts = watcher::find_conflicts(&settings, &path.display().to_string());
+            if claudeminer_hooks || !conflicts.is_empty() {
+                reports.push(ProjectHookReport {
+                    project_path: project.to_string(),
+                    settings_path: path.display().to_string(),
+                    claudeminer_hooks,
+                    conflicts,
+                });
+            }
+        }
+    }
+    reports
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_report_project_hooks() {
+        let project = TempDir::new().unwrap();
+        let claude_dir = project.path().join(".claude");
+        std::fs::create_dir_all(&claude_dir).unwrap();
+        std::fs::write(
+            claude_dir.join("settings.json"),
+            r#"{"hooks": {"Stop": [{"matcher": "*", "hooks": [{"type": "command", "command": "notify-send done"}]}]}}"#,
+        ).unwrap();
+
+        let local = ProjectHooksConfig { path: project.path().display().to_string(), local: true };
+        manager::register_hooks(&settings_path(&local), PROJECT_PROFILE).unwrap();
+
+        let project_path = project.path().display().to_string();
+        let reports = report([project_path.as_str()]);
+        assert_eq!(reports.len(), 2);
+        assert!(!reports[0].claudeminer_hooks);
+        assert_eq!(reports[0].conflicts[0].command, "notify-send done");
+        assert!(reports[1].claudeminer_hooks);
+        assert!(reports[1].conflicts.is_empty());
+
+        unregister_project_hooks(&local).unwrap();
+        assert_eq!(report([project_path.as_str()]).len(), 1);
+    }
+
+    #[test]
+    fn test_report_partial_project_settings() {
+        // Only one of the events ClaudeMiner uses is listed (no Stop / UserPromptSubmit)
+        let project = TempDir::new().unwrap();
+        let claude_dir = project.path().join(".claude");
+        std::fs::create_dir_all(&claude_dir).unwrap();
+        std::fs::write(
+            claude_dir.join("settings.local.json"),
+            r#"{"hooks": {"Notification": [{"matcher": "*", "hooks": [{"type": "command", "command": "say needs you"}]}]}}"#,
+        ).unwrap();
+
+        let project_path = project.path().display().to_string();
+        let reports = report([project_path.as_str()]);
+        assert_eq!(reports.len(), 1);
+        assert!(!reports[0].claudeminer_hooks);
+        assert_eq!(reports[0].conflicts.len(), 1);
+        assert_eq!(reports[0].conflicts[0].event, "Notification");
+        assert_eq!(reports[0].conflicts[0].command, "say needs you");
+    }
+}
@@ -0,0 +1,346 @@
+// Hook Pipe Protocol
+//
+// Messages on the hook pipe are NDJSON: one JSON object per line, newline-terminated.
+// Every object is a versioned envelope:
+//   {"v":1,"token":"<pipe token>","evt":"working","sid":"...","profile":"default"}
+// - `v` is PROTOCOL_VERSION; other versions are rejected (a stale hook from another build)
+// - `token` authenticates the writer (see auth.rs)
+// - the rest is the event body, typed as a PipeEvent by its `evt`:
+//     start|working|resting|end  {"sid","profile","pid"}  from the Claude Code hooks
+//     approval                   {"hook","profile"} from the Notification hook, "hook" being
+//                                                   its input ({"session_id","message",...})
+//     killed                     {"pid"}            a miner process was killed
+//     deeplink                   {"url"}            forwarded by a second instance
+// Lines longer than MAX_MESSAGE_LEN are dropped whole. Writes of at most PIPE_BUF bytes
+// to a FIFO are atomic (512 on macOS, 4096 on Linux), so a line and its newline must fit
+// in PIPE_BUF for lines from concurrent hooks never to interleave.
+//
+
+use crate::hooks::auth;
+use crate::session::HookEvent;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Map, Value};
+use std::fmt;
+use std::io::{self, BufRead, Read};
+
+pub const PROTOCOL_VERSION: u64 = 1;
+
+/// Longest accepted line, without the newline (PIPE_BUF, the atomic write size, minus one)
+#[cfg(unix)]
+pub const MAX_MESSAGE_LEN: usize = libc::PIPE_BUF - 1;
+#[cfg(not(unix))]
+pub const MAX_MESSAGE_LEN: usize = 511;  // POSIX minimum PIPE_BUF
+
+/// A decoded, authenticated message
+#[derive(Debug, Clone, PartialEq)]
+pub struct Envelope {
+    pub version: u64,
+    pub body: Map<String, Value>,  // Event fields (without v/token)
+}
+
+impl Envelope {
+    /// The event name (`evt`)
+    pub fn event(&self) -> Option<&str> {
+        self.body.get("evt").and_then(|v| v.as_str())
+    }
+}
+
+/// Hook event with timestamp
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HookEventWithTimestamp {
+    pub sid: String,      // session_id
+    pub evt: String,      // start|working|resting|end
+    #[serde(default = "default_timestamp")]
+    pub timestamp: u64,   // Unix timestamp
+    #[serde(default)]
+    pub profile: Option<String>,  // Claude profile that registered the hook
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pid: Option<u32>,  // $PPID of the hook shell: the Claude process
+}
+
+fn default_timestamp() -> u64 {
+    use std::time::{SystemTime, UNIX_EPOCH};
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_secs()
+}
+
+impl From<HookEventWithTimestamp> for HookEvent {
+    fn from(evt_with_ts: HookEventWithTimestamp) -> Self {
+        HookEvent {
+            sid: evt_with_ts.sid,
+            evt: evt_with_ts.evt,
+            profile: evt_with_ts.profile,
+            timestamp: evt_with_ts.timestamp,
+            pid: evt_with_ts.pid,
+        }
+    }
+}
+
+/// A message on the pipe, by `evt`
+#[derive(Debug, Clone)]
+pub enum PipeEvent {
+    Hook(HookEventWithTimestamp),
+    Approval { sid: String, message: String, profile: Option<String> },
+    Killed { pid: u32 },
+    DeepLink { url: String },
+}
+
+impl PipeEvent {
+    /// Type an envelope's body
+    pub fn from_envelope(envelope: Envelope) -> Result<Self, String> {
+        let field = |name: &str| {
+            envelope.body.get(name).cloned()
+                .ok_or_else(|| format!("'{}' event without {}", envelope.event().unwrap_or(""), name))
+        };
+
+        match envelope.event() {
+            Some("killed") => {
+                let pid = field("pid")?.as_u64().and_then(|pid| u32::try_from(pid).ok())
+                    .ok_or("'killed' event with an invalid pid")?;
+                Ok(PipeEvent::Killed { pid })
+            }
+            Some("deeplink") => {
+                let url = field("url")?.as_str().ok_or("'deeplink' event with an invalid url")?.to_string();
+                Ok(PipeEvent::DeepLink { url })
+            }
+            Some("approval") => {
+                let hook = field("hook")?;
+                let text = |name: &str| hook.get(name).and_then(|v| v.as_str()).map(str::to_string);
+                Ok(PipeEvent::Approval {
+                    sid: text("session_id").ok_or("'approval' event without a session_id")?,
+                    message: text("message").unwrap_or_default(),
+                    profile: envelope.body.get("profile").and_then(|v| v.as_str()).map(str::to_string),
+                })
+            }
+            Some(_) => serde_json::from_value(Value::Object(envelope.body))
+                .map(PipeEvent::Hook)
+                .map_err(|e| e.to_string()),
+            None => Err("message without 'evt'".to_string()),
+        }
+    }
+
+    /// Event body (encode() adds the envelope)
+    pub fn to_body(&self) -> Value {
+        match self {
+            PipeEvent::Hook(hook) => serde_json::to_value(hook).unwrap_or_default(),
+            PipeEvent::Approval { sid, message, profile } => json!({
+                "evt": "approval",
+                "profile": profile,
+                "hook": { "session_id": sid, "message": message },
+            }),
+            PipeEvent::Killed { pid } => json!({ "evt": "killed", "pid": pid }),
+            PipeEvent::DeepLink { url } => json!({ "evt": "deeplink", "url": url }),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FrameError {
+    TooLong(usize),
+    NotUtf8,
+    InvalidJson(String),
+    NotAnObject,
+    UnsupportedVersion(Option<u64>),
+    BadToken,
+}
+
+impl fmt::Display for FrameError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            FrameError::TooLong(len) => write!(f, "message too long ({} bytes, max {})", len, MAX_MESSAGE_LEN),
+            FrameError::NotUtf8 => write!(f, "message is not UTF-8"),
+            FrameError::InvalidJson(e) => write!(f, "invalid JSON: {}", e),
+            FrameError::NotAnObject => write!(f, "message is not a JSON object"),
+            FrameError::UnsupportedVersion(Some(v)) => write!(f, "unsupported protocol version {}", v),
+            FrameError::UnsupportedVersion(None) => write!(f, "missing protocol version"),
+            FrameError::BadToken => write!(f, "missing or invalid token"),
+        }
+    }
+}
+
+/// One line read from the pipe
+#[derive(Debug, PartialEq)]
+pub enum Frame {
+    Line(Vec<u8>),   // Complete line (newline stripped)
+    TooLong(usize),  // Line over MAX_MESSAGE_LEN, discarded
+}
+
+/// Read the next newline-terminated frame; None at end of stream
+/// A final line without a newline is returned as a frame too (writer closed mid-line)
+pub fn read_frame<R: BufRead>(reader: &mut R) -> io::Result<Option<Frame>> {
+    let mut line = Vec::new();
+    let read = reader.by_ref().take(MAX_MESSAGE_LEN as u64 + 1).read_until(b'\n', &mut line)?;
+    if read == 0 {
+        return Ok(None);
+    }
+
+    if line.last() == Some(&b'\n') {
+        line.pop();
+        if line.last() == Some(&b'\r') {
+            line.pop();
+        }
+        return Ok(Some(Frame::Line(line)));
+    }
+    if line.len() <= MAX_MESSAGE_LEN {
+        return Ok(Some(Frame::Line(line)));
+    }
+
+    // Over the limit: skip to the end of the line
+    let mut skipped = line.len();
+    loop {
+        let available = reader.fill_buf()?;
+        if available.is_empty() {
+            break;
+        }
+        match available.iter().position(|&b| b == b'\n') {
+            Some(end) => {
+                skipped += end;
+                reader.consume(end + 1);
+                break;
+            }
+            None => {
+                let len = available.len();
+                skipped += len;
+                reader.consume(len);
+            }
+        }
+    }
+    Ok(Some(Frame::TooLong(skipped)))
+}
+
+/// Decode and authenticate one line
+pub fn decode(line: &[u8]) -> Result<Envelope, FrameError> {
+    if line.len() > MAX_MESSAGE_LEN {
+        return Err(FrameError::TooLong(line.len()));
+    }
+    let text = std::str::from_utf8(line).map_err(|_| FrameError::NotUtf8)?;
+    let value: Value = serde_json::from_str(text).map_err(|e| FrameError::InvalidJson(e.to_string()))?;
+    let Value::Object(mut body) = value else { return Err(FrameError::NotAnObject) };
+
+    let version = body.remove("v").and_then(|v| v.as_u64());
+    if version != Some(PROTOCOL_VERSION) {
+        return Err(FrameError::UnsupportedVersion(version));
+    }
+
+    let token = body.remove("token");
+    if !token.as_ref().and_then(|t| t.as_str()).is_some_and(auth::verify) {
+        return Err(FrameError::BadToken);
+    }
+
+    Ok(Envelope { version: PROTOCOL_VERSION, body })
+}
+
+/// Encode an event as one NDJSON line (with version and token)
+pub fn encode(event: &PipeEvent) -> String {
+    encode_body(event.to_body())
+}
+
+fn encode_body(body: Value) -> String {
+    let mut envelope = Map::new();
+    envelope.insert("v".to_string(), PROTOCOL_VERSION.into());
+    envelope.insert("token".to_string(), auth::token().into());
+    if let Value::Object(fields) = body {
+        envelope.extend(fields.into_iter().filter(|(key, _)| key != "v" && key != "token"));
+    }
+    format!("{}\n", Value::Object(envelope))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+    use std::io::Cursor;
+
+    fn frames(input: &[u8]) -> Vec<Frame> {
+        let mut reader = Cursor::new(input.to_vec());
+        std::iter::from_fn(|| read_frame(&mut reader).unwrap()).collect()
+    }
+
+    #[test]
+    fn test_encode_decode_and_framing() {
+        let line = encode_body(json!({"evt": "working", "sid": "abc", "v": 99}));
+        assert!(line.ends_with('\n'));
+        let envelope = decode(line.trim_end().as_bytes()).unwrap();
+        assert_eq!(envelope.event(), Some("working"));
+        assert_eq!(envelope.body["sid"], "abc");
+        assert!(!envelope.body.contains_key("token"));
+
+        // Back-to-back writes, an oversized line and a trailing partial line
+        let mut input = line.clone().into_bytes();
+        input.extend(vec![b'x'; MAX_MESSAGE_LEN + 10]);
+        input.push(b'\n');
+        input.extend(line.as_bytes());
+        input.extend(b"{\"v\":1");
+        let frames = frames(&input);
+        assert_eq!(frames.len(), 4);
+        assert_eq!(frames[1], Frame::TooLong(MAX_MESSAGE_LEN + 10));
+        assert!(decode(match &frames[2] { Frame::Line(l) => l, _ => panic!() }).is_ok());
+        assert!(matches!(&frames[3], Frame::Line(l) if decode(l).is_err()));
+
+        assert_eq!(decode(br#"{"evt":"working"}"#), Err(FrameError::UnsupportedVersion(None)));
+        assert_eq!(decode(br#"{"v":2,"evt":"working"}"#), Err(FrameError::UnsupportedVersion(Some(2))));
+        assert_eq!(decode(br#"{"v":1,"evt":"working","token":"forged"}"#), Err(FrameError::BadToken));
+        assert_eq!(decode(b"[1]"), Err(FrameError::NotAnObject));
+    }
+
+    #[test]
+    fn test_pipe_event_roundtrip() {
+        let typed = |event: &PipeEvent| PipeEvent::from_envelope(decode(encode(event).trim_end().as_bytes()).unwrap());
+
+        assert!(matches!(typed(&PipeEvent::Killed { pid: 4242 }), Ok(PipeEvent::Killed { pid: 4242 })));
+        assert!(matches!(typed(&PipeEvent::DeepLink { url: "claudeminer://kill/1".to_string() }),
+            Ok(PipeEvent::DeepLink { url }) if url == "claudeminer://kill/1"));
+
+        let hook = HookEventWithTimestamp { sid: "abc".to_string(), evt: "resting".to_string(), timestamp: 7, profile: None, pid: Some(4242) };
+        assert!(matches!(typed(&PipeEvent::Hook(hook)), Ok(PipeEvent::Hook(h)) if h.sid == "abc" && h.timestamp == 7));
+
+        let approval = PipeEvent::Approval {
+            sid: "abc".to_string(),
+            message: "Claude needs your permission to use Bash".to_string(),
+            profile: Some("work".to_string()),
+        };
+        assert!(matches!(typed(&approval), Ok(PipeEvent::Approval { sid, message, profile })
+            if sid == "abc" && message.ends_with("Bash") && profile.as_deref() == Some("work")));
+
+        // The old string-encoded form is not a killed event
+        let legacy = decode(encode_body(json!({"evt": "killed", "sid": "PID-4242"})).trim_end().as_bytes()).unwrap();
+        assert!(PipeEvent::from_envelope(legacy).is_err());
+    }
+
+    proptest! {
+        // Arbitrary bytes never panic the reader or decoder, and frames never exceed the limit
+        #[test]
+        fn fuzz_read_and_decode(input in proptest::collection::vec(any::<u8>(), 0..10_000)) {
+            for frame in frames(&input) {
+                if let Frame::Line(line) = frame {
+                    prop_assert!(line.len() <= MAX_MESSAGE_LEN);
+                    let _ = decode(&line);
+                }
+            }
+        }
+
+        // Any sequence of encoded messages is read back one frame per message
+        #[test]
+        fn fuzz_roundtrip(sids in proptest::collection::vec("\\PC{0,64}", 1..20)) {
+            let input: String = sids.iter().map(|sid| encode_body(json!({"evt": "working", "sid": sid}))).collect();
+            let frames = frames(input.as_bytes());
+            prop_assert_eq!(frames.len(), sids.len());
+            for (frame, sid) in frames.iter().zip(&sids) {
+                let Frame::Line(line) = frame else { return Err(TestCaseError::fail("frame too long")) };
+                let envelope = decode(line).unwrap();
+                prop_assert_eq!(envelope.body["sid"].as_str(), Some(sid.as_str()));
+            }
+        }
+
+        // Mutating a valid message either still decodes or fails cleanly
+        #[test]
+        fn fuzz_mutated_message(position in 0usize..200, byte in any::<u8>()) {
+            let mut line = encode_body(json!({"evt": "resting", "sid": "session"})).trim_end().as_bytes().to_vec();
+            let index = position % line.len();
+            line[index] = byte;
+            let _ = decode(&line);
+        }
+    }
+}
@@ -4,53 +4,27 @@
 // error recovery, and comprehensive monitoring
 //
 
-use crate::session::{MonitorEvent, HookEvent};
-use crate::notification;
-use std::sync::mpsc::Sender;
+use crate::crash::spawn_named;
+use crate::hooks::pipe;
+use crate::hooks::protocol::{self, Envelope, Frame, FrameError, PipeEvent};
+use crate::session::{EventSender, MonitorEvent, HookEvent, ApprovalEvent, ProcessExitEvent, current_timestamp};
 use std::thread;
 use std::fs::{self, OpenOptions};
-use std::io::{BufReader, BufRead};
+use std::io::BufReader;
 use std::path::Path;
 use std::time::{Duration, Instant};
-use serde::{Deserialize, Serialize};
 
-const PIPE_PATH: &str = "/tmp/claudeminer_pipe";
 const RECONNECT_DELAY: Duration = Duration::from_secs(1);
 const MAX_RECONNECT_ATTEMPTS: u32 = 5;
 const PIPE_CHECK_INTERVAL: Duration = Duration::from_secs(30);
 const READ_TIMEOUT: Duration = Duration::from_secs(60);
 
-/// Hook event with timestamp
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct HookEventWithTimestamp {
-    pub sid: String,      // session_id
-    pub evt: String,      // start|working|resting|end
-    #[serde(default = "default_timestamp")]
-    pub timestamp: u64,   // Unix timestamp
-}
-
-fn default_timestamp() -> u64 {
-    use std::time::{SystemTime, UNIX_EPOCH};
-    SystemTime::now()
-        .duration_since(UNIX_EPOCH)
-        .unwrap()
-        .as_secs()
-}
-
-impl From<HookEventWithTimestamp> for HookEvent {
-    fn from(evt_with_ts: HookEventWithTimestamp) -> Self {
-        HookEvent {
-            sid: evt_with_ts.sid,
-            evt: evt_with_ts.evt,
-        }
-    }
-}
-
 /// Hook receiver statistics
 #[derive(Debug)]
 struct ReceiverStats {
     events_received: u64,
     parse_errors: u64,
+    auth_failures: u64,
     read_errors: u64,
     reconnects: u64,
     last_event_time: Option<Instant>,
@@ -62,6 +36,7 @@ impl ReceiverStats {
         Self {
             events_received: 0,
             parse_errors: 0,
+            auth_failures: 0,
             read_errors: 0,
             reconnects: 0,
             last_event_time: None,
@@ -78,6 +53,7 @@ impl ReceiverStats {
         println!("  Uptime: {}h {}m", hours, minutes);
         println!("  Events received: {}", self.events_received);
         println!("  Parse errors: {}", self.parse_errors);
+        println!("  Rejected (bad token): {}", self.auth_failures);
         println!("  Read errors: {}", self.read_errors);
         println!("  Reconnections: {}", self.reconnects);
 
@@ -100,7 +76,7 @@ pub struct ReceiverConfig {
 impl Default for ReceiverConfig {
     fn default() -> Self {
         Self {
-            pipe_path: PIPE_PATH.to_string(),
+            pipe_path: pipe::path().to_string(),
             reconnect_delay: RECONNECT_DELAY,
             max_reconnects: MAX_RECONNECT_ATTEMPTS,
             enable_stats: true,
@@ -109,17 +85,18 @@ impl Default for ReceiverConfig {
 }
 
 /// Start hook receiver thread
-pub fn start_hook_receiver(event_sender: Sender<MonitorEvent>) -> thread::JoinHandle<()> {
+pub fn start_hook_receiver(event_sender: EventSender) -> thread::JoinHandle<()> {
     start_hook_receiver_with_config(event_sender, ReceiverConfig::default())
 }
 
 /// Start hook receiver with custom configuration
 pub fn start_hook_receiver_with_config(
-    event_sender: Sender<MonitorEvent>,
+    event_sender: EventSender,
     config: ReceiverConfig,
 ) -> thread::JoinHandle<()> {
-    thread::spawn(move || {
+    spawn_named("hook-receiver", move || {
         println!("[HookReceiver] Starting hook receiver");
+        pipe::remove_legacy_pipe();
         let mut stats = ReceiverStats::new();
         let mut last_stats_log = Instant::now();
 
@@ -155,7 +132,7 @@ pub fn start_hook_receiver_with_config(
 
 /// Run receiver with automatic recovery
 fn run_receiver_with_recovery(
-    event_sender: &Sender<MonitorEvent>,
+    event_sender: &EventSender,
     config: &ReceiverConfig,
     stats: &mut ReceiverStats,
 ) -> std::io::Result<()> {
@@ -190,7 +167,7 @@ fn run_receiver_with_recovery(
 
 /// Run a single receiver session
 fn run_receiver_session(
-    event_sender: &Sender<MonitorEvent>,
+    event_sender: &EventSender,
     config: &ReceiverConfig,
     stats: &mut ReceiverStats,
 ) -> std::io::Result<()> {
@@ -198,13 +175,12 @@ fn run_receiver_session(
 
     // Open pipe with non-blocking read
     let file = open_pipe_robust(&config.pipe_path)?;
-    let reader = BufReader::new(file);
+    let mut reader = BufReader::new(file);
     let mut last_activity = Instant::now();
-    let mut buffer = String::new();
 
     println!("[HookReceiver] Pipe opened successfully, listening for events...");
 
-    for line_result in reader.lines() {
+    loop {
         // Check for read timeout
         if last_activity.elapsed() > READ_TIMEOUT {
             println!("[HookReceiver] Read timeout, reconnecting...");
@@ -214,88 +190,17 @@ fn run_receiver_session(
             ));
         }
 
-        match line_result {
-            Ok(line) => {
+        // One NDJSON line per message
+        let line = match protocol::read_frame(&mut reader) {
+            Ok(Some(Frame::Line(line))) => line,
+            Ok(Some(Frame::TooLong(len))) => {
                 last_activity = Instant::now();
-
-                if line.trim().is_empty() {
-                    continue;
-                }
-
-                // Handle potential multi-line JSON
-                buffer.push_str(&line);
-
-                // Try to parse JSON (check for killed event first)
-                if let Ok(event) = serde_json::from_str::<serde_json::Value>(&buffer) {
-                    // Check if this is a "killed" event
-                    if let Some(evt) = event.get("evt").and_then(|v| v.as_str()) {
-                        if evt == "killed" {
-                            // Extract PID from sid (format: "PID-{pid}")
-                            if let Some(sid) = event.get("sid").and_then(|v| v.as_str()) {
-                                if sid.starts_with("PID-") {
-                                    if let Some(pid_str) = sid.strip_prefix("PID-") {
-                                        if let Ok(pid) = pid_str.parse::<u32>() {
-                                            println!("[HookReceiver] 💀 Received process killed event for PID {}", pid);
-
-                                            // Send notification via notification module
-                                            notification::send_zombie_killed_notification(pid);
-
-                                            buffer.clear();
-                                            stats.events_received += 1;
-                                            stats.last_event_time = Some(Instant::now());
-                                            continue;
-                                        }
-                                    }
-                                }
-                            }
-                        }
-                    }
-                }
-
-                // Try to parse as regular HookEvent
-                match serde_json::from_str::<HookEventWithTimestamp>(&buffer) {
-                    Ok(event_with_ts) => {
-                        buffer.clear();
-                        stats.events_received += 1;
-                        stats.last_event_time = Some(Instant::now());
-
-                        // Convert to standard HookEvent
-                        let hook_event = HookEvent::from(event_with_ts.clone());
-
-                        // Filter out invalid session IDs (like $SESSION_ID)
-                        if hook_event.sid == "$SESSION_ID" || hook_event.sid.is_empty() {
-                            println!("[HookReceiver] Ignoring event with invalid session ID: '{}'", hook_event.sid);
-                            continue;
-                        }
-
-                        println!("[HookReceiver] Event #{}: session={}, type={}, time={}",
-                            stats.events_received,
-                            &hook_event.sid[..8.min(hook_event.sid.len())],
-                            hook_event.evt,
-                            event_with_ts.timestamp
-                        );
-
-                        // Send to coordinator
-                        if event_sender.send(MonitorEvent::Hook(hook_event)).is_err() {
-                            println!("[HookReceiver] Coordinator channel closed");
-                            return Ok(());
-                        }
-                    }
-                    Err(e) => {
-                        // Check if it might be incomplete JSON
-                        if buffer.contains('{') && !buffer.contains('}') {
-                            // Wait for more data
-                            continue;
-                        } else {
-                            // Invalid JSON, log and clear buffer
-                            stats.parse_errors += 1;
-                            eprintln!("[HookReceiver] Parse error #{}: {} - Data: {}",
-                                stats.parse_errors, e, buffer);
-                            buffer.clear();
-                        }
-                    }
-                }
+                stats.parse_errors += 1;
+                eprintln!("[HookReceiver] Parse error #{}: dropped {}-byte message (max {})",
+                    stats.parse_errors, len, protocol::MAX_MESSAGE_LEN);
+                continue;
             }
+            Ok(None) => break,
             Err(e) => {
                 stats.read_errors += 1;
                 eprintln!("[HookReceiver] Read error #{}: {}", stats.read_errors, e);
@@ -307,7 +212,33 @@ fn run_receiver_session(
                         "Pipe connection broken"
                     ));
                 }
+                continue;
+            }
+        };
+        last_activity = Instant::now();
+
+        if line.iter().all(u8::is_ascii_whitespace) {
+            continue;
+        }
+
+        let envelope = match protocol::decode(&line) {
+            Ok(envelope) => envelope,
+            Err(FrameError::BadToken) => {
+                // Only our hooks and instances know the token; drop anything else
+                stats.auth_failures += 1;
+                eprintln!("[HookReceiver] ⚠️ Rejected message without a valid token (#{})", stats.auth_failures);
+                continue;
+            }
+            Err(e) => {
+                stats.parse_errors += 1;
+                eprintln!("[HookReceiver] Parse error #{}: {}", stats.parse_errors, e);
+                continue;
             }
+        };
+
+        if !dispatch(envelope, event_sender, stats) {
+            println!("[HookReceiver] Coordinator channel closed");
+            return Ok(());
         }
     }
 
@@ -318,6 +249,61 @@ fn run_receiver_session(
     ))
 }
 
+/// Route a decoded message; false when the coordinator is gone
+fn dispatch(envelope: Envelope, event_sender: &EventSender, stats: &mut ReceiverStats) -> bool {
+    let event_with_ts = match PipeEvent::from_envelope(envelope) {
+        Ok(PipeEvent::Hook(event)) => event,
+        Ok(PipeEvent::DeepLink { url }) => {
+            // claudeminer:// URL forwarded by a second instance
+            crate::deeplink::handle(&url);
+            return true;
+        }
+        Ok(PipeEvent::Approval { sid, message, profile }) => {
+            println!("[HookReceiver] ✋ Notification for session {}: {}", sid.get(..8).unwrap_or(&sid), message);
+            stats.events_received += 1;
+            stats.last_event_time = Some(Instant::now());
+
+            let approval = ApprovalEvent { sid, message, profile, timestamp: current_timestamp() };
+            return event_sender.send(MonitorEvent::Approval(approval)).is_ok();
+        }
+        Ok(PipeEvent::Killed { pid }) => {
+            println!("[HookReceiver] 💀 Received process killed event for PID {}", pid);
+            stats.events_received += 1;
+            stats.last_event_time = Some(Instant::now());
+
+            // The coordinator drops the session and sends the notification
+            let killed = ProcessExitEvent { pid, timestamp: current_timestamp() };
+            return event_sender.send(MonitorEvent::ProcessKilled(killed)).is_ok();
+        }
+        Err(e) => {
+            stats.parse_errors += 1;
+            eprintln!("[HookReceiver] Parse error #{}: {}", stats.parse_errors, e);
+            return true;
+        }
+    };
+    stats.events_received += 1;
+    stats.last_event_time = Some(Instant::now());
+
+    // Convert to standard HookEvent
+    let hook_event = HookEvent::from(event_with_ts.clone());
+
+    // Filter out invalid session IDs (like $SESSION_ID)
+    if hook_event.sid == "$SESSION_ID" || hook_event.sid.is_empty() {
+        println!("[HookReceiver] Ignoring event with invalid session ID: '{}'", hook_event.sid);
+        return true;
+    }
+
+    println!("[HookReceiver] Event #{}: session={}, type={}, time={}",
+        stats.events_received,
+        hook_event.sid.get(..8).unwrap_or(&hook_event.sid),
+        hook_event.evt,
+        event_with_ts.timestamp
+    );
+
+    // Send to coordinator
+    event_sender.send(MonitorEvent::Hook(hook_event)).is_ok()
+}
+
 /// Open pipe with robust error handling
 fn open_pipe_robust(path: &str) -> std::io::Result<fs::File> {
     let mut attempts = 0;
@@ -355,7 +341,14 @@ fn ensure_pipe_healthy(path: &str) -> std::io::Result<()> {
 
         #[cfg(unix)]
         {
-            use std::os::unix::fs::FileTypeExt;
+            use std::os::unix::fs::{FileTypeExt, MetadataExt};
+            if metadata.uid() != pipe::current_uid() {
+                // Someone else created our path (shared /tmp): don't read their pipe
+                return Err(std::io::Error::new(
+                    std::io::ErrorKind::PermissionDenied,
+                    format!("{} is owned by another user (uid {})", path, metadata.uid())
+                ));
+            }
             if !metadata.file_type().is_fifo() {
                 println!("[HookReceiver] Path exists but is not a FIFO, recreating...");
                 fs::remove_file(pipe_path)?;
@@ -388,8 +381,8 @@ fn ensure_pipe_healthy(path: &str) -> std::io::Result<()> {
     Ok(())
 }
 
-/// Recreate the named pipe
-fn recreate_pipe(path: &str) -> std::io::Result<()> {
+/// Recreate the named pipe (the receiver picks it up when it reconnects)
+pub fn recreate_pipe(path: &str) -> std::io::Result<()> {
     let pipe_path = Path::new(path);
 
     if pipe_path.exists() {
@@ -419,7 +412,7 @@ fn create_named_pipe(path: &str) -> std::io::Result<()> {
 
     let output = Command::new("mkfifo")
         .arg("-m")
-        .arg("622")  // rw--w--w-
+        .arg("600")  // rw------- (per-user pipe)
         .arg(path)
         .output()?;
 
@@ -444,7 +437,7 @@ fn create_named_pipe(path: &str) -> std::io::Result<()> {
 
     match unistd::mkfifo(
         path,
-        stat::Mode::S_IRUSR | stat::Mode::S_IWUSR | stat::Mode::S_IWGRP | stat::Mode::S_IWOTH
+        stat::Mode::S_IRUSR | stat::Mode::S_IWUSR
     ) {
         Ok(_) => {
             println!("[HookReceiver] Named pipe created: {}", path);
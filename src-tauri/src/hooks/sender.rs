@@ -1,46 +1,49 @@
 // Named Pipe Sender - Send events to ClaudeMiner
 //
-// Sends process termination events to the named pipe for ClaudeMiner to pick up
+// Sends protocol messages (killed processes, deep links, permission requests) to the running instance's pipe
 
-use std::fs::OpenOptions;
-use std::io::Write;
-use std::path::Path;
+use crate::hooks::protocol::{self, PipeEvent};
 
-const PIPE_PATH: &str = "/tmp/claudeminer_pipe";
-
-/// Send a process killed event to the named pipe
+/// Tell the running instance that a miner process was killed
+/// (its coordinator drops the session and sends the notification)
 pub fn send_process_killed_event(pid: u32) -> Result<(), String> {
-    send_named_pipe_message(&format!("PROCESS_KILLED:{}", pid))
+    send_event(&PipeEvent::Killed { pid })
 }
 
-/// Send a raw message to the named pipe
-fn send_named_pipe_message(message: &str) -> Result<(), String> {
-    // Check if pipe exists
-    if !Path::new(PIPE_PATH).exists() {
-        return Err(format!("Named pipe does not exist: {}", PIPE_PATH));
-    }
+/// Report a permission request for a session, as the Notification hook does
+pub fn send_approval_event(sid: &str, message: &str) -> Result<(), String> {
+    send_event(&PipeEvent::Approval { sid: sid.to_string(), message: message.to_string(), profile: None })
+}
+
+/// Forward a claudeminer:// URL to the running instance
+pub fn send_deep_link_event(url: &str) -> Result<(), String> {
+    send_event(&PipeEvent::DeepLink { url: url.to_string() })
+}
+
+/// Write one protocol message to the pipe
+/// Fails right away when no instance is reading the pipe (a leftover FIFO would block)
+fn send_event(event: &PipeEvent) -> Result<(), String> {
+    let message = protocol::encode(event);
 
-    // Open pipe for writing (non-blocking)
-    match OpenOptions::new()
-        .write(true)
-        .open(PIPE_PATH)
+    #[cfg(unix)]
     {
-        Ok(mut pipe) => {
-            // Write message
-            match writeln!(pipe, "{}", message) {
-                Ok(_) => {
-                    println!("[PipeSender] Sent message: {}", message);
-                    Ok(())
-                }
-                Err(e) => {
-                    Err(format!("Failed to write to pipe: {}", e))
-                }
-            }
-        }
-        Err(e) => {
-            Err(format!("Failed to open pipe: {}", e))
-        }
+        use crate::hooks::pipe;
+        use std::fs::OpenOptions;
+        use std::io::Write;
+        use std::os::unix::fs::OpenOptionsExt;
+
+        let mut pipe = OpenOptions::new()
+            .write(true)
+            .custom_flags(libc::O_NONBLOCK)
+            .open(pipe::path())
+            .map_err(|e| format!("No running instance: {}", e))?;
+        pipe.write_all(message.as_bytes()).map_err(|e| format!("Failed to write to pipe: {}", e))?;
+        println!("[PipeSender] Sent message: {}", message.trim_end());
+        Ok(())
     }
+
+    #[cfg(not(unix))]
+    Err(format!("Named pipes are not supported on this platform: {}", message))
 }
 
 #[cfg(test)]
@@ -0,0 +1,213 @@
+// Settings Watcher Thread
+//
+// Watches every Claude home's settings.json. When an edit (by the user or another
+// tool) drops the ClaudeMiner hooks, sessions silently fall back to CPU/log detection,
+// so the hooks are re-registered right away and the user is told about it.
+// The directories are watched rather than the files: editors often replace the
+// file with a rename, which would end a watch on the file itself.
+//
+// Also reports hook conflicts: duplicate ClaudeMiner hooks, and other tools' hooks
+// on the events ClaudeMiner relies on.
+//
+
+use crate::config;
+use crate::hooks::manager::{self, HookConfig, ClaudeSettings};
+use crate::hooks::pipe;
+use crate::notification;
+use notify::{Watcher, RecursiveMode, Event, EventKind};
+use serde::Serialize;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::channel;
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// Wait for writes to settle (editors save in several steps)
+const DEBOUNCE: Duration = Duration::from_millis(500);
+
+/// A hook that may interfere with ClaudeMiner's
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct HookConflict {
+    pub settings_path: String,
+    pub event: &'static str,
+    pub command: String,
+    pub reason: ConflictReason,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ConflictReason {
+    Duplicate,  // ClaudeMiner hook registered more than once for the event
+    Foreign,    // Another tool's hook on the same event (runs alongside ours)
+}
+
+/// Start the settings.json watcher thread
+pub fn start_settings_watcher() -> thread::JoinHandle<()> {
+    thread::spawn(|| {
+        if let Err(e) = run_settings_watcher() {
+            eprintln!("[SettingsWatcher] Error: {}", e);
+        }
+    })
+}
+
+fn run_settings_watcher() -> notify::Result<()> {
+    let (tx, rx) = channel();
+    let mut watcher = notify::recommended_watcher(tx)?;
+
+    // settings.json path -> profile name
+    let settings: HashMap<PathBuf, String> = manager::get_settings_paths()
+        .into_iter()
+        .map(|(profile, path)| (path, profile))
+        .collect();
+
+    let mut watched = 0;
+    for path in settings.keys() {
+        let Some(dir) = path.parent().filter(|d| d.is_dir()) else { continue };
+        match watcher.watch(dir, RecursiveMode::NonRecursive) {
+            Ok(()) => {
+                println!("[SettingsWatcher] Watching: {}", path.display());
+                watched += 1;
+            }
+            Err(e) => eprintln!("[SettingsWatcher] Cannot watch {}: {}", dir.display(), e),
+        }
+    }
+
+    if watched == 0 {
+        println!("[SettingsWatcher] No Claude homes found, settings watching disabled");
+        return Ok(());
+    }
+
+    // Changed settings.json -> when it last changed
+    let mut pending: HashMap<PathBuf, Instant> = HashMap::new();
+
+    loop {
+        match rx.recv_timeout(Duration::from_millis(100)) {
+            Ok(Ok(Event { kind: EventKind::Modify(_) | EventKind::Create(_) | EventKind::Remove(_), paths, .. })) => {
+                for path in paths.into_iter().filter(|p| settings.contains_key(p)) {
+                    pending.insert(path, Instant::now());
+                }
+            }
+            Ok(Ok(_)) => {}
+            Ok(Err(e)) => eprintln!("[SettingsWatcher] Watch error: {}", e),
+            Err(std::sync::mpsc::RecvTimeoutError::Timeout) => {}
+            Err(std::sync::mpsc::RecvTimeoutError::Disconnected) => return Ok(()),
+        }
+
+        let settled: Vec<PathBuf> = pending.iter()
+            .filter(|(_, changed)| changed.elapsed() >= DEBOUNCE)
+            .map(|(path, _)| path.clone())
+            .collect();
+        for path in settled {
+            pending.remove(&path);
+            repair_if_needed(&path, &settings[&path]);
+        }
+    }
+}
+
+/// Re-register the hooks if an edit removed them
+fn repair_if_needed(path: &Path, profile: &str) {
+    if !config::get().hooks.enabled {
+        return;
+    }
+
+    let settings = match manager::read_settings(path) {
+        Ok(settings) => settings,
+        Err(e) => {
+            // Mid-edit or hand-broken JSON: leave it alone, the next save triggers another check
+            eprintln!("[SettingsWatcher] Cannot parse {}: {}", path.display(), e);
+            return;
+        }
+    };
+    for conflict in find_conflicts(&settings, &path.display().to_string()) {
+        println!("[SettingsWatcher] Hook conflict ({:?}) on {}: {}", conflict.reason, conflict.event, conflict.command);
+    }
+    if manager::hooks_up_to_date(&settings) {
+        return;
+    }
+
+    println!("[SettingsWatcher] ⚠️ ClaudeMiner hooks removed or changed in {}, re-registering", path.display());
+    match manager::register_hooks(path, profile) {
+        Ok(()) => notification::send_hooks_repaired_notification(&path.display().to_string()),
+        Err(e) => eprintln!("[SettingsWatcher] Failed to re-register hooks in {}: {}", path.display(), e),
+    }
+}
+
+/// Hook conflicts in every Claude home's settings.json
+pub fn find_all_conflicts() -> Vec<HookConflict> {
+    manager::get_settings_paths()
+        .into_iter()
+        .filter(|(_, path)| path.exists())
+        .filter_map(|(_, path)| {
+            let settings = manager::read_settings(&path).ok()?;
+            Some(find_conflicts(&settings, &path.display().to_string()))
+        })
+        .flatten()
+        .collect()
+}
+
+/// Duplicate ClaudeMiner hooks and foreign hooks on the events ClaudeMiner uses
+pub fn find_conflicts(settings: &ClaudeSettings, settings_path: &str) -> Vec<HookConflict> {
+    let events: [(&'static str, &[HookConfig]); 4] = [
+        ("UserPromptSubmit", &settings.hooks.user_prompt_submit),
+        ("Stop", &settings.hooks.stop),
+        ("Notification", &settings.hooks.notification),
+        ("PostToolUse", &settings.hooks.post_tool_use),
+    ];
+
+    let mut conflicts = Vec::new();
+    for (event, configs) in events {
+        let mut ours = 0;
+        for hook in configs.iter().flat_map(|c| c.hooks.iter()) {
+            let reason = if pipe::is_claudeminer_command(&hook.command) {
+                ours += 1;
+                if ours == 1 {
+                    continue;
+                }
+                ConflictReason::Duplicate
+            } else {
+                ConflictReason::Foreign
+            };
+            conflicts.push(HookConflict {
+                settings_path: settings_path.to_string(),
+                event,
+                command: hook.command.clone(),
+                reason,
+            });
+        }
+    }
+    conflicts
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hooks::manager::{Hook, HookEvents};
+    use serde_json::json;
+
+    fn hook_config(command: &str) -> HookConfig {
+        HookConfig {
+            matcher: "*".to_string(),
+            hooks: vec![Hook { hook_type: "command".to_string(), command: command.to_string() }],
+        }
+    }
+
+    #[test]
+    fn test_find_conflicts() {
+        let ours = format!("echo '{{}}' > {}", pipe::shell_quoted());
+        let settings = ClaudeSettings {
+            hooks: HookEvents {
+                user_prompt_submit: vec![hook_config(&ours)],
+                stop: vec![hook_config(&ours), hook_config("afplay done.aiff"), hook_config(&ours)],
+                ..Default::default()
+            },
+            other: json!({}),
+        };
+
+        let conflicts = find_conflicts(&settings, "settings.json");
+        assert_eq!(conflicts.len(), 2);
+        assert_eq!(conflicts[0].reason, ConflictReason::Foreign);
+        assert_eq!(conflicts[0].command, "afplay done.aiff");
+        assert_eq!(conflicts[1].reason, ConflictReason::Duplicate);
+        assert!(conflicts.iter().all(|c| c.event == "Stop"));
+    }
+}
@@ -0,0 +1,282 @@
+// Kill Safety
+//
+// kill_miner refuses to kill a session that is in the middle of a task (working,
+// waiting for a permission answer, or with recent tool activity in its logs) unless
+// the caller passes force. The refusal is a structured error, so the UI can ask the
+// user to confirm and retry with force instead of showing a plain failure.
+//
+// Tree mode also kills everything the process spawned (bash, node, MCP servers):
+// the process group when the miner leads its own, then every descendant found in
+// the process table, and reports which PIDs actually went away.
+//
+
+use crate::session::SessionState;
+use crate::status::machine;
+use crate::types::WorkingState;
+use serde::Serialize;
+use std::collections::{HashMap, VecDeque};
+use std::fmt;
+use std::thread;
+use std::time::{Duration, Instant};
+use sysinfo::{Pid, ProcessStatus, System};
+
+/// Tool activity in the logs this recent still counts as in progress
+const TOOL_ACTIVITY_SECS: u64 = 30;
+
+/// How long to wait for killed processes to disappear
+const REAP_TIMEOUT: Duration = Duration::from_secs(1);
+
+/// Result of a kill (tree mode lists every process of the tree)
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct KillReport {
+    pub pid: u32,
+    pub reaped: Vec<u32>,      // Confirmed gone
+    pub survived: Vec<u32>,    // Still running after the timeout (e.g. owned by another user)
+    pub process_group: bool,   // The root's process group was signalled
+}
+
+/// Why kill_miner didn't kill (serialized for the UI, tagged by "code")
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "code", rename_all = "snake_case")]
+pub enum KillError {
+    /// The session is mid-task: confirm, then retry with force
+    SessionBusy { pid: u32, session_id: String, status: String, reason: String, message: String },
+    Failed { message: String },
+}
+
+impl fmt::Display for KillError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            KillError::SessionBusy { message, .. } | KillError::Failed { message } => f.write_str(message),
+        }
+    }
+}
+
+impl From<String> for KillError {
+    fn from(message: String) -> Self {
+        KillError::Failed { message }
+    }
+}
+
+/// What a session is busy with, if killing it would interrupt work
+pub fn busy_reason(session: &SessionState, now: u64) -> Option<&'static str> {
+    match session.current_status {
+        machine::WORKING => return Some("working"),
+        machine::WAITING_APPROVAL => return Some("waiting for permission"),
+        _ => {}
+    }
+    session.last_log_event.as_ref()
+        .filter(|e| matches!(e.state, WorkingState::ActivelyWorking))
+        .filter(|e| now.saturating_sub(e.timestamp) < TOOL_ACTIVITY_SECS)
+        .map(|_| "running a tool")
+}
+
+/// Refuse to kill a busy session's process unless forced
+pub fn check_kill_allowed(
+    sessions: &HashMap<String, SessionState>,
+    pid: u32,
+    force: bool,
+    now: u64,
+) -> Result<(), KillError> {
+    if force {
+        return Ok(());
+    }
+    let busy = sessions.values()
+        .filter(|s| s.pid == pid && s.container.is_none())
+        .find_map(|s| busy_reason(s, now).map(|reason| (s, reason)));
+
+    match busy {
+        Some((session, reason)) => {
+            println!("[kill_miner] Refusing to kill PID {}: session {} is {}", pid, session.session_id, reason);
+            Err(KillError::SessionBusy {
+                pid,
+                session_id: session.session_id.clone(),
+                status: session.current_status.to_string(),
+                reason: reason.to_string(),
+                message: format!("Process {} is {} (pass force to kill it anyway)", pid, reason),
+            })
+        }
+        None => Ok(()),
+    }
+}
+
+/// `root` and its descendants from (pid, parent) pairs, deepest first, root last
+pub fn process_tree(processes: &[(u32, Option<u32>)], root: u32) -> Vec<u32> {
+    let mut children: HashMap<u32, Vec<u32>> = HashMap::new();
+    for &(pid, parent) in processes {
+        if let Some(parent) = parent {
+            children.entry(parent).or_default().push(pid);
+        }
+    }
+
+    // Breadth-first, then reversed so children are killed before their parents
+    let mut tree = vec![root];
+    let mut queue = VecDeque::from([root]);
+    while let Some(pid) = queue.pop_front() {
+        for &child in children.get(&pid).into_iter().flatten() {
+            if !tree.contains(&child) {
+                tree.push(child);
+                queue.push_back(child);
+            }
+        }
+    }
+    tree.reverse();
+    tree
+}
+
+/// Kill a process and everything it spawned
+pub fn kill_tree(pid: u32) -> Result<KillReport, String> {
+    let mut sys = System::new();
+    sys.refresh_processes();
+    if sys.process(Pid::from_u32(pid)).is_none() {
+        return Err(format!("Process {} not found", pid));
+    }
+    let processes: Vec<(u32, Option<u32>)> = sys.processes().iter()
+        .map(|(pid, process)| (pid.as_u32(), process.parent().map(|p| p.as_u32())))
+        .collect();
+    let tree = process_tree(&processes, pid);
+    println!("[kill_miner] Killing process tree of PID {}: {:?}", pid, tree);
+
+    let process_group = signal_tree(pid, &tree)?;
+
+    // Wait for the processes to go away (zombies count as gone: only their parent can reap them)
+    let deadline = Instant::now() + REAP_TIMEOUT;
+    let mut survived = tree.clone();
+    loop {
+        survived.retain(|&p| is_running(&mut sys, p));
+        if survived.is_empty() || Instant::now() >= deadline {
+            break;
+        }
+        thread::sleep(Duration::from_millis(50));
+    }
+
+    let reaped: Vec<u32> = tree.iter().copied().filter(|p| !survived.contains(p)).collect();
+    println!("[kill_miner] Reaped {:?}, survived {:?}", reaped, survived);
+    Ok(KillReport { pid, reaped, survived, process_group })
+}
+
+fn is_running(sys: &mut System, pid: u32) -> bool {
+    let pid = Pid::from_u32(pid);
+    sys.refresh_process(pid);
+    sys.process(pid).is_some_and(|p| p.status() != ProcessStatus::Zombie)
+}
+
+/// SIGKILL the root's process group (if it leads one that isn't ours) and every process in `tree`
+/// Returns whether the process group was signalled
+#[cfg(unix)]
+fn signal_tree(root: u32, tree: &[u32]) -> Result<bool, String> {
+    let root = root as libc::pid_t;
+    // SAFETY: getpgid/getpgrp/killpg/kill have no memory preconditions; failures are reported via errno
+    let process_group = unsafe { libc::getpgid(root) == root && libc::getpgrp() != root };
+    if process_group && unsafe { libc::killpg(root, libc::SIGKILL) } != 0 {
+        println!("[kill_miner] ⚠️ killpg({}) failed: {}", root, std::io::Error::last_os_error());
+    }
+    for &pid in tree {
+        // ESRCH is expected for processes the group kill already took
+        unsafe { libc::kill(pid as libc::pid_t, libc::SIGKILL) };
+    }
+    Ok(process_group)
+}
+
+/// SIGKILL a single process
+#[cfg(unix)]
+pub fn signal(pid: u32) -> Result<(), String> {
+    // SAFETY: kill has no memory preconditions; failures are reported via errno
+    if unsafe { libc::kill(pid as libc::pid_t, libc::SIGKILL) } == 0 {
+        Ok(())
+    } else {
+        Err(format!("Failed to kill process {}: {}", pid, std::io::Error::last_os_error()))
+    }
+}
+
+#[cfg(windows)]
+fn signal_tree(root: u32, _tree: &[u32]) -> Result<bool, String> {
+    // taskkill /T walks the tree itself
+    std::process::Command::new("taskkill")
+        .args(["/F", "/T", "/PID", &root.to_string()])
+        .output()
+        .map(|_| false)
+        .map_err(|e| format!("Failed to kill process {}: {}", root, e))
+}
+
+#[cfg(not(any(unix, windows)))]
+fn signal_tree(_root: u32, _tree: &[u32]) -> Result<bool, String> {
+    Err("Unsupported platform".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::session::LogEvent;
+
+    #[test]
+    fn test_process_tree_children_first() {
+        let processes = [
+            (1, None),
+            (10, Some(1)),     // Miner
+            (11, Some(10)),    // bash
+            (12, Some(11)),    // node under bash
+            (13, Some(10)),    // MCP server
+            (20, Some(1)),     // Unrelated
+        ];
+        let tree = process_tree(&processes, 10);
+        assert_eq!(tree.last(), Some(&10));
+        assert_eq!(tree.len(), 4);
+        assert!(!tree.contains(&20));
+        let position = |pid| tree.iter().position(|&p| p == pid).unwrap();
+        assert!(position(12) < position(11));
+        assert_eq!(process_tree(&processes, 12), vec![12]);
+    }
+
+    #[test]
+    fn test_busy_sessions_need_force() {
+        let mut sessions = HashMap::new();
+        let mut working = SessionState::new_legacy(100, "working".to_string());
+        working.current_status = machine::WORKING;
+        sessions.insert("working".to_string(), working);
+
+        let mut tool = SessionState::new_legacy(200, "tool".to_string());
+        tool.current_status = machine::RESTING;
+        tool.last_log_event = Some(LogEvent {
+            session_id: "tool".to_string(),
+            pid: Some(200),
+            timestamp: 1_000,
+            state: WorkingState::ActivelyWorking,
+            has_approval_pending: false,
+            file_mtime: 1_000,
+            transcript: None,
+            profile: None,
+        });
+        sessions.insert("tool".to_string(), tool);
+
+        let mut zombie = SessionState::new_legacy(300, "zombie".to_string());
+        zombie.current_status = machine::ZOMBIE;
+        sessions.insert("zombie".to_string(), zombie);
+
+        match check_kill_allowed(&sessions, 100, false, 1_010) {
+            Err(KillError::SessionBusy { session_id, reason, .. }) => {
+                assert_eq!(session_id, "working");
+                assert_eq!(reason, "working");
+            }
+            other => panic!("expected SessionBusy, got {:?}", other),
+        }
+        assert!(check_kill_allowed(&sessions, 200, false, 1_010).is_err());
+        assert!(check_kill_allowed(&sessions, 200, false, 1_100).is_ok());  // Tool activity is old
+        assert!(check_kill_allowed(&sessions, 300, false, 1_010).is_ok());
+        assert!(check_kill_allowed(&sessions, 100, true, 1_010).is_ok());
+
+        let json = serde_json::to_value(check_kill_allowed(&sessions, 100, false, 1_010).unwrap_err()).unwrap();
+        assert_eq!(json["code"], "session_busy");
+        assert_eq!(json["pid"], 100);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_signal() {
+        use std::os::unix::process::ExitStatusExt;
+
+        let mut child = std::process::Command::new("sleep").arg("30").spawn().unwrap();
+        assert_eq!(signal(child.id()), Ok(()));
+        assert_eq!(child.wait().unwrap().signal(), Some(libc::SIGKILL));
+    }
+}
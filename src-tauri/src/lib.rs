@@ -0,0 +1,43 @@
+// ClaudeMiner Library
+//
+// The monitoring, session and notification modules; main.rs wires them into the Tauri app.
+// Split out of the binary so benches (benches/) can drive the coordinator directly.
+//
+
+#![allow(dead_code)]
+#![allow(unused_variables)]
+#![allow(unused_mut)]
+
+pub mod types;
+pub mod achievements;
+pub mod app_info;
+pub mod automation;
+pub mod autostart;
+pub mod claude_cli;
+pub mod config;
+pub mod crash;
+pub mod debug_log;
+pub mod deeplink;
+pub mod demo;
+pub mod doctor;
+pub mod kill;
+pub mod logs;
+pub mod network;
+pub mod permissions;
+pub mod persist;
+pub mod query;
+pub mod session;
+pub mod status;
+pub mod monitor;
+pub mod hooks;
+pub mod coordinator;
+pub mod notification;
+pub mod overlay;
+pub mod event;
+pub mod terminal;
+pub mod trace;
+#[cfg(test)]
+mod testing;
+pub mod updater;
+pub mod widget;
+pub mod window_state;
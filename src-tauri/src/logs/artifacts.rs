@@ -0,0 +1,103 @@
+// Session Artifacts
+//
+// Reveal a session's debug log, transcript or project directory in Finder/Explorer,
+// or open it with the default application (text editor for logs), using the paths
+// ClaudeMiner already knows (Claude homes and the session's working directory).
+//
+
+use crate::logs::LogSource;
+use serde::Deserialize;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Artifact {
+    DebugLog,
+    Transcript,
+    Project,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OpenMode {
+    Reveal,  // Select it in the file manager
+    Open,    // Default application
+}
+
+/// Locate an artifact; `project_path` is the session's working directory, if known
+pub fn resolve(session_id: &str, artifact: Artifact, project_path: Option<&str>) -> Result<PathBuf, String> {
+    match artifact {
+        Artifact::DebugLog => LogSource::Debug.resolve(session_id),
+        Artifact::Transcript => LogSource::Transcript.resolve(session_id),
+        Artifact::Project => project_path
+            .map(PathBuf::from)
+            .filter(|path| path.is_dir())
+            .ok_or_else(|| format!("Project directory of session {} is unknown", session_id)),
+    }
+}
+
+/// Reveal or open a path with the platform's tools
+pub fn open(path: &Path, mode: OpenMode) -> Result<(), String> {
+    let (program, args) = command_for(path, mode, path.is_dir());
+    println!("[Artifacts] {} {:?}", program, args);
+    let mut child = Command::new(program)
+        .args(&args)
+        .spawn()
+        .map_err(|e| format!("Failed to open {}: {}", path.display(), e))?;
+
+    // Reap it in the background (notepad runs until closed; explorer's exit code means nothing)
+    crate::crash::spawn_named("artifact-opener", move || {
+        let _ = child.wait();
+    });
+    Ok(())
+}
+
+#[cfg(target_os = "macos")]
+fn command_for(path: &Path, mode: OpenMode, is_dir: bool) -> (&'static str, Vec<String>) {
+    let path = path.display().to_string();
+    match mode {
+        OpenMode::Reveal => ("open", vec!["-R".to_string(), path]),
+        OpenMode::Open if is_dir => ("open", vec![path]),
+        OpenMode::Open => ("open", vec!["-t".to_string(), path]),  // .txt/.jsonl in the default text editor
+    }
+}
+
+#[cfg(target_os = "windows")]
+fn command_for(path: &Path, mode: OpenMode, is_dir: bool) -> (&'static str, Vec<String>) {
+    let path = path.display().to_string();
+    match mode {
+        OpenMode::Reveal => ("explorer", vec![format!("/select,{}", path)]),
+        OpenMode::Open if is_dir => ("explorer", vec![path]),
+        OpenMode::Open => ("notepad", vec![path]),  // .jsonl has no default application
+    }
+}
+
+#[cfg(not(any(target_os = "macos", target_os = "windows")))]
+fn command_for(path: &Path, mode: OpenMode, is_dir: bool) -> (&'static str, Vec<String>) {
+    // No portable "select in file manager": show the containing directory
+    let target = match mode {
+        OpenMode::Reveal if !is_dir => path.parent().unwrap_or(path),
+        _ => path,
+    };
+    ("xdg-open", vec![target.display().to_string()])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve_project_and_commands() {
+        let dir = tempfile::tempdir().unwrap();
+        let project = dir.path().to_str().unwrap();
+        assert_eq!(resolve("s", Artifact::Project, Some(project)).unwrap(), dir.path());
+        assert!(resolve("s", Artifact::Project, None).is_err());
+        assert!(resolve("s", Artifact::Project, Some("/nonexistent/project")).is_err());
+
+        let log = dir.path().join("session.txt");
+        let (_, reveal) = command_for(&log, OpenMode::Reveal, false);
+        let (_, open) = command_for(&log, OpenMode::Open, false);
+        assert!(reveal.iter().any(|arg| arg.contains(project)));
+        assert!(open.iter().any(|arg| arg.ends_with("session.txt")));
+    }
+}
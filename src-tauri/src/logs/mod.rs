@@ -0,0 +1,41 @@
+// Session Logs Module
+//
+// Read access to a session's own files for the UI: the debug log
+// (<claude home>/debug/<session_id>.txt) and the project transcript
+// (<claude home>/projects/<project>/<session_id>.jsonl), plus opening them
+// (and the project directory) in the file manager or an editor
+
+pub mod artifacts;
+pub mod search;
+pub mod tail;
+
+use crate::session::finder;
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+/// Which of a session's files to read
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LogSource {
+    #[default]
+    Debug,
+    Transcript,
+}
+
+impl LogSource {
+    /// Locate this file for a session
+    pub fn resolve(self, session_id: &str) -> Result<PathBuf, String> {
+        let path = match self {
+            LogSource::Debug => finder::find_debug_log(session_id),
+            LogSource::Transcript => finder::find_transcript(session_id),
+        };
+        path.ok_or_else(|| format!("No {} log found for session {}", self.as_str(), session_id))
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            LogSource::Debug => "debug",
+            LogSource::Transcript => "transcript",
+        }
+    }
+}
@@ -0,0 +1,146 @@
+// Session Log Search
+//
+// search_session_logs finds lines containing a query (ASCII case-insensitive) in a session's
+// debug log and transcript. Files are scanned backward in fixed-size chunks, so the newest
+// matches come first and a search that hits max_results stops without reading the rest of
+// a large log. Long lines (transcript records) are cut to a snippet around the match.
+//
+
+use crate::logs::LogSource;
+use serde::Serialize;
+use std::fs::File;
+use std::io::{self, Read, Seek, SeekFrom};
+use std::path::Path;
+
+const CHUNK_BYTES: u64 = 64 * 1024;
+const SNIPPET_CHARS: usize = 400;
+pub const DEFAULT_MAX_RESULTS: usize = 50;
+pub const MAX_RESULTS_LIMIT: usize = 500;
+
+/// A matching line
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct LogMatch {
+    pub source: LogSource,
+    pub offset: u64,      // Byte offset of the line in the file
+    pub line: String,     // The line, or a snippet around the match if it's long
+}
+
+/// Newest matches first, debug log before transcript
+pub fn search(session_id: &str, query: &str, max_results: usize) -> Result<Vec<LogMatch>, String> {
+    if query.trim().is_empty() {
+        return Err("Empty search query".to_string());
+    }
+    let max_results = max_results.clamp(1, MAX_RESULTS_LIMIT);
+
+    let mut matches = Vec::new();
+    let mut searched = 0;
+    for source in [LogSource::Debug, LogSource::Transcript] {
+        let Ok(path) = source.resolve(session_id) else { continue };
+        searched += 1;
+        let found = search_file(&path, query, max_results - matches.len(), CHUNK_BYTES)
+            .map_err(|e| format!("Failed to search {}: {}", path.display(), e))?;
+        matches.extend(found.into_iter().map(|(offset, line)| LogMatch { source, offset, line }));
+        if matches.len() >= max_results {
+            break;
+        }
+    }
+
+    if searched == 0 {
+        return Err(format!("No logs found for session {}", session_id));
+    }
+    Ok(matches)
+}
+
+/// (offset, line) of up to `max_results` matching lines, last line first
+fn search_file(path: &Path, query: &str, max_results: usize, chunk_bytes: u64) -> io::Result<Vec<(u64, String)>> {
+    let needle = query.to_ascii_lowercase();
+    let mut matches = Vec::new();
+
+    scan_backward(path, chunk_bytes, |offset, line| {
+        let text = String::from_utf8_lossy(line);
+        if let Some(index) = text.to_ascii_lowercase().find(&needle) {
+            matches.push((offset, snippet(&text, index)));
+        }
+        matches.len() < max_results
+    })?;
+    Ok(matches)
+}
+
+/// Call `visit(offset, line)` for every line from the end of the file until it returns false
+fn scan_backward(path: &Path, chunk_bytes: u64, mut visit: impl FnMut(u64, &[u8]) -> bool) -> io::Result<()> {
+    let mut file = File::open(path)?;
+    let mut end = file.metadata()?.len();
+    let mut carry: Vec<u8> = Vec::new();  // Line read so far whose start is in an earlier chunk
+
+    while end > 0 {
+        let start = end.saturating_sub(chunk_bytes);
+        let mut data = vec![0; (end - start) as usize];
+        file.seek(SeekFrom::Start(start))?;
+        file.read_exact(&mut data)?;
+        data.extend_from_slice(&carry);
+
+        // Every segment after a newline is a complete line; the first one may continue in the previous chunk
+        let mut line_end = data.len();
+        while let Some(newline) = data[..line_end].iter().rposition(|&b| b == b'\n') {
+            let line = &data[newline + 1..line_end];
+            if !line.is_empty() && !visit(start + newline as u64 + 1, line) {
+                return Ok(());
+            }
+            line_end = newline;
+        }
+        carry = data[..line_end].to_vec();
+        end = start;
+    }
+
+    if !carry.is_empty() {
+        visit(0, &carry);
+    }
+    Ok(())
+}
+
+/// The line, or SNIPPET_CHARS characters around byte `index`
+fn snippet(line: &str, index: usize) -> String {
+    let line = line.trim_end_matches('\r');
+    if line.chars().count() <= SNIPPET_CHARS {
+        return line.to_string();
+    }
+    let before = line[..index.min(line.len())].chars().count();
+    let first = before.saturating_sub(SNIPPET_CHARS / 4);
+    let text: String = line.chars().skip(first).take(SNIPPET_CHARS).collect();
+    format!("{}{}…", if first > 0 { "…" } else { "" }, text)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+    use tempfile::NamedTempFile;
+
+    #[test]
+    fn test_search_backward() {
+        let mut file = NamedTempFile::new().unwrap();
+        let lines = ["Read src/main.rs", "ok", "ERROR in src/lib.rs", "error again", "done"];
+        for line in lines {
+            writeln!(file, "{}", line).unwrap();
+        }
+        file.flush().unwrap();
+
+        // Tiny chunks so lines span chunk boundaries
+        let matches = search_file(file.path(), "error", 10, 4).unwrap();
+        assert_eq!(matches, vec![(40, "error again".to_string()), (20, "ERROR in src/lib.rs".to_string())]);
+
+        let matches = search_file(file.path(), "src/", 1, 4).unwrap();
+        assert_eq!(matches, vec![(20, "ERROR in src/lib.rs".to_string())]);
+
+        let matches = search_file(file.path(), "read", 10, 1024).unwrap();
+        assert_eq!(matches, vec![(0, "Read src/main.rs".to_string())]);
+    }
+
+    #[test]
+    fn test_snippet_of_long_line() {
+        let line = format!("{}needle{}", "a".repeat(1000), "b".repeat(1000));
+        let cut = snippet(&line, 1000);
+        assert!(cut.contains("needle"));
+        assert!(cut.starts_with('…') && cut.ends_with('…'));
+    }
+}
@@ -0,0 +1,144 @@
+// Live Log Tail
+//
+// tail_session_log streams a session's debug log or transcript to the frontend:
+// the last lines first, then new lines as they're written ("session-log-lines" events).
+// One tail per session; starting another replaces it, stop_tail_session_log ends it.
+// A tail also ends when its file goes away.
+//
+
+use crate::crash::spawn_named;
+use crate::event::emitter;
+use crate::logs::LogSource;
+use crate::monitor::transcript::read_new_lines;
+use once_cell::sync::Lazy;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{self, Read, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+const BACKLOG_LINES: usize = 100;
+const BACKLOG_BYTES: u64 = 64 * 1024;  // Where to look for the backlog lines
+
+/// Payload of "session-log-lines"
+#[derive(Debug, Clone, Serialize)]
+pub struct LogLines {
+    pub session_id: String,
+    pub source: LogSource,
+    pub lines: Vec<String>,
+}
+
+/// Running tails: session ID -> stop flag
+static TAILS: Lazy<Mutex<HashMap<String, Arc<AtomicBool>>>> = Lazy::new(|| Mutex::new(HashMap::new()));
+
+/// Start streaming a session's log; returns the file being tailed
+pub fn start(session_id: &str, source: LogSource) -> Result<PathBuf, String> {
+    let path = source.resolve(session_id)?;
+    let (backlog, offset) = last_lines(&path, BACKLOG_LINES)
+        .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+
+    let stop_flag = Arc::new(AtomicBool::new(false));
+    if let Some(previous) = TAILS.lock().unwrap().insert(session_id.to_string(), stop_flag.clone()) {
+        previous.store(true, Ordering::Relaxed);
+    }
+    println!("[LogTail] Tailing {} log of session {}: {}", source.as_str(), session_id, path.display());
+
+    let session_id = session_id.to_string();
+    let tailed = path.clone();
+    spawn_named("log-tail", move || {
+        run(LogLines { session_id, source, lines: backlog }, &tailed, offset, &stop_flag);
+    });
+    Ok(path)
+}
+
+/// Stop streaming a session's log; false if it wasn't being tailed
+pub fn stop(session_id: &str) -> bool {
+    match TAILS.lock().unwrap().remove(session_id) {
+        Some(stop_flag) => {
+            stop_flag.store(true, Ordering::Relaxed);
+            println!("[LogTail] Stopped tailing session {}", session_id);
+            true
+        }
+        None => false,
+    }
+}
+
+fn run(mut batch: LogLines, path: &Path, mut offset: u64, stop_flag: &Arc<AtomicBool>) {
+    emitter::emit_session_log_lines(&batch);
+
+    while !stop_flag.load(Ordering::Relaxed) {
+        thread::sleep(POLL_INTERVAL);
+        match read_new_lines(path, &mut offset) {
+            Ok(lines) if lines.is_empty() => {}
+            Ok(lines) => {
+                batch.lines = lines;
+                emitter::emit_session_log_lines(&batch);
+            }
+            Err(e) => {
+                println!("[LogTail] {} is gone ({}), stopping", path.display(), e);
+                break;
+            }
+        }
+    }
+
+    // Forget this tail unless it was already replaced
+    let mut tails = TAILS.lock().unwrap();
+    if tails.get(&batch.session_id).is_some_and(|flag| Arc::ptr_eq(flag, stop_flag)) {
+        tails.remove(&batch.session_id);
+    }
+}
+
+/// The last `count` complete lines of a file, and the offset right after them
+fn last_lines(path: &Path, count: usize) -> io::Result<(Vec<String>, u64)> {
+    let mut file = File::open(path)?;
+    let start = file.metadata()?.len().saturating_sub(BACKLOG_BYTES);
+
+    file.seek(SeekFrom::Start(start))?;
+    let mut buf = Vec::new();
+    file.read_to_end(&mut buf)?;
+
+    // A trailing partial line is left for the tail to pick up once it's complete
+    let complete = buf.iter().rposition(|&b| b == b'\n').map(|pos| pos + 1).unwrap_or(0);
+    let text = String::from_utf8_lossy(&buf[..complete]);
+    let mut lines = text.lines();
+    if start > 0 {
+        lines.next();  // Starts mid-line
+    }
+    let lines: Vec<String> = lines.filter(|l| !l.trim().is_empty()).map(str::to_string).collect();
+    let skip = lines.len().saturating_sub(count);
+
+    Ok((lines.into_iter().skip(skip).collect(), start + complete as u64))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+    use tempfile::NamedTempFile;
+
+    #[test]
+    fn test_last_lines_then_follow() {
+        let mut file = NamedTempFile::new().unwrap();
+        for i in 0..150 {
+            writeln!(file, "line {}", i).unwrap();
+        }
+        write!(file, "partial").unwrap();
+        file.flush().unwrap();
+
+        let (lines, mut offset) = last_lines(file.path(), BACKLOG_LINES).unwrap();
+        assert_eq!(lines.len(), BACKLOG_LINES);
+        assert_eq!(lines.first().map(String::as_str), Some("line 50"));
+        assert_eq!(lines.last().map(String::as_str), Some("line 149"));
+
+        writeln!(file, " line done").unwrap();
+        writeln!(file, "line 151").unwrap();
+        file.flush().unwrap();
+        let new_lines = read_new_lines(file.path(), &mut offset).unwrap();
+        assert_eq!(new_lines, vec!["partial line done", "line 151"]);
+    }
+}
@@ -4,41 +4,28 @@
 #![allow(unused_variables)]
 #![allow(unused_mut)]
 
-// Refactored modules
-mod types;
-mod network;
-mod session;
-mod status;
-mod monitor;
-mod hooks;
-mod coordinator;
-mod notification;
-mod event;
-
+use claude_miner::{
+    achievements, app_info, autostart, claude_cli, config, coordinator, crash, debug_log, deeplink, demo, doctor, event,
+    hooks, kill, logs, monitor, network, notification, overlay, permissions, query, session, status, terminal, trace, types,
+    updater, widget, window_state,
+};
 use types::Miner;
-use session::SessionState;
+use session::{SharedSessions, CleanupEvent, EventSender, MonitorEvent, SessionMerge};
 use sysinfo::{System, Pid};
 use tauri::{Manager, SystemTray, SystemTrayEvent, SystemTrayMenu, CustomMenuItem, Menu, MenuItem, Submenu};
 use std::sync::{Arc, Mutex};
+use std::sync::mpsc::Sender;
 use std::collections::HashMap;
 
-// Type alias for shared sessions
-type SharedSessions = Arc<Mutex<HashMap<String, SessionState>>>;
-
 #[tauri::command]
 fn get_miners(
+    include_archived: Option<bool>,
     shared_sessions: tauri::State<SharedSessions>,
 ) -> Vec<Miner> {
     println!("[get_miners] ===== CALLED =====");
 
     // Get sessions from Coordinator's real-time monitoring
-    let sessions = shared_sessions.lock().unwrap();
-
-    let mut miners = Vec::new();
-
-    // Get fresh process info for memory
-    let mut sys = System::new_all();
-    sys.refresh_all();
+    let sessions = shared_sessions.snapshot();
 
     println!("[get_miners] Retrieved {} sessions from Coordinator", sessions.len());
 
@@ -46,65 +33,9 @@ fn get_miners(
         println!("[get_miners] WARNING: No sessions found! Coordinator may not be detecting sessions.");
     }
 
-    // Convert SessionState to Miner for each session
-    for (session_id, session_state) in sessions.iter() {
-        // Skip only truly invalid sessions ($SESSION_ID or sessions with PID=0 that never got a real PID)
-        if session_id == "$SESSION_ID" {
-            println!("[get_miners] Skipping invalid session: {} (pid={})", session_id, session_state.pid);
-            continue;
-        }
-
-        // Skip sessions with PID=0 only if they're not working (PID=0 means we haven't discovered the PID yet)
-        if session_state.pid == 0 && session_state.current_status != "working" {
-            println!("[get_miners] Skipping session without PID: {} (status={})", session_id, session_state.current_status);
-            continue;
-        }
-
-        println!("[get_miners] Processing session: {}", session_id);
-        println!("[get_miners]   - PID: {}", session_state.pid);
-        println!("[get_miners]   - Status: {}", session_state.current_status);
-        println!("[get_miners]   - Has terminal: {} (zombie={})",
-            session_state.has_terminal,
-            session_state.current_status == "zombie");
-
-        let pid = Pid::from_u32(session_state.pid);
-
-        // Get memory from sysinfo
-        let memory = sys.process(pid)
-            .map(|p| {
-                let mem = p.memory();
-                println!("[get_miners]   - Memory: {} bytes", mem);
-                mem
-            })
-            .unwrap_or_else(|| {
-                println!("[get_miners]   - Memory: 0 (process not found in sysinfo)");
-                0
-            });
-
-        // Get CPU from last CPU event
-        let cpu = session_state.last_cpu_event.as_ref()
-            .map(|e| {
-                println!("[get_miners]   - CPU (from event): {:.1}%", e.cpu_percent);
-                e.cpu_percent
-            })
-            .unwrap_or_else(|| {
-                println!("[get_miners]   - CPU: 0.0% (no CPU event)");
-                0.0
-            });
-
-
-        println!("[get_miners]   Session {}: pid={}, status={}, cpu={:.1}%, mem={}KB, has_terminal={}",
-            &session_id[..8], session_state.pid, session_state.current_status, cpu, memory/1024, session_state.has_terminal);
-
-        miners.push(Miner {
-            pid: session_state.pid,
-            cpu_usage: cpu,
-            memory,
-            status: session_state.current_status.to_string(),
-            has_terminal: session_state.has_terminal,
-            name: "Claude Code".to_string(),
-        });
-    }
+    // Convert SessionState to Miner (refreshes only the tracked PIDs)
+    let mut sys = System::new();
+    let miners = event::build_miners(&sessions, &mut sys, include_archived.unwrap_or(false));
 
     println!("[get_miners] Returning {} miners", miners.len());
     println!("[get_miners] Miners by status:");
@@ -119,10 +50,517 @@ fn get_miners(
     miners
 }
 
+/// Full state of the listed sessions; session-diff events apply on top of it
+#[tauri::command]
+fn get_session_states(shared_sessions: tauri::State<SharedSessions>) -> Vec<session::SessionState> {
+    event::emitter::resync_sessions(&shared_sessions.snapshot())
+}
+
+/// Control the "miners-updated" push cadence (0 = only on change)
+/// Returns the effective interval in milliseconds
+#[tauri::command]
+fn subscribe_miners(interval_ms: u64) -> u64 {
+    event::set_miners_interval(interval_ms)
+}
+
+/// Remove sessions from the list: all of them, or only those with the given status
+/// (zombie sessions are only removed once their process is dead)
+#[tauri::command]
+fn clear_sessions(
+    status: Option<String>,
+    cleanup_sender: tauri::State<Sender<CleanupEvent>>,
+) -> Result<(), String> {
+    if let Some(ref s) = status {
+        if !["working", "resting", "detached", "zombie"].contains(&s.as_str()) {
+            return Err(format!("Unknown session status: {}", s));
+        }
+    }
+
+    cleanup_sender.send(CleanupEvent::ClearSessions(status))
+        .map_err(|_| "Session cleaner is not running".to_string())
+}
+
+/// Remove a single session from the list
+#[tauri::command]
+fn remove_session(
+    session_id: String,
+    cleanup_sender: tauri::State<Sender<CleanupEvent>>,
+) -> Result<(), String> {
+    cleanup_sender.send(CleanupEvent::ForceCleanup(session_id))
+        .map_err(|_| "Session cleaner is not running".to_string())
+}
+
+/// Merge a duplicate session into another (e.g. a temporary PID-only entry into the real session)
+#[tauri::command]
+fn merge_sessions(
+    source_id: String,
+    target_id: String,
+    shared_sessions: tauri::State<SharedSessions>,
+    event_sender: tauri::State<EventSender>,
+) -> Result<(), String> {
+    let source = shared_sessions.get(&source_id).ok_or_else(|| format!("Unknown session: {}", source_id))?;
+    let target = shared_sessions.get(&target_id).ok_or_else(|| format!("Unknown session: {}", target_id))?;
+    session::manager::validate_merge(&source, &target)?;
+
+    event_sender.send(MonitorEvent::MergeSessions(SessionMerge { source_id, target_id }))
+        .map_err(|_| "Coordinator is not running".to_string())
+}
+
+/// Pin or unpin a session (pinned sessions are listed first and always notify)
+#[tauri::command]
+fn pin_session(
+    session_id: String,
+    pinned: bool,
+    event_sender: tauri::State<EventSender>,
+) -> Result<(), String> {
+    session::flags::set_pinned(&session_id, pinned);
+    println!("[Command] Session {} pinned={}", session_id, pinned);
+    flags_changed(session_id, &event_sender)
+}
+
+/// Mute a session's notifications, always notify for it, or reset it (mode = null)
+#[tauri::command]
+fn set_session_notifications(
+    session_id: String,
+    mode: Option<config::NotificationOverride>,
+    event_sender: tauri::State<EventSender>,
+) -> Result<(), String> {
+    session::flags::set_notification_override(&session_id, mode);
+    println!("[Command] Session {} notifications={:?}", session_id, mode);
+    flags_changed(session_id, &event_sender)
+}
+
+/// Have the coordinator publish a session whose flags a command changed
+fn flags_changed(session_id: String, event_sender: &EventSender) -> Result<(), String> {
+    event_sender.send(MonitorEvent::FlagsChanged(session_id))
+        .map_err(|_| "Coordinator is not running".to_string())
+}
+
+/// Mute or always notify for a project (path or glob), or remove its override (mode = null)
+#[tauri::command]
+fn set_project_notifications(project: String, mode: Option<config::NotificationOverride>) -> Result<(), String> {
+    config::update(|c| {
+        let overrides = &mut c.notifications.project_overrides;
+        overrides.retain(|o| o.project != project);
+        if let Some(mode) = mode {
+            overrides.push(config::ProjectNotificationOverride { project: project.clone(), mode });
+        }
+    })
+    .map_err(|e| format!("Failed to save config: {}", e))?;
+    println!("[Command] Project {} notifications={:?}", project, mode);
+    Ok(())
+}
+
+/// Recent CPU, disk and network samples for a session's process (oldest first)
+/// plus an estimated API requests/minute
+#[tauri::command]
+fn get_session_metrics(
+    session_id: String,
+    shared_sessions: tauri::State<SharedSessions>,
+) -> Result<monitor::metrics::SessionMetrics, String> {
+    let pid = shared_sessions.get(&session_id)
+        .map(|s| s.pid)
+        .ok_or_else(|| format!("Unknown session: {}", session_id))?;
+    Ok(monitor::metrics::session_metrics(pid))
+}
+
+/// Ignore or un-ignore a session (ignored sessions are tracked but hidden and silent)
+#[tauri::command]
+fn ignore_session(
+    session_id: String,
+    ignored: bool,
+    event_sender: tauri::State<EventSender>,
+) -> Result<(), String> {
+    session::flags::set_ignored(&session_id, ignored);
+    println!("[Command] Session {} ignored={}", session_id, ignored);
+    flags_changed(session_id, &event_sender)
+}
+
+/// Stream a session's debug log (default) or transcript as "session-log-lines" events
+/// Returns the path of the tailed file
+#[tauri::command]
+fn tail_session_log(session_id: String, source: Option<logs::LogSource>) -> Result<String, String> {
+    logs::tail::start(&session_id, source.unwrap_or_default())
+        .map(|path| path.display().to_string())
+}
+
+/// Stop streaming a session's log
+#[tauri::command]
+fn stop_tail_session_log(session_id: String) -> bool {
+    logs::tail::stop(&session_id)
+}
+
+/// Find lines containing `query` in a session's debug log and transcript (newest first)
+#[tauri::command]
+fn search_session_logs(
+    session_id: String,
+    query: String,
+    max_results: Option<usize>,
+) -> Result<Vec<logs::search::LogMatch>, String> {
+    logs::search::search(&session_id, &query, max_results.unwrap_or(logs::search::DEFAULT_MAX_RESULTS))
+}
+
+/// Show a session's debug log, transcript or project directory in Finder/Explorer
+#[tauri::command]
+fn reveal_session_artifact(
+    session_id: String,
+    artifact: logs::artifacts::Artifact,
+    shared_sessions: tauri::State<SharedSessions>,
+) -> Result<(), String> {
+    open_session_file(&session_id, artifact, logs::artifacts::OpenMode::Reveal, &shared_sessions)
+}
+
+/// Open a session's debug log, transcript or project directory with the default application
+#[tauri::command]
+fn open_session_artifact(
+    session_id: String,
+    artifact: logs::artifacts::Artifact,
+    shared_sessions: tauri::State<SharedSessions>,
+) -> Result<(), String> {
+    open_session_file(&session_id, artifact, logs::artifacts::OpenMode::Open, &shared_sessions)
+}
+
+fn open_session_file(
+    session_id: &str,
+    artifact: logs::artifacts::Artifact,
+    mode: logs::artifacts::OpenMode,
+    shared_sessions: &SharedSessions,
+) -> Result<(), String> {
+    let project_path = shared_sessions.get(session_id).and_then(|s| s.project_path);
+    let path = logs::artifacts::resolve(session_id, artifact, project_path.as_deref())?;
+    logs::artifacts::open(&path, mode)
+}
+
+/// Tasks mined (total, today, this week), streaks and the last 30 days
+#[tauri::command]
+fn get_achievements() -> achievements::Achievements {
+    achievements::get()
+}
+
+/// Sessions archived after resting too long (most recent first)
+#[tauri::command]
+fn get_archived_sessions() -> Vec<session::archive::ArchivedSession> {
+    session::archive::list()
+}
+
+/// Bring an archived session back into the miner list
+#[tauri::command]
+fn restore_session(
+    session_id: String,
+    event_sender: tauri::State<EventSender>,
+) -> Result<(), String> {
+    session::archive::restore(&session_id, session::current_timestamp())
+        .ok_or_else(|| format!("Session {} is not archived", session_id))?;
+    flags_changed(session_id, &event_sender)
+}
+
+/// Get the ignore rules (cwd globs, command regexes)
+#[tauri::command]
+fn get_ignore_rules() -> config::IgnoreConfig {
+    config::get().ignore
+}
+
+/// Replace the ignore rules; sessions matching them are never shown as miners
+#[tauri::command]
+fn set_ignore_rules(cwd_globs: Vec<String>, command_patterns: Vec<String>) -> Result<(), String> {
+    let rules = config::IgnoreConfig { cwd_globs, command_patterns };
+    session::ignore::IgnoreRules::compile(&rules)?;
+
+    config::update(|c| c.ignore = rules)
+        .map_err(|e| format!("Failed to save config: {}", e))?;
+    println!("[Command] Ignore rules updated");
+    Ok(())
+}
+
+/// Get the per-event notification toggles
+#[tauri::command]
+fn get_notification_settings() -> config::NotificationConfig {
+    config::get().notifications
+}
+
+/// Replace the per-event notification toggles
+#[tauri::command]
+fn set_notification_settings(settings: config::NotificationConfig) -> Result<(), String> {
+    config::update(|c| c.notifications = settings)
+        .map_err(|e| format!("Failed to save config: {}", e))?;
+    println!("[Command] Notification settings updated");
+    Ok(())
+}
+
+/// Get the per-event notification sounds
+#[tauri::command]
+fn get_notification_sounds() -> config::NotificationSoundsConfig {
+    config::get().sounds
+}
+
+/// Replace the per-event notification sounds
+#[tauri::command]
+fn set_notification_sounds(sounds: config::NotificationSoundsConfig) -> Result<(), String> {
+    config::update(|c| c.sounds = sounds)
+        .map_err(|e| format!("Failed to save config: {}", e))?;
+    println!("[Command] Notification sounds updated");
+    Ok(())
+}
+
+/// Set what the macOS menu bar title shows: "count", "time" or "off"
+#[tauri::command]
+fn set_tray_title_mode(mode: config::TrayTitleMode) -> Result<(), String> {
+    config::update(|c| c.tray.title = mode)
+        .map_err(|e| format!("Failed to save config: {}", e))?;
+    event::tray::set_title_now();
+    Ok(())
+}
+
+/// Snooze all notifications for `minutes` (0 = resume); returns the snooze end
+#[tauri::command]
+fn snooze_notifications(minutes: u64) -> Option<u64> {
+    println!("[Command] Notifications snoozed for {} min", minutes);
+    notification::quiet::snooze(minutes)
+}
+
+/// Start focus mode: completion notifications are queued until it ends (`minutes`: 0 = until stopped)
+#[tauri::command]
+fn start_focus(minutes: Option<u64>) -> notification::focus::FocusStatus {
+    notification::focus::start(minutes.unwrap_or(notification::focus::DEFAULT_MINUTES))
+}
+
+/// End focus mode now and deliver the digest of what finished
+#[tauri::command]
+fn stop_focus() -> notification::focus::FocusStatus {
+    notification::focus::stop()
+}
+
+#[tauri::command]
+fn get_focus_status() -> notification::focus::FocusStatus {
+    notification::focus::status()
+}
+
+/// Do Not Disturb state (schedule + snooze)
+#[tauri::command]
+fn get_quiet_status() -> notification::quiet::QuietStatus {
+    notification::quiet::status()
+}
+
+/// Notifications held back by quiet hours / snooze (oldest first)
+#[tauri::command]
+fn get_suppressed_notifications() -> Vec<notification::quiet::SuppressedNotification> {
+    notification::quiet::suppressed()
+}
+
+#[tauri::command]
+fn clear_suppressed_notifications() {
+    notification::quiet::clear_suppressed();
+}
+
+/// Every notification shown or suppressed (newest first), optionally since a unix timestamp
+#[tauri::command]
+fn get_notification_history(since: Option<u64>) -> Vec<notification::history::NotificationRecord> {
+    notification::history::get(since)
+}
+
+#[tauri::command]
+fn clear_notification_history() {
+    notification::history::clear();
+}
+
+/// Get the Claude profiles being monitored ("default" = $CLAUDE_CONFIG_DIR or ~/.claude, then configured ones)
+#[tauri::command]
+fn get_profiles() -> Vec<config::ClaudeProfile> {
+    config::claude_profiles()
+}
+
+/// Set the extra Claude profiles and hook them right away
+/// Log/transcript watchers pick up new profiles on next launch
+#[tauri::command]
+fn set_profiles(profiles: Vec<config::ProfileConfig>) -> Result<(), String> {
+    if let Some(p) = profiles.iter().find(|p| p.name.trim().is_empty() || p.path.trim().is_empty()) {
+        return Err(format!("Profile needs a name and a path: {:?}", p));
+    }
+
+    config::update(|c| c.profiles = profiles)
+        .map_err(|e| format!("Failed to save config: {}", e))?;
+
+    hooks::ensure_hooks_registered()
+        .map_err(|e| format!("Failed to register hooks: {}", e))
+}
+
+/// Open the configured terminal running `claude` in project_path
+/// Returns the new session's ID (linked once its SessionStart hook arrives)
+#[tauri::command]
+fn start_miner(project_path: String, model: Option<String>) -> Result<String, String> {
+    terminal::start_miner(&project_path, model.as_deref())
+}
+
+/// Bring the terminal window/tab hosting a session to the front
+#[tauri::command]
+fn focus_session_terminal(
+    session_id: String,
+    shared_sessions: tauri::State<SharedSessions>,
+) -> Result<(), String> {
+    focus_terminal_of(&shared_sessions, &session_id)
+}
+
+/// Tray menu item of a session waiting on the user (see event::TrayAction)
+fn run_tray_action(app: &tauri::AppHandle, action: event::TrayAction) {
+    let shared_sessions = app.state::<SharedSessions>();
+    let result = match action {
+        event::TrayAction::Focus { session_id, .. } => focus_terminal_of(&shared_sessions, &session_id),
+        event::TrayAction::Kill { pid } => kill_session_process(&shared_sessions, pid, false, false)
+            .map(|_| ())
+            .map_err(|e| e.to_string()),
+    };
+    if let Err(e) = result {
+        eprintln!("[Tray] {}", e);
+    }
+}
+
+fn focus_terminal_of(shared_sessions: &SharedSessions, session_id: &str) -> Result<(), String> {
+    let session = shared_sessions.get(session_id)
+        .ok_or_else(|| format!("Unknown session: {}", session_id))?;
+    let (pid, tty) = (session.pid, session.tty);
+
+    let tty = tty.ok_or_else(|| format!("Session {} has no terminal", session_id))?;
+    terminal::focus_terminal(pid, &tty)
+}
+
+/// Monitor -> coordinator queue depth and drop counters
+#[tauri::command]
+fn get_monitor_health(queue: tauri::State<session::QueueMonitor>) -> session::queue::QueueHealth {
+    queue.health()
+}
+
+/// Start or stop recording monitor events and status transitions as a Chrome trace;
+/// stopping writes trace-<timestamp>.json next to config.json and returns its path
+#[tauri::command]
+fn set_trace_recording(enabled: bool) -> Result<trace::TraceStatus, String> {
+    trace::set_recording(enabled)
+}
+
+/// Turn verbose diagnostics (debug.log next to config.json, rotated by size) on or off
+#[tauri::command]
+fn set_debug_logging(enabled: bool) -> Result<debug_log::DebugLogStatus, String> {
+    Ok(debug_log::set_enabled(enabled))
+}
+
+/// Recent status transitions of a session and what triggered them, oldest first
+#[tauri::command]
+fn get_status_history(session_id: String) -> Vec<status::history::StatusTransition> {
+    status::history::get(&session_id)
+}
+
+/// Working minutes by day of week and hour of day (local time) within `range`
+#[tauri::command]
+fn get_activity_heatmap(range: Option<status::journal::TimeRange>) -> status::heatmap::ActivityHeatmap {
+    status::heatmap::get(range.unwrap_or_default())
+}
+
+/// iCalendar (.ics) document of the work within `range`, one event per stretch of work on a project
+#[tauri::command]
+fn export_calendar(range: Option<status::journal::TimeRange>) -> String {
+    status::calendar::export(range.unwrap_or_default(), session::current_timestamp())
+}
+
+/// Working time, completed tasks and zombie incidents per project
+/// (`since`: only activity that ended at or after this Unix timestamp)
+#[tauri::command]
+fn get_project_stats(since: Option<u64>) -> Vec<status::journal::ProjectStats> {
+    status::journal::project_stats(&status::journal::entries(since.unwrap_or(0)))
+}
+
+/// Zombie incidents (cause, time as a zombie, resolution), newest first
+/// (`since`: detected at or after this Unix timestamp; `project`: one project path only)
+#[tauri::command]
+fn get_zombie_history(since: Option<u64>, project: Option<String>) -> Vec<status::zombies::ZombieIncident> {
+    status::zombies::history(since, project.as_deref(), session::current_timestamp())
+}
+
+/// Counts, longest-running task and last completion for the mini widget
+#[tauri::command]
+fn get_widget_summary(shared_sessions: tauri::State<SharedSessions>) -> widget::WidgetSummary {
+    widget::summary(&shared_sessions.snapshot(), status::journal::last_completion(), session::current_timestamp())
+}
+
+/// Show the mini widget window (always on top)
+#[tauri::command]
+fn open_widget_window(app: tauri::AppHandle) -> Result<(), String> {
+    widget::open_window(&app)
+}
+
+/// File with the one-line miner summary for external status bars
+#[tauri::command]
+fn get_status_line_path() -> String {
+    event::status_line::path().display().to_string()
+}
+
+/// Trigger an immediate CPU scan, log-directory sweep and dead-session check
+#[tauri::command]
+fn force_rescan(rescan_trigger: tauri::State<monitor::RescanTrigger>) {
+    rescan_trigger.trigger_full_rescan("requested by UI");
+}
+
+/// Kill a miner's process; sessions in the middle of a task need `force`
+/// (otherwise a `session_busy` error the UI can confirm and retry)
+/// `tree` also kills its descendants and reports which PIDs were reaped
 #[tauri::command]
-fn kill_miner(pid: u32) -> Result<String, String> {
+fn kill_miner(
+    pid: u32,
+    force: Option<bool>,
+    tree: Option<bool>,
+    shared_sessions: tauri::State<SharedSessions>,
+) -> Result<kill::KillReport, kill::KillError> {
+    kill_session_process(&shared_sessions, pid, force.unwrap_or(false), tree.unwrap_or(false))
+}
+
+fn kill_session_process(
+    shared_sessions: &SharedSessions,
+    pid: u32,
+    force: bool,
+    tree: bool,
+) -> Result<kill::KillReport, kill::KillError> {
+    kill::check_kill_allowed(&shared_sessions.snapshot(), pid, force, session::current_timestamp())?;
+
+    if demo::is_synthetic(pid) {
+        demo::kill(pid);
+        return Ok(kill::KillReport { pid, reaped: vec![pid], ..Default::default() });
+    }
+
+    if !tree {
+        kill_process(pid)?;
+        return Ok(kill::KillReport { pid, reaped: vec![pid], ..Default::default() });
+    }
+
+    if monitor::user::is_foreign_uid(monitor::user::process_uid(pid)) {
+        return Err(format!("Process {} belongs to another user", pid).into());
+    }
+    let report = kill::kill_tree(pid)?;
+    if report.reaped.contains(&pid) {
+        report_killed(pid);
+    }
+    Ok(report)
+}
+
+/// A session process was killed from the app: tell the coordinator through the hook pipe
+/// (it drops the session, notifies and closes the zombie incident), or do the bookkeeping
+/// here when the pipe can't be written
+fn report_killed(pid: u32) {
+    if hooks::sender::send_process_killed_event(pid).is_err() {
+        notification::send_zombie_killed_notification(pid);
+        status::zombies::record_killed(pid, status::zombies::ZombieResolution::Killed, session::current_timestamp());
+    }
+}
+
+fn kill_process(pid: u32) -> Result<String, String> {
     let _sys_pid = Pid::from_u32(pid);
 
+    if demo::is_synthetic(pid) && demo::kill(pid) {
+        return Ok(format!("Killed synthetic process {}", pid));
+    }
+
+    // Sessions of other users are shown read-only
+    if monitor::user::is_foreign_uid(monitor::user::process_uid(pid)) {
+        return Err(format!("Process {} belongs to another user", pid));
+    }
+
     #[cfg(target_os = "macos")]
     {
         use std::process::Command;
@@ -137,10 +575,7 @@ fn kill_miner(pid: u32) -> Result<String, String> {
             Ok(result) => {
                 if result.status.success() {
                     println!("[kill_miner] Successfully killed PID {}", pid);
-
-                    // Send notification directly
-                    notification::send_zombie_killed_notification(pid);
-
+                    report_killed(pid);
                     Ok(format!("Process {} killed successfully", pid))
                 } else {
                     let stderr = String::from_utf8_lossy(&result.stderr);
@@ -159,20 +594,329 @@ fn kill_miner(pid: u32) -> Result<String, String> {
             .output();
 
         match output {
-            Ok(_) => {
+            Ok(result) if result.status.success() => {
                 println!("[kill_miner] Successfully killed PID {}", pid);
+                report_killed(pid);
                 Ok(format!("Process {} killed successfully", pid))
             }
+            Ok(result) => Err(format!("Failed to kill process {}: {}", pid, String::from_utf8_lossy(&result.stderr).trim())),
             Err(e) => Err(format!("Failed to kill process {}: {}", pid, e)),
         }
     }
 
-    #[cfg(not(any(target_os = "macos", target_os = "windows")))]
+    #[cfg(all(unix, not(target_os = "macos")))]
+    {
+        kill::signal(pid)?;
+        println!("[kill_miner] Successfully killed PID {}", pid);
+        report_killed(pid);
+        Ok(format!("Process {} killed successfully", pid))
+    }
+
+    #[cfg(not(any(unix, target_os = "windows")))]
     {
         Err("Unsupported platform".to_string())
     }
 }
 
+/// Session actions for the launcher query socket
+const QUERY_ACTIONS: query::QueryActions = query::QueryActions {
+    kill: |sessions, pid, force| kill_session_process(sessions, pid, force, false).map(|_| ()).map_err(|e| e.to_string()),
+    focus: focus_terminal_of,
+};
+
+/// Kill every zombie session this user owns; returns the killed PIDs
+#[tauri::command]
+fn kill_all_zombies(shared_sessions: tauri::State<SharedSessions>) -> Vec<u32> {
+    kill_zombies(&shared_sessions)
+}
+
+fn kill_zombies(shared_sessions: &SharedSessions) -> Vec<u32> {
+    let pids: Vec<u32> = shared_sessions.snapshot().values()
+        .filter(|s| s.current_status == status::machine::ZOMBIE && s.pid != 0 && s.container.is_none() && !s.ignored)
+        .map(|s| s.pid)
+        .collect();
+
+    pids.into_iter()
+        .filter(|pid| match kill_process(*pid) {
+            Ok(_) => true,
+            Err(e) => {
+                println!("[kill_all_zombies] ⚠️ {}", e);
+                false
+            }
+        })
+        .collect()
+}
+
+/// Show the main window, or hide it when it's visible and focused
+fn toggle_main_window(app: &tauri::AppHandle) {
+    let Some(window) = app.get_window("main") else { return };
+    let visible = window.is_visible().unwrap_or(false);
+    let focused = window.is_focused().unwrap_or(false);
+
+    if visible && focused {
+        let _ = window.hide();
+    } else {
+        let _ = window.show();
+        let _ = window.set_focus();
+    }
+}
+
+/// Register the global shortcuts from config.json "hotkeys"
+/// Register each configured shortcut on its own; returns one message per shortcut that
+/// couldn't be registered (taken by another app, say), the others stay active
+fn register_global_shortcuts(app: &tauri::AppHandle, shared_sessions: SharedSessions) -> Vec<String> {
+    use tauri::GlobalShortcutManager;
+
+    let hotkeys = config::get().hotkeys;
+    let mut manager = app.global_shortcut_manager();
+    let mut failures = Vec::new();
+    if let Err(e) = manager.unregister_all() {
+        failures.push(format!("Failed to unregister the previous shortcuts: {}", e));
+    }
+
+    let mut register = |action: &str, accelerator: Option<String>, callback: Box<dyn Fn() + Send>| {
+        let Some(accelerator) = accelerator else { return };
+        match manager.register(&accelerator, callback) {
+            Ok(()) => println!("[Hotkeys] {} -> {}", accelerator, action),
+            Err(e) => failures.push(format!("Failed to register {} ({}): {}", accelerator, action, e)),
+        }
+    };
+
+    let handle = app.clone();
+    register("toggle window", hotkeys.toggle_window, Box::new(move || toggle_main_window(&handle)));
+    register("kill zombies", hotkeys.kill_zombies, Box::new(move || {
+        let killed = kill_zombies(&shared_sessions);
+        println!("[Hotkeys] Killed {} zombie(s)", killed.len());
+    }));
+    failures
+}
+
+/// Get the global shortcuts
+#[tauri::command]
+fn get_hotkeys() -> config::HotkeyConfig {
+    config::get().hotkeys
+}
+
+/// Replace the global shortcuts and re-register them
+/// Invalid shortcuts are rejected before anything is saved; errors come one per shortcut
+#[tauri::command]
+fn set_hotkeys(
+    hotkeys: config::HotkeyConfig,
+    app: tauri::AppHandle,
+    shared_sessions: tauri::State<SharedSessions>,
+) -> Result<(), Vec<String>> {
+    hotkeys.validate()?;
+    config::update(|c| c.hotkeys = hotkeys)
+        .map_err(|e| vec![format!("Failed to save config: {}", e)])?;
+    let failures = register_global_shortcuts(&app, shared_sessions.inner().clone());
+    if failures.is_empty() { Ok(()) } else { Err(failures) }
+}
+
+/// Duplicate ClaudeMiner hooks and other tools' hooks on the events ClaudeMiner uses
+#[tauri::command]
+fn get_hook_conflicts() -> Vec<hooks::watcher::HookConflict> {
+    hooks::watcher::find_all_conflicts()
+}
+
+/// Timestamped backups of every Claude home's settings.json (newest first)
+#[tauri::command]
+fn list_settings_backups() -> Vec<hooks::backup::SettingsBackup> {
+    hooks::manager::get_settings_paths()
+        .iter()
+        .flat_map(|(_, path)| hooks::backup::describe(path))
+        .collect()
+}
+
+/// Restore a settings.json backup (the current file is backed up first)
+#[tauri::command]
+fn restore_settings_backup(backup_path: String) -> Result<(), String> {
+    let backup_path = std::path::PathBuf::from(backup_path);
+    let settings_path = hooks::manager::get_settings_paths()
+        .into_iter()
+        .map(|(_, path)| path)
+        .find(|path| backup_path.parent() == path.parent())
+        .ok_or_else(|| format!("Not a settings.json backup: {}", backup_path.display()))?;
+
+    hooks::backup::restore(&settings_path, &backup_path)
+}
+
+/// What hook registration would change in each settings.json, without writing
+#[tauri::command]
+fn preview_hook_changes() -> Result<Vec<hooks::manager::HookChangePreview>, String> {
+    hooks::manager::preview_hook_changes()
+        .map_err(|e| format!("Failed to preview hook changes: {}", e))
+}
+
+/// Projects that get the hooks in their own .claude/ settings
+#[tauri::command]
+fn get_project_hooks() -> Vec<config::ProjectHooksConfig> {
+    config::get().hooks.projects
+}
+
+/// Replace the hooked projects (removed ones are unhooked)
+#[tauri::command]
+fn set_project_hooks(projects: Vec<config::ProjectHooksConfig>) -> Result<(), String> {
+    hooks::project::set_projects(projects)
+}
+
+/// Hooks defined in the project settings of monitored sessions
+#[tauri::command]
+fn get_project_hook_report(shared_sessions: tauri::State<SharedSessions>) -> Vec<hooks::project::ProjectHookReport> {
+    let sessions = shared_sessions.snapshot();
+    hooks::project::report(sessions.values().filter_map(|s| s.project_path.as_deref()))
+}
+
+/// Environment diagnostics (logs, hooks, pipe, permissions, tools)
+#[tauri::command]
+fn run_doctor() -> doctor::DoctorReport {
+    doctor::run()
+}
+
+/// Notification permission and access to the Claude homes
+#[tauri::command]
+fn get_permissions() -> permissions::Permissions {
+    permissions::get()
+}
+
+/// Re-request a permission or open its System Settings pane
+#[tauri::command]
+fn run_permission_action(action: permissions::PermissionAction) -> Result<(), String> {
+    permissions::run_action(action)
+}
+
+/// Compare the latest GitHub release with the running version
+/// (async so the network request runs off the main thread)
+#[tauri::command]
+async fn check_for_updates() -> Result<updater::UpdateInfo, String> {
+    updater::check_for_updates()
+}
+
+/// Download and verify the latest release in the background
+/// Progress: "update-progress" {downloaded, total}; done: "update-ready" / "update-failed"
+#[tauri::command]
+fn install_update() -> Result<(), String> {
+    updater::install_update()
+}
+
+/// The downloaded update waiting for a restart, if any
+#[tauri::command]
+fn get_staged_update() -> Option<updater::StagedUpdate> {
+    updater::staged()
+}
+
+/// Install the downloaded update and relaunch
+#[tauri::command]
+fn restart_to_update() -> Result<(), String> {
+    updater::restart_to_update()
+}
+
+/// Version, git commit, build date, platform and cargo features
+#[tauri::command]
+fn get_app_info() -> app_info::AppInfo {
+    app_info::get()
+}
+
+/// Installed Claude Code CLI version and whether ClaudeMiner was tested with it
+#[tauri::command]
+fn get_claude_cli() -> claude_cli::ClaudeCli {
+    claude_cli::get()
+}
+
+/// The most recent crash report (a thread panicked), if any
+#[tauri::command]
+fn get_last_crash() -> Option<crash::CrashReport> {
+    crash::last_crash()
+}
+
+/// Run a doctor fix-it action (register_hooks, recreate_pipe, open_full_disk_access, ...)
+#[tauri::command]
+fn run_doctor_fix(action_id: String) -> Result<(), String> {
+    doctor::fix(&action_id)
+}
+
+/// Are the Claude Code hooks enabled?
+#[tauri::command]
+fn get_hooks_enabled() -> bool {
+    config::get().hooks.enabled
+}
+
+/// Add or remove the ClaudeMiner hooks in every Claude home's settings.json
+#[tauri::command]
+fn set_hooks_enabled(enabled: bool) -> Result<(), String> {
+    config::update(|c| c.hooks.enabled = enabled)
+        .map_err(|e| format!("Failed to save config: {}", e))?;
+
+    if enabled {
+        hooks::ensure_hooks_registered()
+            .map_err(|e| format!("Failed to register hooks: {}", e))
+    } else {
+        hooks::unregister_all_hooks()
+            .map_err(|e| format!("Failed to unregister hooks: {}", e))
+    }
+}
+
+/// Is ClaudeMiner registered to start at login?
+#[tauri::command]
+fn get_autostart() -> Result<bool, String> {
+    autostart::is_enabled()
+}
+
+/// Start ClaudeMiner at login (LaunchAgent / Run key / XDG autostart)
+#[tauri::command]
+fn set_autostart(enabled: bool) -> Result<(), String> {
+    autostart::set_enabled(enabled)
+}
+
+/// Window behavior, last size/position and tab
+#[tauri::command]
+fn get_window_state() -> config::WindowConfig {
+    config::get().window
+}
+
+/// Close-to-tray preference
+#[tauri::command]
+fn set_window_behavior(close_to_tray: bool) -> Result<(), String> {
+    config::update(|c| c.window.close_to_tray = close_to_tray)
+        .map_err(|e| format!("Failed to save config: {}", e))
+}
+
+/// Show or close the always-on-top status overlay; returns whether it is now visible
+#[tauri::command]
+fn toggle_overlay(app: tauri::AppHandle) -> Result<bool, String> {
+    overlay::toggle(&app)
+}
+
+/// Overlay visibility, position and click-through setting
+#[tauri::command]
+fn get_overlay_state() -> config::OverlayConfig {
+    config::get().overlay
+}
+
+/// Let clicks pass through the overlay to the apps underneath
+#[tauri::command]
+fn set_overlay_click_through(app: tauri::AppHandle, enabled: bool) -> Result<(), String> {
+    overlay::set_click_through(&app, enabled)
+}
+
+/// Drag the frameless overlay (called from its mousedown handler)
+#[tauri::command]
+fn start_overlay_drag(window: tauri::Window) -> Result<(), String> {
+    overlay::start_drag(&window)
+}
+
+/// Remember the selected tab so it can be reopened at startup
+#[tauri::command]
+fn set_last_tab(tab: Option<String>) -> Result<(), String> {
+    config::update(|c| c.window.last_tab = tab)
+        .map_err(|e| format!("Failed to save config: {}", e))
+}
+
+/// claudeminer:// link that arrived before the frontend was listening
+#[tauri::command]
+fn take_pending_deep_link() -> Option<deeplink::DeepLink> {
+    deeplink::take_pending()
+}
+
 #[tauri::command]
 fn send_notification(_title: String, _body: String) -> Result<(), String> {
     // Notification will be handled by Tauri's notification API on the frontend
@@ -217,6 +961,10 @@ fn uninstall_app() -> Result<String, String> {
         let result = String::from_utf8_lossy(&output.stdout).trim().to_string();
 
         if result == "uninstalled" {
+            // Don't leave hooks pointing at a pipe nobody reads
+            if let Err(e) = hooks::unregister_all_hooks() {
+                eprintln!("[Uninstall] Failed to remove hooks: {}", e);
+            }
             std::process::exit(0);
         } else {
             Ok("Uninstall cancelled".to_string())
@@ -234,17 +982,6 @@ fn uninstall_app() -> Result<String, String> {
     }
 }
 
-#[tauri::command]
-fn update_tray_menu(
-    total: u32,
-    working: u32,
-    resting: u32,
-    zombie: u32
-) -> Result<(), String> {
-    // Delegate to event module (singleton pattern)
-    event::update_tray_menu(total, working, resting, zombie)
-}
-
 #[tauri::command]
 fn send_test_notification() -> Result<String, String> {
     println!("[TestNotification] 🔔 Sending test notification...");
@@ -253,18 +990,44 @@ fn send_test_notification() -> Result<String, String> {
 }
 
 fn main() {
+    // Crash reports for panics in any thread
+    crash::install();
+
+    // --query: answer a launcher request through the running instance and exit
+    if let Some(code) = query::run_cli(&std::env::args().collect::<Vec<_>>()) {
+        std::process::exit(code);
+    }
+
+    // claudeminer:// URL on the command line: hand it to the running instance if there is one
+    if let Some(url) = deeplink::find_in_args(std::env::args()) {
+        match hooks::sender::send_deep_link_event(&url) {
+            Ok(()) => {
+                println!("[Main] Forwarded {} to the running instance", url);
+                return;
+            }
+            Err(_) => deeplink::set_pending(&url),
+        }
+    }
+
+    #[cfg(target_os = "macos")]
+    deeplink::register_url_event_handler();
+
     // Create session cache for monitor system
     let session_cache = Arc::new(Mutex::new(HashMap::new()));
 
     // Create shared sessions for real-time monitoring
-    let shared_sessions = Arc::new(Mutex::new(HashMap::new()));
+    let shared_sessions: SharedSessions = Arc::new(session::SessionStore::default());
     let shared_sessions_for_command = shared_sessions.clone();
 
     // Create system tray menu
     let show = CustomMenuItem::new("show".to_string(), "Show Window");
+    let widget_item = CustomMenuItem::new("widget".to_string(), "Mini Widget");
+    let overlay_item = CustomMenuItem::new("overlay".to_string(), "Status Overlay");
     let quit = CustomMenuItem::new("quit".to_string(), "Quit");
     let tray_menu = SystemTrayMenu::new()
         .add_item(show)
+        .add_item(widget_item)
+        .add_item(overlay_item)
         .add_item(quit);
 
     let tray = SystemTray::new().with_menu(tray_menu);
@@ -314,6 +1077,10 @@ fn main() {
                 _ => {}
             }
         })
+        .on_window_event(|event| {
+            window_state::on_window_event(event.window(), event.event());
+            overlay::on_window_event(event.window(), event.event());
+        })
         .system_tray(tray)
         .on_system_tray_event(|app, event| match event {
             // Removed LeftClick handler to allow default menu behavior on macOS
@@ -323,18 +1090,125 @@ fn main() {
                     window.show().unwrap();
                     window.set_focus().unwrap();
                 }
+                "widget" => {
+                    if let Err(e) = widget::open_window(app) {
+                        eprintln!("[Widget] {}", e);
+                    }
+                }
+                "focus" => {
+                    if notification::focus::status().active {
+                        notification::focus::stop();
+                    } else {
+                        notification::focus::start(notification::focus::DEFAULT_MINUTES);
+                    }
+                }
+                "overlay" => {
+                    if let Err(e) = overlay::toggle(app) {
+                        eprintln!("[Overlay] {}", e);
+                    }
+                }
                 "quit" => {
+                    window_state::save();
+                    overlay::save();
+                    event::status_line::clear();
+                    query::remove_socket();
                     std::process::exit(0);
                 }
-                _ => {}
+                id => {
+                    if let Some(action) = event::TrayAction::from_menu_id(id) {
+                        run_tray_action(app, action);
+                    }
+                }
             },
             _ => {}
         })
         .invoke_handler(tauri::generate_handler![
             get_miners,
+            get_session_states,
+            subscribe_miners,
+            force_rescan,
+            get_monitor_health,
+            get_status_history,
+            set_trace_recording,
+            set_debug_logging,
+            get_project_stats,
+            get_zombie_history,
+            get_widget_summary,
+            open_widget_window,
+            get_status_line_path,
+            get_activity_heatmap,
+            export_calendar,
+            clear_sessions,
+            kill_all_zombies,
+            get_hotkeys,
+            set_hotkeys,
+            take_pending_deep_link,
+            get_autostart,
+            set_autostart,
+            get_hooks_enabled,
+            get_hook_conflicts,
+            run_doctor,
+            run_doctor_fix,
+            get_permissions,
+            run_permission_action,
+            get_app_info,
+            get_claude_cli,
+            get_last_crash,
+            check_for_updates,
+            install_update,
+            get_staged_update,
+            restart_to_update,
+            get_project_hooks,
+            set_project_hooks,
+            get_project_hook_report,
+            preview_hook_changes,
+            list_settings_backups,
+            restore_settings_backup,
+            set_hooks_enabled,
+            get_window_state,
+            set_window_behavior,
+            toggle_overlay,
+            get_overlay_state,
+            set_overlay_click_through,
+            start_overlay_drag,
+            set_last_tab,
+            remove_session,
+            merge_sessions,
+            pin_session,
+            set_session_notifications,
+            set_project_notifications,
+            get_session_metrics,
+            ignore_session,
+            get_archived_sessions,
+            get_achievements,
+            restore_session,
+            tail_session_log,
+            stop_tail_session_log,
+            search_session_logs,
+            reveal_session_artifact,
+            open_session_artifact,
+            get_ignore_rules,
+            set_ignore_rules,
+            get_notification_settings,
+            set_notification_settings,
+            get_notification_sounds,
+            set_notification_sounds,
+            set_tray_title_mode,
+            snooze_notifications,
+            get_quiet_status,
+            start_focus,
+            stop_focus,
+            get_focus_status,
+            get_suppressed_notifications,
+            clear_suppressed_notifications,
+            get_notification_history,
+            clear_notification_history,
+            get_profiles,
+            set_profiles,
+            start_miner,
+            focus_session_terminal,
             kill_miner,
             send_notification,
-            update_tray_menu,
             uninstall_app,
             send_test_notification
         ])
@@ -348,32 +1222,98 @@ fn main() {
             // Initialize event emitter (singleton pattern)
             event::init(app_handle.clone());
 
+            // Full tray menu with (empty) stats; the coordinator updates it from here on
+            event::refresh_tray_menu();
+
+            // Last window size/position
+            if let Some(window) = app.get_window("main") {
+                window_state::restore(&window);
+            }
+
+            // Status overlay, if it was open at the last quit
+            overlay::restore(&app_handle);
+
+            // claudeminer:// links (session focus, kill prompts)
+            deeplink::init(app_handle.clone());
+            deeplink::register_scheme();
+
+            // In-app updates (progress/ready events)
+            updater::init(app_handle.clone());
+
+            // Installed Claude Code version (warns when newer than the tested formats)
+            claude_cli::detect_in_background();
+
             // Ensure hooks are registered in Claude Code settings.json
             if let Err(e) = hooks::ensure_hooks_registered() {
                 eprintln!("[Main] Failed to register hooks: {}", e);
             }
 
-            // Create communication channels
+            // Create communication channels (bounded monitor -> coordinator queue)
             use std::sync::mpsc::channel;
-            let (event_sender, event_receiver) = channel();
+            let (event_sender, event_receiver) = session::event_queue(session::queue::DEFAULT_CAPACITY);
+            app.manage(event_sender.monitor()); // For get_monitor_health command
+            app.manage(event_sender.clone()); // For merge_sessions command
 
             // Create shared PID set for monitors
             use std::collections::HashSet;
             let claude_pids = Arc::new(Mutex::new(HashSet::new()));
 
-            // Start all monitoring threads
-            let _cpu_monitor = monitor::start_cpu_monitor(event_sender.clone(), claude_pids.clone());
-            let _log_watcher = monitor::start_log_watcher(event_sender.clone());
+            // Create rescan channels (used to wake monitors for an immediate full scan)
+            let (cpu_rescan_sender, cpu_rescan_receiver) = channel();
+            let (log_rescan_sender, log_rescan_receiver) = channel();
+
+            // Start all monitoring threads (demo mode: synthetic sessions instead)
+            if demo::enabled() {
+                let _demo_generator = demo::start_generator(event_sender.clone());
+            } else {
+                let _cpu_monitor = monitor::start_cpu_monitor(event_sender.clone(), claude_pids.clone(), cpu_rescan_receiver);
+                let _log_watcher = monitor::start_log_watcher(event_sender.clone(), log_rescan_receiver);
+                let _transcript_watcher = monitor::start_transcript_watcher(event_sender.clone());
+                let _energy_sampler = monitor::start_energy_sampler(claude_pids.clone());
+                let _network_monitor = network::start_network_monitor(event_sender.clone(), claude_pids.clone());
+            }
 
             // Start hook receiver (no app_handle needed - uses notification module)
             let _hook_receiver = hooks::start_hook_receiver(event_sender.clone());
 
+            // Re-register hooks when an edit to settings.json drops them
+            let _settings_watcher = hooks::start_settings_watcher();
+
             // Start session cleaner (returns handle and sender)
             let (_cleaner_handle, cleanup_sender) = session::start_session_cleaner(
                 shared_sessions.clone(),
                 event_sender.clone(),
             );
 
+            app.manage(cleanup_sender.clone()); // For clear_sessions / remove_session commands
+
+            // Start wake detector (full rescan after system sleep)
+            let rescan_trigger = monitor::RescanTrigger::new(
+                cpu_rescan_sender,
+                log_rescan_sender,
+                cleanup_sender.clone(),
+            );
+            app.manage(rescan_trigger.clone()); // For force_rescan command
+            let _wake_detector = monitor::start_wake_detector(rescan_trigger);
+
+            // Start miners publisher (pushes "miners-updated" to the frontend)
+            let _miners_publisher = event::start_miners_publisher(shared_sessions.clone());
+
+            // Launcher query socket (Raycast / Alfred: list, kill, focus)
+            let _query_server = query::start_query_server(shared_sessions.clone(), QUERY_ACTIONS);
+
+            // Global shortcuts (toggle window, kill all zombies)
+            for failure in register_global_shortcuts(&app_handle, shared_sessions.clone()) {
+                eprintln!("[Hotkeys] {}", failure);
+            }
+
+            // Tray icon follows the aggregate session state
+            let _tray_icon_animator = event::tray_icon::start_tray_icon_animator();
+
+            // Keep the menu bar title's elapsed time ticking between events
+            #[cfg(target_os = "macos")]
+            let _tray_title_timer = event::tray::start_tray_title_timer(shared_sessions.clone());
+
             // Start coordinator with cleanup support (no app_handle needed - uses event module)
             let _coordinator = coordinator::start_coordinator_with_cleanup(
                 event_receiver,
@@ -385,6 +1325,14 @@ fn main() {
             println!("[Main] Multi-threaded monitoring system started with Tauri events");
             Ok(())
         })
-        .run(tauri::generate_context!())
-        .expect("error while running tauri application");
+        .build(tauri::generate_context!())
+        .expect("error while building tauri application")
+        .run(|_app, event| {
+            if let tauri::RunEvent::Exit = event {
+                window_state::save();
+                overlay::save();
+                event::status_line::clear();
+                query::remove_socket();
+            }
+        });
 }
@@ -0,0 +1,196 @@
+// Container Detection
+//
+// Claude sessions inside Docker containers or WSL distros live in another PID
+// namespace and have no host TTY, so the host ps scan either misses them or sees
+// zombies. Lists their processes with `docker top` / `wsl.exe -- ps` instead.
+//
+
+use crate::monitor::matcher;
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::process::Command;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+const SCAN_INTERVAL: Duration = Duration::from_secs(10);  // docker/wsl calls are slow
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ContainerRuntime {
+    Docker,
+    Wsl,
+}
+
+impl ContainerRuntime {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ContainerRuntime::Docker => "docker",
+            ContainerRuntime::Wsl => "wsl",
+        }
+    }
+}
+
+/// Container or WSL distro hosting a miner process
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ContainerInfo {
+    pub runtime: ContainerRuntime,
+    pub name: String,  // Container name or distro name
+}
+
+/// A miner process found inside a container (PID is namespace-local)
+#[derive(Debug, Clone)]
+pub struct ContainerProcess {
+    pub pid: u32,
+    pub cpu_percent: f32,
+    pub kind: String,
+    pub container: ContainerInfo,
+}
+
+struct ScanCache {
+    last_scan: Option<Instant>,
+    processes: Vec<ContainerProcess>,
+}
+
+static CACHE: Lazy<Mutex<ScanCache>> = Lazy::new(|| Mutex::new(ScanCache {
+    last_scan: None,
+    processes: Vec::new(),
+}));
+
+/// Miner processes inside running containers / WSL distros
+/// Rescans at most every SCAN_INTERVAL; returns None when the last scan is still fresh
+pub fn find_container_miners() -> Option<Vec<ContainerProcess>> {
+    let mut cache = CACHE.lock().unwrap();
+    if cache.last_scan.is_some_and(|t| t.elapsed() < SCAN_INTERVAL) {
+        return None;
+    }
+
+    let matchers = matcher::current();
+    let mut processes = Vec::new();
+    for (container, ps_output) in scan_docker().into_iter().chain(scan_wsl()) {
+        for (pid, cpu_percent, command) in parse_ps_output(&ps_output) {
+            if let Some(kind) = matchers.match_command(&command) {
+                processes.push(ContainerProcess {
+                    pid,
+                    cpu_percent,
+                    kind: kind.to_string(),
+                    container: container.clone(),
+                });
+            }
+        }
+    }
+
+    if !processes.is_empty() {
+        println!("[Container] Found {} containerized miner processes", processes.len());
+    }
+
+    cache.last_scan = Some(Instant::now());
+    cache.processes = processes.clone();
+    Some(processes)
+}
+
+/// Whether a containerized process was seen in the last scan
+pub fn is_alive(container: &ContainerInfo, pid: u32) -> bool {
+    CACHE.lock().unwrap().processes.iter()
+        .any(|p| p.pid == pid && &p.container == container)
+}
+
+/// `docker top` output for every running container
+fn scan_docker() -> Vec<(ContainerInfo, String)> {
+    let names = match run("docker", &["ps", "--format", "{{.Names}}"]) {
+        Some(names) => names,
+        None => return Vec::new(),  // Docker not installed or daemon not running
+    };
+
+    names.lines()
+        .map(str::trim)
+        .filter(|name| !name.is_empty())
+        .filter_map(|name| {
+            let output = run("docker", &["top", name, "-eo", "pid,pcpu,args"])?;
+            Some((ContainerInfo { runtime: ContainerRuntime::Docker, name: name.to_string() }, output))
+        })
+        .collect()
+}
+
+/// `ps` output for every running WSL distro (Windows only)
+#[cfg(target_os = "windows")]
+fn scan_wsl() -> Vec<(ContainerInfo, String)> {
+    let distros = match run("wsl.exe", &["--list", "--running", "--quiet"]) {
+        Some(distros) => distros,
+        None => return Vec::new(),
+    };
+
+    distros.lines()
+        .map(str::trim)
+        .filter(|name| !name.is_empty())
+        .filter_map(|name| {
+            let output = run("wsl.exe", &["-d", name, "--", "ps", "-eo", "pid,pcpu,args"])?;
+            Some((ContainerInfo { runtime: ContainerRuntime::Wsl, name: name.to_string() }, output))
+        })
+        .collect()
+}
+
+#[cfg(not(target_os = "windows"))]
+fn scan_wsl() -> Vec<(ContainerInfo, String)> {
+    Vec::new()
+}
+
+fn run(program: &str, args: &[&str]) -> Option<String> {
+    let output = Command::new(program).args(args).output().ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    Some(decode_output(&output.stdout))
+}
+
+/// wsl.exe writes UTF-16LE; everything else is UTF-8
+fn decode_output(bytes: &[u8]) -> String {
+    let looks_utf16 = bytes.len() >= 2 && bytes.len().is_multiple_of(2) && bytes[1] == 0;
+    if looks_utf16 {
+        let units: Vec<u16> = bytes.chunks_exact(2)
+            .map(|c| u16::from_le_bytes([c[0], c[1]]))
+            .collect();
+        String::from_utf16_lossy(&units).replace('\u{feff}', "")
+    } else {
+        String::from_utf8_lossy(bytes).to_string()
+    }
+}
+
+/// Parse "PID %CPU COMMAND" lines (header skipped)
+fn parse_ps_output(output: &str) -> Vec<(u32, f32, String)> {
+    output.lines()
+        .skip(1)
+        .filter_map(|line| {
+            let mut parts = line.split_whitespace();
+            let pid = parts.next()?.parse().ok()?;
+            let cpu = parts.next()?.parse().unwrap_or(0.0);
+            let command = parts.collect::<Vec<_>>().join(" ");
+            if command.is_empty() {
+                return None;
+            }
+            Some((pid, cpu, command))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_ps_output() {
+        let output = "PID                 %CPU                COMMAND\n\
+                      4021                12.5                node /usr/local/bin/claude\n\
+                      4100                0.0                 bash\n";
+        let parsed = parse_ps_output(output);
+        assert_eq!(parsed.len(), 2);
+        assert_eq!(parsed[0], (4021, 12.5, "node /usr/local/bin/claude".to_string()));
+        assert_eq!(parsed[1].2, "bash");
+    }
+
+    #[test]
+    fn test_decode_utf16_output() {
+        let bytes: Vec<u8> = "Ubuntu\r\n".encode_utf16().flat_map(|u| u.to_le_bytes()).collect();
+        assert_eq!(decode_output(&bytes).trim(), "Ubuntu");
+        assert_eq!(decode_output(b"Ubuntu\n").trim(), "Ubuntu");
+    }
+}
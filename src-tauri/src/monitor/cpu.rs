@@ -1,10 +1,16 @@
 // CPU Monitor Thread
 //
-// Monitors Claude process CPU usage with adaptive polling
-
-use crate::session::{MonitorEvent, CpuEvent, current_timestamp};
+// Monitors CPU usage of miner processes (Claude and other configured AI CLIs) with adaptive polling
+
+use crate::crash::spawn_named;
+use crate::session::{EventSender, MonitorEvent, CpuEvent, ProcessExitEvent, current_timestamp};
+use crate::session::cleaner::is_process_alive;
+use crate::session::ignore;
+use crate::monitor::container;
+use crate::monitor::metrics::{self, IoTotals};
+use crate::network;
 use sysinfo::{System, ProcessRefreshKind};
-use std::sync::mpsc::Sender;
+use std::sync::mpsc::{Receiver, RecvTimeoutError};
 use std::sync::{Arc, Mutex};
 use std::collections::{HashMap, HashSet};
 use std::thread;
@@ -12,21 +18,25 @@ use std::time::Duration;
 
 /// Start CPU monitor thread
 pub fn start_cpu_monitor(
-    event_sender: Sender<MonitorEvent>,
+    event_sender: EventSender,
     claude_pids: Arc<Mutex<HashSet<u32>>>,
+    rescan_receiver: Receiver<()>,
 ) -> thread::JoinHandle<()> {
-    thread::spawn(move || {
-        run_cpu_monitor(event_sender, claude_pids);
+    spawn_named("cpu-monitor", move || {
+        run_cpu_monitor(event_sender, claude_pids, rescan_receiver);
     })
 }
 
 fn run_cpu_monitor(
-    event_sender: Sender<MonitorEvent>,
+    event_sender: EventSender,
     claude_pids: Arc<Mutex<HashSet<u32>>>,
+    rescan_receiver: Receiver<()>,
 ) {
     let mut sys = System::new();
     let mut last_cpu: HashMap<u32, f32> = HashMap::new();
     let mut last_zombie_check: HashMap<u32, bool> = HashMap::new(); // Track zombie status
+    let mut ignored_pids: HashSet<u32> = HashSet::new(); // PIDs excluded by ignore rules (for logging once)
+    let mut previous_pids: HashSet<u32> = HashSet::new(); // PIDs tracked on the last scan
 
     println!("[CpuMonitor] Started");
 
@@ -34,25 +44,63 @@ fn run_cpu_monitor(
     loop {
         scan_count += 1;
 
-        // Find Claude PIDs using ps command (returns PID -> (is_zombie))
-        let current_pids_info = find_claude_pids_via_ps();
-        let current_pids: HashSet<u32> = current_pids_info.keys().copied().collect();
+        // Find miner PIDs (returns PID -> (is_zombie, kind, uid))
+        let current_pids_info = crate::trace::span("process scan", find_miner_pids);
+
+        // Load cwd/cmd as well when ignore rules need them
+        let ignore_rules = ignore::current();
+        let refresh_kind = if ignore_rules.is_empty() {
+            ProcessRefreshKind::new().with_cpu().with_disk_usage()
+        } else {
+            ignore::process_refresh_kind().with_cpu().with_disk_usage()
+        };
 
-        if !current_pids.is_empty() {
+        if !current_pids_info.is_empty() {
             // Refresh processes for CPU measurement
-            sys.refresh_processes_specifics(ProcessRefreshKind::new().with_cpu());
+            sys.refresh_processes_specifics(refresh_kind);
             thread::sleep(Duration::from_millis(200));
-            sys.refresh_processes_specifics(ProcessRefreshKind::new().with_cpu());
+            sys.refresh_processes_specifics(refresh_kind);
         }
 
+        // Drop processes matching ignore rules (cwd glob / command regex)
+        let current_pids: HashSet<u32> = current_pids_info.keys()
+            .copied()
+            .filter(|pid| {
+                let is_ignored = !ignore_rules.is_empty() && sys.process(sysinfo::Pid::from_u32(*pid))
+                    .map(|p| ignore_rules.matches_process(p))
+                    .unwrap_or(false);
+                if is_ignored && ignored_pids.insert(*pid) {
+                    println!("[CpuMonitor] 🙈 PID {} matches ignore rules - not tracking", pid);
+                }
+                !is_ignored
+            })
+            .collect();
+        ignored_pids.retain(|pid| current_pids_info.contains_key(pid));
+        metrics::retain(&current_pids);
+
+        // Approximate per-process network counters (cached, nettop is expensive)
+        let tracked: Vec<u32> = current_pids.iter().copied().collect();
+        let net_bytes = if tracked.is_empty() { HashMap::new() } else { network::network_bytes(&tracked) };
+
         let mut claude_found = 0;
         for &pid_u32 in &current_pids {
             claude_found += 1;
-            let is_zombie = current_pids_info.get(&pid_u32).copied().unwrap_or(false);
+            let (is_zombie, kind, uid) = match current_pids_info.get(&pid_u32) {
+                Some(info) => (info.is_zombie, info.kind.clone(), info.uid),
+                None => continue,
+            };
 
             if let Some(process) = sys.process(sysinfo::Pid::from_u32(pid_u32)) {
                 let cpu = process.cpu_usage();
 
+                // Metrics time series (CPU + disk I/O rates for get_session_metrics)
+                let disk = process.disk_usage();
+                metrics::record(pid_u32, current_timestamp(), cpu, IoTotals {
+                    read_bytes: disk.total_read_bytes,
+                    written_bytes: disk.total_written_bytes,
+                    net: net_bytes.get(&pid_u32).copied(),
+                });
+
                 // Check if zombie status changed
                 let zombie_changed = last_zombie_check.get(&pid_u32).copied().unwrap_or(false) != is_zombie;
                 if zombie_changed {
@@ -67,6 +115,9 @@ fn run_cpu_monitor(
                         pid: pid_u32,
                         timestamp: current_timestamp(),
                         cpu_percent: cpu,
+                        kind: kind.clone(),
+                        uid,
+                        container: None,
                     };
                     if event_sender.send(MonitorEvent::Cpu(event)).is_err() {
                         println!("[CpuMonitor] Channel disconnected, shutting down");
@@ -90,6 +141,9 @@ fn run_cpu_monitor(
                         pid: pid_u32,
                         timestamp: current_timestamp(),
                         cpu_percent: cpu,
+                        kind: kind.clone(),
+                        uid,
+                        container: None,
                     };
 
                     if event_sender.send(MonitorEvent::Cpu(event)).is_err() {
@@ -100,6 +154,48 @@ fn run_cpu_monitor(
             }
         }
 
+        // Processes inside Docker/WSL (own PID namespace, CPU reported by the container's ps)
+        if let Some(container_miners) = container::find_container_miners() {
+            for process in container_miners {
+                // Docker on Linux shares host PIDs; those are already tracked above
+                let is_host_pid = cfg!(target_os = "linux")
+                    && process.container.runtime == container::ContainerRuntime::Docker
+                    && current_pids_info.contains_key(&process.pid);
+                if is_host_pid {
+                    continue;
+                }
+
+                let event = CpuEvent {
+                    pid: process.pid,
+                    timestamp: current_timestamp(),
+                    cpu_percent: process.cpu_percent,
+                    kind: process.kind,
+                    uid: None,
+                    container: Some(process.container),
+                };
+                if event_sender.send(MonitorEvent::Cpu(event)).is_err() {
+                    println!("[CpuMonitor] Channel disconnected, shutting down");
+                    return;
+                }
+            }
+        }
+
+        // Tracked PIDs that vanished from the scan and are really gone
+        for &pid in previous_pids.difference(&current_pids) {
+            if current_pids_info.contains_key(&pid) || is_process_alive(pid) {
+                continue;
+            }
+            println!("[CpuMonitor] PID {} exited", pid);
+            last_cpu.remove(&pid);
+            last_zombie_check.remove(&pid);
+            let event = ProcessExitEvent { pid, timestamp: current_timestamp() };
+            if event_sender.send(MonitorEvent::ProcessExit(event)).is_err() {
+                println!("[CpuMonitor] Channel disconnected, shutting down");
+                return;
+            }
+        }
+        previous_pids = current_pids.clone();
+
         // Update shared Claude PIDs set for network monitor
         {
             let mut pids = claude_pids.lock().unwrap();
@@ -112,64 +208,79 @@ fn run_cpu_monitor(
                 scan_count, claude_found, current_pids);
         }
 
-        // Adaptive polling interval
+        // Adaptive polling interval (a rescan request wakes us up early)
         let interval = adaptive_interval(&last_cpu);
-        thread::sleep(interval);
+        match rescan_receiver.recv_timeout(interval) {
+            Ok(()) => {
+                // Forget previous measurements so every PID is re-sent with a fresh timestamp
+                while rescan_receiver.try_recv().is_ok() {}
+                println!("[CpuMonitor] Rescan requested - re-sending all PIDs");
+                last_cpu.clear();
+                last_zombie_check.clear();
+            }
+            Err(RecvTimeoutError::Timeout) => {}
+            Err(RecvTimeoutError::Disconnected) => thread::sleep(interval),
+        }
     }
 }
 
-/// Find Claude PIDs using ps command (macOS-specific)
-/// Returns map of PID -> is_zombie
+/// A process matched by the process matcher rules
+struct MinerProcess {
+    is_zombie: bool,
+    kind: String,  // Type tag from the matching rule ("claude", "aider", ...)
+    uid: Option<u32>,  // Owning user
+}
+
+/// Find miner PIDs via libproc (macOS-specific, no ps spawn)
+/// Every command line is checked against the configured process matchers
+/// Processes of users that aren't monitored are skipped (see monitor::user)
+/// Returns map of PID -> (is_zombie, kind, uid)
 #[cfg(target_os = "macos")]
-fn find_claude_pids_via_ps() -> HashMap<u32, bool> {
-    use std::process::Command;
+fn find_miner_pids() -> HashMap<u32, MinerProcess> {
+    use crate::monitor::{matcher, user};
+    use crate::status::libproc;
     let mut pids_info = HashMap::new();
+    let matchers = matcher::current();
+    let own_pid = std::process::id();
 
-    // Use ps with specific fields and pipe to grep
-    // Format: PID %CPU TTY STAT COMMAND
-    let output = Command::new("sh")
-        .arg("-c")
-        .arg("ps -eo pid,%cpu,tty,stat,command | grep -E '\\bclaude\\b' | grep -v 'claude-miner'")
-        .output();
-
-    if let Ok(output) = output {
-        let stdout = String::from_utf8_lossy(&output.stdout);
-
-        for line in stdout.lines() {
-            let parts: Vec<&str> = line.split_whitespace().collect();
-            if parts.len() >= 5 {
-                // parts[0] = PID, parts[1] = CPU%, parts[2] = TTY, parts[3] = STAT, parts[4..] = command
-                if let Ok(pid) = parts[0].parse::<u32>() {
-                    let cpu = parts[1];
-                    let tty = parts[2];
-                    let stat = parts[3];
-
-                    println!("[CpuMonitor] Found: PID={}, CPU={}%, TTY={}, STAT={}", pid, cpu, tty, stat);
-
-                    // Check if it's a zombie:
-                    // 1. TTY = "??" or "?" (no controlling terminal)
-                    // 2. STAT starts with 'T' (stopped process - unusable session)
-                    let is_zombie = tty == "??" || tty == "?" || stat.starts_with('T');
-                    if is_zombie {
-                        if tty == "??" || tty == "?" {
-                            println!("[CpuMonitor]   → Zombie process detected (TTY='{}')", tty);
-                        } else if stat.starts_with('T') {
-                            println!("[CpuMonitor]   → Zombie process detected (STAT='{}' - Stopped)", stat);
-                        }
-                    }
+    for pid in libproc::list_pids() {
+        if pid == own_pid {
+            continue;
+        }
+        let Some(info) = libproc::info(pid) else {
+            continue;  // Exited since the listing, or not inspectable
+        };
 
-                    pids_info.insert(pid, is_zombie);
-                }
-            }
+        // Other users' processes can't be signalled; skip them unless configured
+        if !user::is_uid_monitored(info.uid) {
+            continue;
         }
 
-        if pids_info.is_empty() {
-            println!("[CpuMonitor] No Claude processes found");
-        } else {
-            println!("[CpuMonitor] Found {} Claude processes: {:?}", pids_info.len(), pids_info.keys());
+        // KERN_PROCARGS2 is denied for some system processes; the short name still matches simple rules
+        let command = libproc::command_line(pid).unwrap_or_else(|| info.name.clone());
+        let kind = match matchers.match_command(&command) {
+            Some(kind) => kind.to_string(),
+            None => continue,
+        };
+
+        let tty = info.tty.as_deref().unwrap_or("??");
+        println!("[CpuMonitor] Found {}: PID={}, TTY={}, stopped={}", kind, pid, tty, info.stopped);
+
+        // Zombie: no controlling terminal, or stopped (unusable session)
+        let is_zombie = info.is_tty_zombie();
+        if info.stopped {
+            println!("[CpuMonitor]   → Zombie process detected (Stopped)");
+        } else if is_zombie {
+            println!("[CpuMonitor]   → Zombie process detected (TTY='{}')", tty);
         }
+
+        pids_info.insert(pid, MinerProcess { is_zombie, kind, uid: Some(info.uid) });
+    }
+
+    if pids_info.is_empty() {
+        println!("[CpuMonitor] No miner processes found");
     } else {
-        println!("[CpuMonitor] Failed to execute ps command");
+        println!("[CpuMonitor] Found {} miner processes: {:?}", pids_info.len(), pids_info.keys());
     }
 
     pids_info
@@ -177,7 +288,7 @@ fn find_claude_pids_via_ps() -> HashMap<u32, bool> {
 
 /// Fallback for non-macOS systems (not implemented yet)
 #[cfg(not(target_os = "macos"))]
-fn find_claude_pids_via_ps() -> HashMap<u32, bool> {
+fn find_miner_pids() -> HashMap<u32, MinerProcess> {
     HashMap::new()
 }
 
@@ -235,14 +346,14 @@ mod tests {
 
         // Low CPU
         last_cpu.insert(1, 2.0);
-        assert_eq!(adaptive_interval(&last_cpu), Duration::from_secs(1));
+        assert_eq!(adaptive_interval(&last_cpu), Duration::from_secs(2));
 
         // Medium CPU
         last_cpu.insert(1, 10.0);
-        assert_eq!(adaptive_interval(&last_cpu), Duration::from_millis(500));
+        assert_eq!(adaptive_interval(&last_cpu), Duration::from_secs(1));
 
         // High CPU
         last_cpu.insert(1, 25.0);
-        assert_eq!(adaptive_interval(&last_cpu), Duration::from_millis(300));
+        assert_eq!(adaptive_interval(&last_cpu), Duration::from_millis(500));
     }
 }
@@ -0,0 +1,106 @@
+// Energy Impact Sampler
+//
+// Optional (config.json "energy.enabled"): samples the macOS "Energy Impact" of each
+// tracked miner PID with `top -stats pid,power`, which unlike powermetrics needs no root.
+// Latest values are kept per PID and attached to the Miner payload.
+//
+
+use crate::crash::spawn_named;
+use crate::config;
+use once_cell::sync::Lazy;
+use std::collections::{HashMap, HashSet};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+
+const DISABLED_POLL: Duration = Duration::from_secs(10);  // Re-check config while disabled
+const MIN_INTERVAL_SECS: u64 = 5;
+
+/// PID -> latest energy impact (same scale as Activity Monitor)
+static ENERGY: Lazy<Mutex<HashMap<u32, f32>>> = Lazy::new(|| Mutex::new(HashMap::new()));
+
+/// Start energy sampler thread (idle unless enabled in config)
+pub fn start_energy_sampler(claude_pids: Arc<Mutex<HashSet<u32>>>) -> thread::JoinHandle<()> {
+    spawn_named("energy-sampler", move || {
+        println!("[EnergySampler] Started");
+        loop {
+            let energy_config = config::get().energy;
+            if !energy_config.enabled {
+                ENERGY.lock().unwrap().clear();
+                thread::sleep(DISABLED_POLL);
+                continue;
+            }
+
+            let pids: Vec<u32> = claude_pids.lock().unwrap().iter().copied().collect();
+            let samples = if pids.is_empty() { HashMap::new() } else { sample_energy(&pids) };
+            *ENERGY.lock().unwrap() = samples;
+
+            thread::sleep(Duration::from_secs(energy_config.interval_secs.max(MIN_INTERVAL_SECS)));
+        }
+    })
+}
+
+/// Latest energy impact for a PID (None when sampling is disabled or unsupported)
+pub fn energy_impact(pid: u32) -> Option<f32> {
+    ENERGY.lock().unwrap().get(&pid).copied()
+}
+
+/// Run `top` for two samples (the first one always reports 0) and parse the second
+#[cfg(target_os = "macos")]
+fn sample_energy(pids: &[u32]) -> HashMap<u32, f32> {
+    use std::process::Command;
+
+    let mut args = vec!["-l".to_string(), "2".to_string(), "-s".to_string(), "1".to_string(),
+        "-stats".to_string(), "pid,power".to_string()];
+    for pid in pids {
+        args.push("-pid".to_string());
+        args.push(pid.to_string());
+    }
+
+    match Command::new("top").args(&args).output() {
+        Ok(output) => parse_top_power(&String::from_utf8_lossy(&output.stdout)),
+        Err(e) => {
+            println!("[EnergySampler] Failed to run top: {}", e);
+            HashMap::new()
+        }
+    }
+}
+
+/// Energy impact is a macOS concept; nothing to sample elsewhere
+#[cfg(not(target_os = "macos"))]
+fn sample_energy(_pids: &[u32]) -> HashMap<u32, f32> {
+    HashMap::new()
+}
+
+/// Parse the last "PID POWER" table in `top -l N` output
+fn parse_top_power(output: &str) -> HashMap<u32, f32> {
+    let lines: Vec<&str> = output.lines().collect();
+    let table_start = match lines.iter().rposition(|line| line.trim_start().starts_with("PID")) {
+        Some(index) => index + 1,
+        None => return HashMap::new(),
+    };
+
+    lines[table_start..].iter()
+        .filter_map(|line| {
+            let mut parts = line.split_whitespace();
+            let pid = parts.next()?.parse().ok()?;
+            let power = parts.next()?.parse().ok()?;
+            Some((pid, power))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_top_power_uses_last_sample() {
+        let output = "Processes: 512 total\nPID    POWER\n4021   0.0\n4100   0.0\n\
+                      Processes: 512 total\nPID    POWER\n4021   12.7\n4100   0.3\n";
+        let energy = parse_top_power(output);
+        assert_eq!(energy.get(&4021), Some(&12.7));
+        assert_eq!(energy.get(&4100), Some(&0.3));
+        assert!(parse_top_power("no table").is_empty());
+    }
+}
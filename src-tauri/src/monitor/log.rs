@@ -1,40 +1,42 @@
 // Log Watcher Thread
 //
-// Monitors ~/.claude/debug directory for log file changes using notify (inotify/FSEvents)
+// Monitors the debug directory of every Claude home (~/.claude/debug, $CLAUDE_CONFIG_DIR/debug, ...)
+// for log file changes using notify (inotify/FSEvents, polling on network homes).
+// Directories that don't exist yet or get recreated are picked up by monitor::watch.
 
-use crate::session::{MonitorEvent, LogEvent, current_timestamp};
+use crate::crash::spawn_named;
+use crate::session::{EventSender, MonitorEvent, LogEvent, current_timestamp};
 use crate::session::analyzer::analyze_log_content;
-use notify::{Watcher, RecursiveMode, Event, EventKind, event::ModifyKind};
-use std::sync::mpsc::{Sender, channel};
+use crate::session::finder::get_claude_debug_dirs;
+use crate::monitor::watch::DirWatcher;
+use notify::{RecursiveMode, Event, EventKind, event::{MetadataKind, ModifyKind}};
+use std::sync::mpsc::Receiver;
 use std::thread;
 use std::time::Duration;
-use std::path::{Path, PathBuf};
+use std::path::Path;
 use std::fs;
 use std::collections::HashMap;
 
+const SWEEP_MAX_AGE_SECS: u64 = 3600; // Rescan sweeps only revisit logs touched in the last hour
+
 /// Start log watcher thread
-pub fn start_log_watcher(event_sender: Sender<MonitorEvent>) -> thread::JoinHandle<()> {
-    thread::spawn(move || {
-        if let Err(e) = run_log_watcher(event_sender) {
-            eprintln!("[LogWatcher] Error: {}", e);
-        }
+pub fn start_log_watcher(
+    event_sender: EventSender,
+    rescan_receiver: Receiver<()>,
+) -> thread::JoinHandle<()> {
+    spawn_named("log-watcher", move || {
+        run_log_watcher(event_sender, rescan_receiver);
     })
 }
 
-fn run_log_watcher(event_sender: Sender<MonitorEvent>) -> notify::Result<()> {
-    // Get debug directory
-    let debug_dir = get_debug_dir();
-
-    println!("[LogWatcher] Watching: {}", debug_dir.display());
-
-    // Create notify channel
-    let (tx, rx) = channel();
-
-    // Create recommended watcher
-    let mut watcher = notify::recommended_watcher(tx)?;
-
-    // Watch debug directory
-    watcher.watch(&debug_dir, RecursiveMode::NonRecursive)?;
+fn run_log_watcher(
+    event_sender: EventSender,
+    rescan_receiver: Receiver<()>,
+) {
+    // Watch the debug directory of each Claude home (armed once it exists)
+    let mode = if crate::config::get().watch.recursive { RecursiveMode::Recursive } else { RecursiveMode::NonRecursive };
+    let targets = get_claude_debug_dirs().into_iter().map(|dir| (dir, mode)).collect();
+    let (mut watcher, rx) = DirWatcher::new("LogWatcher", targets);
 
     // Debouncing: Track last processed time for each file (session_id -> timestamp)
     let mut last_processed: HashMap<String, u64> = HashMap::new();
@@ -42,8 +44,21 @@ fn run_log_watcher(event_sender: Sender<MonitorEvent>) -> notify::Result<()> {
 
     // Event loop
     loop {
+        watcher.maintain();
+
+        // Handle rescan requests (e.g. after system wake)
+        let mut rescan_requested = false;
+        while rescan_receiver.try_recv().is_ok() {
+            rescan_requested = true;
+        }
+        if rescan_requested && !watcher.watched_dirs().iter().all(|dir| sweep_debug_dir(dir, &event_sender)) {
+            println!("[LogWatcher] Channel disconnected, shutting down");
+            break;
+        }
+
         match rx.recv_timeout(Duration::from_millis(100)) {
-            Ok(Ok(Event { kind: EventKind::Modify(ModifyKind::Data(_)), paths, .. })) => {
+            // Polled directories report writes as a new modification time
+            Ok(Ok(Event { kind: EventKind::Modify(ModifyKind::Data(_) | ModifyKind::Metadata(MetadataKind::WriteTime)), paths, .. })) => {
                 let now = current_timestamp();
 
                 // Only process data modification events
@@ -101,13 +116,50 @@ fn run_log_watcher(event_sender: Sender<MonitorEvent>) -> notify::Result<()> {
             }
         }
     }
-
-    Ok(())
 }
 
-fn get_debug_dir() -> PathBuf {
-    let home = std::env::var("HOME").unwrap_or_else(|_| ".".to_string());
-    PathBuf::from(home).join(".claude/debug")
+/// Re-analyze every recently modified log file and send fresh events
+/// Returns false if the coordinator channel is disconnected
+fn sweep_debug_dir(debug_dir: &Path, event_sender: &EventSender) -> bool {
+    let entries = match fs::read_dir(debug_dir) {
+        Ok(e) => e,
+        Err(e) => {
+            eprintln!("[LogWatcher] Sweep failed to read {}: {}", debug_dir.display(), e);
+            return true;
+        }
+    };
+
+    let now = current_timestamp();
+    let mut swept = 0;
+
+    for entry in entries.flatten() {
+        let path = entry.path();
+        let session_id = match extract_session_id(&path) {
+            Some(id) => id,
+            None => continue,
+        };
+
+        // Skip old logs before reading their content
+        let is_recent = entry.metadata()
+            .and_then(|m| m.modified())
+            .ok()
+            .and_then(|t| t.duration_since(std::time::UNIX_EPOCH).ok())
+            .map(|d| now.saturating_sub(d.as_secs()) <= SWEEP_MAX_AGE_SECS)
+            .unwrap_or(false);
+        if !is_recent {
+            continue;
+        }
+
+        if let Ok(log_event) = analyze_log_file(&path, &session_id) {
+            if event_sender.send(MonitorEvent::Log(log_event)).is_err() {
+                return false;
+            }
+            swept += 1;
+        }
+    }
+
+    println!("[LogWatcher] Sweep complete: {} recent log files re-analyzed", swept);
+    true
 }
 
 fn extract_session_id(path: &Path) -> Option<String> {
@@ -153,6 +205,8 @@ fn analyze_log_file(path: &Path, session_id: &str) -> Result<LogEvent, std::io::
         state,
         has_approval_pending,
         file_mtime,
+        transcript: None,
+        profile: crate::config::profile_for_path(path),
     })
 }
 
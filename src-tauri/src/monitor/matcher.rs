@@ -0,0 +1,154 @@
+// Process Matcher
+//
+// Configurable engine deciding which processes are miners and what type they are.
+// Each rule has a type tag ("claude", "aider", "codex", ...) and regexes on the
+// process name and/or full command line. Rules are tried in order; first match wins.
+//
+
+use crate::config::{self, ProcessMatcherConfig};
+use once_cell::sync::Lazy;
+use regex::Regex;
+use std::sync::{Arc, Mutex};
+
+pub const CLAUDE_KIND: &str = "claude";
+
+/// Built-in rule used when config.json doesn't list any matchers
+pub fn default_matchers() -> Vec<ProcessMatcherConfig> {
+    vec![ProcessMatcherConfig {
+        kind: CLAUDE_KIND.to_string(),
+        label: Some("Claude Code".to_string()),
+        name: None,
+        args: Some(r"\bclaude\b".to_string()),
+        exclude: Some(r"claude-miner|ClaudeMiner".to_string()),
+    }]
+}
+
+struct CompiledMatcher {
+    kind: String,
+    name: Option<Regex>,
+    args: Option<Regex>,
+    exclude: Option<Regex>,
+}
+
+/// Compiled matcher rules
+pub struct ProcessMatchers {
+    matchers: Vec<CompiledMatcher>,
+}
+
+/// Compiled rules cache (source config, matchers), rebuilt when the config changes
+type CompiledMatchers = Option<(Vec<ProcessMatcherConfig>, Arc<ProcessMatchers>)>;
+static COMPILED: Lazy<Mutex<CompiledMatchers>> = Lazy::new(|| Mutex::new(None));
+
+impl ProcessMatchers {
+    /// Compile rules, skipping (and logging) rules that fail validation
+    pub fn compile(configs: &[ProcessMatcherConfig]) -> Self {
+        let compile = |pattern: &Option<String>| pattern.as_deref().and_then(|p| Regex::new(p).ok());
+
+        let matchers = configs.iter()
+            .filter_map(|c| {
+                if let Err(e) = c.validate() {
+                    eprintln!("[ProcessMatcher] ⚠️ Skipping rule: {}", e);
+                    return None;
+                }
+                Some(CompiledMatcher {
+                    kind: c.kind.clone(),
+                    name: compile(&c.name),
+                    args: compile(&c.args),
+                    exclude: compile(&c.exclude),
+                })
+            })
+            .collect();
+
+        ProcessMatchers { matchers }
+    }
+
+    /// Type tag of the first rule matching a command line (None = not a miner)
+    /// Name and args patterns must both match when both are given
+    pub fn match_command(&self, command: &str) -> Option<&str> {
+        let executable = command.split_whitespace().next().unwrap_or("");
+        let name = executable.rsplit('/').next().unwrap_or(executable);
+
+        self.matchers.iter()
+            .find(|m| {
+                m.name.as_ref().map(|r| r.is_match(name)).unwrap_or(true)
+                    && m.args.as_ref().map(|r| r.is_match(command)).unwrap_or(true)
+                    && !m.exclude.as_ref().map(|r| r.is_match(command)).unwrap_or(false)
+            })
+            .map(|m| m.kind.as_str())
+    }
+}
+
+/// Get the matchers for the current config
+pub fn current() -> Arc<ProcessMatchers> {
+    let mut configs = config::get().process_matchers;
+    if configs.is_empty() {
+        configs = default_matchers();
+    }
+
+    let mut compiled = COMPILED.lock().unwrap();
+    match compiled.as_ref() {
+        Some((source, matchers)) if *source == configs => matchers.clone(),
+        _ => {
+            let mut matchers = ProcessMatchers::compile(&configs);
+            if matchers.matchers.is_empty() {
+                // Every configured rule is broken; keep detecting Claude rather than nothing
+                eprintln!("[ProcessMatcher] ⚠️ No usable rules in process_matchers, using the built-in Claude rule");
+                matchers = ProcessMatchers::compile(&default_matchers());
+            }
+            let matchers = Arc::new(matchers);
+            println!("[ProcessMatcher] Loaded {} rules: {:?}",
+                matchers.matchers.len(),
+                matchers.matchers.iter().map(|m| m.kind.as_str()).collect::<Vec<_>>());
+            *compiled = Some((configs, matchers.clone()));
+            matchers
+        }
+    }
+}
+
+/// Display name for a type tag (config label, or the tag itself)
+pub fn label_for(kind: &str) -> String {
+    let configs = config::get().process_matchers;
+    configs.iter()
+        .chain(default_matchers().iter())
+        .find(|c| c.kind == kind)
+        .and_then(|c| c.label.clone())
+        .unwrap_or_else(|| kind.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_matcher() {
+        let matchers = ProcessMatchers::compile(&default_matchers());
+        assert_eq!(matchers.match_command("claude --resume"), Some(CLAUDE_KIND));
+        assert_eq!(matchers.match_command("node /usr/local/bin/claude"), Some(CLAUDE_KIND));
+        assert_eq!(matchers.match_command("/Applications/claude-miner.app/Contents/MacOS/claude-miner"), None);
+        assert_eq!(matchers.match_command("vim notes.txt"), None);
+    }
+
+    #[test]
+    fn test_custom_matchers() {
+        let mut configs = default_matchers();
+        configs.push(ProcessMatcherConfig {
+            kind: "aider".to_string(),
+            label: None,
+            name: Some(r"^(aider|python3?)$".to_string()),
+            args: Some(r"\baider\b".to_string()),
+            exclude: None,
+        });
+        configs.push(ProcessMatcherConfig {
+            kind: "broken".to_string(),
+            label: None,
+            name: Some("(".to_string()),
+            args: None,
+            exclude: None,
+        });
+
+        let matchers = ProcessMatchers::compile(&configs);
+        assert_eq!(matchers.matchers.len(), 2);
+        assert_eq!(matchers.match_command("/usr/bin/python3 -m aider --model gpt-4o"), Some("aider"));
+        assert_eq!(matchers.match_command("less aider.log"), None);
+    }
+}
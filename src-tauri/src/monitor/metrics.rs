@@ -0,0 +1,170 @@
+// Metrics Time Series
+//
+// Recent per-PID resource samples recorded by the CPU monitor on every scan.
+// Disk I/O matters because heavy Edit/Write tool usage shows up there rather than in CPU.
+// Outbound network bursts are counted as API requests to spot runaway retry loops.
+// In memory only; served by get_session_metrics.
+//
+
+use crate::network::NetBytes;
+use once_cell::sync::Lazy;
+use serde::Serialize;
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::sync::Mutex;
+use std::time::Instant;
+
+const MAX_SAMPLES: usize = 300;  // ~5-10 minutes at the adaptive CPU polling rate
+const REQUEST_MIN_BYTES_OUT: u64 = 2048;  // Outbound burst that looks like an API request (prompt upload)
+const REQUEST_WINDOW_SECS: u64 = 60;
+
+/// One resource sample for a process
+#[derive(Debug, Clone, Serialize)]
+pub struct MetricSample {
+    pub timestamp: u64,
+    pub cpu_percent: f32,
+    pub disk_read_bytes_per_sec: u64,
+    pub disk_write_bytes_per_sec: u64,
+    pub net_in_bytes_per_sec: Option<u64>,   // None when network counters are unavailable
+    pub net_out_bytes_per_sec: Option<u64>,
+}
+
+/// Time series plus derived estimates for one session (get_session_metrics)
+#[derive(Debug, Clone, Serialize)]
+pub struct SessionMetrics {
+    pub samples: Vec<MetricSample>,
+    pub api_requests_per_minute: u32,  // Estimated from outbound network bursts
+}
+
+/// Cumulative counters of a process: disk (proc_pid_rusage / /proc/<pid>/io via sysinfo)
+/// and network (see network::network_bytes)
+#[derive(Debug, Clone, Copy, Default)]
+pub struct IoTotals {
+    pub read_bytes: u64,
+    pub written_bytes: u64,
+    pub net: Option<NetBytes>,
+}
+
+#[derive(Default)]
+struct PidSeries {
+    samples: VecDeque<MetricSample>,
+    last_io: Option<(Instant, IoTotals)>,
+    in_burst: bool,             // Previous sample was already part of an outbound burst
+    request_starts: VecDeque<u64>,  // Timestamps of estimated API requests
+}
+
+static SERIES: Lazy<Mutex<HashMap<u32, PidSeries>>> = Lazy::new(|| Mutex::new(HashMap::new()));
+
+/// Record a sample; I/O rates are derived from the previous totals
+pub fn record(pid: u32, timestamp: u64, cpu_percent: f32, io: IoTotals) {
+    let now = Instant::now();
+    let mut series = SERIES.lock().unwrap();
+    let entry = series.entry(pid).or_default();
+
+    let mut sample = MetricSample {
+        timestamp,
+        cpu_percent,
+        disk_read_bytes_per_sec: 0,
+        disk_write_bytes_per_sec: 0,
+        net_in_bytes_per_sec: None,
+        net_out_bytes_per_sec: None,
+    };
+
+    if let Some((last_time, last)) = entry.last_io {
+        let secs = now.duration_since(last_time).as_secs_f64();
+        sample.disk_read_bytes_per_sec = per_second(io.read_bytes.saturating_sub(last.read_bytes), secs);
+        sample.disk_write_bytes_per_sec = per_second(io.written_bytes.saturating_sub(last.written_bytes), secs);
+
+        if let (Some(net), Some(last_net)) = (io.net, last.net) {
+            let bytes_out = net.bytes_out.saturating_sub(last_net.bytes_out);
+            sample.net_in_bytes_per_sec = Some(per_second(net.bytes_in.saturating_sub(last_net.bytes_in), secs));
+            sample.net_out_bytes_per_sec = Some(per_second(bytes_out, secs));
+
+            // A new outbound burst after a quiet sample counts as one request
+            let is_burst = bytes_out >= REQUEST_MIN_BYTES_OUT;
+            if is_burst && !entry.in_burst {
+                entry.request_starts.push_back(timestamp);
+            }
+            entry.in_burst = is_burst;
+        }
+    }
+    entry.last_io = Some((now, io));
+
+    while entry.request_starts.front().is_some_and(|t| timestamp.saturating_sub(*t) > REQUEST_WINDOW_SECS) {
+        entry.request_starts.pop_front();
+    }
+
+    entry.samples.push_back(sample);
+    while entry.samples.len() > MAX_SAMPLES {
+        entry.samples.pop_front();
+    }
+}
+
+/// Samples for a PID, oldest first
+pub fn samples(pid: u32) -> Vec<MetricSample> {
+    SERIES.lock().unwrap()
+        .get(&pid)
+        .map(|s| s.samples.iter().cloned().collect())
+        .unwrap_or_default()
+}
+
+/// Samples plus the API request estimate for a PID
+pub fn session_metrics(pid: u32) -> SessionMetrics {
+    let series = SERIES.lock().unwrap();
+    match series.get(&pid) {
+        Some(s) => SessionMetrics {
+            samples: s.samples.iter().cloned().collect(),
+            api_requests_per_minute: s.request_starts.len() as u32,
+        },
+        None => SessionMetrics { samples: Vec::new(), api_requests_per_minute: 0 },
+    }
+}
+
+/// Drop series of PIDs that are no longer tracked
+pub fn retain(pids: &HashSet<u32>) {
+    SERIES.lock().unwrap().retain(|pid, _| pids.contains(pid));
+}
+
+fn per_second(bytes: u64, secs: f64) -> u64 {
+    if secs <= 0.0 {
+        0
+    } else {
+        (bytes as f64 / secs) as u64
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_record_derives_io_rates() {
+        let pid = 999_999_101;
+        record(pid, 1, 5.0, IoTotals { read_bytes: 1000, written_bytes: 1000, net: None });
+        std::thread::sleep(std::time::Duration::from_millis(50));
+        record(pid, 2, 6.0, IoTotals { read_bytes: 1000, written_bytes: 11_000, net: None });
+
+        let samples = samples(pid);
+        assert_eq!(samples.len(), 2);
+        assert_eq!(samples[0].disk_write_bytes_per_sec, 0);
+        assert_eq!(samples[1].disk_read_bytes_per_sec, 0);
+        assert!(samples[1].disk_write_bytes_per_sec > 0);
+
+        assert_eq!(samples[1].net_out_bytes_per_sec, None);
+    }
+
+    #[test]
+    fn test_outbound_bursts_count_as_requests() {
+        let pid = 999_999_102;
+        let net = |bytes_out| Some(NetBytes { bytes_in: 0, bytes_out });
+        record(pid, 100, 1.0, IoTotals { net: net(0), ..Default::default() });
+        record(pid, 101, 9.0, IoTotals { net: net(50_000), ..Default::default() });  // request 1
+        record(pid, 102, 9.0, IoTotals { net: net(90_000), ..Default::default() });  // same burst
+        record(pid, 103, 1.0, IoTotals { net: net(90_100), ..Default::default() });  // quiet
+        record(pid, 104, 9.0, IoTotals { net: net(140_000), ..Default::default() }); // request 2
+        assert_eq!(session_metrics(pid).api_requests_per_minute, 2);
+
+        // Requests older than a minute drop out of the estimate
+        record(pid, 200, 1.0, IoTotals { net: net(140_000), ..Default::default() });
+        assert_eq!(session_metrics(pid).api_requests_per_minute, 0);
+    }
+}
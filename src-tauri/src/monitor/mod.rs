@@ -1,10 +1,23 @@
 // Monitor Module - Pure monitoring functionality
 //
-// This module handles CPU and log file monitoring
+// This module handles CPU, log file and transcript monitoring
 
+pub mod container;
 pub mod cpu;
+pub mod energy;
 pub mod log;
+pub mod matcher;
+pub mod metrics;
+pub mod power;
+pub mod rescan;
+pub mod transcript;
+pub mod user;
+pub mod watch;
 
 // Re-export monitoring functions
 pub use cpu::start_cpu_monitor;
-pub use log::start_log_watcher;
\ No newline at end of file
+pub use energy::start_energy_sampler;
+pub use log::start_log_watcher;
+pub use power::start_wake_detector;
+pub use rescan::RescanTrigger;
+pub use transcript::start_transcript_watcher;
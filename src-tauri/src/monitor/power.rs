@@ -0,0 +1,77 @@
+// Power Monitor Thread
+//
+// Detects system sleep/wake and triggers a full rescan on wake.
+// The monotonic clock does not advance while the machine is asleep,
+// so a wall-clock jump far beyond the monotonic elapsed time means we just woke up.
+
+use crate::crash::spawn_named;
+use crate::monitor::rescan::RescanTrigger;
+use std::thread;
+use std::time::{Duration, Instant, SystemTime};
+
+const CHECK_INTERVAL: Duration = Duration::from_secs(5);
+const SLEEP_GAP_THRESHOLD: Duration = Duration::from_secs(30);  // Ignore small clock drift / NTP adjustments
+
+/// Start wake detector thread
+pub fn start_wake_detector(rescan: RescanTrigger) -> thread::JoinHandle<()> {
+    spawn_named("wake-detector", move || {
+        run_wake_detector(rescan);
+    })
+}
+
+fn run_wake_detector(rescan: RescanTrigger) {
+    println!("[PowerMonitor] Started (check interval: {}s)", CHECK_INTERVAL.as_secs());
+
+    let mut last_wall = SystemTime::now();
+    let mut last_mono = Instant::now();
+
+    loop {
+        thread::sleep(CHECK_INTERVAL);
+
+        let now_wall = SystemTime::now();
+        let wall_elapsed = now_wall.duration_since(last_wall).unwrap_or_default();
+        let mono_elapsed = last_mono.elapsed();
+
+        if let Some(slept) = detect_sleep_gap(wall_elapsed, mono_elapsed) {
+            println!("[PowerMonitor] 💤 System wake detected (asleep for ~{}s)", slept.as_secs());
+            rescan.trigger_full_rescan("system wake");
+        }
+
+        last_wall = now_wall;
+        last_mono = Instant::now();
+    }
+}
+
+/// Returns the approximate sleep duration if the wall clock advanced
+/// significantly more than the monotonic clock
+fn detect_sleep_gap(wall_elapsed: Duration, mono_elapsed: Duration) -> Option<Duration> {
+    let gap = wall_elapsed.saturating_sub(mono_elapsed);
+    if gap >= SLEEP_GAP_THRESHOLD {
+        Some(gap)
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detect_sleep_gap() {
+        // Normal tick: both clocks advanced the same
+        assert_eq!(detect_sleep_gap(Duration::from_secs(5), Duration::from_secs(5)), None);
+
+        // Small drift is ignored
+        assert_eq!(detect_sleep_gap(Duration::from_secs(12), Duration::from_secs(5)), None);
+
+        // Wall clock jumped 10 minutes while monotonic clock advanced 5s
+        assert_eq!(
+            detect_sleep_gap(Duration::from_secs(605), Duration::from_secs(5)),
+            Some(Duration::from_secs(600))
+        );
+
+        // Wall clock moved backwards (manual clock change)
+        assert_eq!(detect_sleep_gap(Duration::from_secs(0), Duration::from_secs(5)), None);
+    }
+}
@@ -0,0 +1,44 @@
+// Rescan Trigger
+//
+// Lets any thread request an immediate full rescan from the monitors:
+// CPU scan, log-directory sweep and dead-session check
+
+use crate::session::CleanupEvent;
+use std::sync::mpsc::Sender;
+
+/// Handle for requesting an immediate full rescan
+#[derive(Clone)]
+pub struct RescanTrigger {
+    cpu_sender: Sender<()>,
+    log_sender: Sender<()>,
+    cleanup_sender: Sender<CleanupEvent>,
+}
+
+impl RescanTrigger {
+    pub fn new(
+        cpu_sender: Sender<()>,
+        log_sender: Sender<()>,
+        cleanup_sender: Sender<CleanupEvent>,
+    ) -> Self {
+        Self {
+            cpu_sender,
+            log_sender,
+            cleanup_sender,
+        }
+    }
+
+    /// Wake up the CPU monitor and log watcher and check for dead sessions
+    pub fn trigger_full_rescan(&self, reason: &str) {
+        println!("[Rescan] 🔄 Full rescan requested ({})", reason);
+
+        if self.cpu_sender.send(()).is_err() {
+            eprintln!("[Rescan] ⚠️ CPU monitor is not running");
+        }
+        if self.log_sender.send(()).is_err() {
+            eprintln!("[Rescan] ⚠️ Log watcher is not running");
+        }
+        if self.cleanup_sender.send(CleanupEvent::CheckDeadSessions).is_err() {
+            eprintln!("[Rescan] ⚠️ Session cleaner is not running");
+        }
+    }
+}
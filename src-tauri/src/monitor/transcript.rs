@@ -0,0 +1,289 @@
+// Transcript Watcher Thread
+//
+// Monitors Claude project transcripts (<claude home>/projects/<project>/<session_id>.jsonl).
+// Debug logs are sparse; transcripts contain one JSON record per message, so this
+// watcher tails each file from its last read offset and sends LogEvents carrying
+// message role, tool calls and token counts.
+// Disabled by `watch.projects = false`; directories are watched through monitor::watch.
+
+use crate::crash::spawn_named;
+use crate::session::{EventSender, MonitorEvent, LogEvent, TranscriptStats, current_timestamp};
+use crate::types::WorkingState;
+use crate::monitor::watch::DirWatcher;
+use notify::{RecursiveMode, Event, EventKind};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::fs::{self, File};
+use std::io::{Read, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
+use std::thread;
+use std::time::Duration;
+
+const MAX_READ_BYTES: u64 = 1024 * 1024; // Cap per read so a huge backlog doesn't stall the watcher
+
+/// Start transcript watcher thread
+pub fn start_transcript_watcher(event_sender: EventSender) -> thread::JoinHandle<()> {
+    spawn_named("transcript-watcher", move || {
+        run_transcript_watcher(event_sender);
+    })
+}
+
+fn run_transcript_watcher(event_sender: EventSender) {
+    if !crate::config::get().watch.projects {
+        println!("[TranscriptWatcher] Disabled by config (watch.projects)");
+        return;
+    }
+
+    // File -> byte offset of the next unread line
+    // Existing transcripts start at EOF: only messages written after startup are reported
+    // (directories created later start empty, so everything in them is new)
+    let mut offsets: HashMap<PathBuf, u64> = HashMap::new();
+    for projects_dir in get_projects_dirs() {
+        seed_offsets(&projects_dir, &mut offsets);
+    }
+    println!("[TranscriptWatcher] Tracking {} existing transcripts", offsets.len());
+
+    // Watch the projects directory of each Claude home (armed once it exists)
+    let targets = get_projects_dirs().into_iter().map(|dir| (dir, RecursiveMode::Recursive)).collect();
+    let (mut watcher, rx) = DirWatcher::new("TranscriptWatcher", targets);
+
+    loop {
+        watcher.maintain();
+
+        match rx.recv_timeout(Duration::from_millis(100)) {
+            Ok(Ok(Event { kind: EventKind::Modify(_) | EventKind::Create(_), paths, .. })) => {
+                for path in paths {
+                    let session_id = match extract_session_id(&path) {
+                        Some(id) => id,
+                        None => continue,
+                    };
+
+                    let offset = offsets.entry(path.clone()).or_insert(0);
+                    let lines = match read_new_lines(&path, offset) {
+                        Ok(lines) => lines,
+                        Err(e) => {
+                            eprintln!("[TranscriptWatcher] Failed to read {}: {}", path.display(), e);
+                            continue;
+                        }
+                    };
+                    if lines.is_empty() {
+                        continue;
+                    }
+
+                    let log_event = build_log_event(&path, &session_id, &lines);
+                    println!("[TranscriptWatcher] Session {}: {} new records, state={:?}",
+                        &session_id[..8], lines.len(), log_event.state);
+
+                    if event_sender.send(MonitorEvent::Log(log_event)).is_err() {
+                        println!("[TranscriptWatcher] Channel disconnected, shutting down");
+                        return;
+                    }
+                }
+            }
+            Ok(Ok(Event { kind: EventKind::Remove(_), paths, .. })) => {
+                for path in paths {
+                    offsets.remove(&path);
+                }
+            }
+            Ok(Ok(_)) => {}
+            Ok(Err(e)) => {
+                eprintln!("[TranscriptWatcher] Watch error: {}", e);
+            }
+            Err(std::sync::mpsc::RecvTimeoutError::Timeout) => {}
+            Err(std::sync::mpsc::RecvTimeoutError::Disconnected) => {
+                println!("[TranscriptWatcher] Channel disconnected, shutting down");
+                break;
+            }
+        }
+    }
+}
+
+pub(crate) fn get_projects_dirs() -> Vec<PathBuf> {
+    crate::config::claude_homes()
+        .into_iter()
+        .map(|home| home.join("projects"))
+        .collect()
+}
+
+/// Record the current size of every existing transcript
+fn seed_offsets(projects_dir: &Path, offsets: &mut HashMap<PathBuf, u64>) {
+    let project_dirs = match fs::read_dir(projects_dir) {
+        Ok(entries) => entries,
+        Err(_) => return,
+    };
+
+    for project in project_dirs.flatten() {
+        if let Ok(files) = fs::read_dir(project.path()) {
+            for file in files.flatten() {
+                let path = file.path();
+                if extract_session_id(&path).is_some() {
+                    if let Ok(metadata) = file.metadata() {
+                        offsets.insert(path, metadata.len());
+                    }
+                }
+            }
+        }
+    }
+}
+
+fn extract_session_id(path: &Path) -> Option<String> {
+    if path.extension().and_then(|e| e.to_str()) != Some("jsonl") {
+        return None;
+    }
+    path.file_stem()
+        .and_then(|s| s.to_str())
+        .filter(|s| s.len() == 36) // UUID length
+        .map(|s| s.to_string())
+}
+
+/// Read complete lines after `offset` and advance it past them
+/// A trailing partial line is left for the next read
+pub(crate) fn read_new_lines(path: &Path, offset: &mut u64) -> std::io::Result<Vec<String>> {
+    let mut file = File::open(path)?;
+    let len = file.metadata()?.len();
+
+    // Truncated or replaced: start over
+    if len < *offset {
+        *offset = 0;
+    }
+    if len == *offset {
+        return Ok(Vec::new());
+    }
+
+    file.seek(SeekFrom::Start(*offset))?;
+    let mut buf = Vec::new();
+    file.take(MAX_READ_BYTES).read_to_end(&mut buf)?;
+
+    let complete = match buf.iter().rposition(|&b| b == b'\n') {
+        Some(pos) => pos + 1,
+        None => return Ok(Vec::new()),
+    };
+    *offset += complete as u64;
+
+    Ok(String::from_utf8_lossy(&buf[..complete])
+        .lines()
+        .filter(|l| !l.trim().is_empty())
+        .map(|l| l.to_string())
+        .collect())
+}
+
+/// Summarize new transcript records
+fn parse_records(lines: &[String]) -> (TranscriptStats, WorkingState) {
+    let mut stats = TranscriptStats::default();
+    let mut state = WorkingState::Unknown;
+
+    for line in lines {
+        let record: Value = match serde_json::from_str(line) {
+            Ok(v) => v,
+            Err(_) => continue,
+        };
+
+        if let Some(cwd) = record.get("cwd").and_then(|v| v.as_str()) {
+            stats.cwd = Some(cwd.to_string());
+        }
+
+        let message = match record.get("message") {
+            Some(m) => m,
+            None => continue,
+        };
+
+        let role = message.get("role").and_then(|v| v.as_str()).unwrap_or("");
+        stats.last_role = Some(role.to_string());
+
+        let content = message.get("content").and_then(|v| v.as_array());
+        let has_block = |kind: &str| content
+            .map(|blocks| blocks.iter().any(|b| b.get("type").and_then(|t| t.as_str()) == Some(kind)))
+            .unwrap_or(false);
+
+        if let Some(blocks) = content {
+            stats.tool_calls.extend(blocks.iter()
+                .filter(|b| b.get("type").and_then(|t| t.as_str()) == Some("tool_use"))
+                .filter_map(|b| b.get("name").and_then(|n| n.as_str()))
+                .map(|n| n.to_string()));
+        }
+
+        if let Some(usage) = message.get("usage") {
+            let count = |key: &str| usage.get(key).and_then(|v| v.as_u64()).unwrap_or(0);
+            stats.input_tokens += count("input_tokens")
+                + count("cache_creation_input_tokens")
+                + count("cache_read_input_tokens");
+            stats.output_tokens += count("output_tokens");
+        }
+
+        // A user prompt / tool result or a tool call means Claude has more to do;
+        // an assistant turn that ended normally means it's waiting for the user
+        let stop_reason = message.get("stop_reason").and_then(|v| v.as_str());
+        state = match role {
+            "user" => WorkingState::ActivelyWorking,
+            "assistant" if has_block("tool_use") || stop_reason == Some("tool_use") => WorkingState::ActivelyWorking,
+            "assistant" if stop_reason == Some("end_turn") => WorkingState::Idle,
+            "assistant" => WorkingState::GeneratingResponse,
+            _ => state,
+        };
+    }
+
+    (stats, state)
+}
+
+fn build_log_event(path: &Path, session_id: &str, lines: &[String]) -> LogEvent {
+    let (stats, state) = parse_records(lines);
+
+    let file_mtime = fs::metadata(path)
+        .and_then(|m| m.modified())
+        .ok()
+        .and_then(|t| t.duration_since(std::time::UNIX_EPOCH).ok())
+        .map(|d| d.as_secs())
+        .unwrap_or_else(current_timestamp);
+
+    LogEvent {
+        session_id: session_id.to_string(),
+        pid: None, // Will be resolved by coordinator
+        timestamp: current_timestamp(),
+        state,
+        has_approval_pending: false,
+        file_mtime,
+        transcript: Some(stats),
+        profile: crate::config::profile_for_path(path),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+
+    #[test]
+    fn test_parse_records() {
+        let lines = vec![
+            r#"{"type":"user","cwd":"/work/repo","message":{"role":"user","content":"fix the bug"}}"#.to_string(),
+            r#"{"type":"assistant","message":{"role":"assistant","content":[{"type":"tool_use","name":"Bash"}],"usage":{"input_tokens":10,"cache_read_input_tokens":5,"output_tokens":7}}}"#.to_string(),
+            "not json".to_string(),
+        ];
+
+        let (stats, state) = parse_records(&lines);
+        assert_eq!(stats.cwd.as_deref(), Some("/work/repo"));
+        assert_eq!(stats.last_role.as_deref(), Some("assistant"));
+        assert_eq!(stats.tool_calls, vec!["Bash".to_string()]);
+        assert_eq!(stats.input_tokens, 15);
+        assert_eq!(stats.output_tokens, 7);
+        assert!(matches!(state, WorkingState::ActivelyWorking));
+
+        let done = vec![r#"{"message":{"role":"assistant","stop_reason":"end_turn","content":[{"type":"text","text":"done"}]}}"#.to_string()];
+        assert!(matches!(parse_records(&done).1, WorkingState::Idle));
+    }
+
+    #[test]
+    fn test_read_new_lines_keeps_partial_line() {
+        let mut file = tempfile::NamedTempFile::new().unwrap();
+        write!(file, "{{\"a\":1}}\n{{\"b\":").unwrap();
+
+        let mut offset = 0;
+        let lines = read_new_lines(file.path(), &mut offset).unwrap();
+        assert_eq!(lines, vec!["{\"a\":1}".to_string()]);
+        assert_eq!(offset, 8);
+
+        writeln!(file, "2}}").unwrap();
+        let lines = read_new_lines(file.path(), &mut offset).unwrap();
+        assert_eq!(lines, vec!["{\"b\":2}".to_string()]);
+    }
+}
@@ -0,0 +1,67 @@
+// User Filter
+//
+// On shared machines ps lists other users' processes, which we can't signal anyway.
+// Only the current user's processes are monitored unless config.json opts into
+// other users; those sessions are marked read-only.
+//
+
+use crate::config;
+
+/// UID of the user running ClaudeMiner
+#[cfg(unix)]
+pub fn current_uid() -> u32 {
+    // SAFETY: getuid has no preconditions and cannot fail
+    unsafe { libc::getuid() }
+}
+
+#[cfg(not(unix))]
+pub fn current_uid() -> u32 {
+    0
+}
+
+/// Whether processes owned by `uid` should be monitored
+pub fn is_uid_monitored(uid: u32) -> bool {
+    let filter = config::get().users;
+    uid == current_uid() || filter.all_users || filter.extra_uids.contains(&uid)
+}
+
+/// Owner of a running process
+#[cfg(unix)]
+pub fn process_uid(pid: u32) -> Option<u32> {
+    use sysinfo::{Pid, ProcessRefreshKind, System, UpdateKind};
+
+    let mut sys = System::new();
+    let pid = Pid::from_u32(pid);
+    sys.refresh_process_specifics(pid, ProcessRefreshKind::new().with_user(UpdateKind::Always));
+    sys.process(pid)
+        .and_then(|p| p.user_id())
+        .map(|uid| **uid)
+}
+
+#[cfg(not(unix))]
+pub fn process_uid(_pid: u32) -> Option<u32> {
+    None
+}
+
+/// Sessions of other users can be watched but not killed
+pub fn is_foreign_uid(uid: Option<u32>) -> bool {
+    uid.is_some_and(|uid| uid != current_uid())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_current_user_is_monitored() {
+        assert!(is_uid_monitored(current_uid()));
+        assert!(!is_foreign_uid(Some(current_uid())));
+        assert!(!is_foreign_uid(None));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_process_uid_of_self() {
+        assert_eq!(process_uid(std::process::id()), Some(current_uid()));
+    }
+}
@@ -0,0 +1,278 @@
+// Resilient Directory Watching
+//
+// notify watches that survive the directory life cycle: a directory missing at startup is
+// created (when its Claude home exists) or waited for, a deleted directory is re-armed once it
+// comes back, and a recreated one (new inode, the old watch is dead) is re-armed too.
+// When the native backend (inotify/FSEvents/ReadDirectoryChangesW) can't be used - it fails to
+// start, refuses the directory, or the directory lives on a network filesystem where it never
+// delivers events - the directory is polled instead.
+//
+
+use notify::{Config, Event, PollWatcher, RecommendedWatcher, RecursiveMode, Watcher};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{channel, Receiver, Sender};
+use std::time::{Duration, Instant};
+
+const RECHECK_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Event stream of a DirWatcher (native and polled directories alike)
+pub type WatchEvents = Receiver<notify::Result<Event>>;
+
+/// Which backend watches a directory
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Backend {
+    Native,
+    Poll,
+}
+
+/// A directory being watched
+struct Armed {
+    backend: Backend,
+    identity: DirIdentity,
+}
+
+/// Watches a fixed set of directories, whether or not they exist yet
+pub struct DirWatcher {
+    name: &'static str,  // Log prefix ("LogWatcher", ...)
+    targets: Vec<(PathBuf, RecursiveMode)>,
+    armed: HashMap<PathBuf, Armed>,
+    native: Option<RecommendedWatcher>,
+    native_failed: bool,  // The native backend couldn't start; poll everything
+    poll: Option<PollWatcher>,
+    poll_interval: Duration,
+    force_polling: bool,
+    sender: Sender<notify::Result<Event>>,
+    last_check: Option<Instant>,
+}
+
+impl DirWatcher {
+    /// Watch `targets`; directories are armed by the first `maintain` call
+    pub fn new(name: &'static str, targets: Vec<(PathBuf, RecursiveMode)>) -> (Self, WatchEvents) {
+        let config = crate::config::get().watch;
+        let (sender, receiver) = channel();
+        let watcher = Self {
+            name,
+            targets,
+            armed: HashMap::new(),
+            native: None,
+            native_failed: false,
+            poll: None,
+            poll_interval: Duration::from_secs(config.poll_interval_secs.max(1)),
+            force_polling: config.force_polling,
+            sender,
+            last_check: None,
+        };
+        (watcher, receiver)
+    }
+
+    /// Arm directories that appeared, re-arm recreated ones, drop deleted ones
+    /// Cheap to call on every loop iteration (checks run every few seconds)
+    pub fn maintain(&mut self) {
+        if self.last_check.is_some_and(|last| last.elapsed() < RECHECK_INTERVAL) {
+            return;
+        }
+        self.last_check = Some(Instant::now());
+
+        for (dir, mode) in self.targets.clone() {
+            create_if_home_exists(&dir);
+            let identity = dir_identity(&dir);
+            let armed = self.armed.get(&dir);
+
+            match (armed, identity) {
+                (Some(_), None) => {
+                    println!("[{}] {} disappeared, waiting for it to come back", self.name, dir.display());
+                    self.disarm(&dir);
+                }
+                (Some(armed), Some(identity)) if armed.identity != identity => {
+                    println!("[{}] {} was recreated, re-arming watch", self.name, dir.display());
+                    self.disarm(&dir);
+                    self.arm(&dir, mode, identity);
+                }
+                (None, Some(identity)) => self.arm(&dir, mode, identity),
+                _ => {}
+            }
+        }
+    }
+
+    /// Directories currently watched
+    pub fn watched_dirs(&self) -> Vec<PathBuf> {
+        self.armed.keys().cloned().collect()
+    }
+
+    fn arm(&mut self, dir: &Path, mode: RecursiveMode, identity: DirIdentity) {
+        let network = is_network_fs(dir);
+        if !self.force_polling && !network {
+            match self.native_watcher().map(|watcher| watcher.watch(dir, mode)) {
+                Some(Ok(())) => {
+                    println!("[{}] Watching: {}", self.name, dir.display());
+                    self.armed.insert(dir.to_path_buf(), Armed { backend: Backend::Native, identity });
+                    return;
+                }
+                Some(Err(e)) => eprintln!("[{}] Native watch of {} failed: {}", self.name, dir.display(), e),
+                None => {}
+            }
+        }
+
+        let reason = if self.force_polling { "configured" } else if network { "network filesystem" } else { "native watch unavailable" };
+        match self.poll_watcher().map(|watcher| watcher.watch(dir, mode)) {
+            Some(Ok(())) => {
+                println!("[{}] Polling every {:?} ({}): {}", self.name, self.poll_interval, reason, dir.display());
+                self.armed.insert(dir.to_path_buf(), Armed { backend: Backend::Poll, identity });
+            }
+            Some(Err(e)) => eprintln!("[{}] Cannot watch {}: {}", self.name, dir.display(), e),
+            None => {}
+        }
+    }
+
+    fn disarm(&mut self, dir: &Path) {
+        let Some(armed) = self.armed.remove(dir) else {
+            return;
+        };
+        // The watch may already be gone with the directory; errors are expected
+        let _ = match armed.backend {
+            Backend::Native => self.native.as_mut().map(|watcher| watcher.unwatch(dir)),
+            Backend::Poll => self.poll.as_mut().map(|watcher| watcher.unwatch(dir)),
+        };
+    }
+
+    fn native_watcher(&mut self) -> Option<&mut RecommendedWatcher> {
+        if self.native.is_none() && !self.native_failed {
+            match notify::recommended_watcher(self.sender.clone()) {
+                Ok(watcher) => self.native = Some(watcher),
+                Err(e) => {
+                    eprintln!("[{}] Native file watching unavailable ({}), falling back to polling", self.name, e);
+                    self.native_failed = true;
+                }
+            }
+        }
+        self.native.as_mut()
+    }
+
+    fn poll_watcher(&mut self) -> Option<&mut PollWatcher> {
+        if self.poll.is_none() {
+            let config = Config::default().with_poll_interval(self.poll_interval);
+            match PollWatcher::new(self.sender.clone(), config) {
+                Ok(watcher) => self.poll = Some(watcher),
+                Err(e) => eprintln!("[{}] Cannot start polling: {}", self.name, e),
+            }
+        }
+        self.poll.as_mut()
+    }
+}
+
+/// Create a missing directory inside an existing Claude home (never the home itself)
+fn create_if_home_exists(dir: &Path) {
+    if dir.exists() || !dir.parent().is_some_and(Path::is_dir) {
+        return;
+    }
+    if std::fs::create_dir(dir).is_ok() {
+        println!("[DirWatcher] Created {}", dir.display());
+    }
+}
+
+/// Identifies a directory across delete + recreate (device and inode)
+type DirIdentity = (u64, u64);
+
+#[cfg(unix)]
+fn dir_identity(dir: &Path) -> Option<DirIdentity> {
+    use std::os::unix::fs::MetadataExt;
+    let metadata = std::fs::metadata(dir).ok().filter(|m| m.is_dir())?;
+    Some((metadata.dev(), metadata.ino()))
+}
+
+#[cfg(not(unix))]
+fn dir_identity(dir: &Path) -> Option<DirIdentity> {
+    let metadata = std::fs::metadata(dir).ok().filter(|m| m.is_dir())?;
+    // No inode numbers; a recreated directory has a new creation time
+    let created = metadata.created().ok()
+        .and_then(|time| time.duration_since(std::time::UNIX_EPOCH).ok())
+        .map(|duration| duration.as_nanos() as u64)
+        .unwrap_or(0);
+    Some((0, created))
+}
+
+/// Native backends receive no events for changes made by other machines
+#[cfg(target_os = "linux")]
+fn is_network_fs(dir: &Path) -> bool {
+    use std::os::unix::ffi::OsStrExt;
+
+    let Ok(path) = std::ffi::CString::new(dir.as_os_str().as_bytes()) else {
+        return false;
+    };
+    let mut stat: libc::statfs = unsafe { std::mem::zeroed() };
+    // SAFETY: `path` is NUL-terminated and statfs fills `stat`
+    if unsafe { libc::statfs(path.as_ptr(), &mut stat) } != 0 {
+        return false;
+    }
+    is_network_fs_magic((stat.f_type as u64 & 0xffff_ffff) as u32)
+}
+
+/// Native backends receive no events for changes made by other machines
+#[cfg(target_os = "macos")]
+fn is_network_fs(dir: &Path) -> bool {
+    use std::os::unix::ffi::OsStrExt;
+
+    let Ok(path) = std::ffi::CString::new(dir.as_os_str().as_bytes()) else {
+        return false;
+    };
+    let mut stat: libc::statfs = unsafe { std::mem::zeroed() };
+    // SAFETY: `path` is NUL-terminated and statfs fills `stat`
+    if unsafe { libc::statfs(path.as_ptr(), &mut stat) } != 0 {
+        return false;
+    }
+    let name: Vec<u8> = stat.f_fstypename.iter().take_while(|&&c| c != 0).map(|&c| c as u8).collect();
+    is_network_fs_name(&String::from_utf8_lossy(&name))
+}
+
+/// Native backends receive no events for changes made by other machines
+#[cfg(not(any(target_os = "linux", target_os = "macos")))]
+fn is_network_fs(dir: &Path) -> bool {
+    // UNC paths (\\server\share)
+    dir.to_string_lossy().starts_with(r"\\")
+}
+
+/// statfs f_type of NFS, SMB/CIFS, AFS, Ceph and FUSE (sshfs & co.)
+fn is_network_fs_magic(magic: u32) -> bool {
+    matches!(magic, 0x6969 | 0x517B | 0xFF53_4D42 | 0xFE53_4D42 | 0x5346_414F | 0x00C3_6400 | 0x6573_5546)
+}
+
+/// statfs f_fstypename of macOS network filesystems
+fn is_network_fs_name(name: &str) -> bool {
+    matches!(name, "nfs" | "smbfs" | "afpfs" | "webdav" | "cifs") || name.starts_with("macfuse") || name.starts_with("osxfuse")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_network_fs_detection() {
+        assert!(is_network_fs_magic(0x6969));  // NFS
+        assert!(is_network_fs_magic(0xFF53_4D42));  // CIFS
+        assert!(!is_network_fs_magic(0xEF53));  // ext4
+        assert!(is_network_fs_name("smbfs"));
+        assert!(!is_network_fs_name("apfs"));
+    }
+
+    #[test]
+    fn test_identity_changes_on_recreate() {
+        let root = tempfile::tempdir().unwrap();
+        let dir = root.path().join("debug");
+        assert_eq!(dir_identity(&dir), None);
+
+        // Missing directory inside an existing home is created
+        create_if_home_exists(&dir);
+        let first = dir_identity(&dir).unwrap();
+
+        std::fs::remove_dir(&dir).unwrap();
+        std::fs::create_dir(root.path().join("other")).unwrap();  // Keep the old inode taken
+        std::fs::create_dir(&dir).unwrap();
+        assert_ne!(dir_identity(&dir), Some(first));
+
+        // No Claude home: nothing is created
+        let orphan = root.path().join("missing-home").join("debug");
+        create_if_home_exists(&orphan);
+        assert!(!orphan.exists());
+    }
+}
@@ -3,7 +3,70 @@
 // This module provides functionality to check active network connections
 // for Claude Code processes to detect API communication.
 
-use std::collections::HashMap;
+use crate::session::{EventSender, MonitorEvent, NetworkEvent, current_timestamp};
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+
+const MONITOR_INTERVAL: Duration = Duration::from_secs(5);
+
+const BYTES_CACHE_TTL: Duration = Duration::from_secs(2);  // nettop lists every process; don't run it on every CPU scan
+
+/// Cumulative network bytes of a process
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct NetBytes {
+    pub bytes_in: u64,
+    pub bytes_out: u64,
+}
+
+/// (sampled at, PID -> bytes) from the last nettop / /proc read
+type BytesSnapshot = Option<(Instant, HashMap<u32, NetBytes>)>;
+static BYTES_CACHE: Lazy<Mutex<BytesSnapshot>> = Lazy::new(|| Mutex::new(None));
+
+/// Start network monitor thread
+/// Sends a NetworkEvent for each tracked PID whose connections or byte counters changed
+pub fn start_network_monitor(
+    event_sender: EventSender,
+    claude_pids: Arc<Mutex<HashSet<u32>>>,
+) -> thread::JoinHandle<()> {
+    thread::spawn(move || {
+        println!("[NetworkMonitor] Started");
+        let mut last_seen: HashMap<u32, (usize, Option<NetBytes>)> = HashMap::new();
+
+        loop {
+            thread::sleep(MONITOR_INTERVAL);
+
+            let pids: Vec<u32> = claude_pids.lock().unwrap().iter().copied().collect();
+            last_seen.retain(|pid, _| pids.contains(pid));
+            if pids.is_empty() {
+                continue;
+            }
+
+            let bytes = network_bytes(&pids);
+            for pid in pids {
+                let current = (count_network_connections(pid), bytes.get(&pid).copied());
+                if last_seen.get(&pid) == Some(&current) {
+                    continue;
+                }
+                last_seen.insert(pid, current);
+
+                let event = NetworkEvent {
+                    pid,
+                    timestamp: current_timestamp(),
+                    connections: current.0,
+                    bytes: current.1,
+                };
+                if event_sender.send(MonitorEvent::Network(event)).is_err() {
+                    println!("[NetworkMonitor] Channel disconnected, shutting down");
+                    return;
+                }
+            }
+        }
+    })
+}
 
 /// Count active ESTABLISHED connections to Anthropic API (:443)
 pub fn count_network_connections(pid: u32) -> usize {
@@ -55,3 +118,127 @@ pub fn is_network_active(
         false
     }
 }
+
+/// Approximate cumulative network bytes for the given PIDs
+/// macOS: nettop per-process counters; Linux: /proc/<pid>/io character I/O minus disk I/O
+/// A sample serves every caller until BYTES_CACHE_TTL passes; PIDs it doesn't have (new
+/// processes) are left out until the next one
+pub fn network_bytes(pids: &[u32]) -> HashMap<u32, NetBytes> {
+    cached_bytes(&mut BYTES_CACHE.lock().unwrap(), pids, Instant::now(), read_network_bytes)
+}
+
+fn cached_bytes(
+    cache: &mut BytesSnapshot,
+    pids: &[u32],
+    now: Instant,
+    read: impl Fn(&[u32]) -> HashMap<u32, NetBytes>,
+) -> HashMap<u32, NetBytes> {
+    let fresh = cache.as_ref().is_some_and(|(sampled, _)| now.duration_since(*sampled) < BYTES_CACHE_TTL);
+    if !fresh {
+        *cache = Some((now, read(pids)));
+    }
+
+    let (_, all) = cache.as_ref().unwrap();
+    pids.iter()
+        .filter_map(|pid| all.get(pid).map(|bytes| (*pid, *bytes)))
+        .collect()
+}
+
+#[cfg(target_os = "macos")]
+fn read_network_bytes(_pids: &[u32]) -> HashMap<u32, NetBytes> {
+    use std::process::Command;
+
+    // One CSV sample of per-process totals: "node.1234,5120,2048,"
+    match Command::new("nettop").args(["-P", "-L", "1", "-x", "-J", "bytes_in,bytes_out"]).output() {
+        Ok(output) => parse_nettop_csv(&String::from_utf8_lossy(&output.stdout)),
+        Err(_) => HashMap::new(),
+    }
+}
+
+/// /proc has no per-process socket counters; rchar/wchar (all read/write syscalls)
+/// minus actual disk I/O is mostly socket and pipe traffic, close enough for estimates
+#[cfg(target_os = "linux")]
+fn read_network_bytes(pids: &[u32]) -> HashMap<u32, NetBytes> {
+    pids.iter()
+        .filter_map(|pid| {
+            let io = std::fs::read_to_string(format!("/proc/{}/io", pid)).ok()?;
+            Some((*pid, parse_proc_io(&io)?))
+        })
+        .collect()
+}
+
+#[cfg(not(any(target_os = "macos", target_os = "linux")))]
+fn read_network_bytes(_pids: &[u32]) -> HashMap<u32, NetBytes> {
+    HashMap::new()
+}
+
+/// Parse `nettop -P -L 1 -x -J bytes_in,bytes_out` output (process column is "name.pid")
+fn parse_nettop_csv(output: &str) -> HashMap<u32, NetBytes> {
+    output.lines()
+        .skip(1)
+        .filter_map(|line| {
+            let mut fields = line.split(',');
+            let (_, pid) = fields.next()?.rsplit_once('.')?;
+            let pid = pid.parse().ok()?;
+            let bytes_in = fields.next()?.trim().parse().ok()?;
+            let bytes_out = fields.next()?.trim().parse().ok()?;
+            Some((pid, NetBytes { bytes_in, bytes_out }))
+        })
+        .collect()
+}
+
+/// Parse /proc/<pid>/io into approximate network bytes
+fn parse_proc_io(io: &str) -> Option<NetBytes> {
+    let fields: HashMap<&str, u64> = io.lines()
+        .filter_map(|line| {
+            let (key, value) = line.split_once(':')?;
+            Some((key.trim(), value.trim().parse().ok()?))
+        })
+        .collect();
+
+    Some(NetBytes {
+        bytes_in: fields.get("rchar")?.saturating_sub(*fields.get("read_bytes")?),
+        bytes_out: fields.get("wchar")?.saturating_sub(*fields.get("write_bytes")?),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_nettop_csv() {
+        let output = ",bytes_in,bytes_out,\nnode.4021,52100,18044,\nGoogle Chrome H.880,10,20,\n";
+        let bytes = parse_nettop_csv(output);
+        assert_eq!(bytes.get(&4021), Some(&NetBytes { bytes_in: 52100, bytes_out: 18044 }));
+        assert_eq!(bytes.get(&880), Some(&NetBytes { bytes_in: 10, bytes_out: 20 }));
+    }
+
+    #[test]
+    fn test_bytes_cache_refreshes_on_time() {
+        let mut cache = None;
+        let reads = std::cell::Cell::new(0);
+        let read = |pids: &[u32]| {
+            reads.set(reads.get() + 1);
+            pids.iter().map(|&pid| (pid, NetBytes { bytes_in: pid as u64, bytes_out: 0 })).collect()
+        };
+        let start = Instant::now();
+
+        assert_eq!(cached_bytes(&mut cache, &[1, 2], start, read).len(), 2);
+        // A new PID doesn't force another read: it is served from the next sample
+        let bytes = cached_bytes(&mut cache, &[1, 3], start + Duration::from_secs(1), read);
+        assert_eq!(bytes.keys().collect::<Vec<_>>(), vec![&1]);
+        assert_eq!(reads.get(), 1);
+
+        let bytes = cached_bytes(&mut cache, &[1, 3], start + BYTES_CACHE_TTL, read);
+        assert_eq!(bytes.len(), 2);
+        assert_eq!(reads.get(), 2);
+    }
+
+    #[test]
+    fn test_parse_proc_io() {
+        let io = "rchar: 5000\nwchar: 3000\nsyscr: 10\nsyscw: 5\nread_bytes: 1000\nwrite_bytes: 4096\ncancelled_write_bytes: 0\n";
+        assert_eq!(parse_proc_io(io), Some(NetBytes { bytes_in: 4000, bytes_out: 0 }));
+        assert_eq!(parse_proc_io("garbage"), None);
+    }
+}
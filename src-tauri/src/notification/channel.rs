@@ -0,0 +1,207 @@
+// Notification Channels
+//
+// Delivery backends behind one trait. The send_* functions in sender.rs decide whether a
+// notification should go out at all (preferences, ignore flag, throttle, quiet hours) and
+// hand the draft to dispatch(), which fans it out to the channels from config.json
+// "notifications.channels" whose `events` include its kind. The native system notification
+// gets every kind unless it is listed explicitly. New backends only need a
+// NotificationChannel impl and a NotificationChannelKind variant.
+//
+
+use crate::automation::actions;
+use crate::config::{self, NotificationChannelConfig, NotificationChannelKind};
+use crate::notification::history::Draft;
+use crate::notification::sender::{self, NotificationKind};
+use std::process::{Command, Stdio};
+use tauri::api::notification::Notification;
+
+pub trait NotificationChannel {
+    fn name(&self) -> &'static str;
+
+    /// Deliver (or start delivering) a notification; remote channels send in the background
+    fn send(&self, draft: &Draft) -> Result<(), String>;
+}
+
+/// System notification with the configured sound
+pub struct NativeChannel;
+
+impl NotificationChannel for NativeChannel {
+    fn name(&self) -> &'static str {
+        "native"
+    }
+
+    fn send(&self, draft: &Draft) -> Result<(), String> {
+        let mut notification = Notification::new(sender::get_bundle_id())
+            .title(&draft.title)
+            .body(&draft.body);
+        if let Some(sound) = sender::sound_for(draft.kind) {
+            notification = notification.sound(sound);
+        }
+        notification.show().map_err(|e| e.to_string())
+    }
+}
+
+/// JSON POST to a URL
+pub struct WebhookChannel {
+    pub url: String,
+}
+
+impl NotificationChannel for WebhookChannel {
+    fn name(&self) -> &'static str {
+        "webhook"
+    }
+
+    fn send(&self, draft: &Draft) -> Result<(), String> {
+        let payload = serde_json::json!({
+            "kind": draft.kind,
+            "title": draft.title,
+            "body": draft.body,
+            "session_id": draft.session_id,
+            "pid": draft.pid,
+        });
+        actions::post_webhook("Notification".to_string(), self.url.clone(), payload);
+        Ok(())
+    }
+}
+
+/// Slack incoming webhook
+pub struct SlackChannel {
+    pub webhook_url: String,
+}
+
+impl NotificationChannel for SlackChannel {
+    fn name(&self) -> &'static str {
+        "slack"
+    }
+
+    fn send(&self, draft: &Draft) -> Result<(), String> {
+        let payload = serde_json::json!({ "text": format!("*{}*\n{}", draft.title, draft.body) });
+        actions::post_webhook("Slack notification".to_string(), self.webhook_url.clone(), payload);
+        Ok(())
+    }
+}
+
+/// Text to speech
+pub struct SpeechChannel {
+    pub voice: Option<String>,
+}
+
+impl NotificationChannel for SpeechChannel {
+    fn name(&self) -> &'static str {
+        "tts"
+    }
+
+    fn send(&self, draft: &Draft) -> Result<(), String> {
+        let mut command = if cfg!(target_os = "macos") {
+            let mut command = Command::new("say");
+            if let Some(voice) = &self.voice {
+                command.args(["-v", voice]);
+            }
+            command
+        } else if cfg!(target_os = "linux") {
+            let mut command = Command::new("spd-say");
+            if let Some(voice) = &self.voice {
+                command.args(["-y", voice]);
+            }
+            command
+        } else {
+            return Err("text to speech is not supported on this platform".to_string());
+        };
+
+        // Emoji read aloud are noise
+        let text: String = draft.title.chars().filter(|c| c.is_ascii() || c.is_alphanumeric()).collect();
+        let mut child = command.arg(text.trim())
+            .stdin(Stdio::null())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .spawn()
+            .map_err(|e| e.to_string())?;
+
+        // Reap it once it has finished speaking
+        crate::crash::spawn_named("tts", move || {
+            let _ = child.wait();
+        });
+        Ok(())
+    }
+}
+
+fn build(kind: &NotificationChannelKind) -> Box<dyn NotificationChannel> {
+    match kind {
+        NotificationChannelKind::Native => Box::new(NativeChannel),
+        NotificationChannelKind::Webhook { url } => Box::new(WebhookChannel { url: url.clone() }),
+        NotificationChannelKind::Slack { webhook_url } => Box::new(SlackChannel { webhook_url: webhook_url.clone() }),
+        NotificationChannelKind::Tts { voice } => Box::new(SpeechChannel { voice: voice.clone() }),
+    }
+}
+
+/// Channel configs a notification kind is routed to
+fn routes(channels: &[NotificationChannelConfig], kind: NotificationKind) -> Vec<NotificationChannelKind> {
+    let name = kind_name(kind);
+    let mut routed: Vec<NotificationChannelKind> = channels.iter()
+        .filter(|c| c.validate().is_ok())
+        .filter(|c| c.events.is_empty() || c.events.iter().any(|e| e == &name))
+        .map(|c| c.channel.clone())
+        .collect();
+
+    // Native notifications stay on unless routed explicitly
+    if !channels.iter().any(|c| c.channel == NotificationChannelKind::Native) {
+        routed.insert(0, NotificationChannelKind::Native);
+    }
+    routed
+}
+
+/// Config name of a kind ("task_completion")
+fn kind_name(kind: NotificationKind) -> String {
+    serde_json::to_value(kind).ok()
+        .and_then(|v| v.as_str().map(str::to_string))
+        .unwrap_or_default()
+}
+
+/// Send a notification through every channel it is routed to; true if any accepted it
+pub fn dispatch(draft: &Draft) -> bool {
+    let channels = config::get().notifications.channels;
+    let mut delivered = false;
+
+    for kind in routes(&channels, draft.kind) {
+        let channel = build(&kind);
+        match channel.send(draft) {
+            Ok(()) => delivered = true,
+            Err(e) => println!("[Notification] ⚠️ Failed to send {} notification: {}", channel.name(), e),
+        }
+    }
+    delivered
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn channel(json: &str) -> NotificationChannelConfig {
+        serde_json::from_str(json).unwrap()
+    }
+
+    #[test]
+    fn test_routes() {
+        // Nothing configured: native only
+        assert_eq!(routes(&[], NotificationKind::TaskCompletion), vec![NotificationChannelKind::Native]);
+
+        let slack = channel(r#"{"type":"slack","webhook_url":"https://hooks.slack.test/x","events":["zombie_detected"]}"#);
+        let tts = channel(r#"{"type":"tts"}"#);
+        let channels = vec![slack.clone(), tts.clone()];
+        assert_eq!(routes(&channels, NotificationKind::ZombieDetected),
+            vec![NotificationChannelKind::Native, slack.channel.clone(), tts.channel.clone()]);
+        assert_eq!(routes(&channels, NotificationKind::TaskCompletion),
+            vec![NotificationChannelKind::Native, tts.channel.clone()]);
+
+        // An explicit native entry limits native notifications to its events
+        let native = channel(r#"{"type":"native","events":["approval_pending"]}"#);
+        let channels = vec![native, slack.clone()];
+        assert_eq!(routes(&channels, NotificationKind::ApprovalPending), vec![NotificationChannelKind::Native]);
+        assert_eq!(routes(&channels, NotificationKind::ZombieDetected), vec![slack.channel]);
+        assert!(routes(&channels, NotificationKind::Milestone).is_empty());
+
+        // A channel naming an unknown kind is skipped (reported when config.json loads)
+        let typo = channel(r#"{"type":"tts","events":["task_complete"]}"#);
+        assert_eq!(routes(&[typo], NotificationKind::TaskCompletion), vec![NotificationChannelKind::Native]);
+    }
+}
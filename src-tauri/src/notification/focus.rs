@@ -0,0 +1,171 @@
+// Focus Mode
+//
+// Pomodoro-style focus: while it's on, task-completion notifications are held back
+// and queued. When focus ends (its timer runs out or it's stopped by hand) one digest
+// notification lists what finished in the meantime. Started/stopped from the UI or the
+// tray; every change is pushed as "focus-changed" and reflected in the tray menu.
+//
+
+use crate::crash::spawn_named;
+use crate::session::{current_timestamp, SessionState};
+use once_cell::sync::Lazy;
+use serde::Serialize;
+use std::sync::Mutex;
+use std::thread;
+use std::time::Duration;
+
+/// Length of a focus session started from the tray
+pub const DEFAULT_MINUTES: u64 = 25;
+
+/// A completion held back during focus
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct QueuedCompletion {
+    pub session_id: String,
+    pub pid: u32,
+    pub timestamp: u64,
+}
+
+/// Current focus state (get_focus_status, "focus-changed")
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct FocusStatus {
+    pub active: bool,
+    pub started_at: Option<u64>,
+    pub ends_at: Option<u64>,  // None: until stopped
+    pub queued: usize,
+}
+
+struct Focus {
+    id: u64,  // Tells a stale timer apart from the current focus
+    started_at: u64,
+    ends_at: Option<u64>,
+    queued: Vec<QueuedCompletion>,
+}
+
+#[derive(Default)]
+struct FocusState {
+    current: Option<Focus>,
+    next_id: u64,
+}
+
+static FOCUS: Lazy<Mutex<FocusState>> = Lazy::new(|| Mutex::new(FocusState::default()));
+
+impl FocusState {
+    fn start(&mut self, now: u64, minutes: u64) -> u64 {
+        self.next_id += 1;
+        // Restarting keeps what was queued so far
+        let queued = self.current.take().map(|f| f.queued).unwrap_or_default();
+        self.current = Some(Focus {
+            id: self.next_id,
+            started_at: now,
+            ends_at: (minutes > 0).then_some(now + minutes * 60),
+            queued,
+        });
+        self.next_id
+    }
+
+    /// End focus (only if it's still focus `id`, when given); returns the queued completions
+    fn stop(&mut self, id: Option<u64>) -> Option<Vec<QueuedCompletion>> {
+        if id.is_some_and(|id| self.current.as_ref().map(|f| f.id) != Some(id)) {
+            return None;
+        }
+        self.current.take().map(|f| f.queued)
+    }
+
+    fn queue(&mut self, session: &SessionState, now: u64) -> bool {
+        match self.current.as_mut() {
+            Some(focus) => {
+                focus.queued.push(QueuedCompletion { session_id: session.session_id.clone(), pid: session.pid, timestamp: now });
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn status(&self) -> FocusStatus {
+        match &self.current {
+            Some(focus) => FocusStatus {
+                active: true,
+                started_at: Some(focus.started_at),
+                ends_at: focus.ends_at,
+                queued: focus.queued.len(),
+            },
+            None => FocusStatus::default(),
+        }
+    }
+}
+
+/// Start focus for `minutes` (0 = until stopped)
+pub fn start(minutes: u64) -> FocusStatus {
+    let (id, status) = {
+        let mut focus = FOCUS.lock().unwrap();
+        let id = focus.start(current_timestamp(), minutes);
+        (id, focus.status())
+    };
+    println!("[Focus] 🍅 Focus started ({})", if minutes > 0 { format!("{} min", minutes) } else { "until stopped".to_string() });
+
+    if minutes > 0 {
+        spawn_named("focus-timer", move || {
+            thread::sleep(Duration::from_secs(minutes * 60));
+            finish(Some(id));
+        });
+    }
+    changed(&status);
+    status
+}
+
+/// Stop focus and deliver the digest
+pub fn stop() -> FocusStatus {
+    finish(None);
+    status()
+}
+
+pub fn status() -> FocusStatus {
+    FOCUS.lock().unwrap().status()
+}
+
+/// Hold back a task completion while focused; true if it was queued
+pub fn queue_if_focused(session: &SessionState) -> bool {
+    let queued = FOCUS.lock().unwrap().queue(session, current_timestamp());
+    if queued {
+        changed(&status());
+    }
+    queued
+}
+
+fn finish(id: Option<u64>) {
+    let Some(queued) = FOCUS.lock().unwrap().stop(id) else { return };
+    println!("[Focus] Focus ended, {} completion(s) queued", queued.len());
+    crate::notification::sender::send_focus_digest_notification(&queued);
+    changed(&FocusStatus::default());
+}
+
+fn changed(status: &FocusStatus) {
+    crate::event::emit_focus_changed(status);
+    crate::event::refresh_tray_menu();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_queue_and_stale_timer() {
+        let mut focus = FocusState::default();
+        let session = SessionState::new_legacy(42, "focus-test".to_string());
+        assert!(!focus.queue(&session, 0));
+
+        let first = focus.start(1_000, 25);
+        assert_eq!(focus.status().ends_at, Some(1_000 + 25 * 60));
+        assert!(focus.queue(&session, 1_100));
+
+        // Restarted: the first timer no longer ends it, the queue carries over
+        let second = focus.start(1_200, 0);
+        assert_eq!(focus.stop(Some(first)), None);
+        assert_eq!(focus.status(), FocusStatus { active: true, started_at: Some(1_200), ends_at: None, queued: 1 });
+
+        let queued = focus.stop(Some(second)).unwrap();
+        assert_eq!(queued, vec![QueuedCompletion { session_id: "focus-test".to_string(), pid: 42, timestamp: 1_100 }]);
+        assert!(!focus.status().active);
+        assert_eq!(focus.stop(None), None);
+    }
+}
@@ -0,0 +1,110 @@
+// Notification History
+//
+// Every notification the app tried to show, with what happened to it (delivered,
+// or suppressed by preferences, ignore flag, throttle or quiet hours), so users can
+// review what they missed. In memory; served by get_notification_history.
+//
+
+use crate::config::NotificationOverride;
+use crate::notification::sender::NotificationKind;
+use crate::session::current_timestamp;
+use once_cell::sync::Lazy;
+use serde::Serialize;
+use std::collections::VecDeque;
+use std::sync::Mutex;
+
+const MAX_RECORDS: usize = 500;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Outcome {
+    Delivered,
+    Failed,     // The OS refused it
+    Disabled,   // Turned off in the notification settings
+    Ignored,    // Session is ignored
+    Throttled,  // Coalesced into a later summary
+    Quiet,      // Quiet hours / snooze
+    Focused,    // Queued for the focus mode digest
+    Watched,    // The session's terminal was already in front
+    Muted,      // Notifications muted for the session or its project
+}
+
+/// A notification before it is shown
+#[derive(Debug, Clone)]
+pub struct Draft {
+    pub kind: NotificationKind,
+    pub session_id: Option<String>,
+    pub pid: Option<u32>,
+    pub title: String,
+    pub body: String,
+    pub mode: Option<NotificationOverride>,  // Session/project override
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct NotificationRecord {
+    pub timestamp: u64,
+    pub kind: NotificationKind,
+    pub session_id: Option<String>,
+    pub pid: Option<u32>,
+    pub title: String,
+    pub body: String,
+    pub outcome: Outcome,
+    pub delivered: bool,
+}
+
+static HISTORY: Lazy<Mutex<VecDeque<NotificationRecord>>> = Lazy::new(|| Mutex::new(VecDeque::new()));
+
+pub fn record(draft: Draft, outcome: Outcome) {
+    let mut history = HISTORY.lock().unwrap();
+    history.push_back(NotificationRecord {
+        timestamp: current_timestamp(),
+        kind: draft.kind,
+        session_id: draft.session_id,
+        pid: draft.pid,
+        title: draft.title,
+        body: draft.body,
+        outcome,
+        delivered: outcome == Outcome::Delivered,
+    });
+    while history.len() > MAX_RECORDS {
+        history.pop_front();
+    }
+}
+
+/// Records newer than `since` (unix seconds), newest first
+pub fn get(since: Option<u64>) -> Vec<NotificationRecord> {
+    HISTORY.lock().unwrap().iter()
+        .rev()
+        .take_while(|r| since.is_none_or(|since| r.timestamp >= since))
+        .cloned()
+        .collect()
+}
+
+pub fn clear() {
+    HISTORY.lock().unwrap().clear();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_record_and_filter() {
+        let draft = |title: &str| Draft {
+            kind: NotificationKind::TaskCompletion,
+            session_id: Some("history-test".to_string()),
+            pid: Some(1),
+            title: title.to_string(),
+            body: String::new(),
+            mode: None,
+        };
+        record(draft("delivered"), Outcome::Delivered);
+        record(draft("quiet"), Outcome::Quiet);
+
+        let records: Vec<_> = get(None).into_iter().filter(|r| r.session_id.as_deref() == Some("history-test")).collect();
+        assert_eq!(records[0].title, "quiet");
+        assert!(!records[0].delivered);
+        assert!(records[1].delivered);
+        assert!(get(Some(u64::MAX)).is_empty());
+    }
+}
@@ -3,7 +3,12 @@
 // This module handles all notification functionality for ClaudeMiner
 // using a singleton pattern for AppHandle management
 
+pub mod channel;
+pub mod focus;
+pub mod history;
+pub mod quiet;
 pub mod sender;
+pub mod throttle;
 
 // Re-export public API
 pub use sender::{
@@ -11,5 +16,12 @@ pub use sender::{
     send_task_completion_notification,
     // send_session_created_notification, // Unused
     send_zombie_killed_notification,
+    send_zombie_detected_notification,
+    send_approval_pending_notification,
+    send_needs_attention_notification,
+    send_hooks_repaired_notification,
+    send_hooks_stale_notification,
+    send_custom_notification,
+    send_milestone_notification,
     send_test_notification,
 };
@@ -0,0 +1,135 @@
+// Quiet Hours
+//
+// Do Not Disturb: a daily schedule (config.json "quiet_hours", local time, may wrap
+// past midnight, optionally all weekend) plus a manual snooze. Notifications
+// suppressed while quiet are kept so the UI can show them later.
+//
+
+use crate::config::{self, QuietHoursConfig};
+use crate::session::current_timestamp;
+use chrono::{Datelike, Local, Timelike, Weekday};
+use once_cell::sync::Lazy;
+use serde::Serialize;
+use std::collections::VecDeque;
+use std::sync::Mutex;
+
+const MAX_SUPPRESSED: usize = 100;
+
+/// Manual snooze end (unix seconds)
+static SNOOZED_UNTIL: Lazy<Mutex<Option<u64>>> = Lazy::new(|| Mutex::new(None));
+
+static SUPPRESSED: Lazy<Mutex<VecDeque<SuppressedNotification>>> = Lazy::new(|| Mutex::new(VecDeque::new()));
+
+/// A notification that was held back by quiet hours or snooze
+#[derive(Debug, Clone, Serialize)]
+pub struct SuppressedNotification {
+    pub timestamp: u64,
+    pub title: String,
+    pub body: String,
+}
+
+/// Current Do Not Disturb state (get_quiet_status)
+#[derive(Debug, Clone, Serialize)]
+pub struct QuietStatus {
+    pub quiet: bool,
+    pub snoozed_until: Option<u64>,
+    pub scheduled: bool,  // Inside the configured schedule
+}
+
+/// Snooze all notifications for the given number of minutes (0 = cancel)
+pub fn snooze(minutes: u64) -> Option<u64> {
+    let until = (minutes > 0).then(|| current_timestamp() + minutes * 60);
+    *SNOOZED_UNTIL.lock().unwrap() = until;
+    until
+}
+
+pub fn status() -> QuietStatus {
+    let now = current_timestamp();
+    let snoozed_until = SNOOZED_UNTIL.lock().unwrap().filter(|until| *until > now);
+    let local = Local::now();
+    let scheduled = in_schedule(&config::get().quiet_hours, local.weekday(), local.hour() * 60 + local.minute());
+
+    QuietStatus { quiet: scheduled || snoozed_until.is_some(), snoozed_until, scheduled }
+}
+
+/// Check DND; when quiet, keep the notification for later and return true
+pub fn suppress_if_quiet(title: &str, body: &str) -> bool {
+    if !status().quiet {
+        return false;
+    }
+
+    println!("[Notification] 🌙 Quiet hours - holding back '{}'", title);
+    let mut suppressed = SUPPRESSED.lock().unwrap();
+    suppressed.push_back(SuppressedNotification {
+        timestamp: current_timestamp(),
+        title: title.to_string(),
+        body: body.to_string(),
+    });
+    while suppressed.len() > MAX_SUPPRESSED {
+        suppressed.pop_front();
+    }
+    true
+}
+
+/// Notifications held back so far (oldest first)
+pub fn suppressed() -> Vec<SuppressedNotification> {
+    SUPPRESSED.lock().unwrap().iter().cloned().collect()
+}
+
+pub fn clear_suppressed() {
+    SUPPRESSED.lock().unwrap().clear();
+}
+
+/// Is the given local weekday/time (minutes since midnight) inside the schedule?
+pub fn in_schedule(config: &QuietHoursConfig, weekday: Weekday, minute_of_day: u32) -> bool {
+    if !config.enabled {
+        return false;
+    }
+    if config.weekends && matches!(weekday, Weekday::Sat | Weekday::Sun) {
+        return true;
+    }
+
+    let (Some(start), Some(end)) = (parse_hhmm(&config.start), parse_hhmm(&config.end)) else {
+        return false;
+    };
+    if start <= end {
+        (start..end).contains(&minute_of_day)
+    } else {
+        // Wraps past midnight (e.g. 22:00-08:00)
+        minute_of_day >= start || minute_of_day < end
+    }
+}
+
+/// "HH:MM" -> minutes since midnight
+fn parse_hhmm(value: &str) -> Option<u32> {
+    let (hours, minutes) = value.trim().split_once(':')?;
+    let (hours, minutes): (u32, u32) = (hours.parse().ok()?, minutes.parse().ok()?);
+    (hours < 24 && minutes < 60).then_some(hours * 60 + minutes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_in_schedule() {
+        let config = QuietHoursConfig {
+            enabled: true,
+            start: "22:00".to_string(),
+            end: "08:00".to_string(),
+            weekends: true,
+        };
+        assert!(in_schedule(&config, Weekday::Tue, 23 * 60));
+        assert!(in_schedule(&config, Weekday::Tue, 7 * 60 + 59));
+        assert!(!in_schedule(&config, Weekday::Tue, 8 * 60));
+        assert!(!in_schedule(&config, Weekday::Tue, 12 * 60));
+        assert!(in_schedule(&config, Weekday::Sat, 12 * 60));
+
+        let daytime = QuietHoursConfig { start: "12:00".to_string(), end: "13:00".to_string(), weekends: false, ..config.clone() };
+        assert!(in_schedule(&daytime, Weekday::Sat, 12 * 60 + 30));
+        assert!(!in_schedule(&daytime, Weekday::Sat, 13 * 60));
+
+        assert!(!in_schedule(&QuietHoursConfig { enabled: false, ..config }, Weekday::Tue, 23 * 60));
+        assert_eq!(parse_hhmm("25:00"), None);
+    }
+}
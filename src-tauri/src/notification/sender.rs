@@ -4,15 +4,31 @@
 // - Task completion notifications
 // - Session state change notifications
 // - Zombie process termination notifications
+// Every attempt (delivered or suppressed) is recorded in the notification history
+// Delivery itself goes through the configured channels (channel.rs)
 //
 
+use crate::achievements::Milestone;
+use crate::config::{self, NotificationOverride, ProjectNotificationOverride};
+use crate::event::schema::{self, EventPayload};
+use crate::notification::focus::QueuedCompletion;
+use crate::notification::history::{self, Outcome};
+use crate::notification::{channel, focus, quiet, throttle};
 use crate::session::SessionState;
-use tauri::api::notification::Notification;
+use crate::terminal;
+use serde::{Deserialize, Serialize};
+use tauri::api::notification::Sound;
 use once_cell::sync::OnceCell;
+use std::sync::Mutex;
+
+const HOOKS_STALE_WARNING_SECS: u64 = 3600;
 
 /// Global AppHandle singleton for notifications
 static APP_HANDLE: OnceCell<tauri::AppHandle> = OnceCell::new();
 
+/// When hooks were last reported silent (send_hooks_stale_notification)
+static LAST_HOOKS_STALE_WARNING: Mutex<Option<u64>> = Mutex::new(None);
+
 /// Initialize the notification system with AppHandle
 /// This should be called once during app setup
 pub fn init(app_handle: tauri::AppHandle) {
@@ -23,7 +39,7 @@ pub fn init(app_handle: tauri::AppHandle) {
 }
 
 /// Get the bundle identifier for notifications
-fn get_bundle_id() -> String {
+pub fn get_bundle_id() -> String {
     APP_HANDLE
         .get()
         .map(|handle| handle.config().tauri.bundle.identifier.clone())
@@ -33,85 +49,426 @@ fn get_bundle_id() -> String {
         })
 }
 
+/// Notification types (all but Rule/HooksRepaired/HooksStale/FocusDigest/Test can be toggled in config.json "notifications")
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum NotificationKind {
+    TaskCompletion,
+    SessionCreated,
+    ZombieKilled,
+    ZombieDetected,
+    ApprovalPending,
+    NeedsAttention,  // Blocked on a permission prompt seen in the debug log (no hooks to report it)
+    LongTask,
+    Milestone,  // Achievements (tasks mined, streaks)
+    Rule,  // Automation rule "notify" action
+    HooksRepaired,
+    HooksStale,  // A Hook session stopped receiving hook events
+    FocusDigest,
+    Test,
+}
+
+/// Check the user's per-event preference
+pub fn is_enabled(kind: NotificationKind) -> bool {
+    let prefs = config::get().notifications;
+    match kind {
+        NotificationKind::TaskCompletion => prefs.task_completion,
+        NotificationKind::SessionCreated => prefs.session_created,
+        NotificationKind::ZombieKilled => prefs.zombie_killed,
+        NotificationKind::ZombieDetected => prefs.zombie_detected,
+        NotificationKind::ApprovalPending | NotificationKind::NeedsAttention => prefs.approval_pending,
+        NotificationKind::LongTask => prefs.long_task,
+        NotificationKind::Milestone => prefs.milestones,
+        NotificationKind::Rule | NotificationKind::HooksRepaired | NotificationKind::HooksStale
+            | NotificationKind::FocusDigest | NotificationKind::Test => true,
+    }
+}
+
+/// Configured sound for a notification type (None = silent)
+pub fn sound_for(kind: NotificationKind) -> Option<Sound> {
+    let sounds = config::get().sounds;
+    let name = match kind {
+        NotificationKind::TaskCompletion => sounds.completion,
+        NotificationKind::ApprovalPending | NotificationKind::NeedsAttention => sounds.approval_pending,
+        NotificationKind::ZombieDetected | NotificationKind::ZombieKilled => sounds.zombie,
+        _ => None,
+    }?;
+
+    match name.trim() {
+        "" => None,
+        "default" => Some(Sound::Default),
+        name => Some(Sound::from(name)),
+    }
+}
+
+/// Ignored sessions never notify; pinned sessions always do
+fn should_notify(session: &SessionState) -> bool {
+    session.pinned || !session.ignored
+}
+
+/// The session's own override, else the first project override matching its path
+pub fn override_for(session: &SessionState) -> Option<NotificationOverride> {
+    session.notification_override.or_else(|| {
+        let project_path = session.project_path.as_deref()?;
+        project_override(&config::get().notifications.project_overrides, project_path)
+    })
+}
+
+fn project_override(overrides: &[ProjectNotificationOverride], project_path: &str) -> Option<NotificationOverride> {
+    overrides.iter()
+        .find(|o| o.project == project_path
+            || glob::Pattern::new(&o.project).is_ok_and(|pattern| pattern.matches(project_path)))
+        .map(|o| o.mode)
+}
+
+/// Per-event preference, unless the session/project always notifies
+fn enabled(draft: &history::Draft) -> bool {
+    draft.mode == Some(NotificationOverride::Always) || is_enabled(draft.kind)
+}
+
+/// What a notification is about (for the history)
+fn about(kind: NotificationKind, session: Option<&SessionState>, pid: Option<u32>, title: &str, body: &str) -> history::Draft {
+    history::Draft {
+        kind,
+        session_id: session.map(|s| s.session_id.clone()),
+        pid: session.map(|s| s.pid).or(pid),
+        title: title.to_string(),
+        body: body.to_string(),
+        mode: session.and_then(override_for),
+    }
+}
+
+/// Button offered with a notification, handled by the frontend with existing commands
+/// ("focus" -> focus_session_terminal, "kill" -> kill_miner)
+#[derive(Debug, Clone, Copy, Serialize)]
+pub struct NotificationAction {
+    pub id: &'static str,
+    pub label: &'static str,
+}
+
+pub const FOCUS_ACTION: NotificationAction = NotificationAction { id: "focus", label: "Focus terminal" };
+pub const KILL_ACTION: NotificationAction = NotificationAction { id: "kill", label: "Kill now" };
+
+/// Tauri v1 system notifications can't carry buttons: after showing one, mirror it to
+/// the frontend, which renders an in-app toast with the action buttons. The same actions
+/// are in the tray menu while the session still needs them (event::TrayAction), for when
+/// the window is hidden
+fn show_with_actions(draft: history::Draft, actions: &[NotificationAction]) -> bool {
+    if !show(draft.clone()) {
+        return false;
+    }
+    if let Some(handle) = APP_HANDLE.get() {
+        let payload = EventPayload::NotificationActions {
+            kind: draft.kind,
+            session_id: draft.session_id.as_deref(),
+            pid: draft.pid,
+            title: &draft.title,
+            body: &draft.body,
+            actions,
+        };
+        if let Err(e) = schema::emit(handle, payload) {
+            println!("[Notification] ⚠️ Failed to emit notification-actions: {}", e);
+        }
+    }
+    true
+}
+
+/// Show a notification unless it is muted or quiet hours hold it back; every attempt is recorded
+fn show(draft: history::Draft) -> bool {
+    if draft.mode == Some(NotificationOverride::Mute) {
+        println!("[Notification] 🔇 Muted: {}", draft.title);
+        history::record(draft, Outcome::Muted);
+        return false;
+    }
+    // Sessions/projects set to always notify skip quiet hours
+    if draft.mode != Some(NotificationOverride::Always) && quiet::suppress_if_quiet(&draft.title, &draft.body) {
+        history::record(draft, Outcome::Quiet);
+        return false;
+    }
+    if deliver(&draft) {
+        history::record(draft, Outcome::Delivered);
+        true
+    } else {
+        history::record(draft, Outcome::Failed);
+        false
+    }
+}
+
+/// Hand a notification to its channels
+#[cfg(not(test))]
+fn deliver(draft: &history::Draft) -> bool {
+    channel::dispatch(draft)
+}
+
+/// Tests record what would have been shown without reaching the OS
+#[cfg(test)]
+fn deliver(_draft: &history::Draft) -> bool {
+    true
+}
+
 /// Send notification when Claude task completes (working → resting)
 pub fn send_task_completion_notification(session: &SessionState) {
     let session_short = &session.session_id[..8.min(session.session_id.len())];
+    let draft = about(NotificationKind::TaskCompletion, Some(session), None,
+        "Claude Task Completed ✅", &format!("Claude #{} has finished working", session.pid));
+
+    if !enabled(&draft) {
+        println!("[Notification] 🔕 Task completion notifications disabled");
+        history::record(draft, Outcome::Disabled);
+        return;
+    }
+
+    if !should_notify(session) {
+        println!("[Notification] 🔕 Skipping notification for ignored session {}", session_short);
+        history::record(draft, Outcome::Ignored);
+        return;
+    }
+
+    // Asking for the frontmost window spawns osascript/xdotool: not on the caller's (coordinator) thread
+    if config::get().notifications.skip_when_terminal_focused {
+        let session = session.clone();
+        terminal::frontmost::check_in_background(session.pid, session.tty.clone(), move |frontmost| {
+            if frontmost {
+                println!("[Notification] 👀 Terminal of session {} is in front, skipping", &session.session_id[..8.min(session.session_id.len())]);
+                history::record(draft, Outcome::Watched);
+            } else {
+                notify_task_completion(draft, &session);
+            }
+        });
+        return;
+    }
+    notify_task_completion(draft, session);
+}
+
+/// Task completion past the per-kind and per-session checks: focus mode, throttle, delivery
+fn notify_task_completion(draft: history::Draft, session: &SessionState) {
+    let session_short = &session.session_id[..8.min(session.session_id.len())];
+    if draft.mode != Some(NotificationOverride::Always) && focus::queue_if_focused(session) {
+        println!("[Notification] 🍅 Focus mode - queueing completion of session {}", session_short);
+        history::record(draft, Outcome::Focused);
+        return;
+    }
+
+    let pid = session.pid;
+    let session_id = session.session_id.clone();
+    let mode = draft.mode;
+    if !throttle::admit(&session.session_id, move |count| send_task_summary_notification(session_id, pid, mode, count)) {
+        println!("[Notification] ⏳ Coalescing task completion for session {}", session_short);
+        history::record(draft, Outcome::Throttled);
+        return;
+    }
 
     println!("[Notification] 📢 Sending task completion notification for session {} (PID: {})",
         session_short, session.pid);
 
-    let notification_result = Notification::new(&get_bundle_id())
-        .title("Claude Task Completed ✅")
-        .body(&format!("Claude #{} has finished working", session.pid))
-        .show();
-
-    match notification_result {
-        Ok(_) => {
-            println!("[Notification] ✅ Task completion notification sent successfully");
-        }
-        Err(e) => {
-            println!("[Notification] ⚠️ Failed to send notification: {}", e);
-        }
+    if show_with_actions(draft, &[FOCUS_ACTION]) {
+        println!("[Notification] ✅ Task completion notification sent successfully");
     }
 }
 
+/// Summary for task completions held back by the throttle
+fn send_task_summary_notification(session_id: String, pid: u32, mode: Option<NotificationOverride>, count: u32) {
+    let body = format!("Claude #{}: {} more task{} completed", pid, count, if count == 1 { "" } else { "s" });
+    let mut draft = about(NotificationKind::TaskCompletion, None, Some(pid), "Claude Tasks Completed ✅", &body);
+    draft.session_id = Some(session_id);
+    draft.mode = mode;
+
+    println!("[Notification] 📢 Sending task summary notification for PID {} ({} coalesced)", pid, count);
+    show(draft);
+}
+
 /// Send notification when new session is created
 pub fn send_session_created_notification(session: &SessionState) {
     let session_short = &session.session_id[..8.min(session.session_id.len())];
+    let draft = about(NotificationKind::SessionCreated, Some(session), None,
+        "New Claude Session Started 🚀", &format!("Claude #{} has started", session.pid));
+
+    if !enabled(&draft) {
+        println!("[Notification] 🔕 Session created notifications disabled");
+        history::record(draft, Outcome::Disabled);
+        return;
+    }
+
+    if !should_notify(session) {
+        println!("[Notification] 🔕 Skipping notification for ignored session {}", session_short);
+        history::record(draft, Outcome::Ignored);
+        return;
+    }
 
     println!("[Notification] 📢 Sending new session notification for session {} (PID: {})",
         session_short, session.pid);
 
-    let notification_result = Notification::new(&get_bundle_id())
-        .title("New Claude Session Started 🚀")
-        .body(&format!("Claude #{} has started", session.pid))
-        .show();
+    if show(draft) {
+        println!("[Notification] ✅ Session created notification sent successfully");
+    }
+}
 
-    match notification_result {
-        Ok(_) => {
-            println!("[Notification] ✅ Session created notification sent successfully");
-        }
-        Err(e) => {
-            println!("[Notification] ⚠️ Failed to send notification: {}", e);
-        }
+/// Send notification when a session turns into a zombie (offers to kill it)
+pub fn send_zombie_detected_notification(session: &SessionState) {
+    let session_short = &session.session_id[..8.min(session.session_id.len())];
+    let draft = about(NotificationKind::ZombieDetected, Some(session), None,
+        "🧟 Zombie Claude Detected", &format!("Claude #{} lost its terminal", session.pid));
+
+    if !enabled(&draft) {
+        history::record(draft, Outcome::Disabled);
+        return;
+    }
+
+    if !should_notify(session) {
+        println!("[Notification] 🔕 Skipping notification for ignored session {}", session_short);
+        history::record(draft, Outcome::Ignored);
+        return;
+    }
+
+    println!("[Notification] 📢 Sending zombie detected notification for session {} (PID: {})",
+        session_short, session.pid);
+
+    // Read-only sessions (containers, other users) can't be killed from here
+    let foreign = crate::monitor::user::is_foreign_uid(session.last_cpu_event.as_ref().and_then(|c| c.uid));
+    let actions: &[NotificationAction] = if session.container.is_some() || foreign { &[] } else { &[KILL_ACTION] };
+    show_with_actions(draft, actions);
+}
+
+/// Send notification when a session is blocked on a permission prompt (offers to focus it)
+pub fn send_approval_pending_notification(session: &SessionState, tool: Option<&str>) {
+    let session_short = &session.session_id[..8.min(session.session_id.len())];
+    let body = match tool {
+        Some(tool) => format!("Claude #{} needs permission to use {}", session.pid, tool),
+        None => format!("Claude #{} is waiting for your approval", session.pid),
+    };
+    let draft = about(NotificationKind::ApprovalPending, Some(session), None, "✋ Claude Needs Permission", &body);
+
+    if !enabled(&draft) {
+        println!("[Notification] 🔕 Approval pending notifications disabled");
+        history::record(draft, Outcome::Disabled);
+        return;
+    }
+
+    if !should_notify(session) {
+        println!("[Notification] 🔕 Skipping notification for ignored session {}", session_short);
+        history::record(draft, Outcome::Ignored);
+        return;
+    }
+
+    println!("[Notification] 📢 Sending approval pending notification for session {} (PID: {})",
+        session_short, session.pid);
+
+    if show_with_actions(draft, &[FOCUS_ACTION]) {
+        println!("[Notification] ✅ Approval pending notification sent successfully");
+    }
+}
+
+/// Send notification when a session without working hooks is blocked on a permission prompt
+pub fn send_needs_attention_notification(session: &SessionState) {
+    let session_short = &session.session_id[..8.min(session.session_id.len())];
+    let draft = about(NotificationKind::NeedsAttention, Some(session), None, "✋ Claude Is Blocked",
+        &format!("Claude #{} is waiting on a permission prompt", session.pid));
+
+    if !enabled(&draft) {
+        println!("[Notification] 🔕 Approval pending notifications disabled");
+        history::record(draft, Outcome::Disabled);
+        return;
+    }
+
+    if !should_notify(session) {
+        println!("[Notification] 🔕 Skipping notification for ignored session {}", session_short);
+        history::record(draft, Outcome::Ignored);
+        return;
     }
+
+    println!("[Notification] 📢 Sending needs attention notification for session {} (PID: {})",
+        session_short, session.pid);
+    show_with_actions(draft, &[FOCUS_ACTION]);
 }
 
 /// Send notification when zombie process is killed
 pub fn send_zombie_killed_notification(pid: u32) {
+    let draft = about(NotificationKind::ZombieKilled, None, Some(pid),
+        "✅ Zombie Process Terminated", &format!("Successfully killed zombie process #{}", pid));
+
+    if !is_enabled(NotificationKind::ZombieKilled) {
+        println!("[Notification] 🔕 Zombie killed notifications disabled");
+        history::record(draft, Outcome::Disabled);
+        return;
+    }
+
     println!("[Notification] 📢 Sending zombie killed notification for PID: {}", pid);
 
-    let notification_result = Notification::new(&get_bundle_id())
-        .title("✅ Zombie Process Terminated")
-        .body(&format!("Successfully killed zombie process #{}", pid))
-        .show();
+    if show(draft) {
+        println!("[Notification] ✅ Zombie killed notification sent successfully");
+    }
+}
 
-    match notification_result {
-        Ok(_) => {
-            println!("[Notification] ✅ Zombie killed notification sent successfully");
-        }
-        Err(e) => {
-            println!("[Notification] ⚠️ Failed to send notification: {}", e);
+/// Send notification when hooks removed from settings.json were re-registered
+pub fn send_hooks_repaired_notification(settings_path: &str) {
+    println!("[Notification] 📢 Sending hooks repaired notification for {}", settings_path);
+    show(about(NotificationKind::HooksRepaired, None, None,
+        "🔧 ClaudeMiner Hooks Restored",
+        &format!("Hooks were removed from {} and have been re-registered", settings_path)));
+}
+
+/// Warn that hooks look broken (a Hook session went silent); at most once an hour,
+/// since a wedged pipe silences every session at once
+pub fn send_hooks_stale_notification(session: &SessionState) {
+    let now = crate::session::current_timestamp();
+    {
+        let mut last = LAST_HOOKS_STALE_WARNING.lock().unwrap();
+        if last.is_some_and(|last| now.saturating_sub(last) < HOOKS_STALE_WARNING_SECS) {
+            return;
         }
+        *last = Some(now);
     }
+
+    println!("[Notification] 📢 Sending hooks stale notification for session {}", &session.session_id[..8.min(session.session_id.len())]);
+    show(about(NotificationKind::HooksStale, Some(session), None,
+        "⚠️ ClaudeMiner Hooks Silent",
+        &format!("No hook events from Claude #{} for {} min - falling back to CPU/log detection. Run Doctor to check the hooks.",
+            session.pid, now.saturating_sub(session.last_hook_at) / 60)));
 }
 
-/// Send test notification for debugging
+/// Send a notification with arbitrary text (automation rules)
+pub fn send_custom_notification(title: &str, body: &str) {
+    println!("[Notification] 📢 Sending notification: {}", title);
+    show(about(NotificationKind::Rule, None, None, title, body));
+}
+
+/// Send notification when an achievement milestone is reached
+pub fn send_milestone_notification(milestone: &Milestone) {
+    let draft = about(NotificationKind::Milestone, None, None, &milestone.title(), &milestone.body());
+    if !is_enabled(NotificationKind::Milestone) {
+        println!("[Notification] 🔕 Milestone notifications disabled");
+        history::record(draft, Outcome::Disabled);
+        return;
+    }
+    println!("[Notification] 📢 Sending milestone notification: {}", draft.title);
+    show(draft);
+}
+
+/// Send the digest of completions queued while focus mode was on
+pub fn send_focus_digest_notification(queued: &[QueuedCompletion]) {
+    let mut sessions: Vec<u32> = queued.iter().map(|q| q.pid).collect();
+    sessions.sort_unstable();
+    sessions.dedup();
+    let body = match queued.len() {
+        0 => "No tasks finished while you were focused".to_string(),
+        1 => format!("Claude #{} finished a task while you were focused", queued[0].pid),
+        count => format!("{} tasks finished in {} session(s) while you were focused", count, sessions.len()),
+    };
+    println!("[Notification] 📢 Sending focus digest: {}", body);
+    show(about(NotificationKind::FocusDigest, None, None, "🍅 Focus Session Over", &body));
+}
+
+/// Send test notification for debugging (ignores quiet hours)
 pub fn send_test_notification() {
     println!("[Notification] 🔔 Sending test notification");
+    let draft = about(NotificationKind::Test, None, None,
+        "🧪 Test Notification", "ClaudeMiner notification system is working correctly!");
 
-    let notification_result = Notification::new(&get_bundle_id())
-        .title("🧪 Test Notification")
-        .body("ClaudeMiner notification system is working correctly!")
-        .show();
-
-    match notification_result {
-        Ok(_) => {
-            println!("[Notification] ✅ Test notification sent successfully");
-        }
-        Err(e) => {
-            println!("[Notification] ⚠️ Failed to send test notification: {}", e);
-        }
+    if channel::dispatch(&draft) {
+        println!("[Notification] ✅ Test notification sent successfully");
+        history::record(draft, Outcome::Delivered);
+    } else {
+        history::record(draft, Outcome::Failed);
     }
 }
 
@@ -119,6 +476,23 @@ pub fn send_test_notification() {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_project_override() {
+        let overrides = vec![
+            ProjectNotificationOverride { project: "/work/release".to_string(), mode: NotificationOverride::Always },
+            ProjectNotificationOverride { project: "/scratch/**".to_string(), mode: NotificationOverride::Mute },
+        ];
+        assert_eq!(project_override(&overrides, "/work/release"), Some(NotificationOverride::Always));
+        assert_eq!(project_override(&overrides, "/scratch/try-1"), Some(NotificationOverride::Mute));
+        assert_eq!(project_override(&overrides, "/work/other"), None);
+
+        // A session flag wins over its project's override
+        let mut session = SessionState::new_legacy(1, "override-test".to_string());
+        session.project_path = Some("/scratch/try-1".to_string());
+        session.notification_override = Some(NotificationOverride::Always);
+        assert_eq!(override_for(&session), Some(NotificationOverride::Always));
+    }
+
     #[test]
     fn test_notification_message_format() {
         // Test that notification messages are properly formatted
@@ -0,0 +1,78 @@
+// Notification Throttle
+//
+// Working <-> resting flaps would otherwise produce a task-completion notification
+// per flap. The first completion of a session is shown right away; further ones
+// within the window are counted and shown as a single summary when it closes.
+//
+
+use once_cell::sync::Lazy;
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::thread;
+use std::time::{Duration, Instant};
+
+pub const WINDOW: Duration = Duration::from_secs(60);
+
+struct Window {
+    opened: Instant,
+    coalesced: u32,  // Notifications held back since the window opened
+}
+
+/// Session ID -> open throttle window
+static WINDOWS: Lazy<Mutex<HashMap<String, Window>>> = Lazy::new(|| Mutex::new(HashMap::new()));
+
+/// Admit a notification for a session: true = show it now; false = coalesced into the
+/// summary that `on_summary` receives (with the held-back count) when the window closes
+pub fn admit<F>(session_id: &str, on_summary: F) -> bool
+where
+    F: FnOnce(u32) + Send + 'static,
+{
+    let mut windows = WINDOWS.lock().unwrap();
+    let now = Instant::now();
+
+    match windows.get_mut(session_id) {
+        Some(window) if now.duration_since(window.opened) < WINDOW => {
+            window.coalesced += 1;
+            if window.coalesced == 1 {
+                // First held-back notification: schedule the summary for the end of the window
+                let remaining = WINDOW.saturating_sub(now.duration_since(window.opened));
+                let session_id = session_id.to_string();
+                thread::spawn(move || {
+                    thread::sleep(remaining);
+                    let coalesced = close(&session_id);
+                    if coalesced > 0 {
+                        on_summary(coalesced);
+                    }
+                });
+            }
+            false
+        }
+        _ => {
+            windows.insert(session_id.to_string(), Window { opened: now, coalesced: 0 });
+            true
+        }
+    }
+}
+
+/// Close a session's window, returning how many notifications it held back
+fn close(session_id: &str) -> u32 {
+    WINDOWS.lock().unwrap().remove(session_id).map(|w| w.coalesced).unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_repeated_notifications_are_coalesced() {
+        let id = "throttle-test-session";
+        assert!(admit(id, |_| {}));
+        assert!(!admit(id, |_| {}));
+        assert!(!admit(id, |_| {}));
+        assert_eq!(close(id), 2);
+
+        // Window closed: the next one goes through again
+        assert!(admit(id, |_| {}));
+        assert_eq!(close(id), 0);
+    }
+}
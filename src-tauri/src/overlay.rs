@@ -0,0 +1,154 @@
+// Status Overlay
+//
+// Tiny always-on-top strip (overlay.html) with the working/resting/zombie counts, meant to
+// float over fullscreen editors. Frameless, so it is dragged through start_drag. Its
+// visibility, position and click-through setting live in config.json "overlay"; like the
+// main window, the position is tracked while it moves and written when it closes or the
+// app exits.
+//
+
+use crate::config::{self, OverlayPosition, WindowBounds};
+use crate::window_state;
+use once_cell::sync::Lazy;
+use std::sync::Mutex;
+use tauri::{AppHandle, LogicalPosition, Manager, Window, WindowBuilder, WindowEvent, WindowUrl};
+
+pub const LABEL: &str = "overlay";
+
+const WIDTH: f64 = 180.0;
+const HEIGHT: f64 = 28.0;
+const SCREEN_MARGIN: f64 = 16.0;
+const MENU_BAR_HEIGHT: f64 = 40.0;
+
+/// Position seen since the last save
+static LATEST: Lazy<Mutex<Option<OverlayPosition>>> = Lazy::new(|| Mutex::new(None));
+
+/// Show the overlay if it was open when the app last quit
+pub fn restore(app: &AppHandle) {
+    if config::get().overlay.visible {
+        if let Err(e) = open(app) {
+            eprintln!("[Overlay] {}", e);
+        }
+    }
+}
+
+/// Show or close the overlay; returns whether it is now visible
+pub fn toggle(app: &AppHandle) -> Result<bool, String> {
+    let visible = match app.get_window(LABEL) {
+        Some(window) => {
+            save();
+            window.close().map_err(|e| format!("Failed to close overlay: {}", e))?;
+            false
+        }
+        None => {
+            open(app)?;
+            true
+        }
+    };
+
+    config::update(|c| c.overlay.visible = visible)
+        .map_err(|e| format!("Failed to save config: {}", e))?;
+    println!("[Overlay] {}", if visible { "Shown" } else { "Closed" });
+    Ok(visible)
+}
+
+/// Let mouse events pass through the overlay (applies right away if it is open)
+pub fn set_click_through(app: &AppHandle, enabled: bool) -> Result<(), String> {
+    if let Some(window) = app.get_window(LABEL) {
+        window.set_ignore_cursor_events(enabled).map_err(|e| e.to_string())?;
+    }
+    config::update(|c| c.overlay.click_through = enabled)
+        .map_err(|e| format!("Failed to save config: {}", e))
+}
+
+/// Move the frameless overlay with the mouse (called on mousedown)
+pub fn start_drag(window: &Window) -> Result<(), String> {
+    window.start_dragging().map_err(|e| e.to_string())
+}
+
+fn open(app: &AppHandle) -> Result<(), String> {
+    let settings = config::get().overlay;
+
+    let window = WindowBuilder::new(app, LABEL, WindowUrl::App("overlay.html".into()))
+        .title("ClaudeMiner Overlay")
+        .inner_size(WIDTH, HEIGHT)
+        .decorations(false)
+        .resizable(false)
+        .always_on_top(true)
+        .skip_taskbar(true)
+        .focused(false)
+        .visible(false)
+        .build()
+        .map_err(|e| format!("Failed to open overlay: {}", e))?;
+
+    let monitors = window_state::monitors(&window);
+    let position = settings.position
+        .filter(|p| window_state::is_on_screen(&WindowBounds { x: p.x, y: p.y, width: WIDTH, height: HEIGHT }, &monitors))
+        .or_else(|| {
+            let monitor = window.primary_monitor().ok().flatten()?;
+            let origin = monitor.position().to_logical::<f64>(monitor.scale_factor());
+            let size = monitor.size().to_logical::<f64>(monitor.scale_factor());
+            Some(default_position((origin.x, origin.y, size.width, size.height)))
+        });
+    if let Some(position) = position {
+        let _ = window.set_position(LogicalPosition::new(position.x, position.y));
+    }
+
+    if settings.click_through {
+        let _ = window.set_ignore_cursor_events(true);
+    }
+    window.show().map_err(|e| e.to_string())
+}
+
+/// Top-right corner of a monitor (x, y, width, height), below the menu bar
+fn default_position((x, y, width, _): (f64, f64, f64, f64)) -> OverlayPosition {
+    OverlayPosition {
+        x: x + width - WIDTH - SCREEN_MARGIN,
+        y: y + MENU_BAR_HEIGHT,
+    }
+}
+
+/// Window event handler for the overlay (registered next to window_state's)
+pub fn on_window_event(window: &Window, event: &WindowEvent) {
+    if window.label() != LABEL {
+        return;
+    }
+
+    match event {
+        WindowEvent::Moved(_) => remember(window),
+        WindowEvent::CloseRequested { .. } => save(),
+        _ => {}
+    }
+}
+
+fn remember(window: &Window) {
+    let Ok(scale) = window.scale_factor() else { return };
+    let Ok(position) = window.outer_position() else { return };
+    let position = position.to_logical::<f64>(scale);
+    *LATEST.lock().unwrap() = Some(OverlayPosition { x: position.x, y: position.y });
+}
+
+/// Write the tracked position to config.json (no-op when it didn't move)
+pub fn save() {
+    let Some(position) = LATEST.lock().unwrap().take() else { return };
+    if config::get().overlay.position == Some(position) {
+        return;
+    }
+    if let Err(e) = config::update(|c| c.overlay.position = Some(position)) {
+        eprintln!("[Overlay] Failed to save position: {}", e);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_position() {
+        let position = default_position((1440.0, 0.0, 1920.0, 1080.0));
+        assert_eq!(position, OverlayPosition { x: 1440.0 + 1920.0 - WIDTH - SCREEN_MARGIN, y: MENU_BAR_HEIGHT });
+
+        let bounds = WindowBounds { x: position.x, y: position.y, width: WIDTH, height: HEIGHT };
+        assert!(window_state::is_on_screen(&bounds, &[(1440.0, 0.0, 1920.0, 1080.0)]));
+    }
+}
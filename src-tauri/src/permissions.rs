@@ -0,0 +1,196 @@
+// Permissions
+//
+// What the OS lets ClaudeMiner do, so "status silently stopped updating" is diagnosable:
+// - notifications: macOS per-app "Allow Notifications" setting. Tauri v1 has no API for
+//   it (its JS isPermissionGranted is always true on desktop), so it is read from the
+//   Notification Center preferences (com.apple.ncprefs); no entry = never prompted
+// - claude_dir: can ~/.claude (and other Claude homes) be read? Without Full Disk Access
+//   macOS may refuse with "Operation not permitted"
+// Each status carries the action that fixes it (System Settings pane or re-request).
+//
+
+use crate::config;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::Path;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PermissionState {
+    Granted,
+    Denied,
+    Unknown,
+}
+
+/// What the UI can do about a permission
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PermissionAction {
+    RequestNotifications,       // Send a notification so macOS prompts
+    OpenNotificationSettings,   // System Settings > Notifications > ClaudeMiner
+    OpenFullDiskAccess,         // System Settings > Privacy & Security > Full Disk Access
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct PermissionStatus {
+    pub state: PermissionState,
+    pub detail: String,
+    pub action: Option<PermissionAction>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Permissions {
+    pub notifications: PermissionStatus,
+    pub claude_dir: PermissionStatus,
+}
+
+pub fn get() -> Permissions {
+    Permissions {
+        notifications: notifications(),
+        claude_dir: claude_dir_access(),
+    }
+}
+
+/// macOS notification permission for our bundle
+pub fn notifications() -> PermissionStatus {
+    #[cfg(target_os = "macos")]
+    {
+        match notification_flags("com.claudeminer.app") {
+            Some(flags) => notification_status(flags),
+            None => PermissionStatus {
+                state: PermissionState::Unknown,
+                detail: "Not yet asked (no Notification Center entry)".to_string(),
+                action: Some(PermissionAction::RequestNotifications),
+            },
+        }
+    }
+
+    #[cfg(not(target_os = "macos"))]
+    PermissionStatus {
+        state: PermissionState::Granted,
+        detail: "No per-app permission on this platform".to_string(),
+        action: None,
+    }
+}
+
+/// "Allow Notifications" bit of an ncprefs app entry
+const NCPREFS_ALLOW: u64 = 1 << 25;
+
+fn notification_status(flags: u64) -> PermissionStatus {
+    if flags & NCPREFS_ALLOW != 0 {
+        PermissionStatus { state: PermissionState::Granted, detail: "Allowed".to_string(), action: None }
+    } else {
+        PermissionStatus {
+            state: PermissionState::Denied,
+            detail: "Turned off in System Settings > Notifications".to_string(),
+            action: Some(PermissionAction::OpenNotificationSettings),
+        }
+    }
+}
+
+/// Flags of our entry in the Notification Center preferences
+#[cfg(target_os = "macos")]
+fn notification_flags(bundle_id: &str) -> Option<u64> {
+    let output = std::process::Command::new("defaults")
+        .args(["export", "com.apple.ncprefs", "-"])
+        .output()
+        .ok()?;
+    parse_ncprefs_flags(&String::from_utf8_lossy(&output.stdout), bundle_id)
+}
+
+/// Find `<key>flags</key><integer>N</integer>` in the app dict whose bundle-id matches
+/// (XML plist as printed by `defaults export`)
+fn parse_ncprefs_flags(plist: &str, bundle_id: &str) -> Option<u64> {
+    let marker = format!("<string>{}</string>", bundle_id);
+    let position = plist.find(&marker)?;
+
+    // The entry is the <dict> enclosing the bundle-id
+    let start = plist[..position].rfind("<dict>")?;
+    let end = position + plist[position..].find("</dict>")?;
+    let entry = &plist[start..end];
+
+    let after_key = &entry[entry.find("<key>flags</key>")?..];
+    let value_start = after_key.find("<integer>")? + "<integer>".len();
+    let value_end = after_key.find("</integer>")?;
+    after_key.get(value_start..value_end)?.trim().parse().ok()
+}
+
+/// Can every existing Claude home (and its settings.json) be read?
+pub fn claude_dir_access() -> PermissionStatus {
+    let homes: Vec<_> = config::claude_homes().into_iter().filter(|home| home.exists()).collect();
+    if homes.is_empty() {
+        return PermissionStatus {
+            state: PermissionState::Unknown,
+            detail: "No Claude home found (is Claude Code installed?)".to_string(),
+            action: None,
+        };
+    }
+
+    let denied: Vec<String> = homes.iter()
+        .filter(|home| fs::read_dir(home).is_err() || !file_readable(&home.join("settings.json")))
+        .map(|home| home.display().to_string())
+        .collect();
+
+    if denied.is_empty() {
+        PermissionStatus { state: PermissionState::Granted, detail: "Readable".to_string(), action: None }
+    } else {
+        PermissionStatus {
+            state: PermissionState::Denied,
+            detail: format!("Cannot read {}", denied.join(", ")),
+            action: cfg!(target_os = "macos").then_some(PermissionAction::OpenFullDiskAccess),
+        }
+    }
+}
+
+fn file_readable(path: &Path) -> bool {
+    !path.exists() || fs::File::open(path).is_ok()
+}
+
+/// Perform a permission action
+pub fn run_action(action: PermissionAction) -> Result<(), String> {
+    match action {
+        PermissionAction::RequestNotifications => {
+            // macOS prompts on the first notification an app posts
+            crate::notification::send_test_notification();
+            Ok(())
+        }
+        PermissionAction::OpenNotificationSettings => {
+            open_system_settings("x-apple.systempreferences:com.apple.preference.notifications?id=com.claudeminer.app")
+        }
+        PermissionAction::OpenFullDiskAccess => {
+            open_system_settings("x-apple.systempreferences:com.apple.preference.security?Privacy_AllFiles")
+        }
+    }
+}
+
+fn open_system_settings(url: &str) -> Result<(), String> {
+    #[cfg(target_os = "macos")]
+    {
+        std::process::Command::new("open")
+            .arg(url)
+            .status()
+            .map_err(|e| format!("Failed to open System Settings: {}", e))?;
+        Ok(())
+    }
+
+    #[cfg(not(target_os = "macos"))]
+    Err(format!("System Settings are only available on macOS ({})", url))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_ncprefs_flags() {
+        let plist = r#"<plist><dict><key>apps</key><array>
+            <dict><key>bundle-id</key><string>com.other.app</string><key>flags</key><integer>8454</integer></dict>
+            <dict><key>bundle-id</key><string>com.claudeminer.app</string><key>flags</key><integer>41951566</integer></dict>
+        </array></dict></plist>"#;
+
+        assert_eq!(parse_ncprefs_flags(plist, "com.claudeminer.app"), Some(41951566));
+        assert_eq!(parse_ncprefs_flags(plist, "com.missing.app"), None);
+        assert_eq!(notification_status(41951566).state, PermissionState::Granted);
+        assert_eq!(notification_status(8454).state, PermissionState::Denied);
+    }
+}
@@ -0,0 +1,120 @@
+// Persisted State
+//
+// Where the app keeps its files (config.json and the stores next to it) and JsonStore, the
+// load/save pair the JSON ones share: a missing file is empty state, an unreadable one is
+// logged and moved aside (<file>.corrupt-<time>) rather than overwritten by the next save,
+// and saves are atomic (hooks::backup::write_atomic). Tests get a fresh per-process temp
+// directory instead of the user's config directory, so the save paths run under test too.
+//
+
+use once_cell::sync::OnceCell;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::fs;
+use std::io;
+use std::marker::PhantomData;
+use std::path::{Path, PathBuf};
+
+static DATA_DIR: OnceCell<PathBuf> = OnceCell::new();
+
+/// Directory of config.json and every other file the app keeps
+pub fn data_dir() -> &'static Path {
+    DATA_DIR.get_or_init(default_data_dir)
+}
+
+#[cfg(not(test))]
+fn default_data_dir() -> PathBuf {
+    dirs::config_dir()
+        .unwrap_or_else(|| PathBuf::from("."))
+        .join("com.claudeminer.app")
+}
+
+#[cfg(test)]
+fn default_data_dir() -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("claudeminer-test-{}", std::process::id()));
+    let _ = fs::remove_dir_all(&dir);  // Left behind by an earlier run with the same PID
+    fs::create_dir_all(&dir).expect("create the test data directory");
+    dir
+}
+
+/// A JSON file holding a `T`
+pub struct JsonStore<T> {
+    label: &'static str,  // Log prefix ("Achievements")
+    path: PathBuf,
+    value: PhantomData<fn() -> T>,
+}
+
+impl<T: Serialize + DeserializeOwned> JsonStore<T> {
+    /// `file` in the data directory
+    pub fn new(label: &'static str, file: &str) -> Self {
+        Self::at(label, data_dir().join(file))
+    }
+
+    pub fn at(label: &'static str, path: PathBuf) -> Self {
+        Self { label, path, value: PhantomData }
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Read the file; None when it doesn't exist or can't be parsed (it is moved aside then)
+    pub fn load(&self) -> Option<T> {
+        let json = fs::read_to_string(&self.path).ok()?;
+        match serde_json::from_str(&json) {
+            Ok(value) => Some(value),
+            Err(e) => {
+                let file_name = self.path.file_name().unwrap_or_default().to_string_lossy();
+                let aside = self.path.with_file_name(format!("{}.corrupt-{}", file_name, crate::session::current_timestamp()));
+                eprintln!("[{}] ⚠️ {:?} is unreadable ({}), moving it to {:?}", self.label, self.path, e, aside);
+                if let Err(e) = fs::rename(&self.path, &aside) {
+                    eprintln!("[{}] Failed to move {:?} aside: {}", self.label, self.path, e);
+                }
+                None
+            }
+        }
+    }
+
+    pub fn save(&self, value: &T) -> io::Result<()> {
+        let json = serde_json::to_string_pretty(value)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        crate::hooks::backup::write_atomic(&self.path, &json)
+    }
+
+    /// Save, logging a failure (for stores whose callers can't do anything about it)
+    pub fn save_or_log(&self, value: &T) {
+        if let Err(e) = self.save(value) {
+            eprintln!("[{}] Failed to save {:?}: {}", self.label, self.path, e);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_json_store() {
+        let dir = data_dir().join("json-store");
+        let store: JsonStore<Vec<u32>> = JsonStore::at("Test", dir.join("numbers.json"));
+        assert_eq!(store.load(), None);
+
+        store.save(&vec![1, 2, 3]).unwrap();
+        assert_eq!(store.load(), Some(vec![1, 2, 3]));
+
+        // A corrupt file is kept aside and the next save starts over
+        fs::write(store.path(), "[1, 2,").unwrap();
+        assert_eq!(store.load(), None);
+        assert!(!store.path().exists());
+        let aside: Vec<_> = fs::read_dir(&dir).unwrap()
+            .filter_map(|entry| entry.ok())
+            .map(|entry| entry.file_name().to_string_lossy().into_owned())
+            .filter(|name| name.starts_with("numbers.json.corrupt-"))
+            .collect();
+        assert_eq!(aside.len(), 1);
+        assert_eq!(fs::read_to_string(dir.join(&aside[0])).unwrap(), "[1, 2,");
+
+        store.save(&vec![4]).unwrap();
+        assert_eq!(store.load(), Some(vec![4]));
+    }
+}
@@ -0,0 +1,307 @@
+// Launcher Query Interface
+//
+// Small JSON interface for launcher extensions (Raycast script commands, Alfred workflows):
+// the running app listens on <config dir>/query.sock (Unix only, owner-only permissions)
+// and answers one JSON request per line with one JSON response line:
+//   {"cmd":"list"}                                  -> sessions with status and durations
+//   {"cmd":"kill","session_id":"...","force":false} -> kill the session's process
+//   {"cmd":"focus","session_id":"..."}              -> bring its terminal forward
+// `ClaudeMiner --query list|kill <id> [--force]|focus <id>` (or a raw JSON request) sends a
+// request to the running instance and prints the response, for scripts without a socket client.
+// Kill and focus go through the same checks as the GUI commands (QueryActions from main.rs).
+//
+
+use crate::event::build_miners;
+use crate::session::{current_timestamp, SessionState, SharedSessions};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use sysinfo::System;
+
+const SOCKET_FILE: &str = "query.sock";
+
+/// Whether this instance owns the socket
+static LISTENING: AtomicBool = AtomicBool::new(false);
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "cmd", rename_all = "snake_case")]
+pub enum QueryRequest {
+    List,
+    Kill {
+        session_id: String,
+        #[serde(default)]
+        force: bool,  // Also kill sessions that are mid-task
+    },
+    Focus { session_id: String },
+}
+
+/// A session as listed to launchers
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct QuerySession {
+    pub session_id: String,
+    pub pid: u32,
+    pub name: String,
+    pub status: String,
+    pub project_path: Option<String>,
+    pub project: Option<String>,  // Last path component, for list titles
+    pub status_since: u64,
+    pub status_secs: u64,  // Time in the current status
+    pub total_working_seconds: u64,
+    pub needs_attention: bool,
+    pub has_terminal: bool,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct QueryResponse {
+    pub ok: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sessions: Option<Vec<QuerySession>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl QueryResponse {
+    fn done() -> Self {
+        Self { ok: true, ..Default::default() }
+    }
+
+    fn failed(error: impl Into<String>) -> Self {
+        Self { ok: false, error: Some(error.into()), ..Default::default() }
+    }
+}
+
+/// Session actions implemented by the app (the same paths as kill_miner / focus_session_terminal)
+#[derive(Clone, Copy)]
+pub struct QueryActions {
+    pub kill: fn(&SharedSessions, u32, bool) -> Result<(), String>,
+    pub focus: fn(&SharedSessions, &str) -> Result<(), String>,
+}
+
+pub fn socket_path() -> PathBuf {
+    crate::config::store::get_config_path().with_file_name(SOCKET_FILE)
+}
+
+/// Answer one request
+pub fn handle(request: &QueryRequest, sessions: &SharedSessions, actions: &QueryActions) -> QueryResponse {
+    match request {
+        QueryRequest::List => QueryResponse {
+            ok: true,
+            sessions: Some(list(&sessions.snapshot(), current_timestamp())),
+            error: None,
+        },
+        QueryRequest::Kill { session_id, force } => {
+            match sessions.get(session_id) {
+                Some(session) if session.pid != 0 => match (actions.kill)(sessions, session.pid, *force) {
+                    Ok(()) => QueryResponse::done(),
+                    Err(e) => QueryResponse::failed(e),
+                },
+                Some(_) => QueryResponse::failed(format!("Session {} has no process yet", session_id)),
+                None => QueryResponse::failed(format!("Unknown session: {}", session_id)),
+            }
+        }
+        QueryRequest::Focus { session_id } => match (actions.focus)(sessions, session_id) {
+            Ok(()) => QueryResponse::done(),
+            Err(e) => QueryResponse::failed(e),
+        },
+    }
+}
+
+/// Visible sessions (same filter and order as the miner list)
+fn list(sessions: &HashMap<String, SessionState>, now: u64) -> Vec<QuerySession> {
+    build_miners(sessions, &mut System::new(), false).into_iter()
+        .map(|m| QuerySession {
+            project: m.project_path.as_deref()
+                .and_then(|p| std::path::Path::new(p).file_name())
+                .map(|name| name.to_string_lossy().into_owned()),
+            status_secs: now.saturating_sub(m.status_since),
+            session_id: m.session_id,
+            pid: m.pid,
+            name: m.name,
+            status: m.status,
+            project_path: m.project_path,
+            status_since: m.status_since,
+            total_working_seconds: m.total_working_seconds,
+            needs_attention: m.needs_attention,
+            has_terminal: m.has_terminal,
+        })
+        .collect()
+}
+
+/// Request from `--query ...` command-line arguments (None when there's no --query)
+pub fn request_from_args(args: &[String]) -> Option<Result<QueryRequest, String>> {
+    let index = args.iter().position(|arg| arg == "--query")?;
+    let rest = &args[index + 1..];
+    let session_id = || rest.get(1).cloned().ok_or_else(|| format!("Usage: --query {} <session_id>", rest[0]));
+
+    Some(match rest.first().map(String::as_str) {
+        Some("list") => Ok(QueryRequest::List),
+        Some("kill") => session_id().map(|session_id| QueryRequest::Kill {
+            session_id,
+            force: rest.iter().any(|arg| arg == "--force"),
+        }),
+        Some("focus") => session_id().map(|session_id| QueryRequest::Focus { session_id }),
+        Some(json) if json.starts_with('{') => serde_json::from_str(json).map_err(|e| format!("Invalid request: {}", e)),
+        _ => Err("Usage: --query list | kill <session_id> [--force] | focus <session_id>".to_string()),
+    })
+}
+
+/// Handle `--query` on the command line: send it to the running app and print the response.
+/// Returns the process exit code, or None when there's no --query argument.
+pub fn run_cli(args: &[String]) -> Option<i32> {
+    let request = match request_from_args(args)? {
+        Ok(request) => request,
+        Err(e) => {
+            eprintln!("{}", e);
+            return Some(2);
+        }
+    };
+
+    match send(&request) {
+        Ok(response) => {
+            println!("{}", response.trim_end());
+            let ok = serde_json::from_str::<serde_json::Value>(&response)
+                .map(|v| v["ok"] == true)
+                .unwrap_or(false);
+            Some(if ok { 0 } else { 1 })
+        }
+        Err(e) => {
+            println!("{}", serde_json::to_string(&QueryResponse::failed(e)).unwrap_or_default());
+            Some(1)
+        }
+    }
+}
+
+#[cfg(unix)]
+fn send(request: &QueryRequest) -> Result<String, String> {
+    use std::io::{BufRead, BufReader, Write};
+    use std::os::unix::net::UnixStream;
+
+    let mut stream = UnixStream::connect(socket_path())
+        .map_err(|e| format!("ClaudeMiner is not running ({})", e))?;
+    let line = serde_json::to_string(request).map_err(|e| e.to_string())?;
+    writeln!(stream, "{}", line).map_err(|e| e.to_string())?;
+
+    let mut response = String::new();
+    BufReader::new(stream).read_line(&mut response).map_err(|e| e.to_string())?;
+    Ok(response)
+}
+
+#[cfg(not(unix))]
+fn send(_request: &QueryRequest) -> Result<String, String> {
+    Err("The query interface is only available on macOS and Linux".to_string())
+}
+
+/// Start the query socket server
+#[cfg(unix)]
+pub fn start_query_server(sessions: SharedSessions, actions: QueryActions) -> Option<std::thread::JoinHandle<()>> {
+    use std::os::unix::fs::PermissionsExt;
+    use std::os::unix::net::{UnixListener, UnixStream};
+
+    let path = socket_path();
+    // A socket that still accepts connections belongs to a running instance; only a dead one is stale
+    if UnixStream::connect(&path).is_ok() {
+        eprintln!("[Query] {} is in use by another instance, not listening", path.display());
+        return None;
+    }
+    let _ = std::fs::remove_file(&path);
+    let listener = match UnixListener::bind(&path) {
+        Ok(listener) => listener,
+        Err(e) => {
+            eprintln!("[Query] Failed to bind {}: {}", path.display(), e);
+            return None;
+        }
+    };
+    let _ = std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o600));
+    println!("[Query] Listening on {}", path.display());
+    LISTENING.store(true, Ordering::SeqCst);
+
+    Some(crate::crash::spawn_named("query-server", move || {
+        for stream in listener.incoming() {
+            let Ok(stream) = stream else { continue };
+            // A slow client (or a kill waiting for its tree to exit) doesn't hold up the others
+            let sessions = sessions.clone();
+            crate::crash::spawn_named("query-client", move || serve(stream, &sessions, &actions));
+        }
+    }))
+}
+
+/// Read one request from a client and write the response
+#[cfg(unix)]
+fn serve(mut stream: std::os::unix::net::UnixStream, sessions: &SharedSessions, actions: &QueryActions) {
+    use std::io::{BufRead, BufReader, Write};
+    use std::time::Duration;
+
+    let _ = stream.set_read_timeout(Some(Duration::from_secs(5)));
+    let mut line = String::new();
+    if BufReader::new(&stream).read_line(&mut line).is_err() {
+        return;
+    }
+    let response = match serde_json::from_str::<QueryRequest>(line.trim()) {
+        Ok(request) => {
+            println!("[Query] {:?}", request);
+            handle(&request, sessions, actions)
+        }
+        Err(e) => QueryResponse::failed(format!("Invalid request: {}", e)),
+    };
+    let _ = writeln!(stream, "{}", serde_json::to_string(&response).unwrap_or_default());
+}
+
+#[cfg(not(unix))]
+pub fn start_query_server(_sessions: SharedSessions, _actions: QueryActions) -> Option<std::thread::JoinHandle<()>> {
+    println!("[Query] Query socket not supported on this platform");
+    None
+}
+
+/// Remove the socket (app exit), unless it belongs to another instance
+pub fn remove_socket() {
+    if LISTENING.load(Ordering::SeqCst) {
+        let _ = std::fs::remove_file(socket_path());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+
+    fn args(line: &str) -> Vec<String> {
+        line.split_whitespace().map(str::to_string).collect()
+    }
+
+    #[test]
+    fn test_request_from_args() {
+        assert_eq!(request_from_args(&args("ClaudeMiner")), None);
+        assert_eq!(request_from_args(&args("ClaudeMiner --query list")), Some(Ok(QueryRequest::List)));
+        assert_eq!(
+            request_from_args(&args("ClaudeMiner --query kill abc --force")),
+            Some(Ok(QueryRequest::Kill { session_id: "abc".to_string(), force: true })),
+        );
+        assert_eq!(
+            request_from_args(&args(r#"ClaudeMiner --query {"cmd":"focus","session_id":"abc"}"#)),
+            Some(Ok(QueryRequest::Focus { session_id: "abc".to_string() })),
+        );
+        assert!(matches!(request_from_args(&args("ClaudeMiner --query focus")), Some(Err(_))));
+        assert!(matches!(request_from_args(&args("ClaudeMiner --query")), Some(Err(_))));
+    }
+
+    #[test]
+    fn test_handle() {
+        let sessions: SharedSessions = Arc::new(crate::session::SessionStore::default());
+        let actions = QueryActions {
+            kill: |_, pid, force| if force { Ok(()) } else { Err(format!("busy {}", pid)) },
+            focus: |_, _| Ok(()),
+        };
+
+        let response = handle(&QueryRequest::List, &sessions, &actions);
+        assert!(response.ok);
+        assert_eq!(response.sessions, Some(Vec::new()));
+
+        let kill = QueryRequest::Kill { session_id: "missing".to_string(), force: true };
+        let response = handle(&kill, &sessions, &actions);
+        assert_eq!(response.error.as_deref(), Some("Unknown session: missing"));
+
+        let focus = QueryRequest::Focus { session_id: "any".to_string() };
+        assert_eq!(handle(&focus, &sessions, &actions), QueryResponse::done());
+    }
+}
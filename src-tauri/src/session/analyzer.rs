@@ -2,47 +2,42 @@
 //
 // Analyzes Claude Code debug logs to determine working state
 
-use crate::session::finder::get_claude_debug_dir;
+use crate::session::finder::find_debug_log;
 use crate::types::WorkingState;
 use std::fs;
 use std::time::UNIX_EPOCH;
 
+/// Debug log lines written around tool calls
+const TOOL_PATTERNS: &[&str] = &["executePreToolHooks", "executePostToolHooks", "Tool execution", "PreToolUse", "PostToolUse"];
+
 /// Analyze log content to determine working state
-/// For legacy sessions, checks for "Stream started" or "compacting" patterns
+/// For legacy sessions, checks for "Stream started", tool hook or "compacting" patterns;
+/// the newest one decides: a stream after the last tool call is text generation
 /// The transition from Working → Resting is handled by mtime + CPU check in the caller
 pub fn analyze_log_content(log_content: &str) -> WorkingState {
-    let last_100_lines: Vec<&str> = log_content.lines().rev().take(100).collect();
-
-    // Check for "Stream started - received first chunk" pattern
-    // This indicates Claude is actively working on a response
-    let has_stream_started = last_100_lines.iter().any(|line| {
-        line.contains("Stream started - received first chunk")
-    });
-
-    // Check for "compacting" pattern - also indicates working
-    // Database compacting is a working operation
-    let has_compacting = last_100_lines.iter().any(|line| {
-        line.to_lowercase().contains("compacting")
-    });
-
-    if has_stream_started || has_compacting {
-        WorkingState::ActivelyWorking
-    } else {
-        // Default to Unknown - caller will determine Resting based on mtime/CPU
-        WorkingState::Unknown
+    for line in log_content.lines().rev().take(100) {
+        // Tool calls (and database compacting) are work besides the response stream
+        if TOOL_PATTERNS.iter().any(|pattern| line.contains(pattern)) || line.to_lowercase().contains("compacting") {
+            return WorkingState::ActivelyWorking;
+        }
+        // "Stream started - received first chunk": Claude is writing a response
+        if line.contains("Stream started - received first chunk") {
+            return WorkingState::GeneratingResponse;
+        }
     }
+
+    // Default to Unknown - caller will determine Resting based on mtime/CPU
+    WorkingState::Unknown
 }
 
 /// Check session activity based on log file
 /// Returns (WorkingState, log_modification_time)
 pub fn check_session_activity(session_id: &str) -> (WorkingState, u64) {
-    let debug_dir = match get_claude_debug_dir() {
-        Some(dir) => dir,
+    let log_file = match find_debug_log(session_id) {
+        Some(path) => path,
         None => return (WorkingState::Unknown, u64::MAX),
     };
 
-    let log_file = debug_dir.join(format!("{}.txt", session_id));
-
     // Get file modification time
     let mtime = if let Ok(metadata) = fs::metadata(&log_file) {
         if let Ok(modified) = metadata.modified() {
@@ -63,3 +58,20 @@ pub fn check_session_activity(session_id: &str) -> (WorkingState, u64) {
 
     (working_state, mtime)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_analyze_log_content() {
+        let writing = "[DEBUG] executePreToolHooks called for tool: Bash\n[DEBUG] Tool execution completed\n[DEBUG] Stream started - received first chunk";
+        assert!(matches!(analyze_log_content(writing), WorkingState::GeneratingResponse));
+
+        let tools = "[DEBUG] Stream started - received first chunk\n[DEBUG] executePreToolHooks called for tool: Edit";
+        assert!(matches!(analyze_log_content(tools), WorkingState::ActivelyWorking));
+
+        assert!(matches!(analyze_log_content("[DEBUG] Compacting conversation"), WorkingState::ActivelyWorking));
+        assert!(matches!(analyze_log_content("[DEBUG] Loaded settings"), WorkingState::Unknown));
+    }
+}
@@ -0,0 +1,192 @@
+// Session Archive
+//
+// Sessions resting longer than config.json "archive.idle_secs" are archived: still tracked,
+// but left out of get_miners (and the miners-updated push) unless archived ones are asked for.
+// Archived sessions are kept in <config dir>/archived_sessions.json, so they can be listed
+// and restored after the process (or the app) is gone. A session that starts working again
+// is restored automatically; restore_session restores it by hand and restarts its idle clock.
+//
+
+use crate::persist::JsonStore;
+use crate::session::SessionState;
+use crate::status::machine;
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+const ARCHIVE_FILE: &str = "archived_sessions.json";
+const MAX_ARCHIVED: usize = 200;
+
+/// An archived session (as persisted)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ArchivedSession {
+    pub session_id: String,
+    pub pid: u32,
+    pub kind: String,
+    pub project_path: Option<String>,
+    pub profile: Option<String>,
+    pub idle_since: u64,   // Resting since (Unix timestamp)
+    pub archived_at: u64,
+}
+
+#[derive(Default)]
+struct Archive {
+    sessions: Vec<ArchivedSession>,  // Oldest first
+    restored: HashMap<String, u64>,  // Session ID -> restore time (restarts the idle clock)
+}
+
+static ARCHIVE: Lazy<Mutex<Archive>> = Lazy::new(|| {
+    let sessions = store().load().unwrap_or_default();
+    Mutex::new(Archive { sessions, restored: HashMap::new() })
+});
+
+fn store() -> JsonStore<Vec<ArchivedSession>> {
+    JsonStore::new("SessionArchive", ARCHIVE_FILE)
+}
+
+fn save(archive: &Archive) {
+    store().save_or_log(&archive.sessions);
+}
+
+impl Archive {
+    fn contains(&self, session_id: &str) -> bool {
+        self.sessions.iter().any(|s| s.session_id == session_id)
+    }
+
+    fn archive(&mut self, session: &SessionState, idle_since: u64, now: u64) {
+        self.sessions.push(ArchivedSession {
+            session_id: session.session_id.clone(),
+            pid: session.pid,
+            kind: session.kind.clone(),
+            project_path: session.project_path.clone(),
+            profile: session.profile.clone(),
+            idle_since,
+            archived_at: now,
+        });
+        if self.sessions.len() > MAX_ARCHIVED {
+            self.sessions.remove(0);
+        }
+        self.restored.remove(&session.session_id);
+    }
+
+    fn restore(&mut self, session_id: &str, now: u64) -> Option<ArchivedSession> {
+        let index = self.sessions.iter().position(|s| s.session_id == session_id)?;
+        self.restored.insert(session_id.to_string(), now);
+        Some(self.sessions.remove(index))
+    }
+
+    /// Archive sessions idle for `idle_secs` (0 = never), restore archived ones that became active
+    /// `last_change` gives the time a session entered its current status
+    fn sweep(
+        &mut self,
+        sessions: &HashMap<String, SessionState>,
+        idle_secs: u64,
+        now: u64,
+        last_change: impl Fn(&str) -> Option<u64>,
+    ) -> bool {
+        let mut changed = false;
+
+        for (session_id, session) in sessions {
+            let active = matches!(session.current_status, machine::WORKING | machine::WAITING_APPROVAL);
+            if self.contains(session_id) {
+                if active {
+                    println!("[SessionArchive] Session {} is active again, restoring", short(session_id));
+                    self.restore(session_id, now);
+                    changed = true;
+                }
+                continue;
+            }
+
+            if idle_secs == 0 || session.pinned || session.current_status != machine::RESTING {
+                continue;
+            }
+            let Some(status_since) = last_change(session_id) else {
+                continue;
+            };
+            let idle_since = status_since.max(self.restored.get(session_id).copied().unwrap_or(0));
+            if now.saturating_sub(idle_since) >= idle_secs {
+                println!("[SessionArchive] Archiving session {} (resting since {})", short(session_id), idle_since);
+                self.archive(session, idle_since, now);
+                changed = true;
+            }
+        }
+
+        changed
+    }
+}
+
+fn short(session_id: &str) -> &str {
+    &session_id[..8.min(session_id.len())]
+}
+
+/// Is this session archived?
+pub fn is_archived(session_id: &str) -> bool {
+    ARCHIVE.lock().unwrap().contains(session_id)
+}
+
+/// Archived sessions, most recently archived first
+pub fn list() -> Vec<ArchivedSession> {
+    ARCHIVE.lock().unwrap().sessions.iter().rev().cloned().collect()
+}
+
+/// Take a session out of the archive (None if it isn't archived)
+pub fn restore(session_id: &str, now: u64) -> Option<ArchivedSession> {
+    let mut archive = ARCHIVE.lock().unwrap();
+    let restored = archive.restore(session_id, now)?;
+    save(&archive);
+    println!("[SessionArchive] Restored session {}", short(session_id));
+    Some(restored)
+}
+
+/// Archive idle sessions and restore active ones (run periodically by the coordinator)
+/// Returns whether the archive changed
+pub fn sweep(sessions: &HashMap<String, SessionState>, now: u64) -> bool {
+    let idle_secs = crate::config::get().archive.idle_secs;
+    let mut archive = ARCHIVE.lock().unwrap();
+    let changed = archive.sweep(sessions, idle_secs, now, crate::status::history::last_change);
+    if changed {
+        save(&archive);
+    }
+    changed
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn resting(session_id: &str) -> SessionState {
+        let mut session = SessionState::new_hook(session_id.to_string());
+        session.current_status = machine::RESTING;
+        session
+    }
+
+    #[test]
+    fn test_sweep_archives_and_restores() {
+        let mut archive = Archive::default();
+        let mut sessions = HashMap::new();
+        sessions.insert("idle".to_string(), resting("idle"));
+        sessions.insert("recent".to_string(), resting("recent"));
+        let mut pinned = resting("pinned");
+        pinned.pinned = true;
+        sessions.insert("pinned".to_string(), pinned);
+
+        let last_change = |id: &str| Some(if id == "recent" { 9_000 } else { 1_000 });
+        assert!(archive.sweep(&sessions, 3_600, 10_000, last_change));
+        assert!(archive.contains("idle"));
+        assert!(!archive.contains("recent"));
+        assert!(!archive.contains("pinned"));
+        assert_eq!(archive.sessions[0].idle_since, 1_000);
+
+        // Restoring restarts the idle clock
+        assert!(archive.restore("idle", 10_000).is_some());
+        assert!(!archive.sweep(&sessions, 3_600, 11_000, last_change));
+        assert!(archive.sweep(&sessions, 3_600, 13_600, last_change));
+        assert!(archive.contains("idle"));
+
+        // Working again: restored automatically
+        sessions.get_mut("idle").unwrap().current_status = machine::WORKING;
+        assert!(archive.sweep(&sessions, 0, 14_000, last_change));
+        assert!(!archive.contains("idle"));
+    }
+}
@@ -1,137 +1,246 @@
 // Session ID Finder
 //
-// Locates Claude Code session IDs by searching debug log files
+// Locates Claude Code session IDs by searching debug log files.
+// Each PID -> session mapping records how it was found: holding the debug log open is
+// conclusive, a mention of the PID in a log's contents is not (see PidEvidence).
 
+use serde::Serialize;
 use std::collections::HashMap;
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, SystemTime};
+
+/// PID -> session ID found in the debug logs (shared between coordinator and commands)
+pub type SessionCache = Arc<Mutex<HashMap<u32, CachedSession>>>;
+
+/// How a process was tied to a session
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PidEvidence {
+    DebugFile,    // The process holds the session's debug log open
+    HookPayload,  // A hook fired by the process reported the session ID
+    Cwd,          // The process runs in the session's project directory
+    LogMention,   // The session's debug log mentions the PID (several processes can match)
+}
 
-/// Helper function to get Claude debug directory
-pub fn get_claude_debug_dir() -> Option<PathBuf> {
-    // Try HOME environment variable (Unix/Linux/macOS)
-    if let Ok(home) = std::env::var("HOME") {
-        return Some(PathBuf::from(home).join(".claude/debug"));
+impl PidEvidence {
+    /// Strong enough to attach the PID to a session that already exists without one
+    pub fn is_conclusive(self) -> bool {
+        self != PidEvidence::LogMention
     }
+}
+
+/// A cached PID -> session mapping
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CachedSession {
+    pub session_id: String,
+    pub start_time: Option<u64>,  // Start time of the process the mapping was found for
+    pub evidence: PidEvidence,
+}
 
-    // Try USERPROFILE environment variable (Windows)
-    if let Ok(home) = std::env::var("USERPROFILE") {
-        return Some(PathBuf::from(home).join(".claude/debug"));
+impl CachedSession {
+    /// Does the mapping still belong to the process now running under this PID?
+    /// Unknown start times can't prove a reuse and keep the entry
+    fn matches(&self, start_time: Option<u64>) -> bool {
+        match (self.start_time, start_time) {
+            (Some(cached), Some(current)) => cached == current,
+            _ => true,
+        }
     }
+}
 
-    // If all else fails, return None (no hard-coded paths)
+/// Cached session of `pid`; an entry recorded for an earlier process with the same PID is dropped
+fn cached_session_id(session_cache: &mut HashMap<u32, CachedSession>, pid: u32, start_time: Option<u64>) -> Option<String> {
+    let cached = session_cache.get(&pid)?;
+    if cached.matches(start_time) {
+        return Some(cached.session_id.clone());
+    }
+    println!("[SessionFinder] PID {} was reused (cached session {} belongs to an exited process)", pid, cached.session_id);
+    session_cache.remove(&pid);
     None
 }
 
-/// Find session ID for a given PID by searching log files
-pub fn find_session_id_for_pid(pid: u32, session_cache: &mut HashMap<u32, String>) -> Option<String> {
-    use std::fs::OpenOptions;
-    use std::io::Write as IoWrite;
+/// Helper function to get the debug directories of all Claude homes
+pub fn get_claude_debug_dirs() -> Vec<PathBuf> {
+    crate::config::claude_homes()
+        .into_iter()
+        .map(|home| home.join("debug"))
+        .collect()
+}
 
-    // Check cache first
-    if let Some(session_id) = session_cache.get(&pid) {
-        return Some(session_id.clone());
+/// Claude session IDs are UUIDs; anything else (hook input is untrusted) must not reach a path
+fn is_session_file_id(session_id: &str) -> bool {
+    session_id.len() == 36 && uuid::Uuid::try_parse(session_id).is_ok()
+}
+
+/// Find the debug log of a session in any Claude home
+pub fn find_debug_log(session_id: &str) -> Option<PathBuf> {
+    if !is_session_file_id(session_id) {
+        return None;
     }
+    get_claude_debug_dirs()
+        .into_iter()
+        .map(|dir| dir.join(format!("{}.txt", session_id)))
+        .find(|path| path.exists())
+}
 
-    // Search for PID in debug log files
-    // Claude logs contain patterns like ".tmp.{PID}." in file paths
-    let debug_dir = match get_claude_debug_dir() {
-        Some(dir) => dir,
-        None => return None,
-    };
+/// Find the project transcript of a session (<claude home>/projects/<project>/<session_id>.jsonl)
+pub fn find_transcript(session_id: &str) -> Option<PathBuf> {
+    if !is_session_file_id(session_id) {
+        return None;
+    }
+    let file_name = format!("{}.jsonl", session_id);
+    crate::monitor::transcript::get_projects_dirs()
+        .iter()
+        .filter_map(|dir| fs::read_dir(dir).ok())
+        .flat_map(|projects| projects.flatten())
+        .map(|project| project.path().join(&file_name))
+        .find(|path| path.exists())
+}
+
+/// Profile of the Claude home holding a session's debug log
+pub fn find_session_profile(session_id: &str) -> Option<String> {
+    find_debug_log(session_id).and_then(|path| crate::config::profile_for_path(&path))
+}
 
-    let entries = match fs::read_dir(&debug_dir) {
-        Ok(e) => e,
-        Err(_) => return None,
+/// Find session ID for a given PID: the debug log it holds open, else a search of the log contents
+pub fn find_session_id_for_pid(pid: u32, session_cache: &mut HashMap<u32, CachedSession>) -> Option<(String, PidEvidence)> {
+    // Check cache first (unless the PID now belongs to another process)
+    let start_time = crate::session::cleaner::process_start_time(pid);
+    if let Some(session_id) = cached_session_id(session_cache, pid, start_time) {
+        let evidence = session_cache.get(&pid).map_or(PidEvidence::LogMention, |cached| cached.evidence);
+        return Some((session_id, evidence));
+    }
+
+    let debug_dirs = get_claude_debug_dirs();
+    let (session_id, evidence) = match find_open_debug_log(pid, &debug_dirs) {
+        Some(session_id) => {
+            println!("[SessionFinder] PID {} holds debug log of session {} open", pid, session_id);
+            (session_id, PidEvidence::DebugFile)
+        }
+        None => (search_debug_logs(pid, &debug_dirs, start_time)?, PidEvidence::LogMention),
     };
+    session_cache.insert(pid, CachedSession { session_id: session_id.clone(), start_time, evidence });
+    Some((session_id, evidence))
+}
 
-    let search_pattern = format!(".tmp.{}.", pid);
+/// Session of the debug log `pid` has open (/proc/<pid>/fd or lsof)
+fn find_open_debug_log(pid: u32, debug_dirs: &[PathBuf]) -> Option<String> {
+    // lsof and /proc report resolved paths; compare against both spellings of each directory
+    let dirs: Vec<PathBuf> = debug_dirs.iter()
+        .flat_map(|dir| [Some(dir.clone()), fs::canonicalize(dir).ok()])
+        .flatten()
+        .collect();
+    crate::status::file_lock::open_files(pid)
+        .iter()
+        .find_map(|path| debug_log_session_id(path, &dirs))
+}
 
-    // Debug logging (best effort, ignore errors)
-    if let Ok(mut debug_file) = OpenOptions::new()
-        .create(true)
-        .append(true)
-        .open("/tmp/claudeminer_session_debug.log")
-    {
-        let _ = writeln!(debug_file, "\n=== Searching session for PID {} ===", pid);
-        let _ = writeln!(debug_file, "Search pattern: {}", search_pattern);
-        let _ = writeln!(debug_file, "Debug dir: {:?}", debug_dir);
+/// "<debug dir>/<session_id>.txt" -> session_id
+fn debug_log_session_id(path: &Path, debug_dirs: &[PathBuf]) -> Option<String> {
+    if path.extension().and_then(|s| s.to_str()) != Some("txt") {
+        return None;
+    }
+    let parent = path.parent()?;
+    if !debug_dirs.iter().any(|dir| dir == parent) {
+        return None;
     }
+    path.file_stem().and_then(|s| s.to_str()).map(str::to_string)
+}
+
+/// Search for the PID in debug log contents (slow; for processes that don't keep their log open)
+/// Claude logs contain patterns like ".tmp.{PID}." in file paths
+fn search_debug_logs(pid: u32, debug_dirs: &[PathBuf], start_time: Option<u64>) -> Option<String> {
+    let search_pattern = format!(".tmp.{}.", pid);
+    crate::debug_log::log("SessionFinder", || format!("Searching debug logs for '{}' in {:?}", search_pattern, debug_dirs));
 
     // Collect all matching files first, then pick the most recently modified
-    let mut matching_files: Vec<(PathBuf, std::time::SystemTime)> = Vec::new();
+    let mut matching_files: Vec<(PathBuf, SystemTime)> = Vec::new();
+
+    let entries = debug_dirs.iter()
+        .filter_map(|dir| fs::read_dir(dir).ok())
+        .flat_map(|entries| entries.flatten());
 
-    for entry in entries.flatten() {
+    for entry in entries {
         let path = entry.path();
-        if path.extension().and_then(|s| s.to_str()) == Some("txt") {
-            // Use grep for faster search in large files
-            #[cfg(target_os = "macos")]
-            {
-                use std::process::Command;
-
-                let grep_result = Command::new("grep")
-                    .arg("-l")  // Only output filename
-                    .arg("-F")  // Fixed string (faster)
-                    .arg(&search_pattern)
-                    .arg(&path)
-                    .output();
-
-                if let Ok(output) = grep_result {
-                    // Debug logging
-                    if let Ok(mut debug_file) = OpenOptions::new()
-                        .create(true)
-                        .append(true)
-                        .open("/tmp/claudeminer_session_debug.log")
-                    {
-                        let _ = writeln!(debug_file, "  Checking file: {:?}", path.file_name());
-                        let _ = writeln!(debug_file, "  Grep exit code: {}", output.status.code().unwrap_or(-1));
-                        let _ = writeln!(debug_file, "  Grep success: {}", output.status.success());
-                    }
-
-                    if output.status.success() {
-                        // Found a matching file
-                        if let Ok(mut debug_file) = OpenOptions::new()
-                            .create(true)
-                            .append(true)
-                            .open("/tmp/claudeminer_session_debug.log")
-                        {
-                            let _ = writeln!(debug_file, "  ✅ MATCH FOUND in {:?}", path.file_name());
-                        }
-
-                        if let Ok(metadata) = fs::metadata(&path) {
-                            if let Ok(modified) = metadata.modified() {
-                                matching_files.push((path.clone(), modified));
-                            }
-                        }
-                    }
-                }
-            }
+        if path.extension().and_then(|s| s.to_str()) != Some("txt") {
+            continue;
+        }
 
-            #[cfg(not(target_os = "macos"))]
-            {
-                // Fallback: read entire file
-                if let Ok(content) = fs::read_to_string(&path) {
-                    if content.contains(&search_pattern) {
-                        if let Ok(metadata) = fs::metadata(&path) {
-                            if let Ok(modified) = metadata.modified() {
-                                matching_files.push((path.clone(), modified));
-                            }
-                        }
-                    }
-                }
+        // Use grep for faster search in large files
+        #[cfg(target_os = "macos")]
+        let found = std::process::Command::new("grep")
+            .arg("-q")  // Exit status only
+            .arg("-F")  // Fixed string (faster)
+            .arg(&search_pattern)
+            .arg(&path)
+            .status()
+            .is_ok_and(|status| status.success());
+
+        // Fallback: read entire file
+        #[cfg(not(target_os = "macos"))]
+        let found = fs::read_to_string(&path).is_ok_and(|content| content.contains(&search_pattern));
+
+        if found {
+            if let Ok(modified) = fs::metadata(&path).and_then(|metadata| metadata.modified()) {
+                matching_files.push((path, modified));
             }
         }
     }
 
-    // Sort by most recently modified and pick the first one
+    // Logs untouched since the process started were written by an earlier process with this PID
+    if let Some(start_time) = start_time {
+        let started = SystemTime::UNIX_EPOCH + Duration::from_secs(start_time);
+        matching_files.retain(|(_, modified)| *modified >= started);
+    }
+
+    // Most recently modified first
     matching_files.sort_by(|a, b| b.1.cmp(&a.1));
+    crate::debug_log::log("SessionFinder", || format!("{} debug log(s) mention PID {}", matching_files.len(), pid));
 
-    if let Some((path, _)) = matching_files.first() {
-        if let Some(file_name) = path.file_stem().and_then(|s| s.to_str()) {
-            let session_id = file_name.to_string();
-            session_cache.insert(pid, session_id.clone());
-            return Some(session_id);
-        }
+    let (path, _) = matching_files.first()?;
+    path.file_stem().and_then(|s| s.to_str()).map(str::to_string)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cached_session_invalidated_on_pid_reuse() {
+        let mut cache = HashMap::new();
+        cache.insert(4242, CachedSession { session_id: "old-session".to_string(), start_time: Some(1_000), evidence: PidEvidence::DebugFile });
+
+        assert_eq!(cached_session_id(&mut cache, 4242, Some(1_000)).as_deref(), Some("old-session"));
+        // Start time unknown (process just exited): keep the mapping
+        assert_eq!(cached_session_id(&mut cache, 4242, None).as_deref(), Some("old-session"));
+
+        // Same PID, newer process
+        assert_eq!(cached_session_id(&mut cache, 4242, Some(2_000)), None);
+        assert!(cache.is_empty());
+        assert_eq!(cached_session_id(&mut cache, 7, Some(2_000)), None);
     }
 
-    None
+    #[test]
+    fn test_session_file_ids() {
+        assert!(is_session_file_id("0b7c2f1e-4d3a-4b5c-9e8f-1a2b3c4d5e6f"));
+        assert!(!is_session_file_id("../../.ssh/id_rsa"));
+        assert!(!is_session_file_id("0b7c2f1e-4d3a-4b5c-9e8f-1a2b3c4d5e6f/../x"));
+        assert!(!is_session_file_id("{0b7c2f1e-4d3a-4b5c-9e8f-1a2b3c4d5e6f}"));
+        assert!(!is_session_file_id("claude-pid-4242"));
+        assert_eq!(find_debug_log("../../etc/passwd"), None);
+        assert_eq!(find_transcript("../settings"), None);
+    }
+
+    #[test]
+    fn test_debug_log_session_id() {
+        let dirs = vec![PathBuf::from("/Users/me/.claude/debug")];
+        let session = |path: &str| debug_log_session_id(Path::new(path), &dirs);
+
+        assert_eq!(session("/Users/me/.claude/debug/0b7c2f1e-aaaa.txt").as_deref(), Some("0b7c2f1e-aaaa"));
+        assert_eq!(session("/Users/me/.claude/debug/latest.log"), None);
+        assert_eq!(session("/Users/me/.claude/projects/x/0b7c2f1e-aaaa.txt"), None);
+        assert_eq!(session("/dev/ttys003"), None);
+    }
 }
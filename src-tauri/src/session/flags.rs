@@ -0,0 +1,86 @@
+// Session Flags
+//
+// User-assigned pin/ignore flags and notification overrides. Kept outside the coordinator so commands can
+// change them at any time; the coordinator copies them onto SessionState on every sync
+// (along with the archived flag, see archive.rs). Commands that change one send it a
+// FlagsChanged event so the change is published right away.
+//
+
+use crate::config::NotificationOverride;
+use crate::session::SessionState;
+use once_cell::sync::Lazy;
+use std::collections::{HashMap, HashSet};
+use std::sync::Mutex;
+
+#[derive(Default)]
+struct SessionFlags {
+    pinned: HashSet<String>,
+    ignored: HashSet<String>,
+    notifications: HashMap<String, NotificationOverride>,
+}
+
+static FLAGS: Lazy<Mutex<SessionFlags>> = Lazy::new(|| Mutex::new(SessionFlags::default()));
+
+/// Pin or unpin a session (pinned sessions sort first and always notify)
+pub fn set_pinned(session_id: &str, pinned: bool) {
+    let mut flags = FLAGS.lock().unwrap();
+    if pinned {
+        flags.pinned.insert(session_id.to_string());
+    } else {
+        flags.pinned.remove(session_id);
+    }
+}
+
+/// Ignore or un-ignore a session (ignored sessions are tracked but hidden)
+pub fn set_ignored(session_id: &str, ignored: bool) {
+    let mut flags = FLAGS.lock().unwrap();
+    if ignored {
+        flags.ignored.insert(session_id.to_string());
+    } else {
+        flags.ignored.remove(session_id);
+    }
+}
+
+/// Mute a session's notifications, always notify for it, or go back to the defaults (None)
+pub fn set_notification_override(session_id: &str, mode: Option<NotificationOverride>) {
+    let mut flags = FLAGS.lock().unwrap();
+    match mode {
+        Some(mode) => flags.notifications.insert(session_id.to_string(), mode),
+        None => flags.notifications.remove(session_id),
+    };
+}
+
+/// Copy the current flags onto a session
+pub fn apply(session: &mut SessionState) {
+    let flags = FLAGS.lock().unwrap();
+    session.pinned = flags.pinned.contains(&session.session_id);
+    session.ignored = flags.ignored.contains(&session.session_id);
+    session.notification_override = flags.notifications.get(&session.session_id).copied();
+    session.archived = crate::session::archive::is_archived(&session.session_id);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_apply_flags() {
+        let mut session = SessionState::new_legacy(1, "flags-test-session".to_string());
+
+        set_pinned("flags-test-session", true);
+        set_ignored("flags-test-session", true);
+        set_notification_override("flags-test-session", Some(NotificationOverride::Mute));
+        apply(&mut session);
+        assert!(session.pinned);
+        assert!(session.ignored);
+        assert_eq!(session.notification_override, Some(NotificationOverride::Mute));
+
+        set_pinned("flags-test-session", false);
+        set_ignored("flags-test-session", false);
+        set_notification_override("flags-test-session", None);
+        apply(&mut session);
+        assert!(!session.pinned);
+        assert!(!session.ignored);
+        assert_eq!(session.notification_override, None);
+    }
+}
@@ -0,0 +1,187 @@
+// Session Ignore Rules
+//
+// Compiles the config's ignore list (glob on cwd, regex on command line)
+// and matches Claude processes against it
+//
+
+use crate::config::{self, IgnoreConfig};
+use crate::session::SessionState;
+use once_cell::sync::Lazy;
+use regex::Regex;
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+use sysinfo::{Pid, Process, ProcessRefreshKind, System, UpdateKind};
+
+/// Compiled ignore rules
+pub struct IgnoreRules {
+    cwd_globs: Vec<glob::Pattern>,
+    command_patterns: Vec<Regex>,
+}
+
+/// Compiled rules cache (source config, rules), rebuilt when the config changes
+type CompiledRules = Option<(IgnoreConfig, Arc<IgnoreRules>)>;
+static COMPILED: Lazy<Mutex<CompiledRules>> = Lazy::new(|| Mutex::new(None));
+
+impl IgnoreRules {
+    /// Compile rules, rejecting the first invalid pattern
+    pub fn compile(config: &IgnoreConfig) -> Result<Self, String> {
+        let cwd_globs = config.cwd_globs.iter()
+            .map(|p| glob::Pattern::new(p).map_err(|e| format!("Invalid cwd glob '{}': {}", p, e)))
+            .collect::<Result<Vec<_>, _>>()?;
+        let command_patterns = config.command_patterns.iter()
+            .map(|p| Regex::new(p).map_err(|e| format!("Invalid command pattern '{}': {}", p, e)))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(IgnoreRules { cwd_globs, command_patterns })
+    }
+
+    /// Compile rules, skipping (and logging) invalid patterns
+    fn compile_lenient(config: &IgnoreConfig) -> Self {
+        let cwd_globs = config.cwd_globs.iter()
+            .filter_map(|p| glob::Pattern::new(p)
+                .map_err(|e| eprintln!("[IgnoreRules] ⚠️ Skipping invalid cwd glob '{}': {}", p, e))
+                .ok())
+            .collect();
+        let command_patterns = config.command_patterns.iter()
+            .filter_map(|p| Regex::new(p)
+                .map_err(|e| eprintln!("[IgnoreRules] ⚠️ Skipping invalid command pattern '{}': {}", p, e))
+                .ok())
+            .collect();
+
+        IgnoreRules { cwd_globs, command_patterns }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cwd_globs.is_empty() && self.command_patterns.is_empty()
+    }
+
+    /// Check a working directory / command line against the rules
+    pub fn matches(&self, cwd: Option<&Path>, command: &str) -> bool {
+        if let Some(cwd) = cwd {
+            if self.cwd_globs.iter().any(|g| g.matches_path(cwd)) {
+                return true;
+            }
+        }
+        self.command_patterns.iter().any(|r| r.is_match(command))
+    }
+
+    /// Check a process (needs cwd and cmd refreshed)
+    pub fn matches_process(&self, process: &Process) -> bool {
+        self.matches(process.cwd(), &process.cmd().join(" "))
+    }
+
+    /// Check a PID, refreshing only that process
+    pub fn matches_pid(&self, pid: u32) -> bool {
+        if self.is_empty() || pid == 0 {
+            return false;
+        }
+
+        let mut sys = System::new();
+        let pid = Pid::from_u32(pid);
+        sys.refresh_process_specifics(pid, process_refresh_kind());
+        sys.process(pid)
+            .map(|p| self.matches_process(p))
+            .unwrap_or(false)
+    }
+}
+
+/// Refresh kind that loads what the rules need
+pub fn process_refresh_kind() -> ProcessRefreshKind {
+    ProcessRefreshKind::new()
+        .with_cwd(UpdateKind::OnlyIfNotSet)
+        .with_cmd(UpdateKind::OnlyIfNotSet)
+}
+
+/// Get the rules for the current config
+pub fn current() -> Arc<IgnoreRules> {
+    let config = config::get().ignore;
+    let mut compiled = COMPILED.lock().unwrap();
+
+    match compiled.as_ref() {
+        Some((source, rules)) if *source == config => rules.clone(),
+        _ => {
+            let rules = Arc::new(IgnoreRules::compile_lenient(&config));
+            if !rules.is_empty() {
+                println!("[IgnoreRules] Loaded {} cwd globs, {} command patterns",
+                    rules.cwd_globs.len(), rules.command_patterns.len());
+            }
+            *compiled = Some((config, rules.clone()));
+            rules
+        }
+    }
+}
+
+/// Coordinator-side record of sessions excluded by ignore rules
+#[derive(Default)]
+pub struct IgnoredSessions {
+    session_ids: HashSet<String>,
+    checked_pids: HashMap<u32, bool>,  // PID -> matched (each PID is inspected once)
+    rules: Option<Arc<IgnoreRules>>,
+}
+
+impl IgnoredSessions {
+    pub fn contains(&self, session_id: &str) -> bool {
+        self.session_ids.contains(session_id)
+    }
+
+    /// Remove sessions whose process matches the current rules
+    /// Returns the removed session IDs
+    pub fn prune(&mut self, sessions: &mut HashMap<String, SessionState>) -> Vec<String> {
+        let rules = current();
+
+        // Rules changed: forget previous decisions so sessions are re-evaluated
+        if !self.rules.as_ref().is_some_and(|r| Arc::ptr_eq(r, &rules)) {
+            self.session_ids.clear();
+            self.checked_pids.clear();
+            self.rules = Some(rules.clone());
+        }
+
+        if rules.is_empty() {
+            return Vec::new();
+        }
+
+        let checked_pids = &mut self.checked_pids;
+        let matched: Vec<String> = sessions.iter()
+            .filter(|(_, s)| s.pid != 0 && s.container.is_none())
+            .filter(|(_, s)| *checked_pids.entry(s.pid).or_insert_with(|| rules.matches_pid(s.pid)))
+            .map(|(id, _)| id.clone())
+            .collect();
+
+        for id in matched.iter() {
+            sessions.remove(id);
+            self.session_ids.insert(id.clone());
+        }
+        self.checked_pids.retain(|pid, matched| *matched || sessions.values().any(|s| s.pid == *pid));
+
+        matched
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_matches() {
+        let rules = IgnoreRules::compile(&IgnoreConfig {
+            cwd_globs: vec!["/tmp/ci-*/**".to_string()],
+            command_patterns: vec![r"--sandbox\b".to_string()],
+        }).unwrap();
+
+        assert!(rules.matches(Some(Path::new("/tmp/ci-1234/repo")), "claude"));
+        assert!(rules.matches(Some(Path::new("/Users/me/project")), "claude --sandbox"));
+        assert!(!rules.matches(Some(Path::new("/Users/me/project")), "claude"));
+        assert!(!rules.matches(None, "claude --sandboxed"));
+    }
+
+    #[test]
+    fn test_compile_rejects_invalid_patterns() {
+        let config = IgnoreConfig {
+            cwd_globs: vec!["/tmp/[".to_string()],
+            command_patterns: vec!["(".to_string()],
+        };
+        assert!(IgnoreRules::compile(&config).is_err());
+        assert!(IgnoreRules::compile_lenient(&config).is_empty());
+    }
+}
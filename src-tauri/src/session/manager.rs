@@ -1,370 +1,842 @@
 // Session Manager
 //
-// Manages session state and lifecycle, separated from Coordinator
-// Handles Legacy/Hook session logic and state transitions
+// Owns all session state: the working map mutated by event handlers, the PID index,
+// and publishing snapshots to the session store read by commands, the publisher and the cleaner.
+// Handlers only change state and report what happened (SessionUpdateResult);
+// the coordinator routes events here and turns results into UI events and notifications.
+//
+// Every change stamps the session with a new generation. Removals decided from an older
+// snapshot (the cleaner) only apply if the generation still matches, and removed sessions
+// leave a tombstone so events queued before the removal don't bring them back.
 //
 
-use crate::session::{SessionState, SessionType, LogEvent, CpuEvent, HookEvent, current_timestamp};
-use crate::types::WorkingState;
-use crate::status::hybrid::is_zombie_by_tty;
+use crate::session::{is_temporary_session_id, SessionState, SessionType, LogEvent, CpuEvent, HookEvent, ApprovalEvent, NetworkEvent, ProcessExitEvent, SessionRemoval, SharedSessions, current_timestamp};
+use crate::session::cleaner::{is_process_alive, process_cwd, process_start_time};
+use crate::session::finder::{find_session_id_for_pid, find_session_profile, PidEvidence, SessionCache};
+use crate::session::ignore::IgnoredSessions;
+use crate::status::history;
+use crate::status::hybrid::{is_zombie_by_tty, get_tty};
+use crate::status::machine::{self, StatusInputs};
+use crate::status::multiplexer::detect_multiplexer;
+use crate::monitor::matcher::CLAUDE_KIND;
+use crate::terminal;
+use crate::types::SessionStatus;
 use std::collections::HashMap;
-use std::sync::{Arc, Mutex};
-// use sysinfo::{System, Pid}; // Unused
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
 
-const STALE_MTIME_THRESHOLD_SECS: u64 = 30;  // mtime older than 30s = stale
-const LOW_CPU_THRESHOLD: f32 = 20.0;          // CPU < 20% = likely not working
-const HIGH_CPU_THRESHOLD: f32 = 50.0;         // CPU > 50% = likely working
-const CPU_AGE_THRESHOLD_SECS: u64 = 10;       // CPU data older than 10s = stale
+/// How long a removed session's ID stays tombstoned
+const TOMBSTONE_SECS: u64 = 600;
 
 /// Session Manager - Manages all session states and transitions
 pub struct SessionManager {
-    sessions: Arc<Mutex<HashMap<String, SessionState>>>,
-    pid_to_session: Arc<Mutex<HashMap<u32, String>>>,
+    sessions: HashMap<String, SessionState>,
+    pid_to_session: HashMap<u32, String>,
+    shared_sessions: SharedSessions,  // Published snapshots (get_miners, cleaner)
+    session_cache: SessionCache,  // PID -> session ID from debug files
     app_start_time: u64,
+    generation: u64,  // Last generation handed out
+    tombstones: HashMap<String, u64>,  // Removed session ID -> removal time
+    process_cwds: HashMap<u32, Option<PathBuf>>,  // Working directories looked up for adoption checks
 }
 
 impl SessionManager {
-    /// Create a new session manager
+    /// Create a session manager with its own (unshared) maps
     pub fn new() -> Self {
+        Self::with_shared_sessions(Arc::default(), Arc::default())
+    }
+
+    /// Create a session manager publishing to `shared_sessions`
+    pub fn with_shared_sessions(
+        shared_sessions: SharedSessions,
+        session_cache: SessionCache,
+    ) -> Self {
         Self {
-            sessions: Arc::new(Mutex::new(HashMap::new())),
-            pid_to_session: Arc::new(Mutex::new(HashMap::new())),
+            sessions: HashMap::new(),
+            pid_to_session: HashMap::new(),
+            shared_sessions,
+            session_cache,
             app_start_time: current_timestamp(),
+            generation: 0,
+            tombstones: HashMap::new(),
+            process_cwds: HashMap::new(),
         }
     }
 
     /// Get shared sessions reference (for other threads)
-    pub fn get_shared_sessions(&self) -> Arc<Mutex<HashMap<String, SessionState>>> {
-        self.sessions.clone()
+    pub fn get_shared_sessions(&self) -> SharedSessions {
+        self.shared_sessions.clone()
+    }
+
+    /// Current sessions (not yet published)
+    pub fn sessions(&self) -> &HashMap<String, SessionState> {
+        &self.sessions
     }
 
     /// Get all sessions snapshot
     pub fn get_all_sessions(&self) -> HashMap<String, SessionState> {
-        self.sessions.lock().unwrap().clone()
+        self.sessions.clone()
     }
 
     /// Get session by ID
     pub fn get_session(&self, session_id: &str) -> Option<SessionState> {
-        self.sessions.lock().unwrap().get(session_id).cloned()
+        self.sessions.get(session_id).cloned()
     }
 
     /// Get session by PID
     pub fn get_session_by_pid(&self, pid: u32) -> Option<SessionState> {
-        let pid_map = self.pid_to_session.lock().unwrap();
-        if let Some(session_id) = pid_map.get(&pid) {
-            self.get_session(session_id)
-        } else {
-            None
-        }
+        self.pid_to_session.get(&pid).and_then(|session_id| self.get_session(session_id))
     }
 
-    /// Process log event
-    pub fn handle_log_event(&self, event: LogEvent) -> SessionUpdateResult {
-        let session_id = event.session_id.clone();
+    /// Process log event (debug logs and project transcripts)
+    pub fn handle_log_event(&mut self, log_event: LogEvent) -> SessionUpdateResult {
+        let session_id = log_event.session_id.clone();
         let mut result = SessionUpdateResult::default();
 
-        // Lock sessions
-        let mut sessions = self.sessions.lock().unwrap();
-        let mut pid_map = self.pid_to_session.lock().unwrap();
+        println!("[SessionManager] handle_log_event: session={}, pid={:?}", short(&session_id), log_event.pid);
 
-        // Check if this is a new session
-        let is_new = !sessions.contains_key(&session_id);
-        result.is_new_session = is_new;
+        // A temporary (PID-only) session of the same process is folded into this one
+        let temp_id = match log_event.pid {
+            Some(pid) if !self.sessions.contains_key(&session_id) => self.pid_to_session.get(&pid)
+                .filter(|temp_id| is_temporary_session_id(temp_id))
+                .cloned(),
+            _ => None,
+        };
 
-        // Get or create session (Legacy type from log)
-        let session = sessions.entry(session_id.clone()).or_insert_with(|| {
-            let pid = event.pid.unwrap_or(0);
-            println!("[SessionManager] Creating LEGACY session from log: {} (PID: {})",
-                &session_id[..8.min(session_id.len())], pid);
+        // Check if PID is dead before creating/updating session
+        if let Some(pid) = log_event.pid {
+            if pid != 0 && !is_process_alive(pid) {
+                println!("[SessionManager] ⚠️ Ignoring log event for dead process: PID {} (session: {})",
+                    pid, short(&session_id));
+                return result;
+            }
+        }
+
+        // Check if this is a new session
+        let is_new_session = !self.sessions.contains_key(&session_id);
+        if is_new_session && self.is_tombstoned(&session_id, log_event.timestamp) {
+            return result;
+        }
 
-            if pid != 0 {
-                pid_map.insert(pid, session_id.clone());
+        // Create the session (Legacy type - from log files)
+        if is_new_session {
+            let pid = log_event.pid.unwrap_or(0);
+            println!("[SessionManager] Creating LEGACY session {} with PID {}", short(&session_id), pid);
+            self.sessions.insert(session_id.clone(), SessionState::new_legacy(pid, session_id.clone()));
+            if let Some(temp_id) = temp_id {
+                self.absorb_session(&temp_id, &session_id);
+                println!("[SessionManager] Merged temporary session {} into {} (PID {})", temp_id, short(&session_id), pid);
             }
+        }
+        let Some(session) = self.sessions.get_mut(&session_id) else {
+            return result;
+        };
 
-            SessionState::new_legacy(pid, session_id.clone())
-        });
+        // Check if existing session has a dead PID (prevents zombie resurrection)
+        // Don't remove the session, just skip updating it to prevent resurrection
+        if session.pid != 0 && !is_process_alive(session.pid) {
+            println!("[SessionManager] ⚠️ Existing session has dead PID: {} (session: {}), skipping update",
+                session.pid, short(&session_id));
+            return result;  // Skip update but keep session for cleanup later
+        }
 
-        // Update session with log event
-        session.last_log_event = Some(event.clone());
-        session.last_update = current_timestamp();
+        // Tag the session with the profile whose directory the file is in
+        if log_event.profile.is_some() {
+            session.profile = log_event.profile.clone();
+        }
 
-        // Determine if this is a legacy session (created before app start)
-        if session.session_type == SessionType::Legacy {
-            // Check TTY status
-            if session.pid != 0 {
-                session.has_terminal = !is_zombie_by_tty(session.pid);
+        // Transcript details: accumulate token usage, learn the project directory
+        if let Some(ref stats) = log_event.transcript {
+            session.token_usage.input_tokens += stats.input_tokens;
+            session.token_usage.output_tokens += stats.output_tokens;
+            if session.project_path.is_none() {
+                session.project_path = stats.cwd.clone();
+            }
+            if !stats.tool_calls.is_empty() {
+                println!("[SessionManager] Transcript for session {}: tools={:?}", short(&session_id), stats.tool_calls);
             }
         }
 
-        // Decide new status
-        let old_status = session.current_status;
-        let new_status = self.decide_session_status(session);
+        println!("[SessionManager] Log event for session {}: state={:?}, approval_pending={}",
+            short(&session_id), log_event.state, log_event.has_approval_pending);
 
+        // Update log event (a log write is activity, whatever it says)
+        session.mark_active(log_event.file_mtime);
+        session.last_log_event = Some(log_event);
+        session.last_update = current_timestamp();
+
+        // Decide new status (only update if changed)
+        let old_status = session.current_status;
+        let new_status = decide_status(session);
         if new_status != old_status {
             println!("[SessionManager] Session {} status change: {} -> {}",
-                &session_id[..8.min(session_id.len())], old_status, new_status);
+                short(&session_id), old_status, new_status);
             session.current_status = new_status;
-            result.status_changed = true;
-            result.new_status = Some(new_status.to_string());
+            result.status_change(old_status, new_status);
+            flag_stale_hooks(session, &mut result);
         }
 
+        result.is_new_session = is_new_session && session.pid != 0;
+        refresh_attention(session, &mut result);
+        stamp(&mut self.generation, session);
         result.session = session.clone();
         result
     }
 
     /// Process CPU event
-    pub fn handle_cpu_event(&self, event: CpuEvent) -> SessionUpdateResult {
+    pub fn handle_cpu_event(&mut self, cpu_event: CpuEvent) -> SessionUpdateResult {
+        if cpu_event.container.is_some() {
+            return self.handle_container_cpu_event(cpu_event);
+        }
+
         let mut result = SessionUpdateResult::default();
-        let mut sessions = self.sessions.lock().unwrap();
-        let mut pid_map = self.pid_to_session.lock().unwrap();
-
-        // Find session by PID
-        let session_id = if let Some(sid) = pid_map.get(&event.pid).cloned() {
-            sid
-        } else {
-            // Create temporary session for unknown PID
-            let temp_id = format!("pid-{}", event.pid);
-            println!("[SessionManager] Creating temporary session for PID {}", event.pid);
-
-            sessions.insert(
-                temp_id.clone(),
-                SessionState::new_legacy(event.pid, temp_id.clone())
-            );
-            pid_map.insert(event.pid, temp_id.clone());
-            temp_id
+
+        let Some(session_id) = self.pid_to_session.get(&cpu_event.pid).cloned() else {
+            return self.handle_unknown_pid_cpu_event(cpu_event);
+        };
+        let Some(session) = self.sessions.get_mut(&session_id) else {
+            return result;
         };
 
-        // Update session
-        if let Some(session) = sessions.get_mut(&session_id) {
-            session.last_cpu_event = Some(event.clone());
-            session.last_update = current_timestamp();
+        println!("[SessionManager] CPU event for session {}: pid={}, cpu={:.1}%",
+            short(&session_id), cpu_event.pid, cpu_event.cpu_percent);
+
+        session.last_cpu_event = Some(cpu_event.clone());
+        session.last_update = current_timestamp();
+
+        // Update PID if it was placeholder
+        if session.pid == 0 {
+            session.pid = cpu_event.pid;
+        }
+
+        // Record the controlling terminal (used by focus_session_terminal)
+        if session.tty.is_none() {
+            session.tty = get_tty(cpu_event.pid);
+        }
+
+        // tmux/screen sessions survive detach, so they're never TTY zombies
+        session.multiplexer = detect_multiplexer(cpu_event.pid);
 
-            // Update PID if needed
-            if session.pid == 0 {
-                session.pid = event.pid;
-                pid_map.insert(event.pid, session_id.clone());
+        // Update last active timestamp if CPU is high
+        if cpu_event.cpu_percent > 1.0 {
+            session.mark_active(current_timestamp());
+        }
+
+        // Check TTY for zombie detection (Legacy sessions only)
+        if session.session_type == SessionType::Legacy {
+            let is_zombie = session.multiplexer.is_none() && is_zombie_by_tty(cpu_event.pid);
+            let has_tty = !is_zombie;
+
+            // Debug output for TTY status
+            if is_zombie {
+                println!("[SessionManager]   TTY check: pid={} is ZOMBIE (TTY='?' or '??')", cpu_event.pid);
+            }
+
+            let tty_changed = session.has_terminal != has_tty;
+            if tty_changed {
+                println!("[SessionManager]   TTY changed: {} -> {} (pid={}, is_zombie={})",
+                    session.has_terminal, has_tty, cpu_event.pid, is_zombie);
+                session.has_terminal = has_tty;
             }
 
-            // Check TTY for zombie detection (Legacy sessions only)
-            if session.session_type == SessionType::Legacy {
-                let has_tty = !is_zombie_by_tty(event.pid);
-                if session.has_terminal != has_tty {
-                    session.has_terminal = has_tty;
+            // Became zombie (or wasn't marked yet): force status update immediately,
+            // and let the cleaner check whether the process is actually dead
+            if is_zombie && (tty_changed || session.current_status != machine::ZOMBIE) {
+                println!("[SessionManager]   Session became zombie due to TTY loss (pid={})", cpu_event.pid);
+                session.current_status = machine::ZOMBIE;
+                result.became_zombie = true;
+            }
+        }
 
-                    // Re-evaluate status if TTY changed
-                    let old_status = session.current_status;
-                    let new_status = self.decide_session_status(session);
+        // Check for idle detection on CPU events (and CPU evidence for Hook sessions whose hooks went silent)
+        let legacy_working = session.current_status == machine::WORKING && session.session_type == SessionType::Legacy;
+        let stale_hook = session.session_type == SessionType::Hook && hooks_stale(session, current_timestamp());
+        if legacy_working || stale_hook {
+            let old_status = session.current_status;
+            let new_status = decide_status(session);
+
+            if new_status != old_status {
+                println!("[SessionManager] Session {} status change (CPU): {} -> {}",
+                    short(&session_id), old_status, new_status);
+                session.current_status = new_status;
+                result.status_change(old_status, new_status);
+                flag_stale_hooks(session, &mut result);
+            }
+        }
+
+        if let Some(old_status) = apply_multiplexer_status(session) {
+            result.status_change(old_status, session.current_status);
+        }
+
+        refresh_attention(session, &mut result);
+        stamp(&mut self.generation, session);
+        result.session = session.clone();
+        result
+    }
+
+    /// CPU event for a PID without a session yet
+    fn handle_unknown_pid_cpu_event(&mut self, cpu_event: CpuEvent) -> SessionUpdateResult {
+        let mut result = SessionUpdateResult::default();
+
+        // Unknown PID - try to find real session ID first
+        println!("[SessionManager] CPU event for unknown PID: {}, cpu={:.1}%",
+            cpu_event.pid, cpu_event.cpu_percent);
 
-                    if new_status != old_status {
-                        session.current_status = new_status;
-                        result.status_changed = true;
-                        result.new_status = Some(new_status.to_string());
+        // Other AI CLIs have no Claude debug logs: track them by PID alone
+        if cpu_event.kind != CLAUDE_KIND {
+            let session_id = format!("{}-pid-{}", cpu_event.kind, cpu_event.pid);
+            if self.is_tombstoned(&session_id, cpu_event.timestamp) {
+                return result;
+            }
+            println!("[SessionManager] Creating {} session: {}", cpu_event.kind, session_id);
+
+            let mut session = SessionState::new_legacy(cpu_event.pid, session_id.clone());
+            session.kind = cpu_event.kind.clone();
+            session.tty = get_tty(cpu_event.pid);
+            session.has_terminal = !is_zombie_by_tty(cpu_event.pid);
+            session.last_cpu_event = Some(cpu_event.clone());
+            session.current_status = decide_status(&session);
+            stamp(&mut self.generation, &mut session);
+
+            result.is_new_session = true;
+            result.session = session.clone();
+            self.pid_to_session.insert(cpu_event.pid, session_id.clone());
+            self.sessions.insert(session_id, session);
+            return result;
+        }
+
+        // Try to find session ID from debug files
+        let Some((session_id, evidence)) = find_session_id_for_pid(cpu_event.pid, &mut self.session_cache.lock().unwrap()) else {
+            // No session ID found - just log and ignore
+            println!("[SessionManager] No session ID found for PID {}, ignoring CPU event", cpu_event.pid);
+            return result;
+        };
+        println!("[SessionManager] Found real session ID {} for PID {} ({:?})", session_id, cpu_event.pid, evidence);
+        if !self.sessions.contains_key(&session_id) && self.is_tombstoned(&session_id, cpu_event.timestamp) {
+            return result;
+        }
+
+        // An existing session only takes this PID on corroborated evidence
+        // (a process's working directory doesn't change: look it up once per PID)
+        let mut evidence = evidence;
+        if let Some(existing) = self.sessions.get(&session_id) {
+            let cwds = &mut self.process_cwds;
+            let cwd = || cwds.entry(cpu_event.pid).or_insert_with(|| process_cwd(cpu_event.pid)).clone();
+            match check_adoption(existing, cpu_event.pid, evidence, cwd) {
+                Ok(PidEvidence::Cwd) if evidence != PidEvidence::Cwd => {
+                    evidence = PidEvidence::Cwd;
+                    if let Some(cached) = self.session_cache.lock().unwrap().get_mut(&cpu_event.pid) {
+                        cached.evidence = PidEvidence::Cwd;
                     }
                 }
+                Ok(_) => {}
+                Err(reason) => {
+                    crate::debug_log::log("SessionManager", || format!("Not attaching PID {} to session {}: {}", cpu_event.pid, short(&session_id), reason));
+                    return result;
+                }
             }
+        }
 
-            result.session = session.clone();
+        // Get or create session for this PID (Legacy type - discovered from CPU)
+        let session = self.sessions.entry(session_id.clone()).or_insert_with(|| {
+            println!("[SessionManager] Creating LEGACY session: {}", session_id);
+            let mut new_session = SessionState::new_legacy(cpu_event.pid, session_id.clone());
+            // Set initial status based on current state
+            new_session.current_status = machine::RESTING; // Default to resting instead of unknown
+            new_session.profile = find_session_profile(&session_id);
+            new_session
+        });
+
+        session.last_cpu_event = Some(cpu_event.clone());
+        session.last_update = current_timestamp();
+
+        // Check TTY for zombie detection (tmux/screen sessions are never zombies)
+        session.multiplexer = detect_multiplexer(cpu_event.pid);
+        let is_zombie = session.multiplexer.is_none() && is_zombie_by_tty(cpu_event.pid);
+        session.has_terminal = !is_zombie;
+
+        if is_zombie {
+            println!("[SessionManager] Session '{}' is ZOMBIE (TTY='?' or '??', pid={})",
+                short(&session_id), cpu_event.pid);
+            session.current_status = machine::ZOMBIE;
+        }
+
+        // Take over the PID (replacing an exited process that resumed the session)
+        if session.pid != cpu_event.pid {
+            println!("[SessionManager] 🔗 Session {} adopted PID {} ({:?})", short(&session_id), cpu_event.pid, evidence);
+            if session.pid != 0 {
+                self.pid_to_session.remove(&session.pid);
+            }
+            session.pid = cpu_event.pid;
+        }
+        self.pid_to_session.insert(cpu_event.pid, session_id.clone());
+
+        // Re-decide status
+        let old_status = session.current_status;
+        let new_status = decide_status(session);
+        if new_status != old_status {
+            println!("[SessionManager] Session {} status change (CPU): {} -> {}",
+                short(&session_id), old_status, new_status);
+            session.current_status = new_status;
+            result.status_change(old_status, new_status);
         }
 
+        if let Some(old_status) = apply_multiplexer_status(session) {
+            result.status_change(old_status, session.current_status);
+        }
+
+        refresh_attention(session, &mut result);
+        stamp(&mut self.generation, session);
+        result.session = session.clone();
+        result
+    }
+
+    /// Processes inside Docker/WSL have a namespace-local PID and no host TTY:
+    /// track them by container + PID (never via the host PID map) and skip zombie checks
+    fn handle_container_cpu_event(&mut self, cpu_event: CpuEvent) -> SessionUpdateResult {
+        let mut result = SessionUpdateResult::default();
+        let Some(container) = cpu_event.container.clone() else {
+            return result;
+        };
+        let session_id = format!("{}-{}-pid-{}", container.runtime.as_str(), container.name, cpu_event.pid);
+
+        let is_new = !self.sessions.contains_key(&session_id);
+        if is_new && self.is_tombstoned(&session_id, cpu_event.timestamp) {
+            return result;
+        }
+        let session = self.sessions.entry(session_id.clone()).or_insert_with(|| {
+            println!("[SessionManager] Creating containerized {} session: {}", cpu_event.kind, session_id);
+            let mut session = SessionState::new_legacy(cpu_event.pid, session_id.clone());
+            session.kind = cpu_event.kind.clone();
+            session.container = Some(container);
+            session
+        });
+
+        session.last_cpu_event = Some(cpu_event.clone());
+        session.last_update = current_timestamp();
+        if cpu_event.cpu_percent > 1.0 {
+            session.mark_active(current_timestamp());
+        }
+
+        let old_status = session.current_status;
+        let new_status = decide_status(session);
+        session.current_status = new_status;
+
+        if is_new {
+            result.is_new_session = true;
+        } else if new_status != old_status {
+            println!("[SessionManager] Session {} status change (container CPU): {} -> {}",
+                session_id, old_status, new_status);
+            result.status_change(old_status, new_status);
+        }
+
+        refresh_attention(session, &mut result);
+        stamp(&mut self.generation, session);
+        result.session = session.clone();
         result
     }
 
     /// Process hook event
-    pub fn handle_hook_event(&self, event: HookEvent) -> SessionUpdateResult {
-        let session_id = event.sid.clone();
+    pub fn handle_hook_event(&mut self, hook_event: HookEvent) -> SessionUpdateResult {
+        let session_id = hook_event.sid.clone();
         let mut result = SessionUpdateResult::default();
 
-        let mut sessions = self.sessions.lock().unwrap();
+        println!("[SessionManager] handle_hook_event: session={}, evt={}, time={}",
+            short(&session_id), hook_event.evt, hook_event.timestamp);
+
+        // A late-delivered event must not clobber newer hook state
+        if let Some(session) = self.sessions.get_mut(&session_id) {
+            if !session.accept_hook_timestamp(hook_event.timestamp) {
+                println!("[SessionManager] Ignoring stale '{}' hook event for session {} ({} < {})",
+                    hook_event.evt, short(&session_id), hook_event.timestamp, session.last_hook_timestamp);
+                result.session = session.clone();
+                return result;
+            }
+        }
 
-        match event.evt.as_str() {
+        match hook_event.evt.as_str() {
             "start" => {
-                // Session start event
-                let is_new = !sessions.contains_key(&session_id);
-                result.is_new_session = is_new;
+                // Create or activate Hook session
+                result.is_new_session = !self.sessions.contains_key(&session_id);
+                if result.is_new_session && self.is_tombstoned(&session_id, hook_event.timestamp) {
+                    result.is_new_session = false;
+                    return result;
+                }
 
-                let session = sessions.entry(session_id.clone()).or_insert_with(|| {
-                    println!("[SessionManager] Creating HOOK session: {}",
-                        &session_id[..8.min(session_id.len())]);
+                let session = self.sessions.entry(session_id.clone()).or_insert_with(|| {
+                    println!("[SessionManager] Creating HOOK session from Hook: {}", short(&session_id));
                     SessionState::new_hook(session_id.clone())
                 });
+                session.accept_hook_timestamp(hook_event.timestamp);
 
                 // Upgrade Legacy to Hook if needed
-                if session.session_type == SessionType::Legacy {
-                    if session.upgrade_to_hook() {
-                        result.session_upgraded = true;
-                        println!("[SessionManager] Session {} upgraded to Hook on 'start' event", &session_id[..8]);
-                    }
+                if session.session_type == SessionType::Legacy && session.upgrade_to_hook() {
+                    println!("[SessionManager] ✅ Session {} upgraded to Hook on 'start' event", short(&session_id));
+                    result.session_upgraded = true;
                 }
 
-                session.current_status = "resting";
+                session.current_status = machine::RESTING; // Just started, waiting for work
                 session.last_update = current_timestamp();
-                result.session = session.clone();
-            }
-
-            "working" => {
-                if let Some(session) = sessions.get_mut(&session_id) {
-                    // Upgrade Legacy to Hook if needed
-                    if session.session_type == SessionType::Legacy {
-                        if session.upgrade_to_hook() {
-                            result.session_upgraded = true;
-                            println!("[SessionManager] Session {} upgraded to Hook on 'working' event", &session_id[..8]);
-                        }
-                    }
-
-                    let old_status = session.current_status;
-                    session.current_status = "working";
-                    session.last_update = current_timestamp();
 
-                    if old_status != "working" {
-                        result.status_changed = true;
-                        result.new_status = Some("working".to_string());
-                    }
-
-                    result.session = session.clone();
+                // Link sessions launched from the app (start_miner)
+                if let Some(project_path) = terminal::take_launched_project(&session_id) {
+                    println!("[SessionManager] 🔗 Session {} linked to launch in {}", short(&session_id), project_path);
+                    session.project_path = Some(project_path);
                 }
             }
+            "working" | "resting" => {
+                let new_status = if hook_event.evt == "working" { machine::WORKING } else { machine::RESTING };
 
-            "resting" => {
-                if let Some(session) = sessions.get_mut(&session_id) {
+                if let Some(session) = self.sessions.get_mut(&session_id) {
                     // Upgrade Legacy to Hook if needed
-                    if session.session_type == SessionType::Legacy {
-                        if session.upgrade_to_hook() {
-                            result.session_upgraded = true;
-                            println!("[SessionManager] Session {} upgraded to Hook on 'resting' event", &session_id[..8]);
-                        }
+                    if session.session_type == SessionType::Legacy && session.upgrade_to_hook() {
+                        println!("[SessionManager] ✅ Session {} upgraded to Hook on '{}' event", short(&session_id), hook_event.evt);
+                        result.session_upgraded = true;
                     }
 
                     let old_status = session.current_status;
-                    session.current_status = "resting";
+                    session.current_status = new_status;
                     session.last_update = current_timestamp();
 
-                    if old_status != "resting" {
-                        result.status_changed = true;
-                        result.new_status = Some("resting".to_string());
+                    if old_status != new_status {
+                        println!("[SessionManager] Session {} status change (Hook): {} -> {}",
+                            short(&session_id), old_status, new_status);
+                        result.status_change(old_status, new_status);
                     }
-
-                    result.session = session.clone();
                 }
             }
-
             "end" => {
-                if let Some(session) = sessions.remove(&session_id) {
-                    println!("[SessionManager] Session terminated via hook: {}",
-                        &session_id[..8.min(session_id.len())]);
-
-                    // Remove from PID mapping
-                    if session.pid != 0 {
-                        let mut pid_map = self.pid_to_session.lock().unwrap();
-                        pid_map.remove(&session.pid);
-                    }
-
+                if let Some(session) = self.remove_session(&session_id) {
+                    println!("[SessionManager] 💀 Session terminated via Hook: {}", short(&session_id));
                     result.session_terminated = true;
                     result.session = session;
                 }
+                return result;
             }
-
             _ => {
-                println!("[SessionManager] Unknown hook event: {}", event.evt);
+                println!("[SessionManager] Unknown hook event: {}", hook_event.evt);
             }
         }
 
+        // The hook reported which process fired it
+        if let Some(pid) = hook_event.pid {
+            self.adopt_hook_pid(&session_id, pid);
+        }
+
+        // Tag the session with the profile whose hooks reported it
+        if let Some(session) = self.sessions.get_mut(&session_id) {
+            if hook_event.profile.is_some() {
+                session.profile = hook_event.profile;
+            }
+            refresh_attention(session, &mut result);
+            stamp(&mut self.generation, session);
+            result.session = session.clone();
+        }
+
         result
     }
 
-    /// Decide session status based on type and available data
-    fn decide_session_status(&self, session: &SessionState) -> &'static str {
-        // Priority 0: TTY check for zombie detection
-        if !session.has_terminal {
-            return "zombie";
+    /// Permission request from the Notification hook: the session waits for the user
+    pub fn handle_approval_event(&mut self, approval: ApprovalEvent) -> SessionUpdateResult {
+        let session_id = approval.sid.clone();
+        let mut result = SessionUpdateResult::default();
+
+        if !approval.is_permission_request() {
+            println!("[SessionManager] Ignoring notification for session {}: {}", short(&session_id), approval.message);
+            return result;
         }
 
-        // Different logic based on session type
-        match session.session_type {
-            SessionType::Legacy => self.decide_legacy_status(session),
-            SessionType::Hook => session.current_status, // Hook sessions maintain their status
+        let Some(session) = self.sessions.get_mut(&session_id) else {
+            println!("[SessionManager] Approval request for unknown session {}", short(&session_id));
+            return result;
+        };
+        if !session.accept_hook_timestamp(approval.timestamp) {
+            println!("[SessionManager] Ignoring stale approval request for session {}", short(&session_id));
+            return result;
         }
+
+        // Upgrade Legacy to Hook if needed (hook sessions keep the status set here)
+        if session.session_type == SessionType::Legacy && session.upgrade_to_hook() {
+            println!("[SessionManager] ✅ Session {} upgraded to Hook on approval request", short(&session_id));
+            result.session_upgraded = true;
+        }
+        if approval.profile.is_some() {
+            session.profile = approval.profile;
+        }
+
+        let old_status = session.current_status;
+        session.current_status = machine::WAITING_APPROVAL;
+        session.last_update = current_timestamp();
+
+        if old_status != machine::WAITING_APPROVAL {
+            println!("[SessionManager] Session {} status change (Hook): {} -> {}",
+                short(&session_id), old_status, machine::WAITING_APPROVAL);
+            result.status_change(old_status, machine::WAITING_APPROVAL);
+        }
+
+        refresh_attention(session, &mut result);
+        stamp(&mut self.generation, session);
+        result.session = session.clone();
+        result
     }
 
-    /// Decide status for Legacy sessions
-    fn decide_legacy_status(&self, session: &SessionState) -> &'static str {
+    /// Record network activity; open API connections count as activity for idle tracking
+    pub fn handle_network_event(&mut self, network_event: NetworkEvent) {
+        let session = match self.pid_to_session.get(&network_event.pid).and_then(|id| self.sessions.get_mut(id)) {
+            Some(session) => session,
+            None => return,  // Not linked to a session yet
+        };
+
+        if network_event.connections > 0 {
+            session.mark_active(network_event.timestamp);
+        }
+        session.last_network_event = Some(network_event);
+        stamp(&mut self.generation, session);
+    }
+
+    /// Re-evaluate Hook sessions that haven't heard a hook in a while with the CPU/log/TTY logic
+    /// (without events for them, nothing else would)
+    pub fn check_stale_hooks(&mut self) -> Vec<SessionUpdateResult> {
         let now = current_timestamp();
+        let mut results = Vec::new();
+        for session in self.sessions.values_mut() {
+            if session.session_type != SessionType::Hook || !hooks_stale(session, now) {
+                continue;
+            }
+            let old_status = session.current_status;
+            let new_status = decide_status(session);
+            if new_status == old_status {
+                continue;
+            }
+
+            println!("[SessionManager] Session {} status change (hooks stale): {} -> {}",
+                short(&session.session_id), old_status, new_status);
+            let mut result = SessionUpdateResult::default();
+            session.current_status = new_status;
+            result.status_change(old_status, new_status);
+            flag_stale_hooks(session, &mut result);
+            refresh_attention(session, &mut result);
+            stamp(&mut self.generation, session);
+            result.session = session.clone();
+            results.push(result);
+        }
+        results
+    }
 
-        // Check log event
-        if let Some(ref log) = session.last_log_event {
-            let mtime_age = now.saturating_sub(log.file_mtime);
+    /// Drop the session of a process that exited (no need to wait for the cleaner)
+    pub fn handle_process_exit_event(&mut self, exit_event: ProcessExitEvent) -> Option<SessionState> {
+        self.process_cwds.remove(&exit_event.pid);
+        let session_id = self.pid_to_session.remove(&exit_event.pid)?;
+        crate::status::multiplexer::forget(exit_event.pid);
 
-            // If "Stream started - received first chunk" was found → working
-            if matches!(log.state, WorkingState::ActivelyWorking) {
-                // But check if it's stale
-                if mtime_age >= STALE_MTIME_THRESHOLD_SECS {
-                    return "resting";
-                }
+        let session = self.remove_session(&session_id)?;
+        println!("[SessionManager] 💀 Session terminated (process exited): {}", short(&session_id));
+        Some(session)
+    }
+
+    /// Remove a session and its PID mapping, leaving a tombstone
+    fn remove_session(&mut self, session_id: &str) -> Option<SessionState> {
+        let session = self.sessions.remove(session_id)?;
+        // Containerized PIDs are never mapped
+        if session.pid != 0 && session.container.is_none() {
+            self.pid_to_session.remove(&session.pid);
+        }
+
+        let now = current_timestamp();
+        self.tombstones.retain(|_, removed_at| now.saturating_sub(*removed_at) < TOMBSTONE_SECS);
+        self.tombstones.insert(session_id.to_string(), now);
+        Some(session)
+    }
 
-                // Also check CPU to confirm still working
-                if let Some(ref cpu) = session.last_cpu_event {
-                    let cpu_age = now.saturating_sub(cpu.timestamp);
-                    if cpu_age < CPU_AGE_THRESHOLD_SECS && cpu.cpu_percent < LOW_CPU_THRESHOLD {
-                        return "resting";
+    /// Attach the PID a hook reported to its session (a temporary session of that PID is folded in)
+    fn adopt_hook_pid(&mut self, session_id: &str, pid: u32) {
+        let Some(session) = self.sessions.get(session_id) else {
+            return;
+        };
+        if session.pid == pid || session.container.is_some() {
+            return;
+        }
+        if let Err(reason) = check_adoption(session, pid, PidEvidence::HookPayload, || None) {
+            println!("[SessionManager] ⚠️ Not attaching hook PID {} to session {}: {}", pid, short(session_id), reason);
+            return;
+        }
+
+        match self.pid_to_session.get(&pid).cloned() {
+            Some(temp_id) if is_temporary_session_id(&temp_id) => {
+                if session.pid != 0 {
+                    self.pid_to_session.remove(&session.pid);
+                    if let Some(session) = self.sessions.get_mut(session_id) {
+                        session.pid = 0;
                     }
                 }
-
-                return "working";
+                self.absorb_session(&temp_id, session_id);
+                println!("[SessionManager] Merged temporary session {} into {} (hook PID {})", temp_id, short(session_id), pid);
+                return;
+            }
+            Some(other_id) if other_id != session_id => {
+                println!("[SessionManager] ⚠️ Not attaching hook PID {} to session {}: PID belongs to session {}",
+                    pid, short(session_id), short(&other_id));
+                return;
             }
+            _ => {}
         }
 
-        // Check CPU usage (fallback)
-        if let Some(ref cpu) = session.last_cpu_event {
-            let cpu_age = now.saturating_sub(cpu.timestamp);
-            if cpu_age < CPU_AGE_THRESHOLD_SECS && cpu.cpu_percent > HIGH_CPU_THRESHOLD {
-                return "working";
+        let old_pid = session.pid;
+        if old_pid != 0 {
+            self.pid_to_session.remove(&old_pid);
+        }
+        self.pid_to_session.insert(pid, session_id.to_string());
+        if let Some(session) = self.sessions.get_mut(session_id) {
+            session.pid = pid;
+            if session.tty.is_none() {
+                session.tty = get_tty(pid);
             }
+            println!("[SessionManager] 🔗 Session {} adopted PID {} (hook payload)", short(session_id), pid);
         }
+    }
 
-        // Default to resting
-        "resting"
+    /// Merge a duplicate entry: `target_id` keeps its ID and takes over the source's process and details
+    /// Returns the removed source session and the target's update
+    pub fn merge_sessions(&mut self, source_id: &str, target_id: &str) -> Result<(SessionState, SessionUpdateResult), String> {
+        let source = self.sessions.get(source_id).ok_or_else(|| format!("Unknown session: {}", source_id))?;
+        let target = self.sessions.get(target_id).ok_or_else(|| format!("Unknown session: {}", target_id))?;
+        validate_merge(source, target)?;
+
+        let source = self.absorb_session(source_id, target_id)
+            .ok_or_else(|| format!("Cannot merge {} into {}", source_id, target_id))?;
+        let Some(session) = self.sessions.get_mut(target_id) else {
+            return Err(format!("Unknown session: {}", target_id));
+        };
+        println!("[SessionManager] 🔗 Merged session {} into {} (PID {})", short(source_id), short(target_id), session.pid);
+
+        let mut result = SessionUpdateResult::default();
+        let old_status = session.current_status;
+        let new_status = decide_status(session);
+        if new_status != old_status {
+            session.current_status = new_status;
+            result.status_change(old_status, new_status);
+        }
+        refresh_attention(session, &mut result);
+        stamp(&mut self.generation, session);
+        result.session = session.clone();
+        Ok((source, result))
     }
 
-    /// Remove stale sessions
-    pub fn cleanup_stale_sessions(&self, threshold_secs: u64) -> Vec<SessionState> {
-        let now = current_timestamp();
-        let mut removed = Vec::new();
+    /// Remove `source_id` and carry its process and details over to `target_id` (no checks)
+    fn absorb_session(&mut self, source_id: &str, target_id: &str) -> Option<SessionState> {
+        if source_id == target_id || !self.sessions.contains_key(target_id) {
+            return None;
+        }
+        let source = self.remove_session(source_id)?;
+        let target = self.sessions.get_mut(target_id)?;
 
-        let mut sessions = self.sessions.lock().unwrap();
-        let mut pid_map = self.pid_to_session.lock().unwrap();
+        if target.pid == 0 {
+            target.pid = source.pid;
+            target.has_terminal = source.has_terminal;
+        }
+        target.tty = target.tty.take().or_else(|| source.tty.clone());
+        target.multiplexer = target.multiplexer.take().or_else(|| source.multiplexer.clone());
+        target.container = target.container.take().or_else(|| source.container.clone());
+        target.project_path = target.project_path.take().or_else(|| source.project_path.clone());
+        target.profile = target.profile.take().or_else(|| source.profile.clone());
+        target.last_cpu_event = target.last_cpu_event.take().or_else(|| source.last_cpu_event.clone());
+        target.last_network_event = target.last_network_event.take().or_else(|| source.last_network_event.clone());
+        if source.last_log_event.as_ref().map(|log| log.timestamp) > target.last_log_event.as_ref().map(|log| log.timestamp) {
+            target.last_log_event = source.last_log_event.clone();
+        }
+        target.token_usage.input_tokens += source.token_usage.input_tokens;
+        target.token_usage.output_tokens += source.token_usage.output_tokens;
+        if let Some(active) = source.last_active_timestamp {
+            target.mark_active(active);
+        }
+        target.started_at = target.started_at.min(source.started_at);
+        target.total_working_seconds += source.working_seconds(current_timestamp());
 
-        sessions.retain(|session_id, session| {
-            let age = now.saturating_sub(session.last_update);
-            if age > threshold_secs {
-                println!("[SessionManager] Removing stale session: {} (age: {}s)",
-                    &session_id[..8.min(session_id.len())], age);
+        // Containerized PIDs are never mapped
+        if target.pid != 0 && target.container.is_none() {
+            self.pid_to_session.insert(target.pid, target_id.to_string());
+        }
+        Some(source)
+    }
 
-                // Remove from PID mapping
-                if session.pid != 0 {
-                    pid_map.remove(&session.pid);
+    /// Was this session removed at or after `event_time`? (the event predates the removal)
+    fn is_tombstoned(&self, session_id: &str, event_time: u64) -> bool {
+        let Some(&removed_at) = self.tombstones.get(session_id) else {
+            return false;
+        };
+        if event_time > removed_at {
+            return false;
+        }
+        println!("[SessionManager] Ignoring event for removed session {} (event {} <= removal {})",
+            short(session_id), event_time, removed_at);
+        true
+    }
+
+    /// Drop sessions whose process matches ignore rules (cwd glob / command regex)
+    /// Returns the removed session IDs
+    pub fn prune_ignored(&mut self, ignored_sessions: &mut IgnoredSessions) -> Vec<String> {
+        let removed = ignored_sessions.prune(&mut self.sessions);
+        for id in &removed {
+            self.pid_to_session.retain(|_, sid| sid != id);
+        }
+        removed
+    }
+
+    /// Remove sessions the cleaner found dead (or the user cleared)
+    /// Sessions changed since the snapshot the cleaner decided from are kept
+    pub fn remove_sessions(&mut self, removals: &[SessionRemoval]) -> Vec<SessionState> {
+        removals.iter()
+            .filter_map(|removal| {
+                let session_id = removal.session_id.as_str();
+                let current = self.sessions.get(session_id)?.generation;
+                if removal.generation.is_some_and(|seen| seen != current) {
+                    println!("[SessionManager] Keeping session {}: changed since the cleaner's snapshot (generation {} -> {})",
+                        short(session_id), removal.generation.unwrap_or_default(), current);
+                    return None;
                 }
+                let removed = self.remove_session(session_id)?;
+                println!("[SessionManager] Session {} was removed by cleaner", short(session_id));
+                Some(removed)
+            })
+            .collect()
+    }
 
-                removed.push(session.clone());
-                false
-            } else {
-                true
-            }
-        });
+    /// Publish a snapshot of the sessions (with current pin/ignore flags) to the store
+    pub fn publish(&mut self) {
+        for session in self.sessions.values_mut() {
+            crate::session::flags::apply(session);
+        }
+        self.shared_sessions.publish(self.sessions.clone());
+    }
 
-        removed
+    /// Remove sessions without updates for `threshold_secs`
+    pub fn cleanup_stale_sessions(&mut self, threshold_secs: u64) -> Vec<SessionState> {
+        let now = current_timestamp();
+        let stale: Vec<String> = self.sessions.iter()
+            .filter(|(_, session)| now.saturating_sub(session.last_update) > threshold_secs)
+            .map(|(session_id, _)| session_id.clone())
+            .collect();
+
+        stale.iter()
+            .filter_map(|session_id| {
+                println!("[SessionManager] 💀 Session terminated (stale): {}", short(session_id));
+                self.remove_session(session_id)
+            })
+            .collect()
     }
 
     /// Get session statistics
     pub fn get_statistics(&self) -> SessionStatistics {
-        let sessions = self.sessions.lock().unwrap();
-
-        let mut stats = SessionStatistics::default();
-        stats.total_sessions = sessions.len();
+        let mut stats = SessionStatistics {
+            total_sessions: self.sessions.len(),
+            ..Default::default()
+        };
 
-        for (_id, session) in sessions.iter() {
+        for session in self.sessions.values() {
             match session.current_status {
-                "working" => stats.working_count += 1,
-                "resting" => stats.resting_count += 1,
-                "zombie" => stats.zombie_count += 1,
+                machine::WORKING => stats.working_count += 1,
+                machine::RESTING => stats.resting_count += 1,
+                machine::ZOMBIE => stats.zombie_count += 1,
                 _ => stats.unknown_count += 1,
             }
 
@@ -378,15 +850,154 @@ impl SessionManager {
     }
 }
 
+/// First 8 characters of a session ID (for logs)
+fn short(session_id: &str) -> &str {
+    session_id.get(..8).unwrap_or(session_id)
+}
+
+/// May `session` take `pid` on this evidence? Returns the evidence the adoption rests on
+/// A session keeps a live process it already has; otherwise it needs conclusive evidence,
+/// or a log mention corroborated by the process running in the session's project (`cwd`,
+/// only looked up then; new sessions are still created from a log mention alone - there is
+/// nothing to mis-merge)
+fn check_adoption(
+    session: &SessionState,
+    pid: u32,
+    evidence: PidEvidence,
+    cwd: impl FnOnce() -> Option<PathBuf>,
+) -> Result<PidEvidence, String> {
+    if session.pid == pid {
+        return Ok(evidence);
+    }
+    if session.pid != 0 && is_process_alive(session.pid) {
+        return Err(format!("session already belongs to running PID {}", session.pid));
+    }
+    if evidence.is_conclusive() {
+        return Ok(evidence);
+    }
+    let Some(project) = session.project_path.as_deref() else {
+        return Err("its debug log only mentions the PID, and the session has no project to compare".to_string());
+    };
+    if cwd().is_some_and(|cwd| Path::new(project) == cwd) {
+        Ok(PidEvidence::Cwd)
+    } else {
+        Err("its debug log only mentions the PID, with no corroborating evidence".to_string())
+    }
+}
+
+/// Can `source` be merged into `target`? (they must be the same process, or one has none yet)
+pub fn validate_merge(source: &SessionState, target: &SessionState) -> Result<(), String> {
+    if source.session_id == target.session_id {
+        return Err("Cannot merge a session into itself".to_string());
+    }
+    if source.pid != 0 && target.pid != 0 && source.pid != target.pid {
+        return Err(format!("Sessions belong to different processes (PID {} and {})", source.pid, target.pid));
+    }
+    if source.container.is_some() && target.container.is_some() && source.container != target.container {
+        return Err("Sessions run in different containers".to_string());
+    }
+    Ok(())
+}
+
+/// Decide a session's status with the status state machine
+/// The live TTY check is the only input looked up here
+fn decide_status(session: &SessionState) -> SessionStatus {
+    let has_host_tty = session.multiplexer.is_none() && session.container.is_none();
+    let tty_zombie = session.pid != 0 && has_host_tty && is_zombie_by_tty(session.pid);
+
+    let decision = machine::decide(&StatusInputs::from_session(session, tty_zombie), current_timestamp());
+    println!("[SessionManager] decide_status: session={} -> {} ({})",
+        short(&session.session_id), decision.status, decision.reason);
+    history::note_reason(&session.session_id, decision.reason);
+    decision.status
+}
+
+/// No hook event from the session for the configured "hooks.stale_secs"
+fn hooks_stale(session: &SessionState, now: u64) -> bool {
+    machine::hooks_stale(session.last_hook_at, now, crate::config::get().hooks.stale_secs)
+}
+
+/// A Hook session's status just changed without hooks: warn once until a hook arrives again
+fn flag_stale_hooks(session: &mut SessionState, result: &mut SessionUpdateResult) {
+    if session.session_type == SessionType::Hook && !session.hooks_stale
+        && hooks_stale(session, current_timestamp())
+    {
+        println!("[SessionManager] ⚠️ No hook events from session {} for {} min - using CPU/log detection",
+            short(&session.session_id), current_timestamp().saturating_sub(session.last_hook_at) / 60);
+        session.hooks_stale = true;
+        result.hooks_stale = true;
+    }
+}
+
+/// Re-evaluate the needs-attention flag after a change (see machine::needs_attention)
+fn refresh_attention(session: &mut SessionState, result: &mut SessionUpdateResult) {
+    let needs_attention = machine::needs_attention(&StatusInputs::from_session(session, false), current_timestamp());
+    if needs_attention != session.needs_attention {
+        println!("[SessionManager] Session {} needs_attention: {} -> {}",
+            short(&session.session_id), session.needs_attention, needs_attention);
+        session.needs_attention = needs_attention;
+        result.attention_changed = true;
+    }
+}
+
+/// Show detached tmux/screen sessions as "detached" (see machine::multiplexer_status)
+/// Returns the previous status if it changed
+fn apply_multiplexer_status(session: &mut SessionState) -> Option<SessionStatus> {
+    let attached = session.multiplexer.as_ref().map(|m| m.attached);
+    let new_status = machine::multiplexer_status(session.current_status, attached);
+    if new_status == session.current_status {
+        return None;
+    }
+
+    println!("[SessionManager] Session {} status change (multiplexer): {} -> {}",
+        short(&session.session_id), session.current_status, new_status);
+    let old_status = session.current_status;
+    session.current_status = new_status;
+    session.has_terminal = true;
+    Some(old_status)
+}
+
+/// Mark a session as changed with the next generation
+fn stamp(generation: &mut u64, session: &mut SessionState) {
+    *generation += 1;
+    session.generation = *generation;
+    session.track_status_time(current_timestamp());
+
+    // Sessions found already running started with their process, not when first seen
+    if session.pid != 0 && session.container.is_none() && session.start_time_pid != session.pid {
+        session.start_time_pid = session.pid;
+        if let Some(started) = process_start_time(session.pid) {
+            session.started_at = session.started_at.min(started);
+        }
+    }
+}
+
 /// Result of session update operation
 #[derive(Debug)]
 pub struct SessionUpdateResult {
     pub session: SessionState,
-    pub is_new_session: bool,
+    pub is_new_session: bool,   // Announce as a new session
     pub status_changed: bool,
-    pub new_status: Option<String>,
+    pub old_status: Option<SessionStatus>,  // Status before the first change
+    pub new_status: Option<SessionStatus>,
     pub session_upgraded: bool,
     pub session_terminated: bool,
+    pub became_zombie: bool,    // Lost its TTY: the cleaner should check the process
+    pub hooks_stale: bool,      // Hooks went silent and the status fell back to CPU/log: warn
+    pub attention_changed: bool,  // needs_attention was raised or cleared
+}
+
+impl SessionUpdateResult {
+    fn status_change(&mut self, old_status: SessionStatus, new_status: SessionStatus) {
+        self.status_changed = true;
+        self.old_status.get_or_insert(old_status);
+        self.new_status = Some(new_status);
+    }
+
+    /// Did a task just finish (working -> resting)?
+    pub fn task_completed(&self) -> bool {
+        self.old_status == Some(machine::WORKING) && self.new_status == Some(machine::RESTING)
+    }
 }
 
 impl Default for SessionUpdateResult {
@@ -395,9 +1006,13 @@ impl Default for SessionUpdateResult {
             session: SessionState::new_legacy(0, String::new()),
             is_new_session: false,
             status_changed: false,
+            old_status: None,
             new_status: None,
             session_upgraded: false,
             session_terminated: false,
+            became_zombie: false,
+            hooks_stale: false,
+            attention_changed: false,
         }
     }
 }
@@ -435,6 +1050,21 @@ impl SessionStatistics {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::types::WorkingState;
+
+    fn removal(session_id: &str, generation: Option<u64>) -> SessionRemoval {
+        SessionRemoval { session_id: session_id.to_string(), generation }
+    }
+
+    fn hook(sid: &str, evt: &str, timestamp: u64) -> HookEvent {
+        HookEvent {
+            sid: sid.to_string(),
+            evt: evt.to_string(),
+            profile: None,
+            timestamp,
+            pid: None,
+        }
+    }
 
     #[test]
     fn test_session_manager_creation() {
@@ -445,77 +1075,299 @@ mod tests {
 
     #[test]
     fn test_hook_event_handling() {
-        let manager = SessionManager::new();
+        let mut manager = SessionManager::new();
 
         // Test session start
-        let start_event = HookEvent {
-            sid: "test-session".to_string(),
-            evt: "start".to_string(),
-        };
-
-        let result = manager.handle_hook_event(start_event);
+        let result = manager.handle_hook_event(hook("test-session", "start", 0));
         assert!(result.is_new_session);
         assert_eq!(result.session.session_type, SessionType::Hook);
-        assert_eq!(result.session.current_status, "resting");
+        assert_eq!(result.session.current_status, machine::RESTING);
 
         // Test transition to working
-        let working_event = HookEvent {
-            sid: "test-session".to_string(),
-            evt: "working".to_string(),
+        let result = manager.handle_hook_event(hook("test-session", "working", 0));
+        assert!(result.status_changed);
+        assert_eq!(result.new_status, Some(machine::WORKING));
+
+        // Back to resting: a finished task
+        let result = manager.handle_hook_event(hook("test-session", "resting", 0));
+        assert!(result.task_completed());
+
+        // Session end
+        let result = manager.handle_hook_event(hook("test-session", "end", 0));
+        assert!(result.session_terminated);
+        assert!(manager.get_session("test-session").is_none());
+    }
+
+    #[test]
+    fn test_idle_time_from_activity() {
+        let mut manager = SessionManager::new();
+        crate::testing::set_now(5_000);
+        manager.handle_hook_event(hook("idle-session", "start", 5_000));
+        assert_eq!(manager.get_session("idle-session").unwrap().idle_secs(5_000), Some(0));
+
+        // Activity from another source; an older sample never moves the clock back
+        crate::testing::set_now(5_300);
+        let session = manager.sessions.get_mut("idle-session").unwrap();
+        session.mark_active(5_200);
+        session.mark_active(5_100);
+        assert_eq!(manager.get_session("idle-session").unwrap().idle_secs(5_300), Some(100));
+
+        manager.handle_hook_event(hook("idle-session", "working", 5_300));
+        assert_eq!(manager.get_session("idle-session").unwrap().idle_secs(5_300), Some(0));
+    }
+
+    #[test]
+    fn test_merge_sessions() {
+        use crate::testing::{set_process, MockProcess};
+        let (pid_a, pid_b) = (80_000_301, 80_000_302);
+        set_process(pid_a, MockProcess::Running);
+        set_process(pid_b, MockProcess::Running);
+        let mut manager = SessionManager::new();
+        for pid in [pid_a, pid_b] {
+            manager.handle_cpu_event(CpuEvent { pid, timestamp: 0, cpu_percent: 5.0, kind: "aider".to_string(), uid: None, container: None });
+        }
+        assert!(is_temporary_session_id(&format!("aider-pid-{}", pid_a)));
+
+        // A log event folds in the temporary session of its own PID only
+        let session_id = "7c0ffee0-c045-4274-8f37-c4e41fb6104a";
+        manager.handle_log_event(LogEvent {
+            session_id: session_id.to_string(),
+            pid: Some(pid_a),
+            timestamp: 0,
+            state: WorkingState::Idle,
+            has_approval_pending: false,
+            file_mtime: 0,
+            transcript: None,
+            profile: None,
+        });
+        assert!(manager.get_session(&format!("aider-pid-{}", pid_a)).is_none());
+        assert!(manager.get_session(&format!("aider-pid-{}", pid_b)).is_some());
+        let merged = manager.get_session_by_pid(pid_a).unwrap();
+        assert_eq!(merged.session_id, session_id);
+        assert!(merged.last_cpu_event.is_some());
+
+        // Explicit merge into a hook session that has no PID yet
+        manager.handle_hook_event(hook("hook-session", "start", 0));
+        let (source, result) = manager.merge_sessions(&format!("aider-pid-{}", pid_b), "hook-session").unwrap();
+        assert_eq!(source.pid, pid_b);
+        assert_eq!(result.session.pid, pid_b);
+        assert_eq!(manager.get_session_by_pid(pid_b).unwrap().session_id, "hook-session");
+
+        // Different processes, the same session, unknown sessions
+        let err = manager.merge_sessions(session_id, "hook-session").unwrap_err();
+        assert!(err.contains("different processes"), "{}", err);
+        assert!(manager.merge_sessions("hook-session", "hook-session").is_err());
+        assert!(manager.merge_sessions("missing", "hook-session").is_err());
+    }
+
+    #[test]
+    fn test_pid_adoption_needs_evidence() {
+        use crate::session::CachedSession;
+        use crate::testing::{set_process, MockProcess};
+        let (pid_a, pid_b) = (80_000_311, 80_000_312);
+        set_process(pid_a, MockProcess::Running);
+        set_process(pid_b, MockProcess::Running);
+        let mut manager = SessionManager::new();
+        let cpu = |pid| CpuEvent { pid, timestamp: 0, cpu_percent: 5.0, kind: CLAUDE_KIND.to_string(), uid: None, container: None };
+        let cache = |manager: &SessionManager, pid, evidence| {
+            manager.session_cache.lock().unwrap()
+                .insert(pid, CachedSession { session_id: "hook-a".to_string(), start_time: None, evidence });
         };
 
-        let result = manager.handle_hook_event(working_event);
-        assert!(result.status_changed);
-        assert_eq!(result.new_status, Some("working".to_string()));
+        // A mention of the PID in the debug log alone doesn't attach it
+        manager.handle_hook_event(hook("hook-a", "start", 1));
+        cache(&manager, pid_b, PidEvidence::LogMention);
+        manager.handle_cpu_event(cpu(pid_b));
+        assert_eq!(manager.get_session("hook-a").unwrap().pid, 0);
+        assert!(manager.get_session_by_pid(pid_b).is_none());
+
+        // The PID in a hook payload does
+        manager.handle_hook_event(HookEvent { pid: Some(pid_a), ..hook("hook-a", "working", 2) });
+        assert_eq!(manager.get_session_by_pid(pid_a).unwrap().session_id, "hook-a");
+
+        // Conclusive evidence for another process waits until the session's process has exited
+        cache(&manager, pid_b, PidEvidence::DebugFile);
+        manager.handle_cpu_event(cpu(pid_b));
+        assert_eq!(manager.get_session("hook-a").unwrap().pid, pid_a);
+
+        set_process(pid_a, MockProcess::Exited);
+        manager.handle_cpu_event(cpu(pid_b));
+        assert_eq!(manager.get_session("hook-a").unwrap().pid, pid_b);
+        assert!(manager.get_session_by_pid(pid_a).is_none());
+    }
+
+    #[test]
+    fn test_log_mention_corroborated_by_cwd() {
+        let mut session = SessionState::new_legacy(0, "legacy-a".to_string());
+        let lookups = std::cell::Cell::new(0);
+        let cwd = |dir: &'static str| {
+            let lookups = &lookups;
+            move || {
+                lookups.set(lookups.get() + 1);
+                Some(PathBuf::from(dir))
+            }
+        };
+
+        // Without a project there is nothing to compare: the working directory isn't looked up
+        assert!(check_adoption(&session, 42, PidEvidence::LogMention, cwd("/work/app")).is_err());
+        assert_eq!(check_adoption(&session, 42, PidEvidence::DebugFile, cwd("/work/app")), Ok(PidEvidence::DebugFile));
+        assert_eq!(lookups.get(), 0);
+
+        session.project_path = Some("/work/app".to_string());
+        assert_eq!(check_adoption(&session, 42, PidEvidence::LogMention, cwd("/work/app")), Ok(PidEvidence::Cwd));
+        assert!(check_adoption(&session, 42, PidEvidence::LogMention, cwd("/work/other")).is_err());
+        assert_eq!(lookups.get(), 2);
+    }
+
+    #[test]
+    fn test_status_durations() {
+        let mut manager = SessionManager::new();
+        let mut at = |now: u64, evt: &str| {
+            crate::testing::set_now(now);
+            manager.handle_hook_event(hook("timed", evt, now)).session
+        };
+
+        let session = at(1_000, "start");
+        assert_eq!((session.started_at, session.status_since, session.total_working_seconds), (1_000, 1_000, 0));
+
+        at(1_010, "working");
+        let session = at(1_070, "resting");
+        assert_eq!((session.status_since, session.total_working_seconds), (1_070, 60));
+
+        // An update mid-stretch counts the working time so far; status_since stays put
+        at(1_100, "working");
+        let session = at(1_130, "working");
+        assert_eq!((session.status_since, session.total_working_seconds), (1_100, 90));
+        assert_eq!(session.working_seconds(1_150), 110);
+        assert_eq!(session.started_at, 1_000);
+    }
+
+    #[test]
+    fn test_stale_hook_event_ignored() {
+        let mut manager = SessionManager::new();
+
+        manager.handle_hook_event(hook("test-session", "start", 100));
+        manager.handle_hook_event(hook("test-session", "resting", 120));
+
+        // A 'working' event fired before the 'resting' one, delivered late
+        let result = manager.handle_hook_event(hook("test-session", "working", 110));
+        assert!(!result.status_changed);
+        assert_eq!(result.session.current_status, machine::RESTING);
+
+        // Same-second events still apply in arrival order
+        let result = manager.handle_hook_event(hook("test-session", "working", 120));
+        assert_eq!(result.new_status, Some(machine::WORKING));
     }
 
     #[test]
     fn test_legacy_to_hook_upgrade() {
-        let manager = SessionManager::new();
+        let mut manager = SessionManager::new();
 
-        // Create legacy session via log event
+        // Create legacy session via log event (upgrade requires a UUID session ID,
+        // and log events for dead processes are ignored)
+        let session_id = "286e962f-c045-4274-8f37-c4e41fb6104a";
         let log_event = LogEvent {
-            session_id: "test-session".to_string(),
-            pid: Some(1234),
+            session_id: session_id.to_string(),
+            pid: Some(std::process::id()),
             timestamp: current_timestamp(),
-            state: WorkingState::MaybeWorking,
+            state: WorkingState::ActivelyWorking,
             has_approval_pending: false,
             file_mtime: current_timestamp(),
+            transcript: None,
+            profile: None,
         };
 
         let result = manager.handle_log_event(log_event);
+        assert!(result.is_new_session);
         assert_eq!(result.session.session_type, SessionType::Legacy);
 
         // Now send hook event for same session
-        let hook_event = HookEvent {
+        let result = manager.handle_hook_event(hook(session_id, "working", 0));
+        assert!(result.session_upgraded);
+        assert_eq!(result.session.session_type, SessionType::Hook);
+    }
+
+    #[test]
+    fn test_approval_event() {
+        let mut manager = SessionManager::new();
+        manager.handle_hook_event(hook("test-session", "start", 0));
+        manager.handle_hook_event(hook("test-session", "working", 0));
+
+        let approval = |message: &str| ApprovalEvent {
             sid: "test-session".to_string(),
-            evt: "working".to_string(),
+            message: message.to_string(),
+            profile: None,
+            timestamp: 0,
         };
 
-        let result = manager.handle_hook_event(hook_event);
-        assert!(result.session_upgraded);
-        assert_eq!(result.session.session_type, SessionType::Hook);
+        // Idle reminders are not permission requests
+        assert!(!manager.handle_approval_event(approval("Claude is waiting for your input")).status_changed);
+
+        let result = manager.handle_approval_event(approval("Claude needs your permission to use Bash"));
+        assert_eq!(result.new_status, Some(machine::WAITING_APPROVAL));
+
+        // Approved: the tool runs and PostToolUse reports working again
+        let result = manager.handle_hook_event(hook("test-session", "working", 0));
+        assert_eq!(result.old_status, Some(machine::WAITING_APPROVAL));
+    }
+
+    #[test]
+    fn test_publish_and_remove() {
+        let mut manager = SessionManager::new();
+        let shared = manager.get_shared_sessions();
+
+        manager.handle_hook_event(hook("session-a", "start", 0));
+        manager.handle_hook_event(hook("session-b", "start", 0));
+        manager.publish();
+        let published = shared.snapshot();
+        assert_eq!(published.len(), 2);
+
+        // The cleaner asks for one to go, an 'end' hook removes the other
+        let removed = manager.remove_sessions(&[removal("session-a", None), removal("unknown", None)]);
+        assert_eq!(removed.len(), 1);
+        assert_eq!(removed[0].session_id, "session-a");
+        manager.handle_hook_event(hook("session-b", "end", 0));
+
+        manager.publish();
+        assert!(manager.sessions().is_empty());
+        assert!(shared.snapshot().is_empty());
+        assert_eq!(published.len(), 2);
+    }
+
+    #[test]
+    fn test_generation_and_tombstones() {
+        let mut manager = SessionManager::new();
+        manager.handle_hook_event(hook("session-a", "start", 10));
+        let seen = manager.get_session("session-a").unwrap().generation;
+
+        // Updated after the cleaner's snapshot: the removal is stale
+        manager.handle_hook_event(hook("session-a", "working", 11));
+        let current = manager.get_session("session-a").unwrap().generation;
+        assert!(current > seen);
+        assert!(manager.remove_sessions(&[removal("session-a", Some(seen))]).is_empty());
+        assert_eq!(manager.remove_sessions(&[removal("session-a", Some(current))]).len(), 1);
+
+        // Events queued before the removal don't resurrect it, later ones do
+        let result = manager.handle_hook_event(hook("session-a", "start", 12));
+        assert!(!result.is_new_session);
+        assert!(manager.get_session("session-a").is_none());
+
+        let result = manager.handle_hook_event(hook("session-a", "start", current_timestamp() + 1));
+        assert!(result.is_new_session);
+        assert!(manager.get_session("session-a").is_some());
     }
 
     #[test]
     fn test_statistics() {
-        let manager = SessionManager::new();
+        let mut manager = SessionManager::new();
 
         // Add some sessions
         for i in 0..5 {
-            let event = HookEvent {
-                sid: format!("session-{}", i),
-                evt: if i % 2 == 0 { "working" } else { "resting" }.to_string(),
-            };
+            let sid = format!("session-{}", i);
 
-            // Start session first
-            manager.handle_hook_event(HookEvent {
-                sid: format!("session-{}", i),
-                evt: "start".to_string(),
-            });
-
-            // Then set status
-            manager.handle_hook_event(event);
+            // Start session first, then set status
+            manager.handle_hook_event(hook(&sid, "start", 0));
+            manager.handle_hook_event(hook(&sid, if i % 2 == 0 { "working" } else { "resting" }, 0));
         }
 
         let stats = manager.get_statistics();
@@ -524,4 +1376,4 @@ mod tests {
         assert!(stats.working_count > 0);
         assert!(stats.resting_count > 0);
     }
-}
\ No newline at end of file
+}
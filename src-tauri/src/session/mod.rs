@@ -3,17 +3,25 @@
 // This module handles all session-related functionality
 
 pub mod analyzer;
+pub mod archive;
 pub mod finder;
 pub mod manager;
 pub mod cleaner;
+pub mod flags;
+pub mod ignore;
+pub mod queue;
 pub mod state;
+pub mod store;
 
 // Core types
-pub use state::{SessionState, SessionType, MonitorEvent, LogEvent, CpuEvent, HookEvent, current_timestamp};
+pub use state::{SessionState, SessionType, MonitorEvent, LogEvent, CpuEvent, HookEvent, ApprovalEvent, NetworkEvent, ProcessExitEvent, SessionMerge, SessionRemoval, TranscriptStats, is_temporary_session_id, current_timestamp};
 
 // Session management
-// pub use manager::{SessionManager, SessionUpdateResult, SessionStatistics}; // Unused
+pub use manager::{SessionManager, SessionUpdateResult};
+pub use store::{SessionStore, SharedSessions};
+pub use finder::{CachedSession, PidEvidence, SessionCache};
 pub use cleaner::{start_session_cleaner, CleanupEvent};
+pub use queue::{event_queue, EventReceiver, EventSender, QueueMonitor};
 
 // Session utilities
 // pub use analyzer::{analyze_log_content, check_session_activity}; // Unused
@@ -0,0 +1,238 @@
+// Monitor Event Queue
+//
+// Bounded multi-producer queue from the monitor threads into the coordinator.
+// When full, the oldest low-value event (CPU/network ticks, which are re-sent on the
+// next scan anyway) is dropped; log/hook/exit events are never dropped and apply
+// backpressure instead. Depth and drop counters are exposed via get_monitor_health.
+//
+
+use crate::session::MonitorEvent;
+use serde::Serialize;
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering};
+use std::sync::mpsc::RecvError;
+use std::sync::{Arc, Condvar, Mutex};
+use std::time::Duration;
+
+pub const DEFAULT_CAPACITY: usize = 1024;
+const WAIT_SLICE: Duration = Duration::from_millis(200);  // Re-check disconnection while blocked
+
+struct Shared {
+    events: Mutex<VecDeque<MonitorEvent>>,
+    not_empty: Condvar,
+    not_full: Condvar,
+    capacity: usize,
+    senders: AtomicUsize,
+    receiver_alive: AtomicBool,
+    sent: AtomicU64,
+    dropped: AtomicU64,
+    blocked_sends: AtomicU64,
+    max_depth: AtomicUsize,
+}
+
+/// Queue statistics (get_monitor_health)
+#[derive(Debug, Clone, Serialize)]
+pub struct QueueHealth {
+    pub depth: usize,
+    pub capacity: usize,
+    pub max_depth: usize,        // High-water mark since start
+    pub sent: u64,
+    pub dropped_low_value: u64,  // CPU/network ticks dropped because the queue was full
+    pub blocked_sends: u64,      // Sends that had to wait for the coordinator
+}
+
+/// The coordinator (receiving half) has gone away
+#[derive(Debug)]
+pub struct QueueClosed;
+
+/// Sending half (cloned into every monitor thread)
+pub struct EventSender {
+    shared: Arc<Shared>,
+}
+
+/// Receiving half (owned by the coordinator)
+pub struct EventReceiver {
+    shared: Arc<Shared>,
+}
+
+/// Read-only handle for health reporting (doesn't keep the queue open)
+#[derive(Clone)]
+pub struct QueueMonitor {
+    shared: Arc<Shared>,
+}
+
+/// Create a bounded event queue
+pub fn event_queue(capacity: usize) -> (EventSender, EventReceiver) {
+    let shared = Arc::new(Shared {
+        events: Mutex::new(VecDeque::with_capacity(capacity)),
+        not_empty: Condvar::new(),
+        not_full: Condvar::new(),
+        capacity: capacity.max(1),
+        senders: AtomicUsize::new(1),
+        receiver_alive: AtomicBool::new(true),
+        sent: AtomicU64::new(0),
+        dropped: AtomicU64::new(0),
+        blocked_sends: AtomicU64::new(0),
+        max_depth: AtomicUsize::new(0),
+    });
+    (EventSender { shared: shared.clone() }, EventReceiver { shared })
+}
+
+/// Events that are superseded by the next scan and may be dropped under load
+fn is_low_value(event: &MonitorEvent) -> bool {
+    matches!(event, MonitorEvent::Cpu(_) | MonitorEvent::Network(_))
+}
+
+impl EventSender {
+    /// Queue an event; Err only when the coordinator has gone away
+    pub fn send(&self, event: MonitorEvent) -> Result<(), QueueClosed> {
+        let shared = &self.shared;
+        let mut events = shared.events.lock().unwrap();
+        let mut blocked = false;
+
+        loop {
+            if !shared.receiver_alive.load(Ordering::Relaxed) {
+                return Err(QueueClosed);
+            }
+
+            if events.len() >= shared.capacity {
+                if let Some(index) = events.iter().position(is_low_value) {
+                    events.remove(index);
+                    shared.dropped.fetch_add(1, Ordering::Relaxed);
+                } else if is_low_value(&event) {
+                    shared.dropped.fetch_add(1, Ordering::Relaxed);
+                    return Ok(());
+                } else {
+                    if !blocked {
+                        blocked = true;
+                        shared.blocked_sends.fetch_add(1, Ordering::Relaxed);
+                        println!("[EventQueue] ⚠️ Queue full ({} events) - waiting for coordinator", events.len());
+                    }
+                    events = shared.not_full.wait_timeout(events, WAIT_SLICE).unwrap().0;
+                    continue;
+                }
+            }
+
+            events.push_back(event);
+            shared.sent.fetch_add(1, Ordering::Relaxed);
+            shared.max_depth.fetch_max(events.len(), Ordering::Relaxed);
+            shared.not_empty.notify_one();
+            return Ok(());
+        }
+    }
+
+    /// Health handle for get_monitor_health
+    pub fn monitor(&self) -> QueueMonitor {
+        QueueMonitor { shared: self.shared.clone() }
+    }
+}
+
+impl Clone for EventSender {
+    fn clone(&self) -> Self {
+        self.shared.senders.fetch_add(1, Ordering::Relaxed);
+        Self { shared: self.shared.clone() }
+    }
+}
+
+impl Drop for EventSender {
+    fn drop(&mut self) {
+        if self.shared.senders.fetch_sub(1, Ordering::AcqRel) == 1 {
+            // Last sender gone: wake the receiver so it can report disconnection
+            let _guard = self.shared.events.lock().unwrap();
+            self.shared.not_empty.notify_all();
+        }
+    }
+}
+
+impl EventReceiver {
+    /// Block until an event arrives; Err once every sender is gone and the queue is empty
+    pub fn recv(&self) -> Result<MonitorEvent, RecvError> {
+        let shared = &self.shared;
+        let mut events = shared.events.lock().unwrap();
+
+        loop {
+            if let Some(event) = events.pop_front() {
+                shared.not_full.notify_one();
+                return Ok(event);
+            }
+            if shared.senders.load(Ordering::Acquire) == 0 {
+                return Err(RecvError);
+            }
+            events = shared.not_empty.wait_timeout(events, WAIT_SLICE).unwrap().0;
+        }
+    }
+}
+
+impl Drop for EventReceiver {
+    fn drop(&mut self) {
+        self.shared.receiver_alive.store(false, Ordering::Relaxed);
+        let _guard = self.shared.events.lock().unwrap();
+        self.shared.not_full.notify_all();
+    }
+}
+
+impl QueueMonitor {
+    pub fn health(&self) -> QueueHealth {
+        let shared = &self.shared;
+        QueueHealth {
+            depth: shared.events.lock().unwrap().len(),
+            capacity: shared.capacity,
+            max_depth: shared.max_depth.load(Ordering::Relaxed),
+            sent: shared.sent.load(Ordering::Relaxed),
+            dropped_low_value: shared.dropped.load(Ordering::Relaxed),
+            blocked_sends: shared.blocked_sends.load(Ordering::Relaxed),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::session::{CpuEvent, HookEvent};
+
+    fn cpu(pid: u32) -> MonitorEvent {
+        MonitorEvent::Cpu(CpuEvent { pid, timestamp: 0, cpu_percent: 0.0, kind: "claude".to_string(), uid: None, container: None })
+    }
+
+    fn hook(sid: &str) -> MonitorEvent {
+        MonitorEvent::Hook(HookEvent { sid: sid.to_string(), evt: "working".to_string(), profile: None, timestamp: 0, pid: None })
+    }
+
+    #[test]
+    fn test_full_queue_drops_oldest_low_value() {
+        let (sender, receiver) = event_queue(2);
+        sender.send(cpu(1)).unwrap();
+        sender.send(hook("session-a")).unwrap();
+        sender.send(cpu(2)).unwrap();  // Drops cpu(1)
+        sender.send(cpu(3)).unwrap();  // Drops cpu(2)
+
+        let health = sender.monitor().health();
+        assert_eq!(health.depth, 2);
+        assert_eq!(health.dropped_low_value, 2);
+
+        assert!(matches!(receiver.recv(), Ok(MonitorEvent::Hook(_))));
+        assert!(matches!(receiver.recv(), Ok(MonitorEvent::Cpu(e)) if e.pid == 3));
+    }
+
+    #[test]
+    fn test_low_value_dropped_when_queue_full_of_high_value() {
+        let (sender, receiver) = event_queue(1);
+        sender.send(hook("session-a")).unwrap();
+        sender.send(cpu(1)).unwrap();
+        assert_eq!(sender.monitor().health().dropped_low_value, 1);
+        assert!(matches!(receiver.recv(), Ok(MonitorEvent::Hook(_))));
+    }
+
+    #[test]
+    fn test_disconnection() {
+        let (sender, receiver) = event_queue(4);
+        sender.send(hook("session-a")).unwrap();
+        drop(sender);
+        assert!(receiver.recv().is_ok());
+        assert!(receiver.recv().is_err());
+
+        let (sender, receiver) = event_queue(4);
+        drop(receiver);
+        assert!(sender.send(hook("session-a")).is_err());
+    }
+}
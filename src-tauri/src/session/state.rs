@@ -2,7 +2,11 @@
 //
 // Event types for multi-threaded monitoring system
 
-use crate::types::WorkingState;
+use crate::config::NotificationOverride;
+use crate::types::{SessionStatus, WorkingState};
+use crate::monitor::container::ContainerInfo;
+use crate::network::NetBytes;
+use crate::status::multiplexer::MultiplexerInfo;
 use serde::{Serialize, Deserialize};
 
 /// Unified monitor event
@@ -11,10 +15,37 @@ pub enum MonitorEvent {
     Log(LogEvent),
     Cpu(CpuEvent),
     Hook(HookEvent),
+    Approval(ApprovalEvent),  // Notification hook (permission request)
+    Network(NetworkEvent),
+    ProcessExit(ProcessExitEvent),
+    ProcessKilled(ProcessExitEvent),  // Killed by ClaudeMiner (via the hook pipe)
+    RemoveSessions(Vec<SessionRemoval>),  // Sessions the cleaner found dead or the user cleared
+    MergeSessions(SessionMerge),  // User-requested merge of a duplicate entry (merge_sessions)
+    FlagsChanged(String),  // A command pinned, ignored, muted or restored this session
+}
+
+/// Fold the source session into the target (the source entry disappears)
+#[derive(Debug, Clone, PartialEq)]
+pub struct SessionMerge {
+    pub source_id: String,
+    pub target_id: String,
+}
+
+/// Temporary session tracked by PID alone ("pid-123", "aider-pid-123", "docker-web-pid-7")
+pub fn is_temporary_session_id(session_id: &str) -> bool {
+    let tail = session_id.rsplit_once("-pid-").map(|(_, tail)| tail).or_else(|| session_id.strip_prefix("pid-"));
+    tail.is_some_and(|pid| !pid.is_empty() && pid.chars().all(|c| c.is_ascii_digit()))
+}
+
+/// A session the cleaner wants removed, decided from a published snapshot
+#[derive(Debug, Clone, PartialEq)]
+pub struct SessionRemoval {
+    pub session_id: String,
+    pub generation: Option<u64>,  // Generation seen in the snapshot; None = remove regardless (user request)
 }
 
 /// Log file change event
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct LogEvent {
     pub session_id: String,
     pub pid: Option<u32>,
@@ -22,14 +53,52 @@ pub struct LogEvent {
     pub state: WorkingState,
     pub has_approval_pending: bool,
     pub file_mtime: u64,  // File modification time (Unix timestamp)
+    pub transcript: Option<TranscriptStats>,  // Set for events from project transcripts
+    pub profile: Option<String>,  // Claude profile whose directory the file is in
+}
+
+/// Message-level details from new transcript records (~/.claude/projects)
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct TranscriptStats {
+    pub last_role: Option<String>,   // "user" | "assistant"
+    pub tool_calls: Vec<String>,     // Tool names called in the new records
+    pub input_tokens: u64,           // Including cache reads/creation
+    pub output_tokens: u64,
+    pub cwd: Option<String>,
+}
+
+/// Cumulative token usage seen in a session's transcript
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+pub struct TokenUsage {
+    pub input_tokens: u64,
+    pub output_tokens: u64,
 }
 
 /// CPU usage change event
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CpuEvent {
     pub pid: u32,
     pub timestamp: u64,
     pub cpu_percent: f32,
+    pub kind: String,  // Process type tag from the matcher ("claude", "aider", ...)
+    pub uid: Option<u32>,  // Owning user (from ps)
+    pub container: Option<ContainerInfo>,  // Set for processes inside Docker/WSL (PID is namespace-local)
+}
+
+/// Network activity of a miner process
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NetworkEvent {
+    pub pid: u32,
+    pub timestamp: u64,
+    pub connections: usize,  // ESTABLISHED :443 connections
+    pub bytes: Option<NetBytes>,  // Cumulative bytes (None when unavailable)
+}
+
+/// A tracked miner process exited (or was killed)
+#[derive(Debug, Clone, Serialize)]
+pub struct ProcessExitEvent {
+    pub pid: u32,
+    pub timestamp: u64,
 }
 
 /// Hook event from Claude Code hooks (via named pipe)
@@ -37,30 +106,93 @@ pub struct CpuEvent {
 pub struct HookEvent {
     pub sid: String,      // session_id
     pub evt: String,      // start|working|resting|end
+    #[serde(default)]
+    pub profile: Option<String>,  // Claude profile whose settings.json registered the hook
+    #[serde(default)]
+    pub timestamp: u64,   // When the hook fired (Unix timestamp)
+    #[serde(default)]
+    pub pid: Option<u32>,  // Claude process that fired the hook (the hook shell's parent)
+}
+
+/// Claude Code notification forwarded by the Notification hook
+#[derive(Debug, Clone, Serialize)]
+pub struct ApprovalEvent {
+    pub sid: String,
+    pub message: String,  // "Claude needs your permission to use Bash"
+    pub profile: Option<String>,
+    pub timestamp: u64,
+}
+
+impl ApprovalEvent {
+    /// Notification hooks also fire for other messages ("Claude is waiting for your input")
+    pub fn is_permission_request(&self) -> bool {
+        self.message.contains("permission")
+    }
+
+    /// The tool waiting for approval, if the message names one
+    pub fn tool(&self) -> Option<&str> {
+        let (_, tool) = self.message.split_once("permission to use ")?;
+        Some(tool.trim()).filter(|t| !t.is_empty())
+    }
 }
 
 /// Session type: Legacy (pre-app start) or Hook (post-app start)
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum SessionType {
     Legacy,  // Pre-app start: managed by mtime, CPU, log analysis
     Hook,    // Post-app start: managed by hook events
 }
 
 /// Session state aggregated from all events
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SessionState {
     pub pid: u32,
     pub session_id: String,
     pub session_type: SessionType,
     pub last_log_event: Option<LogEvent>,
     pub last_cpu_event: Option<CpuEvent>,
-    pub current_status: &'static str,
+    pub last_network_event: Option<NetworkEvent>,
+    pub current_status: SessionStatus,
     pub has_terminal: bool,
     pub last_update: u64,
-    pub last_active_timestamp: Option<u64>,  // For idle detection
+    pub last_active_timestamp: Option<u64>,  // Last CPU, log, hook or network activity (see mark_active)
+    pub pinned: bool,   // User flag: sort first, always notify
+    pub ignored: bool,  // User flag: tracked but hidden from UI, tray and notifications
+    pub archived: bool,  // Idle for long: hidden from the miner list (see archive.rs)
+    pub notification_override: Option<NotificationOverride>,  // User flag: mute or always notify
+    pub project_path: Option<String>,  // Working directory (known for sessions launched from the app)
+    pub tty: Option<String>,  // Controlling terminal (e.g. "ttys003"), used to focus the hosting terminal
+    pub multiplexer: Option<MultiplexerInfo>,  // tmux/screen session hosting the process
+    pub token_usage: TokenUsage,  // From transcripts, since app start
+    pub profile: Option<String>,  // Claude profile (config directory) the session belongs to
+    pub kind: String,  // Process type tag ("claude", or another AI CLI from the process matchers)
+    pub container: Option<ContainerInfo>,  // Docker container / WSL distro hosting the process
+    pub last_hook_timestamp: u64,  // Newest hook event applied (0 = none yet)
+    pub last_hook_at: u64,  // When that hook event arrived (0 = none yet)
+    pub hooks_stale: bool,  // Hooks went silent and the status fell back to CPU/log evidence
+    pub needs_attention: bool,  // Blocked on a permission prompt (see machine::needs_attention)
+    pub generation: u64,  // Bumped by the session manager on every change (0 = not applied yet)
+    pub started_at: u64,  // When the process started (when the session was first seen, if unknown)
+    pub status_since: u64,  // When current_status was entered
+    pub total_working_seconds: u64,  // Time spent working, up to the last update
+    // Clock state is not serialized; a deserialized session resumes it on its next update
+    #[serde(skip)]
+    pub timed_status: SessionStatus,  // Status that status_since and the working clock refer to
+    #[serde(skip)]
+    pub working_checkpoint: u64,  // Working time is counted up to here
+    #[serde(skip)]
+    pub start_time_pid: u32,  // PID whose start time was looked up for started_at
 }
 
 impl SessionState {
+    /// Sub-status of a working session from its latest log evidence ("writing" / "running_tools")
+    pub fn activity(&self) -> Option<&'static str> {
+        if self.current_status != SessionStatus::Working {
+            return None;
+        }
+        self.last_log_event.as_ref().and_then(|log| log.state.activity())
+    }
+
     /// Create new Legacy session (pre-app start)
     pub fn new_legacy(pid: u32, session_id: String) -> Self {
         Self {
@@ -69,10 +201,33 @@ impl SessionState {
             session_type: SessionType::Legacy,
             last_log_event: None,
             last_cpu_event: None,
-            current_status: "unknown",
+            last_network_event: None,
+            current_status: SessionStatus::Unknown,
             has_terminal: true,
             last_update: current_timestamp(),
             last_active_timestamp: None,
+            pinned: false,
+            ignored: false,
+            archived: false,
+            notification_override: None,
+            project_path: None,
+            tty: None,
+            multiplexer: None,
+            token_usage: TokenUsage::default(),
+            profile: None,
+            kind: "claude".to_string(),
+            container: None,
+            last_hook_timestamp: 0,
+            last_hook_at: 0,
+            hooks_stale: false,
+            needs_attention: false,
+            generation: 0,
+            started_at: current_timestamp(),
+            status_since: current_timestamp(),
+            total_working_seconds: 0,
+            timed_status: SessionStatus::Unknown,
+            working_checkpoint: current_timestamp(),
+            start_time_pid: 0,
         }
     }
 
@@ -84,13 +239,86 @@ impl SessionState {
             session_type: SessionType::Hook,
             last_log_event: None,
             last_cpu_event: None,
-            current_status: "resting",
+            last_network_event: None,
+            current_status: SessionStatus::Resting,
             has_terminal: true,
             last_update: current_timestamp(),
             last_active_timestamp: None,
+            pinned: false,
+            ignored: false,
+            archived: false,
+            notification_override: None,
+            project_path: None,
+            tty: None,
+            multiplexer: None,
+            token_usage: TokenUsage::default(),
+            profile: None,
+            kind: "claude".to_string(),
+            container: None,
+            last_hook_timestamp: 0,
+            last_hook_at: 0,
+            hooks_stale: false,
+            needs_attention: false,
+            generation: 0,
+            started_at: current_timestamp(),
+            status_since: current_timestamp(),
+            total_working_seconds: 0,
+            timed_status: SessionStatus::Resting,
+            working_checkpoint: current_timestamp(),
+            start_time_pid: 0,
         }
     }
 
+    /// Accept a hook event fired at `timestamp` unless a newer one was already applied
+    /// (events can be delivered late, e.g. after the pipe was reopened)
+    /// Any hook arriving shows hooks are delivered again
+    pub fn accept_hook_timestamp(&mut self, timestamp: u64) -> bool {
+        self.last_hook_at = current_timestamp();
+        self.hooks_stale = false;
+        if timestamp < self.last_hook_timestamp {
+            return false;
+        }
+        self.last_hook_timestamp = timestamp;
+        self.mark_active(self.last_hook_at);
+        true
+    }
+
+    /// Record activity at `timestamp` (CPU, log writes, hooks, network); never moves backwards
+    pub fn mark_active(&mut self, timestamp: u64) {
+        if self.last_active_timestamp.is_none_or(|last| timestamp > last) {
+            self.last_active_timestamp = Some(timestamp);
+        }
+    }
+
+    /// Move the status clock to `now` (the session manager calls this on every update):
+    /// the working stretch so far is added to total_working_seconds, a status change restarts status_since
+    pub fn track_status_time(&mut self, now: u64) {
+        // Deserialized sessions have no clock yet: resume it from the serialized status
+        if self.working_checkpoint == 0 {
+            self.timed_status = self.current_status;
+            self.working_checkpoint = now;
+        }
+        if self.timed_status == SessionStatus::Working {
+            self.total_working_seconds += now.saturating_sub(self.working_checkpoint);
+        }
+        self.working_checkpoint = now;
+        if self.current_status != self.timed_status {
+            self.timed_status = self.current_status;
+            self.status_since = now;
+        }
+    }
+
+    /// Time spent working, including the ongoing stretch
+    pub fn working_seconds(&self, now: u64) -> u64 {
+        let ongoing = if self.timed_status == SessionStatus::Working { now.saturating_sub(self.working_checkpoint) } else { 0 };
+        self.total_working_seconds + ongoing
+    }
+
+    /// Seconds since the last activity (None until some was seen)
+    pub fn idle_secs(&self, now: u64) -> Option<u64> {
+        self.last_active_timestamp.map(|last| now.saturating_sub(last))
+    }
+
     /// Upgrade Legacy session to Hook session (승격)
     /// Returns true if upgrade was successful, false otherwise
     pub fn upgrade_to_hook(&mut self) -> bool {
@@ -99,7 +327,7 @@ impl SessionState {
             // 검증 2: 임시 세션(pid-XXXXX)이 아닌지 확인
             // 검증 3: 잘못된 세션($SESSION_ID)이 아닌지 확인
             if self.session_id.len() == 36 &&
-               !self.session_id.starts_with("pid-") &&
+               !is_temporary_session_id(&self.session_id) &&
                !self.session_id.starts_with("$") {
 
                 println!("[SessionState] 🔼 Upgrading session {} from Legacy to Hook",
@@ -126,8 +354,86 @@ impl SessionState {
 /// Get current Unix timestamp in seconds
 pub fn current_timestamp() -> u64 {
     use std::time::{SystemTime, UNIX_EPOCH};
+    #[cfg(test)]
+    if let Some(now) = crate::testing::mock_now() {
+        return now;
+    }
     SystemTime::now()
         .duration_since(UNIX_EPOCH)
         .unwrap()
         .as_secs()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_status_wire_format() {
+        let statuses = [
+            SessionStatus::Working,
+            SessionStatus::Resting,
+            SessionStatus::Zombie,
+            SessionStatus::WaitingApproval,
+            SessionStatus::Detached,
+            SessionStatus::Unknown,
+        ];
+        for status in statuses {
+            assert_eq!(serde_json::to_value(status).unwrap(), json!(status.as_str()));
+            assert_eq!(serde_json::from_value::<SessionStatus>(json!(status.as_str())).unwrap(), status);
+        }
+    }
+
+    #[test]
+    fn test_log_event_wire_format() {
+        let fixture = json!({
+            "session_id": "abc",
+            "pid": 42,
+            "timestamp": 1_000,
+            "state": "ActivelyWorking",
+            "has_approval_pending": false,
+            "file_mtime": 990,
+            "transcript": { "last_role": "assistant", "tool_calls": ["Bash"], "input_tokens": 10, "output_tokens": 5, "cwd": null },
+            "profile": null,
+        });
+        let event: LogEvent = serde_json::from_value(fixture.clone()).unwrap();
+        assert!(event.state.is_active());
+        assert_eq!(event.transcript.as_ref().unwrap().tool_calls, vec!["Bash"]);
+        assert_eq!(serde_json::to_value(&event).unwrap(), fixture);
+    }
+
+    #[test]
+    fn test_session_state_round_trip() {
+        let mut session = SessionState::new_hook("round-trip".to_string());
+        session.pid = 42;
+        session.current_status = SessionStatus::Working;
+        session.needs_attention = true;
+        session.status_since = 1_000;
+        session.total_working_seconds = 30;
+        session.last_cpu_event = Some(CpuEvent {
+            pid: 42,
+            timestamp: 1_000,
+            cpu_percent: 12.5,
+            kind: "claude".to_string(),
+            uid: Some(501),
+            container: None,
+        });
+
+        let json = serde_json::to_value(&session).unwrap();
+        assert_eq!(json["current_status"], "working");
+        assert_eq!(json["session_type"], "Hook");
+        // Clock state stays internal
+        assert!(json.get("timed_status").is_none());
+        assert!(json.get("working_checkpoint").is_none());
+
+        let mut restored: SessionState = serde_json::from_value(json.clone()).unwrap();
+        assert_eq!(serde_json::to_value(&restored).unwrap(), json);
+
+        // The working clock resumes without restarting status_since
+        restored.track_status_time(1_100);
+        restored.track_status_time(1_160);
+        assert_eq!(restored.status_since, 1_000);
+        assert_eq!(restored.total_working_seconds, 90);
+    }
+}
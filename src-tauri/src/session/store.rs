@@ -0,0 +1,60 @@
+// Session Store
+//
+// The published session list read by commands (get_miners, kill_all_zombies, ...),
+// the miners publisher, the tray and the cleaner.
+// The session manager on the coordinator thread is its only writer: after every event it
+// swaps in a new immutable snapshot. Readers copy the Arc of the current snapshot under a
+// read lock held for a pointer copy, so nobody clones the map or does slow work under a lock.
+// Threads that want sessions gone (the cleaner) ask the coordinator with an event instead.
+//
+
+use crate::session::SessionState;
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
+
+/// Immutable view of all sessions at one point in time
+pub type Snapshot = Arc<HashMap<String, SessionState>>;
+
+/// Shared handle to the store (managed Tauri state)
+pub type SharedSessions = Arc<SessionStore>;
+
+#[derive(Default)]
+pub struct SessionStore {
+    current: RwLock<Snapshot>,
+}
+
+impl SessionStore {
+    /// The current sessions
+    pub fn snapshot(&self) -> Snapshot {
+        self.current.read().unwrap().clone()
+    }
+
+    /// One session from the current snapshot
+    pub fn get(&self, session_id: &str) -> Option<SessionState> {
+        self.current.read().unwrap().get(session_id).cloned()
+    }
+
+    /// Replace the published sessions (session manager only)
+    pub(crate) fn publish(&self, sessions: HashMap<String, SessionState>) {
+        *self.current.write().unwrap() = Arc::new(sessions);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_snapshots_are_stable() {
+        let store = SessionStore::default();
+        store.publish(HashMap::from([("a".to_string(), SessionState::new_hook("a".to_string()))]));
+
+        let before = store.snapshot();
+        store.publish(HashMap::new());
+
+        // Readers keep the snapshot they took; new readers see the new one
+        assert!(before.contains_key("a"));
+        assert!(store.snapshot().is_empty());
+        assert!(store.get("a").is_none());
+    }
+}
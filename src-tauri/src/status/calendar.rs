@@ -0,0 +1,179 @@
+// Calendar Export
+//
+// export_calendar turns the journal's working intervals into an iCalendar (.ics) document:
+// one event per stretch of work on a project ("Claude worked on repo 14:02–14:39").
+// Intervals of the same project less than MERGE_GAP_SECS apart are merged, so a busy
+// afternoon is one event rather than one per task. UIDs are derived from the project and
+// start time, so importing a later export updates events instead of duplicating them.
+//
+
+use crate::status::journal::{self, TimeRange, WorkInterval};
+use chrono::{TimeZone, Utc};
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+use std::path::Path;
+
+/// Work on the same project with gaps shorter than this is one event
+const MERGE_GAP_SECS: u64 = 10 * 60;
+
+/// A merged stretch of work on one project
+#[derive(Debug, Clone, PartialEq)]
+struct WorkBlock {
+    project_path: Option<String>,
+    start: u64,
+    end: u64,
+    tasks_completed: u64,
+    sessions: Vec<String>,
+}
+
+/// iCalendar document of the work within `range`
+pub fn export(range: TimeRange, now: u64) -> String {
+    let blocks = merge(&journal::work_intervals(range));
+    println!("[Calendar] Exporting {} work blocks", blocks.len());
+    render(&blocks, now)
+}
+
+fn merge(intervals: &[WorkInterval]) -> Vec<WorkBlock> {
+    let mut by_project: BTreeMap<Option<&str>, Vec<&WorkInterval>> = BTreeMap::new();
+    for interval in intervals {
+        by_project.entry(interval.project_path.as_deref()).or_default().push(interval);
+    }
+
+    let mut blocks = Vec::new();
+    for (project, mut intervals) in by_project {
+        intervals.sort_by_key(|i| i.start);
+        let mut current: Option<WorkBlock> = None;
+        for interval in intervals {
+            match current.as_mut() {
+                Some(block) if interval.start <= block.end + MERGE_GAP_SECS => {
+                    block.end = block.end.max(interval.end);
+                }
+                _ => {
+                    blocks.extend(current.take());
+                    current = Some(WorkBlock {
+                        project_path: project.map(str::to_string),
+                        start: interval.start,
+                        end: interval.end,
+                        tasks_completed: 0,
+                        sessions: Vec::new(),
+                    });
+                }
+            }
+            let block = current.as_mut().unwrap();
+            block.tasks_completed += interval.completed as u64;
+            if !block.sessions.contains(&interval.session_id) {
+                block.sessions.push(interval.session_id.clone());
+            }
+        }
+        blocks.extend(current);
+    }
+    blocks.sort_by_key(|b| b.start);
+    blocks
+}
+
+fn render(blocks: &[WorkBlock], now: u64) -> String {
+    let mut lines = vec![
+        "BEGIN:VCALENDAR".to_string(),
+        "VERSION:2.0".to_string(),
+        "PRODID:-//ClaudeMiner//Work Sessions//EN".to_string(),
+        "CALSCALE:GREGORIAN".to_string(),
+        "X-WR-CALNAME:ClaudeMiner".to_string(),
+    ];
+
+    for block in blocks {
+        let project = block.project_path.as_deref();
+        let name = project
+            .map(|p| Path::new(p).file_name().map(|n| n.to_string_lossy().into_owned()).unwrap_or_else(|| p.to_string()))
+            .unwrap_or_else(|| "an unknown project".to_string());
+        let mut description = format!("{} task(s) completed in {} session(s)", block.tasks_completed, block.sessions.len());
+        if let Some(path) = project {
+            description = format!("{}\n{}", path, description);
+        }
+
+        lines.push("BEGIN:VEVENT".to_string());
+        lines.push(format!("UID:{}", uid(project, block.start)));
+        lines.push(format!("DTSTAMP:{}", ics_time(now)));
+        lines.push(format!("DTSTART:{}", ics_time(block.start)));
+        lines.push(format!("DTEND:{}", ics_time(block.end)));
+        lines.push(format!("SUMMARY:{}", escape(&format!("Claude worked on {}", name))));
+        lines.push(format!("DESCRIPTION:{}", escape(&description)));
+        lines.push("TRANSP:TRANSPARENT".to_string());  // Don't show as busy
+        lines.push("END:VEVENT".to_string());
+    }
+    lines.push("END:VCALENDAR".to_string());
+
+    lines.iter().map(|line| fold(line) + "\r\n").collect()
+}
+
+fn uid(project: Option<&str>, start: u64) -> String {
+    let digest = Sha256::digest(project.unwrap_or("").as_bytes());
+    let hash: String = digest[..6].iter().map(|b| format!("{:02x}", b)).collect();
+    format!("{}-{}@claudeminer", start, hash)
+}
+
+/// UTC date-time, e.g. 20240101T140200Z
+fn ics_time(timestamp: u64) -> String {
+    Utc.timestamp_opt(timestamp as i64, 0)
+        .single()
+        .map(|t| t.format("%Y%m%dT%H%M%SZ").to_string())
+        .unwrap_or_default()
+}
+
+/// Escape a TEXT value (RFC 5545 3.3.11)
+fn escape(text: &str) -> String {
+    text.replace('\\', "\\\\")
+        .replace(';', "\\;")
+        .replace(',', "\\,")
+        .replace('\n', "\\n")
+}
+
+/// Fold lines longer than 75 octets (continuation lines start with a space)
+fn fold(line: &str) -> String {
+    let mut folded = String::new();
+    let mut width = 0;
+    for c in line.chars() {
+        if width + c.len_utf8() > 75 {
+            folded.push_str("\r\n ");
+            width = 1;
+        }
+        folded.push(c);
+        width += c.len_utf8();
+    }
+    folded
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn interval(session: &str, project: Option<&str>, start: u64, end: u64) -> WorkInterval {
+        WorkInterval { session_id: session.to_string(), project_path: project.map(str::to_string), start, end, completed: true }
+    }
+
+    #[test]
+    fn test_merge_and_render() {
+        let repo = Some("/work/repo,x");
+        // 2024-01-01 14:02 UTC
+        let start = 1_704_117_720;
+        let intervals = [
+            interval("a", repo, start, start + 600),
+            interval("b", repo, start + 900, start + 2340),   // 5 min gap: same event
+            interval("a", repo, start + 9000, start + 9600),  // Much later: new event
+            interval("c", None, start, start + 60),
+        ];
+        let blocks = merge(&intervals);
+        assert_eq!(blocks.len(), 3);
+        let first = blocks.iter().find(|b| b.project_path.is_some()).unwrap();
+        assert_eq!((first.start, first.end), (start, start + 2340));
+        assert_eq!(first.tasks_completed, 2);
+        assert_eq!(first.sessions, vec!["a", "b"]);
+
+        let ics = render(&blocks, start);
+        assert!(ics.starts_with("BEGIN:VCALENDAR\r\n"));
+        assert_eq!(ics.matches("BEGIN:VEVENT").count(), 3);
+        assert!(ics.contains("DTSTART:20240101T140200Z\r\n"));
+        assert!(ics.contains("DTEND:20240101T144100Z\r\n"));
+        assert!(ics.contains("SUMMARY:Claude worked on repo\\,x\r\n"));
+        assert!(ics.lines().all(|line| line.len() <= 75));
+    }
+}
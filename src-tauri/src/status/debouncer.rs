@@ -7,13 +7,9 @@
 // - Working → Resting: Requires 5 consecutive "resting" checks (5 seconds)
 // - Resting → Working: Requires 3 consecutive "working" checks (3 seconds)
 
+use crate::debug_log;
 use std::collections::HashMap;
 
-#[cfg(debug_assertions)]
-use std::fs::OpenOptions;
-#[cfg(debug_assertions)]
-use std::io::Write;
-
 /// Apply debouncing to status changes
 ///
 /// # Arguments
@@ -30,27 +26,12 @@ pub fn apply_debouncing(
     debouncer: &mut HashMap<u32, (String, u8)>,
     skip_debounce: bool,
 ) -> String {
-    // Debug logging (only in debug builds)
-    #[cfg(debug_assertions)]
-    if let Ok(mut debug_file) = OpenOptions::new()
-        .create(true)
-        .append(true)
-        .open("/tmp/claudeminer_debug.log")
-    {
-        let _ = writeln!(debug_file, "  Debouncing: PID={}, raw={}, skip={}", pid, raw_status, skip_debounce);
-    }
+    debug_log::log("Debouncer", || format!("PID={}, raw={}, skip={}", pid, raw_status, skip_debounce));
 
     // If hook event detected, skip debouncing for immediate response
     if skip_debounce {
         debouncer.insert(pid, (raw_status.to_string(), 0));
-        #[cfg(debug_assertions)]
-        if let Ok(mut debug_file) = OpenOptions::new()
-            .create(true)
-            .append(true)
-            .open("/tmp/claudeminer_debug.log")
-        {
-            let _ = writeln!(debug_file, "  Debouncing: Skipped, returning: {}", raw_status);
-        }
+        debug_log::log("Debouncer", || format!("Skipped, returning: {}", raw_status));
         return raw_status.to_string();
     }
 
@@ -64,14 +45,7 @@ pub fn apply_debouncing(
     if raw_status == current_status {
         // Same status, reset counter
         debouncer.insert(pid, (current_status.clone(), 0));
-        #[cfg(debug_assertions)]
-        if let Ok(mut debug_file) = OpenOptions::new()
-            .create(true)
-            .append(true)
-            .open("/tmp/claudeminer_debug.log")
-        {
-            let _ = writeln!(debug_file, "  Debouncing: Same status, returning: {}", current_status);
-        }
+        debug_log::log("Debouncer", || format!("Same status, returning: {}", current_status));
         return current_status;
     }
 
@@ -87,28 +61,12 @@ pub fn apply_debouncing(
     if new_count >= threshold {
         // Threshold reached, change status
         debouncer.insert(pid, (raw_status.to_string(), 0));
-        #[cfg(debug_assertions)]
-        if let Ok(mut debug_file) = OpenOptions::new()
-            .create(true)
-            .append(true)
-            .open("/tmp/claudeminer_debug.log")
-        {
-            let _ = writeln!(debug_file, "  Debouncing: Threshold reached ({}/{}), changing: {} -> {}",
-                new_count, threshold, current_status, raw_status);
-        }
+        debug_log::log("Debouncer", || format!("Threshold reached ({}/{}), changing: {} -> {}", new_count, threshold, current_status, raw_status));
         raw_status.to_string()
     } else {
         // Keep current status, increment counter
         debouncer.insert(pid, (current_status.clone(), new_count));
-        #[cfg(debug_assertions)]
-        if let Ok(mut debug_file) = OpenOptions::new()
-            .create(true)
-            .append(true)
-            .open("/tmp/claudeminer_debug.log")
-        {
-            let _ = writeln!(debug_file, "  Debouncing: Counter {}/{}, keeping: {}",
-                new_count, threshold, current_status);
-        }
+        debug_log::log("Debouncer", || format!("Counter {}/{}, keeping: {}", new_count, threshold, current_status));
         current_status
     }
 }
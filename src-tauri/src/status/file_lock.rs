@@ -1,9 +1,10 @@
 // File Lock Checker
 //
 // Check if a log file is currently opened by a process using lsof
+// (and which files a process holds open: /proc/<pid>/fd on Linux, lsof elsewhere)
 
 use std::process::Command;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 /// Check if a file is currently opened by any process
 /// Returns true if file is being written to (working)
@@ -65,14 +66,62 @@ pub fn get_pid_with_file_opened(file_path: &Path) -> Option<u32> {
     }
 }
 
+/// Files a process holds open (empty if it can't be inspected)
+#[cfg(target_os = "linux")]
+pub fn open_files(pid: u32) -> Vec<PathBuf> {
+    let Ok(entries) = std::fs::read_dir(format!("/proc/{}/fd", pid)) else {
+        return Vec::new();
+    };
+    entries.flatten()
+        .filter_map(|entry| std::fs::read_link(entry.path()).ok())
+        .filter(|path| path.is_absolute())  // Skip "socket:[123]", "pipe:[456]", ...
+        .collect()
+}
+
+/// Files a process holds open (empty if it can't be inspected)
+#[cfg(all(unix, not(target_os = "linux")))]
+pub fn open_files(pid: u32) -> Vec<PathBuf> {
+    // -a: AND the filters, -Fn: machine-readable name fields only
+    let output = Command::new("lsof")
+        .args(["-a", "-p", &pid.to_string(), "-d", "0-999", "-Fn"])
+        .output();
+    match output {
+        Ok(result) => parse_lsof_names(&String::from_utf8_lossy(&result.stdout)),
+        Err(_) => Vec::new(),
+    }
+}
+
+/// Files a process holds open (not available on this platform)
+#[cfg(not(unix))]
+pub fn open_files(_pid: u32) -> Vec<PathBuf> {
+    Vec::new()
+}
+
+/// Paths from `lsof -Fn` output ("p<pid>", "f<fd>", "n<name>" lines)
+fn parse_lsof_names(output: &str) -> Vec<PathBuf> {
+    output.lines()
+        .filter_map(|line| line.strip_prefix('n'))
+        .filter(|name| name.starts_with('/'))
+        .map(PathBuf::from)
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use std::path::PathBuf;
 
     #[test]
     fn test_file_not_opened() {
         let path = PathBuf::from("/tmp/nonexistent_file.txt");
         assert_eq!(is_file_opened(&path), false);
     }
+
+    #[test]
+    fn test_parse_lsof_names() {
+        let output = "p4242\nf3\nn/Users/me/.claude/debug/0b7c.txt\nf4\nn->0x1234\nf5\nn/dev/ttys003\n";
+        assert_eq!(parse_lsof_names(output), vec![
+            PathBuf::from("/Users/me/.claude/debug/0b7c.txt"),
+            PathBuf::from("/dev/ttys003"),
+        ]);
+    }
 }
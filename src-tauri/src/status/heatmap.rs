@@ -0,0 +1,85 @@
+// Activity Heatmap
+//
+// get_activity_heatmap buckets the journal's working intervals by day of week and hour
+// of day (local time), in minutes, for a GitHub-style heatmap of when Claude works.
+// Intervals are split at hour boundaries, so a task from 13:50 to 14:20 adds 10 minutes
+// to 13:00 and 20 to 14:00.
+//
+
+use crate::status::journal::{self, TimeRange, WorkInterval};
+use chrono::{Datelike, Local, TimeZone, Timelike};
+use serde::Serialize;
+
+/// Payload of get_activity_heatmap
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ActivityHeatmap {
+    pub minutes: Vec<Vec<u64>>,  // [day of week, Monday first][hour of day]
+    pub max_minutes: u64,        // Busiest cell (color scale)
+    pub total_minutes: u64,
+}
+
+/// Working minutes per local weekday and hour within `range`
+pub fn get(range: TimeRange) -> ActivityHeatmap {
+    build(&journal::work_intervals(range), &Local)
+}
+
+fn build<Tz: TimeZone>(intervals: &[WorkInterval], tz: &Tz) -> ActivityHeatmap {
+    let mut seconds = vec![vec![0u64; 24]; 7];
+
+    for interval in intervals {
+        let mut cursor = interval.start;
+        while cursor < interval.end {
+            let Some(local) = tz.timestamp_opt(cursor as i64, 0).earliest() else { break };
+            let into_hour = (local.minute() * 60 + local.second()) as u64;
+            let next = (cursor - into_hour + 3600).min(interval.end);
+            let day = local.weekday().num_days_from_monday() as usize;
+            seconds[day][local.hour() as usize] += next - cursor;
+            cursor = next;
+        }
+    }
+
+    let minutes: Vec<Vec<u64>> = seconds.iter()
+        .map(|day| day.iter().map(|s| (s + 30) / 60).collect())
+        .collect();
+    ActivityHeatmap {
+        max_minutes: minutes.iter().flatten().copied().max().unwrap_or(0),
+        total_minutes: (seconds.iter().flatten().sum::<u64>() + 30) / 60,
+        minutes,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+
+    fn interval(start: u64, end: u64) -> WorkInterval {
+        WorkInterval { session_id: "s".to_string(), project_path: None, start, end, completed: true }
+    }
+
+    #[test]
+    fn test_intervals_split_at_hours() {
+        // 2024-01-01 (a Monday) 13:50 UTC
+        let monday_1350 = 1_704_117_000;
+        let heatmap = build(&[interval(monday_1350, monday_1350 + 30 * 60)], &Utc);
+        assert_eq!(heatmap.minutes[0][13], 10);
+        assert_eq!(heatmap.minutes[0][14], 20);
+        assert_eq!(heatmap.max_minutes, 20);
+        assert_eq!(heatmap.total_minutes, 30);
+
+        // Sunday 23:30 to Monday 00:30
+        let sunday_2330 = monday_1350 - 14 * 3600 - 20 * 60;
+        let heatmap = build(&[interval(sunday_2330, sunday_2330 + 3600)], &Utc);
+        assert_eq!(heatmap.minutes[6][23], 30);
+        assert_eq!(heatmap.minutes[0][0], 30);
+    }
+
+    #[test]
+    fn test_range_clips_intervals() {
+        let range = TimeRange { from: Some(100), to: Some(200) };
+        assert_eq!(range.clip(50, 150), Some((100, 150)));
+        assert_eq!(range.clip(150, 500), Some((150, 200)));
+        assert_eq!(range.clip(200, 300), None);
+        assert_eq!(TimeRange::default().clip(1, 2), Some((1, 2)));
+    }
+}
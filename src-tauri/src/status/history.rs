@@ -0,0 +1,139 @@
+// Status History
+//
+// Last N status transitions per session, with the event that triggered each one
+// and the state machine's reason, so a flip to zombie can be explained after the fact.
+// Served by get_status_history.
+//
+
+use crate::session::MonitorEvent;
+use crate::types::SessionStatus;
+use once_cell::sync::Lazy;
+use serde::Serialize;
+use std::collections::{HashMap, VecDeque};
+use std::sync::Mutex;
+
+const MAX_TRANSITIONS: usize = 50;  // Per session
+const MAX_SESSIONS: usize = 200;    // Histories of ended sessions are kept until this many
+
+/// What triggered a transition
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Evidence {
+    Hook,
+    Cpu,
+    Log,
+    Tty,
+    Multiplexer,
+    Network,
+    Exit,
+    Merge,  // A duplicate session was merged in (merge_sessions)
+    Command,  // A user command (pin, ignore, restore); changes flags, not the status
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct StatusTransition {
+    pub timestamp: u64,
+    pub from: Option<SessionStatus>,  // None when the session was created
+    pub to: SessionStatus,
+    pub evidence: Evidence,
+    pub detail: Option<String>,      // Event details (hook name, CPU %, log state, TTY)
+    pub reason: Option<&'static str>,  // State machine reason, when it decided the status
+}
+
+#[derive(Default)]
+struct History {
+    transitions: HashMap<String, VecDeque<StatusTransition>>,
+    reasons: HashMap<String, &'static str>,  // Latest decision reason per session (not yet recorded)
+}
+
+static HISTORY: Lazy<Mutex<History>> = Lazy::new(|| Mutex::new(History::default()));
+
+/// Evidence and details for an incoming monitor event
+pub fn evidence_for(event: &MonitorEvent) -> (Evidence, Option<String>) {
+    match event {
+        MonitorEvent::Hook(e) => (Evidence::Hook, Some(format!("hook '{}'", e.evt))),
+        MonitorEvent::Approval(e) => (Evidence::Hook, Some(format!("notification '{}'", e.message))),
+        MonitorEvent::Cpu(e) => (Evidence::Cpu, Some(format!("cpu={:.1}%", e.cpu_percent))),
+        MonitorEvent::Log(e) => (Evidence::Log, Some(format!("log state={:?}", e.state))),
+        MonitorEvent::Network(e) => (Evidence::Network, Some(format!("connections={}", e.connections))),
+        MonitorEvent::ProcessExit(e) => (Evidence::Exit, Some(format!("pid {} exited", e.pid))),
+        MonitorEvent::ProcessKilled(e) => (Evidence::Exit, Some(format!("pid {} killed", e.pid))),
+        MonitorEvent::RemoveSessions(removals) => (Evidence::Exit, Some(format!("{} session(s) removed by cleaner", removals.len()))),
+        MonitorEvent::MergeSessions(merge) => (Evidence::Merge, Some(format!("merged {}", merge.source_id))),
+        MonitorEvent::FlagsChanged(_) => (Evidence::Command, Some("flags changed".to_string())),
+    }
+}
+
+/// Remember why the state machine picked a status (attached to the next recorded transition)
+pub fn note_reason(session_id: &str, reason: &'static str) {
+    HISTORY.lock().unwrap().reasons.insert(session_id.to_string(), reason);
+}
+
+/// Record a transition
+pub fn record(session_id: &str, transition: StatusTransition) {
+    let mut history = HISTORY.lock().unwrap();
+    let reason = history.reasons.remove(session_id);
+
+    if !history.transitions.contains_key(session_id) && history.transitions.len() >= MAX_SESSIONS {
+        evict_oldest(&mut history.transitions);
+    }
+
+    let entries = history.transitions.entry(session_id.to_string()).or_default();
+    entries.push_back(StatusTransition { reason: transition.reason.or(reason), ..transition });
+    while entries.len() > MAX_TRANSITIONS {
+        entries.pop_front();
+    }
+}
+
+/// Transitions of a session, oldest first
+pub fn get(session_id: &str) -> Vec<StatusTransition> {
+    HISTORY.lock().unwrap().transitions
+        .get(session_id)
+        .map(|t| t.iter().cloned().collect())
+        .unwrap_or_default()
+}
+
+/// When a session entered its current status (its last transition)
+pub fn last_change(session_id: &str) -> Option<u64> {
+    HISTORY.lock().unwrap().transitions
+        .get(session_id)
+        .and_then(|t| t.back())
+        .map(|t| t.timestamp)
+}
+
+/// Drop the session whose last transition is the oldest
+fn evict_oldest(transitions: &mut HashMap<String, VecDeque<StatusTransition>>) {
+    let oldest = transitions.iter()
+        .min_by_key(|(_, t)| t.back().map(|t| t.timestamp).unwrap_or(0))
+        .map(|(id, _)| id.clone());
+    if let Some(id) = oldest {
+        transitions.remove(&id);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn transition(to: SessionStatus, evidence: Evidence) -> StatusTransition {
+        StatusTransition { timestamp: 1, from: Some(SessionStatus::Resting), to, evidence, detail: None, reason: None }
+    }
+
+    #[test]
+    fn test_record_attaches_reason_and_caps_length() {
+        let id = "history-test-session";
+        note_reason(id, "TTY lost or process stopped");
+        record(id, transition(SessionStatus::Zombie, Evidence::Tty));
+        record(id, transition(SessionStatus::Resting, Evidence::Hook));
+
+        let history = get(id);
+        assert_eq!(history.len(), 2);
+        assert_eq!(history[0].reason, Some("TTY lost or process stopped"));
+        assert_eq!(history[1].reason, None);
+
+        for _ in 0..MAX_TRANSITIONS {
+            record(id, transition(SessionStatus::Working, Evidence::Cpu));
+        }
+        assert_eq!(get(id).len(), MAX_TRANSITIONS);
+    }
+}
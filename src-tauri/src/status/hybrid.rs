@@ -1,196 +1,110 @@
-// Hybrid Status Detection Module
+// Process Probes
 //
-// Combines multiple detection methods for real-time status monitoring:
+// Live process lookups used as inputs to status decisions (see status::machine):
 // 1. TTY check (zombie detection)
-// 2. Log file activity monitoring (real-time changes)
-// 3. CPU sampling (validation)
+// 2. Controlling terminal and parent chain (terminal focus, multiplexer detection)
+// The platform-specific lookups live in status::probe.
 
+use super::probe::ZombieCause;
+use once_cell::sync::Lazy;
 use std::collections::HashMap;
-use std::sync::{Arc, Mutex};
-use std::time::{SystemTime, UNIX_EPOCH};
-use std::path::PathBuf;
-use std::fs;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
 
-/// Log activity tracker
-/// Maps session_id -> last_modified_timestamp
-pub type LogActivityTracker = Arc<Mutex<HashMap<String, u64>>>;
+/// A PID found without a controlling terminal isn't probed again for this long
+const TTY_RETRY: Duration = Duration::from_secs(60);
 
-/// Check if process has a terminal (zombie detection via TTY and STAT)
+/// PIDs without a controlling terminal -> when that was found
+static NO_TTY: Lazy<Mutex<HashMap<u32, Instant>>> = Lazy::new(|| Mutex::new(HashMap::new()));
+
+/// Check if process has a terminal (zombie detection via TTY and process state)
 /// Returns true if process is zombie (no terminal OR stopped process)
 pub fn is_zombie_by_tty(pid: u32) -> bool {
-    #[cfg(target_os = "macos")]
-    {
-        use std::process::Command;
-        let output = Command::new("ps")
-            .args(["-p", &pid.to_string(), "-o", "tty=,stat="])
-            .output();
-
-        if let Ok(output) = output {
-            let line = String::from_utf8_lossy(&output.stdout);
-            let parts: Vec<&str> = line.split_whitespace().collect();
-
-            if parts.len() >= 2 {
-                let tty = parts[0];
-                let stat = parts[1];
-
-                // Zombie conditions:
-                // 1. TTY is "??" or "?" (no controlling terminal)
-                // 2. STAT starts with 'T' (stopped process - unusable session)
-                let is_zombie = tty.is_empty() || tty == "??" || tty == "?" || stat.starts_with('T');
-
-                if is_zombie {
-                    if stat.starts_with('T') {
-                        println!("[is_zombie_by_tty] PID {} is zombie (STAT='{}' - Stopped)", pid, stat);
-                    } else {
-                        println!("[is_zombie_by_tty] PID {} is zombie (TTY='{}')", pid, tty);
-                    }
-                }
-
-                return is_zombie;
-            }
-
-            false
-        } else {
-            false
-        }
-    }
+    zombie_cause(pid).is_some()
+}
 
-    #[cfg(not(target_os = "macos"))]
-    {
-        false
+/// Why the process is a zombie (None if it isn't)
+pub fn zombie_cause(pid: u32) -> Option<ZombieCause> {
+    if crate::demo::is_synthetic(pid) {
+        return crate::demo::is_tty_lost(pid).then_some(ZombieCause::TtyLost);
+    }
+    #[cfg(test)]
+    if let Some(process) = crate::testing::mock_process(pid) {
+        return (process == crate::testing::MockProcess::TtyLost).then_some(ZombieCause::TtyLost);
     }
+
+    super::probe::current().zombie_cause(pid)
 }
 
-/// Monitor log file changes for real-time activity detection
-/// Returns true if log was modified AND contains meaningful work activity
-pub fn is_log_recently_active(
-    session_id: &str,
-    debug_dir: &PathBuf,
-    log_tracker: &LogActivityTracker,
-    threshold_secs: u64,
-) -> bool {
-    let log_file = debug_dir.join(format!("{}.txt", session_id));
-
-    // Get current modification time
-    let current_mtime = if let Ok(metadata) = fs::metadata(&log_file) {
-        if let Ok(modified) = metadata.modified() {
-            modified.duration_since(UNIX_EPOCH).unwrap().as_secs()
-        } else {
-            return false;
-        }
-    } else {
-        return false;
-    };
-
-    // Update tracker
-    let mut tracker = log_tracker.lock().unwrap();
-    let _last_known_mtime = tracker.get(session_id).copied().unwrap_or(0);
-    tracker.insert(session_id.to_string(), current_mtime);
-    drop(tracker);
-
-    // Check if file was recently modified
-    let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs();
-    let age = now.saturating_sub(current_mtime);
-
-    // If file is old, definitely not active
-    if age > threshold_secs {
-        return false;
-    }
+/// Get the controlling terminal of a process (e.g. "ttys003"), None if it has none
+/// Callers retry while a session has no TTY, so a miss is remembered for TTY_RETRY
+pub fn get_tty(pid: u32) -> Option<String> {
+    cached_tty(&mut NO_TTY.lock().unwrap(), pid, Instant::now(), |pid| super::probe::current().tty(pid))
+}
 
-    // File is recent, but check CONTENT to avoid false positives
-    // Background hooks update the log even when idle
-    if let Ok(content) = fs::read_to_string(&log_file) {
-        let last_30_lines: Vec<&str> = content.lines().rev().take(30).collect();
-
-        // Check for meaningful work activities
-        let has_meaningful_work = last_30_lines.iter().any(|line| {
-            line.contains("Stream started") ||
-            line.contains("executePreToolHooks") ||
-            line.contains("Tool execution") ||
-            line.contains("Writing to temp file") ||
-            line.contains("FileHistory: Making snapshot")
-        });
-
-        // Check if ONLY background hooks (idle state)
-        let only_background = last_30_lines.iter().take(10).all(|line| {
-            line.contains("Hooks: checkForNewResponses") ||
-            line.contains("Hooks: getAsyncHookResponseAttachments") ||
-            line.contains("Hooks: Found 0 total hooks") ||
-            line.contains("Skills and commands") ||
-            line.is_empty()
-        });
-
-        // Return true only if:
-        // 1. File modified recently AND
-        // 2. Has meaningful work (not just background hooks)
-        has_meaningful_work && !only_background
+fn cached_tty(no_tty: &mut HashMap<u32, Instant>, pid: u32, now: Instant, probe: impl Fn(u32) -> Option<String>) -> Option<String> {
+    if no_tty.get(&pid).is_some_and(|&checked| now.duration_since(checked) < TTY_RETRY) {
+        return None;
+    }
+    let tty = probe(pid);
+    if tty.is_none() {
+        no_tty.retain(|_, checked| now.duration_since(*checked) < TTY_RETRY);
+        no_tty.insert(pid, now);
     } else {
-        // Can't read file, assume active if recently modified
-        true
+        no_tty.remove(&pid);
     }
+    tty
 }
 
-/// Hybrid status determination
-/// Priority:
-/// 1. TTY check (zombie detection) - HIGHEST
-/// 2. Log activity (real-time) - FAST
-/// 3. CPU sampling (validation) - ACCURATE
-pub fn determine_hybrid_status(
-    pid: u32,
-    session_id: Option<&str>,
-    cpu: f32,
-    debug_dir: &PathBuf,
-    log_tracker: &LogActivityTracker,
-) -> &'static str {
-    // Priority 1: TTY check for zombie
-    if is_zombie_by_tty(pid) {
-        return "zombie";
-    }
+/// Parent chain of a process as (pid, name), nearest first, excluding the process itself and init
+pub fn process_ancestors(pid: u32) -> Vec<(u32, String)> {
+    use sysinfo::{Pid, ProcessRefreshKind, System};
 
-    // Priority 2: No session = orphaned process (zombie)
-    let sid = match session_id {
-        Some(s) => s,
-        None => return "zombie",
-    };
-
-    // Priority 3: Log activity check (real-time response)
-    // Check if log was modified in last 5 seconds
-    if is_log_recently_active(sid, debug_dir, log_tracker, 5) {
-        // Log is actively being written -> likely working
-        // IMPORTANT: Don't require high CPU here!
-        // AI might be waiting for API response (CPU=0 but still working)
-        return "working";
-    }
-
-    // Priority 4: CPU validation (fallback)
-    // If CPU is high, definitely working
-    if cpu > 5.0 {
-        return "working";
-    }
+    let mut sys = System::new();
+    sys.refresh_processes_specifics(ProcessRefreshKind::new());
 
-    // Priority 5: Check if log is stale (zombie indicator)
-    // If log hasn't been modified in 30 minutes, consider zombie
-    let log_file = debug_dir.join(format!("{}.txt", sid));
-    if let Ok(metadata) = fs::metadata(&log_file) {
-        if let Ok(modified) = metadata.modified() {
-            let mtime = modified.duration_since(UNIX_EPOCH).unwrap().as_secs();
-            let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs();
-            let age = now.saturating_sub(mtime);
-
-            if age > 1800 && cpu < 0.5 {
-                // Log stale for 30+ minutes AND low CPU = zombie
-                return "zombie";
-            }
+    let mut ancestors: Vec<(u32, String)> = Vec::new();
+    let mut current = Pid::from_u32(pid);
+    while let Some(parent) = sys.process(current).and_then(|p| p.parent()) {
+        let parent_pid = parent.as_u32();
+        if parent_pid <= 1 || ancestors.iter().any(|(p, _)| *p == parent_pid) {
+            break;
         }
+        let name = sys.process(parent).map(|p| p.name().to_string()).unwrap_or_default();
+        ancestors.push((parent_pid, name));
+        current = parent;
     }
-
-    // Default: resting (waiting for input)
-    "resting"
+    ancestors
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::cell::Cell;
+
+    #[test]
+    fn test_missing_tty_is_cached() {
+        let mut no_tty = HashMap::new();
+        let probes = Cell::new(0);
+        let no_terminal = |_pid: u32| {
+            probes.set(probes.get() + 1);
+            None
+        };
+        let terminal = |_pid: u32| {
+            probes.set(probes.get() + 1);
+            Some("pts/1".to_string())
+        };
+        let start = Instant::now();
+
+        assert_eq!(cached_tty(&mut no_tty, 10, start, no_terminal), None);
+        assert_eq!(cached_tty(&mut no_tty, 10, start + Duration::from_secs(30), terminal), None);
+        assert_eq!(probes.get(), 1);
+
+        // Probed again once the retry interval has passed
+        assert_eq!(cached_tty(&mut no_tty, 10, start + TTY_RETRY, terminal), Some("pts/1".to_string()));
+        assert_eq!(probes.get(), 2);
+        assert!(no_tty.is_empty());
+    }
 
     #[test]
     fn test_zombie_by_tty() {
@@ -198,14 +112,4 @@ mod tests {
         // In real usage: zombie PIDs should return true
         println!("TTY-based zombie detection test (requires manual verification)");
     }
-
-    #[test]
-    fn test_log_activity_tracking() {
-        let tracker = Arc::new(Mutex::new(HashMap::new()));
-        let debug_dir = PathBuf::from("/tmp");
-
-        // First check should return false (no previous data)
-        let result = is_log_recently_active("test-session", &debug_dir, &tracker, 5);
-        println!("First check result: {}", result);
-    }
 }
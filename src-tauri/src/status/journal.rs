@@ -0,0 +1,358 @@
+// Activity Journal
+//
+// Persisted record of what sessions did: every working interval (and whether it ended
+// in a completed task, i.e. working -> resting; a session removed mid-task ends its interval
+// uncompleted) and every zombie incident (as status::zombies opens it), with the session's
+// project path. Entries are appended to <config dir>/activity.jsonl and kept
+// for a year. get_project_stats aggregates them per project.
+//
+
+use crate::hooks::backup::write_atomic;
+use crate::session::SessionState;
+use crate::status::machine;
+use crate::status::zombies::ZombieIncident;
+use crate::types::SessionStatus;
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::PathBuf;
+use std::sync::Mutex;
+
+const JOURNAL_FILE: &str = "activity.jsonl";
+const RETENTION_SECS: u64 = 365 * 24 * 3600;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum JournalEntry {
+    /// Time spent working (start..end); completed when it ended in resting
+    Work { session_id: String, project_path: Option<String>, start: u64, end: u64, completed: bool },
+    Zombie { session_id: String, project_path: Option<String>, timestamp: u64 },
+}
+
+impl JournalEntry {
+    pub fn project_path(&self) -> Option<&str> {
+        match self {
+            JournalEntry::Work { project_path, .. } | JournalEntry::Zombie { project_path, .. } => project_path.as_deref(),
+        }
+    }
+
+    /// When it ended (for retention and time filters)
+    pub fn timestamp(&self) -> u64 {
+        match self {
+            JournalEntry::Work { end, .. } => *end,
+            JournalEntry::Zombie { timestamp, .. } => *timestamp,
+        }
+    }
+}
+
+impl From<&ZombieIncident> for JournalEntry {
+    fn from(incident: &ZombieIncident) -> Self {
+        JournalEntry::Zombie {
+            session_id: incident.session_id.clone(),
+            project_path: incident.project_path.clone(),
+            timestamp: incident.detected_at,
+        }
+    }
+}
+
+/// A journaled working interval
+#[derive(Debug, Clone, PartialEq)]
+pub struct WorkInterval {
+    pub session_id: String,
+    pub project_path: Option<String>,
+    pub start: u64,
+    pub end: u64,
+    pub completed: bool,
+}
+
+/// A time window of the journal in Unix seconds (either end open)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(default)]
+pub struct TimeRange {
+    pub from: Option<u64>,
+    pub to: Option<u64>,
+}
+
+impl TimeRange {
+    /// Clip start..end to the range; None if they don't overlap
+    pub fn clip(&self, start: u64, end: u64) -> Option<(u64, u64)> {
+        let start = start.max(self.from.unwrap_or(0));
+        let end = end.min(self.to.unwrap_or(u64::MAX));
+        (start < end).then_some((start, end))
+    }
+}
+
+/// Aggregated activity of one project (payload of get_project_stats)
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ProjectStats {
+    pub project_path: Option<String>,  // None: sessions whose working directory is unknown
+    pub working_seconds: u64,
+    pub tasks_completed: u64,
+    pub average_task_seconds: Option<u64>,
+    pub zombie_incidents: u64,
+    pub last_active: u64,
+}
+
+#[derive(Default)]
+struct Journal {
+    entries: Vec<JournalEntry>,          // Oldest first
+    working_since: HashMap<String, (u64, Option<String>)>,  // Sessions currently working -> start, project
+}
+
+static JOURNAL: Lazy<Mutex<Journal>> = Lazy::new(|| {
+    let entries = load();
+    Mutex::new(Journal { entries, working_since: HashMap::new() })
+});
+
+fn journal_path() -> PathBuf {
+    crate::persist::data_dir().join(JOURNAL_FILE)
+}
+
+/// Read the journal, dropping (and rewriting without) entries past retention
+fn load() -> Vec<JournalEntry> {
+    let path = journal_path();
+    let Ok(contents) = fs::read_to_string(&path) else {
+        return Vec::new();
+    };
+    let cutoff = crate::session::current_timestamp().saturating_sub(RETENTION_SECS);
+    let total = contents.lines().count();
+    let entries: Vec<JournalEntry> = contents.lines()
+        .filter_map(|line| serde_json::from_str(line).ok())
+        .filter(|e: &JournalEntry| e.timestamp() >= cutoff)
+        .collect();
+
+    if entries.len() < total {
+        println!("[Journal] Dropping {} old or unreadable entries", total - entries.len());
+        let lines: String = entries.iter()
+            .filter_map(|e| serde_json::to_string(e).ok())
+            .map(|line| line + "\n")
+            .collect();
+        if let Err(e) = write_atomic(&path, &lines) {
+            eprintln!("[Journal] Failed to rewrite {:?}: {}", path, e);
+        }
+    }
+    entries
+}
+
+fn append(entry: &JournalEntry) {
+    let path = journal_path();
+    let result = serde_json::to_string(entry)
+        .map_err(|e| e.to_string())
+        .and_then(|line| {
+            OpenOptions::new().create(true).append(true).open(&path)
+                .and_then(|mut file| writeln!(file, "{}", line))
+                .map_err(|e| e.to_string())
+        });
+    if let Err(e) = result {
+        eprintln!("[Journal] Failed to append to {:?}: {}", path, e);
+    }
+}
+
+impl Journal {
+    /// Entries produced by a session's status change
+    fn observe(&mut self, session: &SessionState, from: Option<SessionStatus>, timestamp: u64) -> Vec<JournalEntry> {
+        let mut produced = Vec::new();
+        let to = session.current_status;
+
+        if to == machine::WORKING {
+            self.working_since.entry(session.session_id.clone()).or_insert((timestamp, session.project_path.clone()));
+        } else if let Some((start, _)) = self.working_since.remove(&session.session_id) {
+            produced.push(JournalEntry::Work {
+                session_id: session.session_id.clone(),
+                project_path: session.project_path.clone(),
+                start,
+                end: timestamp.max(start),
+                completed: from == Some(machine::WORKING) && to == machine::RESTING,
+            });
+        }
+
+        self.entries.extend(produced.iter().cloned());
+        produced
+    }
+
+    /// Close the working interval of a session that went away while working
+    fn removed(&mut self, session_id: &str, timestamp: u64) -> Option<JournalEntry> {
+        let (start, project_path) = self.working_since.remove(session_id)?;
+        let entry = JournalEntry::Work {
+            session_id: session_id.to_string(),
+            project_path,
+            start,
+            end: timestamp.max(start),
+            completed: false,
+        };
+        self.entries.push(entry.clone());
+        Some(entry)
+    }
+}
+
+/// Record a status change of a session (call once per transition)
+pub fn observe(session: &SessionState, from: Option<SessionStatus>, timestamp: u64) {
+    let produced = JOURNAL.lock().unwrap().observe(session, from, timestamp);
+    for entry in &produced {
+        append(entry);
+    }
+}
+
+/// Record that a session was removed (its process exited or it was killed)
+pub fn record_removed(session_id: &str, timestamp: u64) {
+    let produced = JOURNAL.lock().unwrap().removed(session_id, timestamp);
+    if let Some(entry) = produced {
+        append(&entry);
+    }
+}
+
+/// Journal a zombie incident as it opens (status::zombies decides when a session became one)
+pub fn record_zombie(incident: &ZombieIncident) {
+    let entry = JournalEntry::from(incident);
+    JOURNAL.lock().unwrap().entries.push(entry.clone());
+    append(&entry);
+}
+
+/// Journal entries that ended at or after `since`, oldest first
+pub fn entries(since: u64) -> Vec<JournalEntry> {
+    JOURNAL.lock().unwrap().entries.iter()
+        .filter(|e| e.timestamp() >= since)
+        .cloned()
+        .collect()
+}
+
+/// Working intervals overlapping `range`, clipped to it, oldest first
+pub fn work_intervals(range: TimeRange) -> Vec<WorkInterval> {
+    JOURNAL.lock().unwrap().entries.iter()
+        .filter_map(|e| match e {
+            JournalEntry::Work { session_id, project_path, start, end, completed } => range.clip(*start, *end)
+                .map(|(start, end)| WorkInterval {
+                    session_id: session_id.clone(),
+                    project_path: project_path.clone(),
+                    start,
+                    end,
+                    completed: *completed,
+                }),
+            JournalEntry::Zombie { .. } => None,
+        })
+        .collect()
+}
+
+/// The most recent working interval that ended in a completed task
+pub fn last_completion() -> Option<WorkInterval> {
+    work_intervals(TimeRange::default()).into_iter()
+        .filter(|w| w.completed)
+        .max_by_key(|w| w.end)
+}
+
+/// Activity per project, most working time first
+pub fn project_stats(entries: &[JournalEntry]) -> Vec<ProjectStats> {
+    let mut projects: HashMap<Option<&str>, ProjectStats> = HashMap::new();
+    let mut task_seconds: HashMap<Option<&str>, u64> = HashMap::new();
+
+    for entry in entries {
+        let project = entry.project_path();
+        let stats = projects.entry(project).or_insert_with(|| ProjectStats {
+            project_path: project.map(str::to_string),
+            working_seconds: 0,
+            tasks_completed: 0,
+            average_task_seconds: None,
+            zombie_incidents: 0,
+            last_active: 0,
+        });
+        stats.last_active = stats.last_active.max(entry.timestamp());
+
+        match entry {
+            JournalEntry::Work { start, end, completed, .. } => {
+                stats.working_seconds += end - start;
+                if *completed {
+                    stats.tasks_completed += 1;
+                    *task_seconds.entry(project).or_default() += end - start;
+                }
+            }
+            JournalEntry::Zombie { .. } => stats.zombie_incidents += 1,
+        }
+    }
+
+    let mut stats: Vec<ProjectStats> = projects.into_iter()
+        .map(|(project, mut stats)| {
+            stats.average_task_seconds = task_seconds.get(&project).copied().unwrap_or(0).checked_div(stats.tasks_completed);
+            stats
+        })
+        .collect();
+    stats.sort_by(|a, b| b.working_seconds.cmp(&a.working_seconds).then_with(|| a.project_path.cmp(&b.project_path)));
+    stats
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn session(id: &str, project: Option<&str>, status: SessionStatus) -> SessionState {
+        let mut session = SessionState::new_legacy(1, id.to_string());
+        session.project_path = project.map(str::to_string);
+        session.current_status = status;
+        session
+    }
+
+    #[test]
+    fn test_observe_and_project_stats() {
+        let mut journal = Journal::default();
+        let app = Some("/work/app");
+
+        // Two completed tasks (60s and 120s), one interrupted by a permission prompt (30s)
+        journal.observe(&session("a", app, machine::WORKING), Some(machine::RESTING), 0);
+        journal.observe(&session("a", app, machine::RESTING), Some(machine::WORKING), 60);
+        journal.observe(&session("a", app, machine::WORKING), Some(machine::RESTING), 100);
+        journal.observe(&session("a", app, machine::RESTING), Some(machine::WORKING), 220);
+        journal.observe(&session("a", app, machine::WORKING), Some(machine::RESTING), 300);
+        journal.observe(&session("a", app, machine::WAITING_APPROVAL), Some(machine::WORKING), 330);
+
+        // Unknown project: works, then turns into a zombie (the incident comes from status::zombies)
+        journal.observe(&session("b", None, machine::WORKING), None, 0);
+        let produced = journal.observe(&session("b", None, machine::ZOMBIE), Some(machine::WORKING), 500);
+        assert_eq!(produced.len(), 1);
+        journal.entries.push(JournalEntry::from(&ZombieIncident {
+            session_id: "b".to_string(),
+            pid: 1,
+            project_path: None,
+            cause: crate::status::probe::ZombieCause::TtyLost,
+            detected_at: 500,
+            resolved_at: None,
+            resolution: None,
+            duration_secs: 0,
+        }));
+
+        let stats = project_stats(&journal.entries);
+        assert_eq!(stats.len(), 2);
+        assert_eq!(stats[0].project_path, None);
+        assert_eq!(stats[0].working_seconds, 500);
+        assert_eq!(stats[0].tasks_completed, 0);
+        assert_eq!(stats[0].average_task_seconds, None);
+        assert_eq!(stats[0].zombie_incidents, 1);
+
+        assert_eq!(stats[1].project_path.as_deref(), app);
+        assert_eq!(stats[1].working_seconds, 210);
+        assert_eq!(stats[1].tasks_completed, 2);
+        assert_eq!(stats[1].average_task_seconds, Some(90));
+        assert_eq!(stats[1].zombie_incidents, 0);
+        assert_eq!(stats[1].last_active, 330);
+    }
+
+    #[test]
+    fn test_removed_while_working() {
+        let mut journal = Journal::default();
+        journal.observe(&session("a", Some("/work/app"), machine::WORKING), None, 100);
+
+        assert_eq!(journal.removed("a", 160), Some(JournalEntry::Work {
+            session_id: "a".to_string(),
+            project_path: Some("/work/app".to_string()),
+            start: 100,
+            end: 160,
+            completed: false,
+        }));
+        assert_eq!(journal.removed("a", 200), None);  // Already closed
+
+        // Not working when removed: nothing to close
+        journal.observe(&session("b", None, machine::RESTING), None, 100);
+        assert_eq!(journal.removed("b", 160), None);
+        assert_eq!(journal.entries.len(), 1);
+    }
+}
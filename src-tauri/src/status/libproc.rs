@@ -0,0 +1,167 @@
+// libproc Process Info (macOS)
+//
+// Native replacements for `ps`: the PID list and each process's owner, state and controlling
+// terminal come from proc_pidinfo(PROC_PIDTBSDINFO), the command line from sysctl(KERN_PROCARGS2).
+// A polling cycle no longer spawns any subprocess on macOS.
+//
+
+/// What ps used to tell us about a process
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProcInfo {
+    pub pid: u32,
+    pub ppid: u32,
+    pub uid: u32,
+    pub stopped: bool,  // Stopped (Ctrl-Z) or defunct
+    pub tty: Option<String>,  // "ttys003"; None without a controlling terminal
+    pub name: String,  // Executable name, truncated to 16 bytes
+}
+
+impl ProcInfo {
+    /// No controlling terminal, or stopped - unusable session
+    pub fn is_tty_zombie(&self) -> bool {
+        self.stopped || self.tty.is_none()
+    }
+}
+
+/// All PIDs on the system
+#[cfg(target_os = "macos")]
+pub fn list_pids() -> Vec<u32> {
+    use libc::c_int;
+
+    // SAFETY: a null buffer asks for the PID count; the second call writes at most `capacity` entries
+    unsafe {
+        let count = libc::proc_listallpids(std::ptr::null_mut(), 0);
+        if count <= 0 {
+            return Vec::new();
+        }
+        let capacity = count as usize + 64;  // Room for processes started in between
+        let mut pids: Vec<c_int> = vec![0; capacity];
+        let filled = libc::proc_listallpids(
+            pids.as_mut_ptr().cast(),
+            (capacity * std::mem::size_of::<c_int>()) as c_int,
+        );
+        if filled <= 0 {
+            return Vec::new();
+        }
+        pids.truncate(filled as usize);
+        pids.into_iter().filter(|&pid| pid > 0).map(|pid| pid as u32).collect()
+    }
+}
+
+/// BSD info of a process, None if it is gone or not ours to inspect
+#[cfg(target_os = "macos")]
+pub fn info(pid: u32) -> Option<ProcInfo> {
+    let mut bsd: libc::proc_bsdinfo = unsafe { std::mem::zeroed() };
+    let size = std::mem::size_of::<libc::proc_bsdinfo>() as libc::c_int;
+    // SAFETY: proc_pidinfo writes at most `size` bytes into `bsd`
+    let written = unsafe {
+        libc::proc_pidinfo(pid as libc::c_int, libc::PROC_PIDTBSDINFO, 0, (&mut bsd as *mut libc::proc_bsdinfo).cast(), size)
+    };
+    if written != size {
+        return None;
+    }
+
+    let comm: Vec<u8> = bsd.pbi_comm.iter().take_while(|&&c| c != 0).map(|&c| c as u8).collect();
+    Some(ProcInfo {
+        pid,
+        ppid: bsd.pbi_ppid,
+        uid: bsd.pbi_uid,
+        stopped: matches!(bsd.pbi_status, libc::SSTOP | libc::SZOMB),
+        tty: tty_name(bsd.e_tdev),
+        name: String::from_utf8_lossy(&comm).into_owned(),
+    })
+}
+
+/// Full command line (argv joined with spaces, like `ps -o command`)
+#[cfg(target_os = "macos")]
+pub fn command_line(pid: u32) -> Option<String> {
+    use std::ptr::null_mut;
+
+    let mut mib = [libc::CTL_KERN, libc::KERN_PROCARGS2, pid as libc::c_int];
+    let mut size: libc::size_t = 0;
+    // SAFETY: the first call reports the buffer size, the second fills a buffer of that size
+    unsafe {
+        if libc::sysctl(mib.as_mut_ptr(), 3, null_mut(), &mut size, null_mut(), 0) != 0 || size == 0 {
+            return None;
+        }
+        let mut buffer = vec![0u8; size];
+        if libc::sysctl(mib.as_mut_ptr(), 3, buffer.as_mut_ptr().cast(), &mut size, null_mut(), 0) != 0 {
+            return None;
+        }
+        buffer.truncate(size);
+        parse_procargs2(&buffer)
+    }
+}
+
+/// Device name of a controlling terminal
+#[cfg(target_os = "macos")]
+fn tty_name(dev: u32) -> Option<String> {
+    const NODEV: u32 = u32::MAX;
+    // devname returns a static buffer shared by all threads
+    static DEVNAME: std::sync::Mutex<()> = std::sync::Mutex::new(());
+
+    if dev == NODEV {
+        return None;
+    }
+    let _guard = DEVNAME.lock().unwrap();
+    // SAFETY: the result is null or a NUL-terminated string, copied before the lock is released
+    unsafe {
+        let name = libc::devname(dev as libc::dev_t, libc::S_IFCHR);
+        if name.is_null() {
+            return None;
+        }
+        Some(std::ffi::CStr::from_ptr(name).to_string_lossy().into_owned())
+    }
+}
+
+/// KERN_PROCARGS2 layout: argc (native i32), exec path, NUL padding, argv[0..argc], environment
+pub fn parse_procargs2(buffer: &[u8]) -> Option<String> {
+    let argc = i32::from_ne_bytes(buffer.get(..4)?.try_into().ok()?);
+    if argc <= 0 {
+        return None;
+    }
+    let rest = &buffer[4..];
+    let path_end = rest.iter().position(|&b| b == 0)?;
+    let args: Vec<String> = rest[path_end..]
+        .split(|&b| b == 0)
+        .skip_while(|arg| arg.is_empty())
+        .take(argc as usize)
+        .map(|arg| String::from_utf8_lossy(arg).into_owned())
+        .collect();
+    if args.is_empty() {
+        return None;
+    }
+    Some(args.join(" "))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn procargs(argc: i32, rest: &[u8]) -> Vec<u8> {
+        let mut buffer = argc.to_ne_bytes().to_vec();
+        buffer.extend_from_slice(rest);
+        buffer
+    }
+
+    #[test]
+    fn test_parse_procargs2() {
+        let buffer = procargs(2, b"/usr/local/bin/node\0\0\0\0node\0/usr/local/bin/claude\0PATH=/bin\0");
+        assert_eq!(parse_procargs2(&buffer).as_deref(), Some("node /usr/local/bin/claude"));
+
+        // Empty arguments after argv[0] are kept; the environment is not
+        let buffer = procargs(3, b"/bin/claude\0claude\0\0--resume\0HOME=/Users/x\0");
+        assert_eq!(parse_procargs2(&buffer).as_deref(), Some("claude  --resume"));
+
+        assert_eq!(parse_procargs2(&procargs(0, b"/bin/sh\0")), None);
+        assert_eq!(parse_procargs2(&[1, 0]), None);
+    }
+
+    #[test]
+    fn test_tty_zombie() {
+        let info = ProcInfo { pid: 1, ppid: 0, uid: 501, stopped: false, tty: Some("ttys003".into()), name: "claude".into() };
+        assert!(!info.is_tty_zombie());
+        assert!(ProcInfo { tty: None, ..info.clone() }.is_tty_zombie());
+        assert!(ProcInfo { stopped: true, ..info }.is_tty_zombie());
+    }
+}
@@ -0,0 +1,298 @@
+// Status State Machine
+//
+// Single source of truth for session status decisions.
+// Pure: takes a snapshot of the inputs plus the current time and does no process,
+// TTY or file lookups itself, so every transition is covered by the table below.
+//
+
+use crate::session::{SessionState, SessionType};
+use crate::types::{SessionStatus, WorkingState};
+
+pub const WORKING: SessionStatus = SessionStatus::Working;
+pub const RESTING: SessionStatus = SessionStatus::Resting;
+pub const ZOMBIE: SessionStatus = SessionStatus::Zombie;
+pub const WAITING_APPROVAL: SessionStatus = SessionStatus::WaitingApproval;
+pub const DETACHED: SessionStatus = SessionStatus::Detached;
+pub const UNKNOWN: SessionStatus = SessionStatus::Unknown;
+
+const CPU_FRESH_SECS: u64 = 10;        // CPU samples older than this are ignored
+const CPU_IDLE_PERCENT: f32 = 0.5;     // Working session with CPU at/below this is idle
+const CPU_WORKING_PERCENT: f32 = 10.0; // CPU above this confirms work
+const IDLE_LOG_DEBOUNCE_SECS: u64 = 45;     // Idle CPU + log quiet this long -> resting
+const IDLE_NO_LOG_DEBOUNCE_SECS: u64 = 60;  // Idle CPU, no log at all -> resting
+const STREAM_STALE_SECS: u64 = 30;     // "Stream started" older than this is over
+const STREAM_FRESH_SECS: u64 = 5;      // Very fresh stream counts without CPU data
+
+/// Latest log signal
+#[derive(Debug, Clone, Copy)]
+pub struct LogInput {
+    pub state: WorkingState,
+    pub file_mtime: u64,
+    pub approval_pending: bool,  // Permission prompt seen in the debug log
+}
+
+/// Latest CPU sample
+#[derive(Debug, Clone, Copy)]
+pub struct CpuInput {
+    pub percent: f32,
+    pub timestamp: u64,
+}
+
+/// Everything a status decision depends on
+#[derive(Debug, Clone, Copy)]
+pub struct StatusInputs {
+    pub session_type: SessionType,
+    pub current: SessionStatus,
+    pub pid: u32,
+    pub has_terminal: bool,
+    pub tty_zombie: bool,              // Result of a live TTY check (caller-provided)
+    pub multiplexer_attached: Option<bool>,  // Some when hosted in tmux/screen
+    pub containerized: bool,           // Docker/WSL: no host TTY
+    pub log: Option<LogInput>,
+    pub cpu: Option<CpuInput>,
+    pub last_update: u64,
+    pub last_hook_at: u64,             // When the last hook event arrived (0 = none)
+    pub hook_stale_secs: u64,          // Hook silence this long: hooks look broken (config "hooks.stale_secs")
+}
+
+impl StatusInputs {
+    /// Snapshot a session; `tty_zombie` is the caller's live TTY check
+    pub fn from_session(session: &SessionState, tty_zombie: bool) -> Self {
+        Self {
+            session_type: session.session_type,
+            current: session.current_status,
+            pid: session.pid,
+            has_terminal: session.has_terminal,
+            tty_zombie,
+            multiplexer_attached: session.multiplexer.as_ref().map(|m| m.attached),
+            containerized: session.container.is_some(),
+            log: session.last_log_event.as_ref().map(|l| LogInput { state: l.state, file_mtime: l.file_mtime, approval_pending: l.has_approval_pending }),
+            cpu: session.last_cpu_event.as_ref().map(|c| CpuInput { percent: c.cpu_percent, timestamp: c.timestamp }),
+            last_update: session.last_update,
+            last_hook_at: session.last_hook_at,
+            hook_stale_secs: crate::config::get().hooks.stale_secs,
+        }
+    }
+
+    /// Hook session that hasn't heard a hook in a while (see hooks_stale)
+    pub fn hooks_stale(&self, now: u64) -> bool {
+        self.session_type == SessionType::Hook && hooks_stale(self.last_hook_at, now, self.hook_stale_secs)
+    }
+
+    /// Zombie checks only apply to processes with a host TTY
+    fn has_host_tty(&self) -> bool {
+        self.multiplexer_attached.is_none() && !self.containerized
+    }
+}
+
+/// A decided status and why (for logging)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Decision {
+    pub status: SessionStatus,
+    pub reason: &'static str,
+}
+
+fn decision(status: SessionStatus, reason: &'static str) -> Decision {
+    Decision { status, reason }
+}
+
+/// Decide the status of a session
+pub fn decide(inputs: &StatusInputs, now: u64) -> Decision {
+    // Zombie first (tmux/screen and containers are reachable without a host TTY)
+    if inputs.has_host_tty() {
+        if !inputs.has_terminal {
+            return decision(ZOMBIE, "no terminal");
+        }
+        if inputs.tty_zombie {
+            return decision(ZOMBIE, "TTY lost or process stopped");
+        }
+    }
+
+    let decided = match inputs.session_type {
+        SessionType::Legacy => decide_legacy(inputs, now),
+        // Hooks stopped arriving: fall back to CPU/log evidence (a pending approval waits for the user, not hooks)
+        SessionType::Hook if inputs.current != WAITING_APPROVAL && inputs.hooks_stale(now) => {
+            decision(decide_legacy(inputs, now).status, "hooks stale, CPU/log fallback")
+        }
+        // Hook sessions keep the status set by hook events
+        SessionType::Hook => decision(inputs.current, "hook-managed"),
+    };
+
+    // Never return "unknown"
+    if decided.status == UNKNOWN {
+        decision(RESTING, "unknown -> resting")
+    } else {
+        decided
+    }
+}
+
+/// Blocked on a permission prompt: the Notification hook said so, or (for sessions without
+/// working hooks) the debug log shows a pending approval
+pub fn needs_attention(inputs: &StatusInputs, now: u64) -> bool {
+    match inputs.current {
+        WAITING_APPROVAL => true,
+        ZOMBIE => false,
+        // Hooks report approvals themselves; the log flag lags behind their answer
+        _ if inputs.session_type == SessionType::Hook && !inputs.hooks_stale(now) => false,
+        _ => inputs.log.is_some_and(|log| log.approval_pending),
+    }
+}
+
+/// No hook event since `last_hook_at` for `stale_secs` (0 = never had one, can't tell)
+pub fn hooks_stale(last_hook_at: u64, now: u64, stale_secs: u64) -> bool {
+    last_hook_at != 0 && now.saturating_sub(last_hook_at) >= stale_secs
+}
+
+/// Legacy sessions: log mtime + log content + CPU
+fn decide_legacy(inputs: &StatusInputs, now: u64) -> Decision {
+    if !inputs.has_terminal {
+        return decision(ZOMBIE, "no terminal");
+    }
+
+    let fresh_cpu = inputs.cpu.filter(|c| now.saturating_sub(c.timestamp) < CPU_FRESH_SECS);
+
+    // Working but idle: debounce generously (thinking / waiting for tools looks idle too)
+    if inputs.current == WORKING && fresh_cpu.is_some_and(|c| c.percent <= CPU_IDLE_PERCENT) {
+        match inputs.log {
+            Some(log) if now.saturating_sub(log.file_mtime) > IDLE_LOG_DEBOUNCE_SECS => {
+                return decision(RESTING, "working but idle CPU and quiet log");
+            }
+            None if now.saturating_sub(inputs.last_update) > IDLE_NO_LOG_DEBOUNCE_SECS => {
+                return decision(RESTING, "working but idle CPU and no log");
+            }
+            _ => {}
+        }
+    }
+
+    if let Some(log) = inputs.log {
+        if log.state.is_active() {
+            let mtime_age = now.saturating_sub(log.file_mtime);
+            if mtime_age >= STREAM_STALE_SECS {
+                return decision(RESTING, "stream stale");
+            }
+
+            if let Some(cpu) = fresh_cpu {
+                if cpu.percent > CPU_WORKING_PERCENT {
+                    return decision(WORKING, "stream + high CPU");
+                }
+                // Low CPU but fresh log: still thinking / waiting on the API
+                return decision(WORKING, "stream fresh, low CPU");
+            }
+
+            if inputs.pid != 0 && mtime_age < STREAM_FRESH_SECS {
+                return decision(WORKING, "very fresh stream, no CPU yet");
+            }
+            return decision(RESTING, "stream without supporting evidence");
+        }
+    }
+
+    // CPU fallback for sessions without stream activity
+    if fresh_cpu.is_some_and(|c| c.percent > CPU_WORKING_PERCENT) {
+        return decision(WORKING, "high CPU");
+    }
+
+    decision(RESTING, "no recent activity")
+}
+
+/// Detached tmux/screen sessions are alive and can be reattached: show them as "detached"
+/// instead of resting/zombie, and switch back once a client attaches
+pub fn multiplexer_status(current: SessionStatus, multiplexer_attached: Option<bool>) -> SessionStatus {
+    match (multiplexer_attached, current) {
+        (Some(false), RESTING | ZOMBIE | UNKNOWN) => DETACHED,
+        (Some(true), DETACHED | ZOMBIE) => RESTING,
+        (None, DETACHED) => RESTING,
+        (_, status) => status,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const HOOK_STALE_SECS: u64 = 3600;
+
+    const NOW: u64 = 1_000_000;
+
+    fn legacy() -> StatusInputs {
+        StatusInputs {
+            session_type: SessionType::Legacy,
+            current: RESTING,
+            pid: 42,
+            has_terminal: true,
+            tty_zombie: false,
+            multiplexer_attached: None,
+            containerized: false,
+            log: None,
+            cpu: None,
+            last_update: NOW,
+            last_hook_at: 0,
+            hook_stale_secs: HOOK_STALE_SECS,
+        }
+    }
+
+    fn log(state: WorkingState, age: u64) -> Option<LogInput> {
+        Some(LogInput { state, file_mtime: NOW - age, approval_pending: false })
+    }
+
+    fn cpu(percent: f32, age: u64) -> Option<CpuInput> {
+        Some(CpuInput { percent, timestamp: NOW - age })
+    }
+
+    #[test]
+    fn test_decision_table() {
+        let cases: Vec<(&str, StatusInputs, SessionStatus)> = vec![
+            ("idle legacy", legacy(), RESTING),
+            ("no terminal", StatusInputs { has_terminal: false, ..legacy() }, ZOMBIE),
+            ("tty lost", StatusInputs { tty_zombie: true, ..legacy() }, ZOMBIE),
+            ("tty lost in tmux", StatusInputs { tty_zombie: true, multiplexer_attached: Some(false), ..legacy() }, RESTING),
+            ("tty lost in container", StatusInputs { tty_zombie: true, containerized: true, ..legacy() }, RESTING),
+            ("hook keeps status", StatusInputs { session_type: SessionType::Hook, current: WORKING, ..legacy() }, WORKING),
+            ("hook unknown", StatusInputs { session_type: SessionType::Hook, current: UNKNOWN, ..legacy() }, RESTING),
+            ("hook zombie", StatusInputs { session_type: SessionType::Hook, tty_zombie: true, ..legacy() }, ZOMBIE),
+            ("hook recent", StatusInputs { session_type: SessionType::Hook, current: WORKING, last_hook_at: NOW - 60, ..legacy() }, WORKING),
+            ("hook stale, idle", StatusInputs { session_type: SessionType::Hook, current: WORKING, last_hook_at: NOW - HOOK_STALE_SECS, ..legacy() }, RESTING),
+            ("hook stale, busy", StatusInputs { session_type: SessionType::Hook, last_hook_at: NOW - HOOK_STALE_SECS, cpu: cpu(40.0, 1), ..legacy() }, WORKING),
+            ("hook stale, approval", StatusInputs { session_type: SessionType::Hook, current: WAITING_APPROVAL, last_hook_at: NOW - HOOK_STALE_SECS, ..legacy() }, WAITING_APPROVAL),
+            ("high CPU", StatusInputs { cpu: cpu(25.0, 1), ..legacy() }, WORKING),
+            ("high CPU stale sample", StatusInputs { cpu: cpu(25.0, 30), ..legacy() }, RESTING),
+            ("stream + high CPU", StatusInputs { log: log(WorkingState::ActivelyWorking, 3), cpu: cpu(15.0, 1), ..legacy() }, WORKING),
+            ("stream + low CPU", StatusInputs { log: log(WorkingState::ActivelyWorking, 10), cpu: cpu(1.0, 1), ..legacy() }, WORKING),
+            ("stream stale", StatusInputs { log: log(WorkingState::ActivelyWorking, 30), cpu: cpu(50.0, 1), ..legacy() }, RESTING),
+            ("very fresh stream, no CPU", StatusInputs { log: log(WorkingState::ActivelyWorking, 2), ..legacy() }, WORKING),
+            ("fresh stream, no CPU, no PID", StatusInputs { pid: 0, log: log(WorkingState::ActivelyWorking, 2), ..legacy() }, RESTING),
+            ("stream 10s old, no CPU", StatusInputs { log: log(WorkingState::ActivelyWorking, 10), ..legacy() }, RESTING),
+            ("idle log", StatusInputs { log: log(WorkingState::Idle, 1), ..legacy() }, RESTING),
+            ("working, idle CPU, quiet log", StatusInputs { current: WORKING, log: log(WorkingState::Idle, 60), cpu: cpu(0.1, 1), ..legacy() }, RESTING),
+            ("working, idle CPU, log within debounce", StatusInputs { current: WORKING, log: log(WorkingState::ActivelyWorking, 20), cpu: cpu(0.1, 1), ..legacy() }, WORKING),
+            ("working, idle CPU, no log, old", StatusInputs { current: WORKING, cpu: cpu(0.1, 1), last_update: NOW - 120, ..legacy() }, RESTING),
+        ];
+
+        for (name, inputs, expected) in cases {
+            assert_eq!(decide(&inputs, NOW).status, expected, "case: {}", name);
+        }
+    }
+
+    #[test]
+    fn test_needs_attention() {
+        let pending = Some(LogInput { state: WorkingState::Unknown, file_mtime: NOW - 90, approval_pending: true });
+
+        assert!(!needs_attention(&legacy(), NOW));
+        assert!(needs_attention(&StatusInputs { log: pending, ..legacy() }, NOW));
+        assert!(!needs_attention(&StatusInputs { current: ZOMBIE, log: pending, ..legacy() }, NOW));
+        assert!(needs_attention(&StatusInputs { session_type: SessionType::Hook, current: WAITING_APPROVAL, ..legacy() }, NOW));
+        // Hook sessions trust the hooks, unless they went silent
+        let hook = StatusInputs { session_type: SessionType::Hook, current: WORKING, log: pending, last_hook_at: NOW - 60, ..legacy() };
+        assert!(!needs_attention(&hook, NOW));
+        assert!(needs_attention(&StatusInputs { last_hook_at: NOW - HOOK_STALE_SECS, ..hook }, NOW));
+    }
+
+    #[test]
+    fn test_multiplexer_status() {
+        assert_eq!(multiplexer_status(RESTING, Some(false)), DETACHED);
+        assert_eq!(multiplexer_status(ZOMBIE, Some(false)), DETACHED);
+        assert_eq!(multiplexer_status(WORKING, Some(false)), WORKING);
+        assert_eq!(multiplexer_status(DETACHED, Some(true)), RESTING);
+        assert_eq!(multiplexer_status(DETACHED, None), RESTING);
+        assert_eq!(multiplexer_status(RESTING, None), RESTING);
+    }
+}
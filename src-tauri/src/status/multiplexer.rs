@@ -0,0 +1,175 @@
+// Terminal Multiplexer Detection
+//
+// Claude sessions running inside tmux/screen survive detach/reattach.
+// Detects tmux/screen ancestry for a PID and reports the session/window name
+// and whether a client is currently attached. Ancestry is looked up once per PID; the
+// attach state is asked of tmux/screen at most every ATTACH_TTL.
+//
+
+use crate::status::hybrid::process_ancestors;
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::process::Command;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// How long a PID's attach state is reused before tmux/screen is asked again
+const ATTACH_TTL: Duration = Duration::from_secs(5);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum MultiplexerKind {
+    Tmux,
+    Screen,
+}
+
+/// tmux/screen session hosting a Claude process
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MultiplexerInfo {
+    pub kind: MultiplexerKind,
+    pub session_name: String,
+    pub window_name: Option<String>,
+    pub attached: bool,
+}
+
+/// Multiplexer hosting a PID: ancestry doesn't change, attach state does
+struct Host {
+    kind: MultiplexerKind,
+    pids: Vec<u32>,  // The PID and its ancestors
+    checked: Option<(Instant, Option<MultiplexerInfo>)>,  // Last attach state lookup
+}
+
+/// PID -> its host, or None if not hosted in a multiplexer
+static HOSTS: Lazy<Mutex<HashMap<u32, Option<Host>>>> = Lazy::new(|| Mutex::new(HashMap::new()));
+
+/// Detect the tmux/screen session hosting `pid`
+pub fn detect_multiplexer(pid: u32) -> Option<MultiplexerInfo> {
+    let mut hosts = HOSTS.lock().unwrap();
+    let host = hosts.entry(pid)
+        .or_insert_with(|| find_multiplexer_ancestor(pid).map(|(kind, pids)| Host { kind, pids, checked: None }))
+        .as_mut()?;
+
+    let now = Instant::now();
+    if let Some((checked, info)) = &host.checked {
+        if now.duration_since(*checked) < ATTACH_TTL {
+            return info.clone();
+        }
+    }
+    let info = lookup(host.kind, &host.pids);
+    host.checked = Some((now, info.clone()));
+    info
+}
+
+/// Ask tmux/screen which of its sessions hosts one of `pids`
+fn lookup(kind: MultiplexerKind, pids: &[u32]) -> Option<MultiplexerInfo> {
+    match kind {
+        MultiplexerKind::Tmux => {
+            let output = run(&["tmux", "list-panes", "-a", "-F",
+                "#{pane_pid}\t#{session_name}\t#{window_name}\t#{session_attached}"])?;
+            parse_tmux_panes(&output, pids)
+        }
+        MultiplexerKind::Screen => {
+            // screen -ls exits non-zero even on success on some systems; only stdout matters
+            let output = run(&["screen", "-ls"])?;
+            parse_screen_ls(&output, pids)
+        }
+    }
+}
+
+/// Forget cached ancestry for a PID (process exited)
+pub fn forget(pid: u32) {
+    HOSTS.lock().unwrap().remove(&pid);
+}
+
+fn find_multiplexer_ancestor(pid: u32) -> Option<(MultiplexerKind, Vec<u32>)> {
+    let ancestors = process_ancestors(pid);
+
+    let kind = ancestors.iter().find_map(|(_, name)| {
+        let name = name.to_lowercase();
+        if name.starts_with("tmux") {
+            Some(MultiplexerKind::Tmux)
+        } else if name.starts_with("screen") {
+            Some(MultiplexerKind::Screen)
+        } else {
+            None
+        }
+    })?;
+
+    println!("[Multiplexer] PID {} is hosted in {:?}", pid, kind);
+
+    let mut pids = vec![pid];
+    pids.extend(ancestors.iter().map(|(p, _)| *p));
+    Some((kind, pids))
+}
+
+fn run(args: &[&str]) -> Option<String> {
+    let output = Command::new(args[0]).args(&args[1..]).output().ok()?;
+    Some(String::from_utf8_lossy(&output.stdout).to_string())
+}
+
+/// Find the pane whose shell is one of `pids`
+/// Format: pane_pid \t session_name \t window_name \t session_attached
+fn parse_tmux_panes(output: &str, pids: &[u32]) -> Option<MultiplexerInfo> {
+    output.lines().find_map(|line| {
+        let parts: Vec<&str> = line.split('\t').collect();
+        if parts.len() < 4 {
+            return None;
+        }
+        let pane_pid: u32 = parts[0].parse().ok()?;
+        if !pids.contains(&pane_pid) {
+            return None;
+        }
+        Some(MultiplexerInfo {
+            kind: MultiplexerKind::Tmux,
+            session_name: parts[1].to_string(),
+            window_name: Some(parts[2].to_string()),
+            attached: parts[3].parse::<u32>().unwrap_or(0) > 0,
+        })
+    })
+}
+
+/// Find the screen session whose server is one of `pids`
+/// Lines look like: "\t12345.name\t(Detached)"
+fn parse_screen_ls(output: &str, pids: &[u32]) -> Option<MultiplexerInfo> {
+    output.lines().find_map(|line| {
+        let mut parts = line.split_whitespace();
+        let (server_pid, name) = parts.next()?.split_once('.')?;
+        let server_pid: u32 = server_pid.parse().ok()?;
+        if !pids.contains(&server_pid) {
+            return None;
+        }
+        Some(MultiplexerInfo {
+            kind: MultiplexerKind::Screen,
+            session_name: name.to_string(),
+            window_name: None,
+            attached: line.contains("(Attached)"),
+        })
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_tmux_panes() {
+        let output = "100\tmain\tzsh\t1\n200\twork\tclaude\t0\n";
+        let info = parse_tmux_panes(output, &[300, 200]).unwrap();
+        assert_eq!(info.session_name, "work");
+        assert_eq!(info.window_name.as_deref(), Some("claude"));
+        assert!(!info.attached);
+
+        assert!(parse_tmux_panes(output, &[999]).is_none());
+    }
+
+    #[test]
+    fn test_parse_screen_ls() {
+        let output = "There are screens on:\n\t4242.dev\t(Detached)\n\t4343.ops\t(Attached)\n2 Sockets in /tmp.\n";
+        let info = parse_screen_ls(output, &[10, 4242]).unwrap();
+        assert_eq!(info.session_name, "dev");
+        assert!(!info.attached);
+
+        assert!(parse_screen_ls(output, &[4343]).unwrap().attached);
+    }
+}
@@ -0,0 +1,247 @@
+// Platform Process Probes
+//
+// The per-platform half of the TTY checks in status::hybrid: is a process a TTY zombie
+// (no controlling terminal, or stopped) and what is its controlling terminal.
+// macOS asks libproc; Linux reads /proc/<pid>/stat (state and tty_nr) without spawning anything.
+// Windows has no TTYs: a console process is orphaned when the shell or terminal that started
+// it has exited, or when it no longer has a console at all.
+// Elsewhere nothing is known: no process is a zombie and none has a TTY.
+//
+
+use serde::{Deserialize, Serialize};
+
+/// Why a process counts as a zombie
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ZombieCause {
+    TtyLost,       // No controlling terminal (or console) any more
+    Stopped,       // Process state is stopped, traced or defunct (STAT T/t/Z)
+    ParentExited,  // The shell or terminal that started it is gone
+}
+
+/// Process state lookups for one platform
+pub trait ProcessProbe: Send + Sync {
+    /// Why the process is a zombie, None if it isn't (or can't be inspected)
+    fn zombie_cause(&self, pid: u32) -> Option<ZombieCause>;
+
+    /// Controlling terminal ("ttys003", "pts/4"), None if it has none
+    fn tty(&self, pid: u32) -> Option<String>;
+}
+
+/// The probe for this platform
+pub fn current() -> &'static dyn ProcessProbe {
+    #[cfg(target_os = "macos")]
+    return &LibprocProbe;
+    #[cfg(target_os = "linux")]
+    return &ProcProbe;
+    #[cfg(target_os = "windows")]
+    return &ConsoleProbe;
+    #[cfg(not(any(target_os = "macos", target_os = "linux", target_os = "windows")))]
+    return &NullProbe;
+}
+
+/// macOS: proc_pidinfo (see status::libproc)
+#[cfg(target_os = "macos")]
+struct LibprocProbe;
+
+#[cfg(target_os = "macos")]
+impl ProcessProbe for LibprocProbe {
+    fn zombie_cause(&self, pid: u32) -> Option<ZombieCause> {
+        let info = super::libproc::info(pid)?;
+        if info.stopped {
+            println!("[is_zombie_by_tty] PID {} is zombie (stopped)", pid);
+            return Some(ZombieCause::Stopped);
+        }
+        if info.tty.is_none() {
+            println!("[is_zombie_by_tty] PID {} is zombie (no controlling terminal)", pid);
+            return Some(ZombieCause::TtyLost);
+        }
+        None
+    }
+
+    fn tty(&self, pid: u32) -> Option<String> {
+        super::libproc::info(pid)?.tty
+    }
+}
+
+/// Linux: /proc/<pid>/stat
+#[cfg(target_os = "linux")]
+struct ProcProbe;
+
+#[cfg(target_os = "linux")]
+impl ProcProbe {
+    fn stat(pid: u32) -> Option<ProcStat> {
+        let contents = std::fs::read_to_string(format!("/proc/{}/stat", pid)).ok()?;
+        parse_proc_stat(&contents)
+    }
+}
+
+#[cfg(target_os = "linux")]
+impl ProcessProbe for ProcProbe {
+    fn zombie_cause(&self, pid: u32) -> Option<ZombieCause> {
+        let stat = Self::stat(pid)?;
+        // Z: exited but not reaped; T/t: stopped (Ctrl-Z) or traced - unusable session
+        if matches!(stat.state, 'Z' | 'T' | 't') {
+            println!("[is_zombie_by_tty] PID {} is zombie (state='{}')", pid, stat.state);
+            return Some(ZombieCause::Stopped);
+        }
+        if stat.tty_nr == 0 {
+            println!("[is_zombie_by_tty] PID {} is zombie (no controlling terminal)", pid);
+            return Some(ZombieCause::TtyLost);
+        }
+        None
+    }
+
+    fn tty(&self, pid: u32) -> Option<String> {
+        Self::stat(pid).and_then(|stat| tty_name(stat.tty_nr))
+    }
+}
+
+/// Windows: parent liveness (sysinfo) and console attachment (kernel32)
+#[cfg(target_os = "windows")]
+struct ConsoleProbe;
+
+#[cfg(target_os = "windows")]
+#[link(name = "kernel32")]
+extern "system" {
+    fn AttachConsole(pid: u32) -> i32;
+    fn FreeConsole() -> i32;
+}
+
+#[cfg(target_os = "windows")]
+impl ConsoleProbe {
+    /// Has the process that started `pid` exited? None if `pid` itself is gone
+    fn parent_exited(pid: u32) -> Option<bool> {
+        use sysinfo::{Pid, ProcessRefreshKind, System};
+
+        let mut sys = System::new();
+        let pid = Pid::from_u32(pid);
+        if !sys.refresh_process_specifics(pid, ProcessRefreshKind::new()) {
+            return None;
+        }
+        let process = sys.process(pid)?;
+        let start_time = process.start_time();
+        let Some(parent) = process.parent() else {
+            return Some(false);  // Started without a parent we know of
+        };
+        sys.refresh_process_specifics(parent, ProcessRefreshKind::new());
+        Some(orphaned(start_time, sys.process(parent).map(|p| p.start_time())))
+    }
+
+    /// Is `pid` still attached to a console? None if we can't tell
+    fn has_console(pid: u32) -> Option<bool> {
+        const ERROR_INVALID_HANDLE: i32 = 6;  // The process has no console
+        // AttachConsole works on the whole app, one probe at a time
+        static CONSOLE: std::sync::Mutex<()> = std::sync::Mutex::new(());
+
+        let _guard = CONSOLE.lock().unwrap();
+        // SAFETY: plain kernel32 calls without pointers; the borrowed console is released right away
+        if unsafe { AttachConsole(pid) } != 0 {
+            unsafe { FreeConsole() };
+            return Some(true);
+        }
+        // ERROR_ACCESS_DENIED: we have a console of our own (debug builds); other errors: pid is gone
+        match std::io::Error::last_os_error().raw_os_error() {
+            Some(ERROR_INVALID_HANDLE) => Some(false),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(target_os = "windows")]
+impl ProcessProbe for ConsoleProbe {
+    fn zombie_cause(&self, pid: u32) -> Option<ZombieCause> {
+        if Self::parent_exited(pid) == Some(true) {
+            println!("[is_zombie_by_tty] PID {} is zombie (parent shell/terminal exited)", pid);
+            return Some(ZombieCause::ParentExited);
+        }
+        if Self::has_console(pid) == Some(false) {
+            println!("[is_zombie_by_tty] PID {} is zombie (console closed)", pid);
+            return Some(ZombieCause::TtyLost);
+        }
+        None
+    }
+
+    /// Consoles have no TTY names
+    fn tty(&self, _pid: u32) -> Option<String> {
+        None
+    }
+}
+
+/// A process whose parent is gone, or whose parent PID now belongs to a process started
+/// after it (the PID was reused), has lost the shell or terminal that started it
+fn orphaned(start_time: u64, parent_start_time: Option<u64>) -> bool {
+    parent_start_time.is_none_or(|parent_start| parent_start > start_time)
+}
+
+/// No probe on this platform
+#[cfg(not(any(target_os = "macos", target_os = "linux", target_os = "windows")))]
+struct NullProbe;
+
+#[cfg(not(any(target_os = "macos", target_os = "linux", target_os = "windows")))]
+impl ProcessProbe for NullProbe {
+    fn zombie_cause(&self, _pid: u32) -> Option<ZombieCause> {
+        None
+    }
+
+    fn tty(&self, _pid: u32) -> Option<String> {
+        None
+    }
+}
+
+/// The fields of /proc/<pid>/stat we need
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct ProcStat {
+    state: char,
+    tty_nr: u32,
+}
+
+/// "pid (comm) state ppid pgrp session tty_nr ..."; comm may contain spaces and parentheses
+fn parse_proc_stat(contents: &str) -> Option<ProcStat> {
+    let rest = &contents[contents.rfind(')')? + 1..];
+    let mut fields = rest.split_whitespace();
+    let state = fields.next()?.chars().next()?;
+    let tty_nr = fields.nth(3)?.parse::<i64>().ok()? as u32;
+    Some(ProcStat { state, tty_nr })
+}
+
+/// Device name of a tty_nr: Unix98 ptys (major 136-143) and virtual consoles / serial ports (major 4)
+fn tty_name(tty_nr: u32) -> Option<String> {
+    let major = (tty_nr >> 8) & 0xfff;
+    let minor = (tty_nr & 0xff) | ((tty_nr >> 12) & 0xfff00);
+    match major {
+        0 => None,
+        136..=143 => Some(format!("pts/{}", (major - 136) * 256 + minor)),
+        4 if minor < 64 => Some(format!("tty{}", minor)),
+        4 => Some(format!("ttyS{}", minor - 64)),
+        _ => Some(format!("tty({}:{})", major, minor)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_proc_stat() {
+        let stat = parse_proc_stat("4242 (claude) S 4200 4242 4200 34819 4242 4194560 1536 0 0 0").unwrap();
+        assert_eq!(stat, ProcStat { state: 'S', tty_nr: 34819 });
+        assert_eq!(tty_name(stat.tty_nr).as_deref(), Some("pts/3"));
+
+        // Spaces and parentheses in the command name; no terminal
+        let stat = parse_proc_stat("77 (node (x) y) T 1 77 77 0 -1 4194560").unwrap();
+        assert_eq!(stat, ProcStat { state: 'T', tty_nr: 0 });
+        assert_eq!(tty_name(stat.tty_nr), None);
+
+        assert_eq!(parse_proc_stat("12 (truncated"), None);
+        assert_eq!(tty_name(4 << 8 | 2).as_deref(), Some("tty2"));
+    }
+
+    #[test]
+    fn test_orphaned() {
+        assert!(!orphaned(1_000, Some(900)));
+        assert!(orphaned(1_000, None));
+        // Parent PID taken over by a newer process
+        assert!(orphaned(1_000, Some(1_500)));
+    }
+}
@@ -0,0 +1,203 @@
+// Zombie Incidents
+//
+// Every time a session turns into a zombie: why (TTY lost, stopped process, parent exited),
+// how long it stayed one and how it ended (recovered, process exited, killed from the app
+// or killed by a rule). Kept in <config dir>/zombies.json (newest 500) so workflows that keep
+// producing zombies stand out; get_zombie_history serves them. This is the one place that
+// decides a session became a zombie: the activity journal's zombie entries come from here.
+// Incidents still open when the app quit are closed as app_restart on the next start (a
+// session that is still a zombie then opens a new one).
+//
+
+use crate::persist::JsonStore;
+use crate::session::SessionState;
+use crate::status::journal;
+use crate::status::probe::ZombieCause;
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::sync::Mutex;
+
+const ZOMBIES_FILE: &str = "zombies.json";
+const MAX_INCIDENTS: usize = 500;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ZombieResolution {
+    Recovered,   // Back to a live status (terminal reattached, process resumed)
+    Exited,      // The process went away (or the session was removed)
+    Killed,      // Killed from the app
+    AutoKilled,  // Killed by an automation rule
+    AppRestart,  // Still open when the app quit; closed when it started again
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ZombieIncident {
+    pub session_id: String,
+    pub pid: u32,
+    pub project_path: Option<String>,
+    pub cause: ZombieCause,
+    pub detected_at: u64,
+    pub resolved_at: Option<u64>,        // None while still a zombie
+    pub resolution: Option<ZombieResolution>,
+    pub duration_secs: u64,              // Time as a zombie (so far, for open incidents)
+}
+
+#[derive(Default)]
+struct Incidents {
+    incidents: Vec<ZombieIncident>,  // Oldest first
+}
+
+impl Incidents {
+    /// Open an incident unless the session already has one; returns the new incident
+    fn detected(&mut self, session: &SessionState, cause: ZombieCause, now: u64) -> Option<&ZombieIncident> {
+        if self.incidents.iter().any(|i| i.session_id == session.session_id && i.resolved_at.is_none()) {
+            return None;
+        }
+        self.incidents.push(ZombieIncident {
+            session_id: session.session_id.clone(),
+            pid: session.pid,
+            project_path: session.project_path.clone(),
+            cause,
+            detected_at: now,
+            resolved_at: None,
+            resolution: None,
+            duration_secs: 0,
+        });
+        if self.incidents.len() > MAX_INCIDENTS {
+            self.incidents.remove(0);
+        }
+        self.incidents.last()
+    }
+
+    /// Close every open incident (left over from the previous run); returns how many
+    fn close_stale(&mut self, now: u64) -> usize {
+        let mut closed = 0;
+        while self.resolve(|_| true, ZombieResolution::AppRestart, now) {
+            closed += 1;
+        }
+        closed
+    }
+
+    /// Close the open incident matching `open`
+    fn resolve(&mut self, open: impl Fn(&ZombieIncident) -> bool, resolution: ZombieResolution, now: u64) -> bool {
+        let Some(incident) = self.incidents.iter_mut().rev().find(|i| i.resolved_at.is_none() && open(i)) else {
+            return false;
+        };
+        incident.resolved_at = Some(now);
+        incident.resolution = Some(resolution);
+        incident.duration_secs = now.saturating_sub(incident.detected_at);
+        true
+    }
+
+    /// Incidents detected at or after `since` (of one project, if given), newest first
+    fn history(&self, since: u64, project: Option<&str>, now: u64) -> Vec<ZombieIncident> {
+        self.incidents.iter()
+            .rev()
+            .filter(|i| i.detected_at >= since)
+            .filter(|i| project.is_none() || i.project_path.as_deref() == project)
+            .map(|i| {
+                let mut incident = i.clone();
+                if incident.resolved_at.is_none() {
+                    incident.duration_secs = now.saturating_sub(incident.detected_at);
+                }
+                incident
+            })
+            .collect()
+    }
+}
+
+static INCIDENTS: Lazy<Mutex<Incidents>> = Lazy::new(|| {
+    let mut incidents = Incidents { incidents: store().load().unwrap_or_default() };
+    let closed = incidents.close_stale(crate::session::current_timestamp());
+    if closed > 0 {
+        println!("[Zombies] Closed {} incident(s) left open by the previous run", closed);
+        save(&incidents);
+    }
+    Mutex::new(incidents)
+});
+
+/// zombies.json (an unreadable one is moved aside rather than overwritten, see persist.rs)
+fn store() -> JsonStore<Vec<ZombieIncident>> {
+    JsonStore::new("Zombies", ZOMBIES_FILE)
+}
+
+fn save(incidents: &Incidents) {
+    store().save_or_log(&incidents.incidents);
+}
+
+/// A session just became a zombie
+pub fn record_detected(session: &SessionState, cause: ZombieCause, now: u64) {
+    let mut incidents = INCIDENTS.lock().unwrap();
+    if let Some(incident) = incidents.detected(session, cause, now) {
+        println!("[Zombies] Session {} is a zombie ({:?})", &session.session_id[..8.min(session.session_id.len())], cause);
+        journal::record_zombie(incident);
+        save(&incidents);
+    }
+}
+
+/// A zombie session recovered or went away
+pub fn record_resolved(session_id: &str, resolution: ZombieResolution, now: u64) {
+    let mut incidents = INCIDENTS.lock().unwrap();
+    if incidents.resolve(|i| i.session_id == session_id, resolution, now) {
+        save(&incidents);
+    }
+}
+
+/// A zombie's process was killed (manually or by a rule)
+pub fn record_killed(pid: u32, resolution: ZombieResolution, now: u64) {
+    let mut incidents = INCIDENTS.lock().unwrap();
+    if pid != 0 && incidents.resolve(|i| i.pid == pid, resolution, now) {
+        save(&incidents);
+    }
+}
+
+/// Payload of get_zombie_history
+pub fn history(since: Option<u64>, project: Option<&str>, now: u64) -> Vec<ZombieIncident> {
+    INCIDENTS.lock().unwrap().history(since.unwrap_or(0), project, now)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn session(id: &str, pid: u32, project: Option<&str>) -> SessionState {
+        let mut session = SessionState::new_legacy(pid, id.to_string());
+        session.project_path = project.map(str::to_string);
+        session
+    }
+
+    #[test]
+    fn test_incident_lifecycle() {
+        let mut incidents = Incidents::default();
+        let a = session("a", 10, Some("/work/app"));
+        let b = session("b", 20, None);
+
+        assert!(incidents.detected(&a, ZombieCause::TtyLost, 100).is_some());
+        assert!(incidents.detected(&a, ZombieCause::Stopped, 110).is_none());  // Still the same incident
+        assert!(incidents.detected(&b, ZombieCause::ParentExited, 200).is_some());
+
+        // Killed by PID, then the session removal finds nothing left to close
+        assert!(incidents.resolve(|i| i.pid == 10, ZombieResolution::Killed, 160));
+        assert!(!incidents.resolve(|i| i.session_id == "a", ZombieResolution::Exited, 170));
+
+        // A new incident for the same session
+        assert!(incidents.detected(&a, ZombieCause::Stopped, 300).is_some());
+        assert!(incidents.resolve(|i| i.session_id == "a", ZombieResolution::Recovered, 330));
+
+        let history = incidents.history(0, None, 400);
+        assert_eq!(history.len(), 3);
+        assert_eq!((history[0].cause, history[0].resolution, history[0].duration_secs),
+            (ZombieCause::Stopped, Some(ZombieResolution::Recovered), 30));
+        assert_eq!((history[1].session_id.as_str(), history[1].resolved_at, history[1].duration_secs), ("b", None, 200));
+        assert_eq!((history[2].resolution, history[2].duration_secs), (Some(ZombieResolution::Killed), 60));
+
+        let app = incidents.history(150, Some("/work/app"), 400);
+        assert_eq!(app.len(), 1);
+        assert_eq!(app[0].detected_at, 300);
+
+        // Restart: b's incident is closed, and b can open a new one
+        assert_eq!(incidents.close_stale(500), 1);
+        assert_eq!(incidents.history(0, None, 600)[1].resolution, Some(ZombieResolution::AppRestart));
+        assert!(incidents.detected(&b, ZombieCause::TtyLost, 510).is_some());
+    }
+}
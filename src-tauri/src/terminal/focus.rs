@@ -0,0 +1,152 @@
+// Terminal Focus
+//
+// Brings the terminal window/tab hosting a session to the front, using the TTY
+// recorded for its PID: AppleScript for Terminal/iTerm on macOS, tmux panes anywhere,
+// and wmctrl (window owned by an ancestor process) on other desktops.
+//
+
+use std::process::Command;
+
+/// Focus the terminal hosting `pid` (controlling terminal `tty`, e.g. "ttys003")
+pub fn focus_terminal(pid: u32, tty: &str) -> Result<(), String> {
+    let tty_path = if tty.starts_with("/dev/") { tty.to_string() } else { format!("/dev/{}", tty) };
+
+    println!("[TerminalFocus] Focusing terminal for PID {} ({})", pid, tty_path);
+
+    // tmux pane first: the outer terminal shows whichever window is selected
+    if select_tmux_pane(&tty_path) {
+        println!("[TerminalFocus] Selected tmux pane for {}", tty_path);
+    }
+
+    if focus_terminal_window(pid, &tty_path)? {
+        Ok(())
+    } else {
+        Err(format!("No terminal window found for {}", tty_path))
+    }
+}
+
+/// Select the tmux window/pane whose pane_tty matches
+fn select_tmux_pane(tty_path: &str) -> bool {
+    let output = match Command::new("tmux")
+        .args(["list-panes", "-a", "-F", "#{pane_tty} #{session_name}:#{window_index}.#{pane_index}"])
+        .output()
+    {
+        Ok(o) if o.status.success() => o,
+        _ => return false,
+    };
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let target = match stdout.lines()
+        .filter_map(|line| line.split_once(' '))
+        .find(|(pane_tty, _)| *pane_tty == tty_path)
+    {
+        Some((_, target)) => target.to_string(),
+        None => return false,
+    };
+
+    let window = target.split('.').next().unwrap_or(&target).to_string();
+    let selected_window = Command::new("tmux").args(["select-window", "-t", &window]).status();
+    let selected_pane = Command::new("tmux").args(["select-pane", "-t", &target]).status();
+
+    matches!((selected_window, selected_pane), (Ok(w), Ok(p)) if w.success() && p.success())
+}
+
+#[cfg(target_os = "macos")]
+fn focus_terminal_window(_pid: u32, tty_path: &str) -> Result<bool, String> {
+    // Terminal.app
+    let terminal_script = format!(
+        r#"if application "Terminal" is running then
+    tell application "Terminal"
+        repeat with w in windows
+            repeat with t in tabs of w
+                if tty of t is "{tty}" then
+                    set selected of t to true
+                    set index of w to 1
+                    activate
+                    return "found"
+                end if
+            end repeat
+        end repeat
+    end tell
+end if
+return "not found""#,
+        tty = tty_path
+    );
+
+    if run_osascript(&terminal_script)? {
+        return Ok(true);
+    }
+
+    // iTerm2
+    let iterm_script = format!(
+        r#"if application "iTerm" is running then
+    tell application "iTerm"
+        repeat with w in windows
+            repeat with t in tabs of w
+                repeat with s in sessions of t
+                    if tty of s is "{tty}" then
+                        select w
+                        tell t to select
+                        tell s to select
+                        activate
+                        return "found"
+                    end if
+                end repeat
+            end repeat
+        end repeat
+    end tell
+end if
+return "not found""#,
+        tty = tty_path
+    );
+
+    run_osascript(&iterm_script)
+}
+
+/// Run an AppleScript that returns "found" on success
+#[cfg(target_os = "macos")]
+fn run_osascript(script: &str) -> Result<bool, String> {
+    let output = Command::new("osascript")
+        .arg("-e")
+        .arg(script)
+        .output()
+        .map_err(|e| format!("Failed to run osascript: {}", e))?;
+
+    if !output.status.success() {
+        eprintln!("[TerminalFocus] osascript failed: {}", String::from_utf8_lossy(&output.stderr).trim());
+        return Ok(false);
+    }
+
+    Ok(String::from_utf8_lossy(&output.stdout).trim() == "found")
+}
+
+#[cfg(not(target_os = "macos"))]
+fn focus_terminal_window(pid: u32, _tty_path: &str) -> Result<bool, String> {
+    // wmctrl -lp: <window id> <desktop> <pid> <host> <title>
+    let output = Command::new("wmctrl")
+        .arg("-lp")
+        .output()
+        .map_err(|e| format!("Failed to run wmctrl (is it installed?): {}", e))?;
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let windows: Vec<(String, u32)> = stdout.lines()
+        .filter_map(|line| {
+            let parts: Vec<&str> = line.split_whitespace().collect();
+            let window_pid = parts.get(2)?.parse().ok()?;
+            Some((parts[0].to_string(), window_pid))
+        })
+        .collect();
+
+    // The terminal emulator owning the window is an ancestor of the Claude process
+    for (ancestor, _) in crate::status::hybrid::process_ancestors(pid) {
+        if let Some((window_id, _)) = windows.iter().find(|(_, window_pid)| *window_pid == ancestor) {
+            let status = Command::new("wmctrl")
+                .args(["-ia", window_id])
+                .status()
+                .map_err(|e| format!("Failed to run wmctrl: {}", e))?;
+            return Ok(status.success());
+        }
+    }
+
+    Ok(false)
+}
@@ -0,0 +1,124 @@
+// Frontmost Terminal
+//
+// Is the user already looking at a session's terminal? The frontmost application
+// (NSWorkspace on macOS, the active X11 window via xdotool elsewhere) must be an
+// ancestor of the Claude process; for Terminal and iTerm2 the selected tab's TTY must
+// also be the session's. Used to skip task-completion notifications that would be noise.
+// Both lookups can spawn a process, so callers on the coordinator thread hand them to the
+// frontmost-check worker (check_in_background).
+//
+
+use crate::status::hybrid::process_ancestors;
+
+type Check = Box<dyn FnOnce() + Send>;
+
+/// Checks waiting for the worker (started on first use)
+#[cfg(not(test))]
+static WORKER: once_cell::sync::Lazy<std::sync::Mutex<std::sync::mpsc::Sender<Check>>> = once_cell::sync::Lazy::new(|| {
+    let (sender, receiver) = std::sync::mpsc::channel::<Check>();
+    crate::crash::spawn_named("frontmost-check", move || {
+        for check in receiver {
+            check();
+        }
+    });
+    std::sync::Mutex::new(sender)
+});
+
+/// Call `then` with is_session_terminal_frontmost(pid, tty), on the worker thread
+pub fn check_in_background(pid: u32, tty: Option<String>, then: impl FnOnce(bool) + Send + 'static) {
+    run(Box::new(move || then(is_session_terminal_frontmost(pid, tty.as_deref()))));
+}
+
+#[cfg(not(test))]
+fn run(check: Check) {
+    let _ = WORKER.lock().unwrap().send(check);
+}
+
+/// Tests check inline, so what they assert on has happened when the caller returns
+#[cfg(test)]
+fn run(check: Check) {
+    check()
+}
+
+/// Does the terminal hosting `pid` (controlling terminal `tty`) have focus?
+pub fn is_session_terminal_frontmost(pid: u32, tty: Option<&str>) -> bool {
+    let Some(front_pid) = frontmost_app_pid() else {
+        return false;
+    };
+    let Some((_, app_name)) = process_ancestors(pid).into_iter().find(|(ancestor, _)| *ancestor == front_pid) else {
+        return false;
+    };
+
+    // The terminal app is in front; when it can tell which tab is selected, that must be the session's
+    match (tty, selected_tty(&app_name)) {
+        (Some(tty), Some(selected)) => same_tty(tty, &selected),
+        _ => true,
+    }
+}
+
+/// "ttys003" and "/dev/ttys003" are the same terminal
+fn same_tty(a: &str, b: &str) -> bool {
+    a.trim().trim_start_matches("/dev/") == b.trim().trim_start_matches("/dev/")
+}
+
+#[cfg(target_os = "macos")]
+fn frontmost_app_pid() -> Option<u32> {
+    use cocoa::base::{id, nil};
+    use objc::{class, msg_send, sel, sel_impl};
+
+    // SAFETY: NSWorkspace.sharedWorkspace and frontmostApplication return autoreleased
+    // objects (or nil, checked) that we only read the process identifier from
+    unsafe {
+        let workspace: id = msg_send![class!(NSWorkspace), sharedWorkspace];
+        let app: id = msg_send![workspace, frontmostApplication];
+        if app == nil {
+            return None;
+        }
+        let pid: i32 = msg_send![app, processIdentifier];
+        u32::try_from(pid).ok()
+    }
+}
+
+#[cfg(target_os = "macos")]
+fn selected_tty(app_name: &str) -> Option<String> {
+    let script = match app_name {
+        "Terminal" => r#"tell application "Terminal" to get tty of selected tab of front window"#,
+        name if name.starts_with("iTerm") => r#"tell application "iTerm" to get tty of current session of current window"#,
+        _ => return None,
+    };
+    let output = std::process::Command::new("osascript").arg("-e").arg(script).output().ok()?;
+    output.status.success().then(|| String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+#[cfg(all(unix, not(target_os = "macos")))]
+fn frontmost_app_pid() -> Option<u32> {
+    let output = std::process::Command::new("xdotool").args(["getactivewindow", "getwindowpid"]).output().ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    String::from_utf8_lossy(&output.stdout).trim().parse().ok()
+}
+
+/// Terminal tabs can't be inspected here
+#[cfg(not(target_os = "macos"))]
+fn selected_tty(_app_name: &str) -> Option<String> {
+    None
+}
+
+/// No foreground-window lookup without extra dependencies: never suppress
+#[cfg(not(unix))]
+fn frontmost_app_pid() -> Option<u32> {
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_same_tty() {
+        assert!(same_tty("ttys003", "/dev/ttys003"));
+        assert!(same_tty("/dev/pts/4\n", "pts/4"));
+        assert!(!same_tty("ttys003", "/dev/ttys004"));
+    }
+}
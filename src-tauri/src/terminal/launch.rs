@@ -0,0 +1,130 @@
+// Session Launcher
+//
+// Opens the configured terminal (Terminal / iTerm / tmux) running `claude` in a project directory.
+// The session ID is assigned up front (--session-id) so the SessionStart hook can be linked
+// back to the launch and the session gets its project path.
+//
+
+use crate::config::{self, TerminalApp};
+use once_cell::sync::Lazy;
+use std::collections::HashMap;
+use std::path::Path;
+use std::process::Command;
+use std::sync::Mutex;
+
+/// Sessions launched from the app that haven't reported a SessionStart hook yet
+/// session_id -> project_path
+static LAUNCHED: Lazy<Mutex<HashMap<String, String>>> = Lazy::new(|| Mutex::new(HashMap::new()));
+
+/// Launch `claude` in `project_path` and return the pre-assigned session ID
+pub fn start_miner(project_path: &str, model: Option<&str>) -> Result<String, String> {
+    let path = Path::new(project_path);
+    if !path.is_dir() {
+        return Err(format!("Not a directory: {}", project_path));
+    }
+
+    if let Some(model) = model {
+        if model.is_empty() || !model.chars().all(|c| c.is_ascii_alphanumeric() || "-._[]".contains(c)) {
+            return Err(format!("Invalid model name: {}", model));
+        }
+    }
+
+    let session_id = uuid::Uuid::new_v4().to_string();
+    let command = build_claude_command(project_path, &session_id, model);
+    let terminal = config::get().launcher.terminal;
+
+    println!("[Launcher] 🚀 Starting Claude in {} via {:?} (session {})",
+        project_path, terminal, &session_id[..8]);
+
+    // Register before launching so a fast SessionStart hook can't race us
+    LAUNCHED.lock().unwrap().insert(session_id.clone(), project_path.to_string());
+
+    if let Err(e) = open_in_terminal(terminal, project_path, &command, &session_id) {
+        LAUNCHED.lock().unwrap().remove(&session_id);
+        eprintln!("[Launcher] ⚠️ Failed to launch: {}", e);
+        return Err(e);
+    }
+
+    Ok(session_id)
+}
+
+/// Claim the project path of a session launched from the app (called on SessionStart)
+pub fn take_launched_project(session_id: &str) -> Option<String> {
+    LAUNCHED.lock().unwrap().remove(session_id)
+}
+
+/// Shell command run inside the terminal
+fn build_claude_command(project_path: &str, session_id: &str, model: Option<&str>) -> String {
+    let mut command = format!("cd {} && claude --session-id {}", shell_quote(project_path), session_id);
+    if let Some(model) = model {
+        command.push_str(&format!(" --model {}", shell_quote(model)));
+    }
+    command
+}
+
+fn open_in_terminal(terminal: TerminalApp, project_path: &str, command: &str, session_id: &str) -> Result<(), String> {
+    match terminal {
+        TerminalApp::Terminal => run_osascript(&format!(
+            "tell application \"Terminal\"\n\tactivate\n\tdo script \"{}\"\nend tell",
+            applescript_escape(command)
+        )),
+        TerminalApp::Iterm => run_osascript(&format!(
+            "tell application \"iTerm\"\n\tactivate\n\tset newWindow to (create window with default profile)\n\ttell current session of newWindow to write text \"{}\"\nend tell",
+            applescript_escape(command)
+        )),
+        TerminalApp::Tmux => {
+            // Detached session; attach with `tmux attach -t claude-<id>`
+            let output = Command::new("tmux")
+                .args(["new-session", "-d", "-s", &format!("claude-{}", &session_id[..8]), "-c", project_path, command])
+                .output()
+                .map_err(|e| format!("Failed to run tmux: {}", e))?;
+            check_output(output)
+        }
+    }
+}
+
+#[cfg(target_os = "macos")]
+fn run_osascript(script: &str) -> Result<(), String> {
+    let output = Command::new("osascript")
+        .arg("-e")
+        .arg(script)
+        .output()
+        .map_err(|e| format!("Failed to run osascript: {}", e))?;
+    check_output(output)
+}
+
+#[cfg(not(target_os = "macos"))]
+fn run_osascript(_script: &str) -> Result<(), String> {
+    Err("Terminal and iTerm are only supported on macOS (use tmux)".to_string())
+}
+
+fn check_output(output: std::process::Output) -> Result<(), String> {
+    if output.status.success() {
+        Ok(())
+    } else {
+        Err(String::from_utf8_lossy(&output.stderr).trim().to_string())
+    }
+}
+
+/// Quote a string for POSIX sh
+fn shell_quote(s: &str) -> String {
+    format!("'{}'", s.replace('\'', "'\\''"))
+}
+
+/// Escape a string for use inside an AppleScript string literal
+fn applescript_escape(s: &str) -> String {
+    s.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_build_claude_command() {
+        let command = build_claude_command("/Users/me/it's here", "abc", Some("opus"));
+        assert_eq!(command, "cd '/Users/me/it'\\''s here' && claude --session-id abc --model 'opus'");
+
+        assert_eq!(applescript_escape(r#"say "hi" \n"#), r#"say \"hi\" \\n"#);
+    }
+}
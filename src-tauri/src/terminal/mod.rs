@@ -0,0 +1,11 @@
+// Terminal Module - Terminal integration
+//
+// Launches new Claude sessions in the user's terminal, focuses existing ones
+// and tells whether one is already in front
+
+pub mod focus;
+pub mod frontmost;
+pub mod launch;
+
+pub use focus::focus_terminal;
+pub use launch::{start_miner, take_launched_project};
@@ -0,0 +1,39 @@
+// Test Doubles
+//
+// Per-thread stand-ins for the wall clock and the live process probes (is the PID alive,
+// has it lost its TTY), so tests can drive the event pipeline through time with fake
+// processes. Nothing is mocked until a test sets it; other tests see the real thing.
+//
+
+use std::cell::{Cell, RefCell};
+use std::collections::HashMap;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MockProcess {
+    Running,
+    TtyLost,  // Alive without a controlling terminal
+    Exited,
+}
+
+thread_local! {
+    static NOW: Cell<Option<u64>> = const { Cell::new(None) };
+    static PROCESSES: RefCell<HashMap<u32, MockProcess>> = RefCell::new(HashMap::new());
+}
+
+/// Mocked time for current_timestamp, if set on this thread
+pub fn mock_now() -> Option<u64> {
+    NOW.with(Cell::get)
+}
+
+pub fn set_now(now: u64) {
+    NOW.with(|n| n.set(Some(now)));
+}
+
+/// Mocked state of a PID, if this thread registered it
+pub fn mock_process(pid: u32) -> Option<MockProcess> {
+    PROCESSES.with(|p| p.borrow().get(&pid).copied())
+}
+
+pub fn set_process(pid: u32, process: MockProcess) {
+    PROCESSES.with(|p| p.borrow_mut().insert(pid, process));
+}
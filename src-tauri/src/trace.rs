@@ -0,0 +1,315 @@
+// Trace Recorder
+//
+// Optional recording of every MonitorEvent the coordinator handles, every status
+// transition and slow probes (process scans, TTY checks) as Chrome trace-event JSON, to open in
+// Perfetto (ui.perfetto.dev) or chrome://tracing. Sessions get their own track with their
+// status as spans; each event carries how late it arrived (late hooks, slow ps calls).
+// Off by default and nearly free when off; toggled with set_trace_recording, which writes
+// trace-<timestamp>.json next to config.json when recording stops.
+//
+
+use crate::session::MonitorEvent;
+use crate::status::history::evidence_for;
+use crate::types::SessionStatus;
+use once_cell::sync::Lazy;
+use serde::Serialize;
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+use std::time::{Instant, SystemTime, UNIX_EPOCH};
+
+const MAX_EVENTS: usize = 500_000;  // ~100MB of JSON; later events are counted, not kept
+const PROCESS_ID: u32 = 1;          // Everything is one process in the trace
+
+static RECORDING: AtomicBool = AtomicBool::new(false);
+static TRACE: Lazy<Mutex<Trace>> = Lazy::new(|| Mutex::new(Trace::default()));
+
+/// Recorder state (set_trace_recording)
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct TraceStatus {
+    pub recording: bool,
+    pub events: usize,
+    pub dropped: u64,
+    pub path: Option<String>,  // Set when a recording was just written
+}
+
+/// One Chrome trace event (https://docs.google.com/document/d/1CvAClvFfyA5R-PhYUmn5OOQtYMH4h6I0nSsKchNAySU)
+#[derive(Debug, Clone, Serialize)]
+struct TraceEvent {
+    name: String,
+    cat: &'static str,
+    ph: &'static str,  // "X" complete, "i" instant, "M" metadata
+    ts: u64,           // Microseconds
+    #[serde(skip_serializing_if = "Option::is_none")]
+    dur: Option<u64>,
+    pid: u32,
+    tid: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    s: Option<&'static str>,  // Instant event scope
+    #[serde(skip_serializing_if = "Value::is_null")]
+    args: Value,
+}
+
+#[derive(Default)]
+struct Trace {
+    events: Vec<TraceEvent>,
+    tracks: HashMap<String, u64>,  // Track name -> tid
+    statuses: HashMap<String, (SessionStatus, u64)>,  // Session -> open status span (status, start)
+    dropped: u64,
+}
+
+impl Trace {
+    fn track(&mut self, name: &str) -> u64 {
+        let next = self.tracks.len() as u64 + 1;
+        *self.tracks.entry(name.to_string()).or_insert(next)
+    }
+
+    fn push(&mut self, event: TraceEvent) {
+        if self.events.len() >= MAX_EVENTS {
+            self.dropped += 1;
+        } else {
+            self.events.push(event);
+        }
+    }
+
+    fn span(&mut self, track: &str, cat: &'static str, name: String, ts: u64, dur: u64, args: Value) {
+        let tid = self.track(track);
+        self.push(TraceEvent { name, cat, ph: "X", ts, dur: Some(dur), pid: PROCESS_ID, tid, s: None, args });
+    }
+
+    fn instant(&mut self, track: &str, cat: &'static str, name: String, ts: u64, args: Value) {
+        let tid = self.track(track);
+        self.push(TraceEvent { name, cat, ph: "i", ts, dur: None, pid: PROCESS_ID, tid, s: Some("t"), args });
+    }
+
+    /// Close the session's current status span at `ts`
+    fn close_status(&mut self, session_id: &str, ts: u64) {
+        if let Some((status, start)) = self.statuses.remove(session_id) {
+            self.span(&session_track(session_id), "status", status.to_string(), start, ts.saturating_sub(start), Value::Null);
+        }
+    }
+
+    fn transition(&mut self, session_id: &str, from: Option<SessionStatus>, to: SessionStatus, ts: u64) {
+        self.close_status(session_id, ts);
+        self.statuses.insert(session_id.to_string(), (to, ts));
+        let name = format!("{} -> {}", from.map_or("new", SessionStatus::as_str), to);
+        self.instant(&session_track(session_id), "transition", name, ts, Value::Null);
+    }
+
+    /// Chrome trace JSON: track names, the events, and status spans still open at `now`
+    fn export(&mut self, now: u64) -> Value {
+        let open: Vec<String> = self.statuses.keys().cloned().collect();
+        for session_id in open {
+            self.close_status(&session_id, now);
+        }
+
+        let names = self.tracks.iter().map(|(name, tid)| TraceEvent {
+            name: "thread_name".to_string(),
+            cat: "__metadata",
+            ph: "M",
+            ts: 0,
+            dur: None,
+            pid: PROCESS_ID,
+            tid: *tid,
+            s: None,
+            args: json!({ "name": name }),
+        });
+        let events: Vec<TraceEvent> = names.chain(self.events.drain(..)).collect();
+        json!({
+            "traceEvents": events,
+            "displayTimeUnit": "ms",
+            "otherData": { "app": "ClaudeMiner", "dropped_events": self.dropped },
+        })
+    }
+}
+
+fn now_us() -> u64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_micros() as u64).unwrap_or(0)
+}
+
+fn session_track(session_id: &str) -> String {
+    format!("session {}", &session_id[..8.min(session_id.len())])
+}
+
+/// Track of an event: its session, else its process
+fn event_track(event: &MonitorEvent) -> String {
+    match event {
+        MonitorEvent::Log(e) => session_track(&e.session_id),
+        MonitorEvent::Hook(e) => session_track(&e.sid),
+        MonitorEvent::Approval(e) => session_track(&e.sid),
+        MonitorEvent::Cpu(e) => format!("pid {}", e.pid),
+        MonitorEvent::Network(e) => format!("pid {}", e.pid),
+        MonitorEvent::ProcessExit(e) | MonitorEvent::ProcessKilled(e) => format!("pid {}", e.pid),
+        MonitorEvent::RemoveSessions(_) => "cleaner".to_string(),
+        MonitorEvent::MergeSessions(merge) => session_track(&merge.target_id),
+        MonitorEvent::FlagsChanged(session_id) => session_track(session_id),
+    }
+}
+
+fn event_name(event: &MonitorEvent) -> &'static str {
+    match event {
+        MonitorEvent::Log(_) => "Log",
+        MonitorEvent::Cpu(_) => "Cpu",
+        MonitorEvent::Hook(_) => "Hook",
+        MonitorEvent::Approval(_) => "Approval",
+        MonitorEvent::Network(_) => "Network",
+        MonitorEvent::ProcessExit(_) => "ProcessExit",
+        MonitorEvent::ProcessKilled(_) => "ProcessKilled",
+        MonitorEvent::RemoveSessions(_) => "RemoveSessions",
+        MonitorEvent::MergeSessions(_) => "MergeSessions",
+        MonitorEvent::FlagsChanged(_) => "FlagsChanged",
+    }
+}
+
+/// When the monitor saw it happen (Unix seconds)
+fn event_time(event: &MonitorEvent) -> Option<u64> {
+    match event {
+        MonitorEvent::Log(e) => Some(e.timestamp),
+        MonitorEvent::Cpu(e) => Some(e.timestamp),
+        MonitorEvent::Hook(e) => Some(e.timestamp),
+        MonitorEvent::Approval(e) => Some(e.timestamp),
+        MonitorEvent::Network(e) => Some(e.timestamp),
+        MonitorEvent::ProcessExit(e) | MonitorEvent::ProcessKilled(e) => Some(e.timestamp),
+        MonitorEvent::RemoveSessions(_) | MonitorEvent::MergeSessions(_) | MonitorEvent::FlagsChanged(_) => None,
+    }
+}
+
+pub fn is_recording() -> bool {
+    RECORDING.load(Ordering::Relaxed)
+}
+
+/// An event being handled by the coordinator
+pub struct PendingEvent {
+    name: &'static str,
+    track: String,
+    ts: u64,
+    started: Instant,
+    args: Value,
+}
+
+/// Start timing the handling of `event` (None when not recording)
+pub fn begin_event(event: &MonitorEvent) -> Option<PendingEvent> {
+    if !is_recording() {
+        return None;
+    }
+    let ts = now_us();
+    let (evidence, detail) = evidence_for(event);
+    // Hook timestamps have second resolution; a lag of a second or more is worth a look
+    let lag_ms = event_time(event).filter(|t| *t > 0).map(|t| (ts / 1000).saturating_sub(t * 1000));
+    Some(PendingEvent {
+        name: event_name(event),
+        track: event_track(event),
+        ts,
+        started: Instant::now(),
+        args: json!({ "evidence": evidence, "detail": detail, "event_time": event_time(event), "lag_ms": lag_ms }),
+    })
+}
+
+/// Record a handled event with how long the coordinator took
+pub fn end_event(pending: Option<PendingEvent>) {
+    let Some(pending) = pending else { return };
+    let dur = pending.started.elapsed().as_micros() as u64;
+    TRACE.lock().unwrap().span(&pending.track, "event", pending.name.to_string(), pending.ts, dur, pending.args);
+}
+
+/// Record a status transition (closes the previous status span)
+pub fn record_transition(session_id: &str, from: Option<SessionStatus>, to: SessionStatus) {
+    if is_recording() {
+        TRACE.lock().unwrap().transition(session_id, from, to, now_us());
+    }
+}
+
+/// Close the status span of a session that went away
+pub fn record_session_end(session_id: &str) {
+    if is_recording() {
+        let ts = now_us();
+        let mut trace = TRACE.lock().unwrap();
+        trace.close_status(session_id, ts);
+        trace.instant(&session_track(session_id), "transition", "ended".to_string(), ts, Value::Null);
+    }
+}
+
+/// Run `f`, recording how long it took on the calling thread's track
+pub fn span<T>(name: &'static str, f: impl FnOnce() -> T) -> T {
+    if !is_recording() {
+        return f();
+    }
+    let ts = now_us();
+    let started = Instant::now();
+    let result = f();
+    let dur = started.elapsed().as_micros() as u64;
+    let track = std::thread::current().name().unwrap_or("unnamed").to_string();
+    TRACE.lock().unwrap().span(&track, "probe", name.to_string(), ts, dur, Value::Null);
+    result
+}
+
+fn trace_path(now: u64) -> PathBuf {
+    crate::config::store::get_config_path().with_file_name(format!("trace-{}.json", now / 1_000_000))
+}
+
+/// Start or stop recording; stopping writes the trace file and returns its path
+pub fn set_recording(enabled: bool) -> Result<TraceStatus, String> {
+    let mut trace = TRACE.lock().unwrap();
+    if enabled {
+        if !RECORDING.swap(true, Ordering::Relaxed) {
+            *trace = Trace::default();
+            println!("[Trace] ⏺️ Recording started");
+        }
+        return Ok(TraceStatus { recording: true, events: trace.events.len(), dropped: trace.dropped, path: None });
+    }
+
+    if !RECORDING.swap(false, Ordering::Relaxed) {
+        return Ok(TraceStatus::default());
+    }
+    let (events, dropped) = (trace.events.len(), trace.dropped);
+    let now = now_us();
+    let json = trace.export(now);
+    *trace = Trace::default();
+    drop(trace);
+
+    let path = trace_path(now);
+    let contents = serde_json::to_string(&json).map_err(|e| format!("Failed to serialize trace: {}", e))?;
+    std::fs::write(&path, contents).map_err(|e| format!("Failed to write {}: {}", path.display(), e))?;
+    println!("[Trace] ⏹️ Recording stopped: {} events written to {}", events, path.display());
+    Ok(TraceStatus { recording: false, events, dropped, path: Some(path.display().to_string()) })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::session::HookEvent;
+
+    #[test]
+    fn test_trace_json() {
+        let mut trace = Trace::default();
+        let session_id = "0123456789abcdef";
+        trace.transition(session_id, None, SessionStatus::Resting, 1_000);
+        trace.span("session 01234567", "event", "Hook".to_string(), 2_000, 150, json!({ "detail": "hook 'working'" }));
+        trace.transition(session_id, Some(SessionStatus::Resting), SessionStatus::Working, 2_100);
+
+        let json = trace.export(5_000);
+        let events = json["traceEvents"].as_array().unwrap();
+        // One track, named after the session
+        assert_eq!(events[0]["ph"], "M");
+        assert_eq!(events[0]["args"]["name"], "session 01234567");
+        assert!(events.iter().all(|e| e["tid"] == 1));
+
+        let spans: Vec<(&str, u64, u64)> = events.iter()
+            .filter(|e| e["cat"] == "status")
+            .map(|e| (e["name"].as_str().unwrap(), e["ts"].as_u64().unwrap(), e["dur"].as_u64().unwrap()))
+            .collect();
+        assert_eq!(spans, vec![("resting", 1_000, 1_100), ("working", 2_100, 2_900)]);
+        assert!(events.iter().any(|e| e["ph"] == "i" && e["name"] == "resting -> working"));
+        assert!(events.iter().all(|e| e.get("dur").is_none() || e["ph"] == "X"));
+    }
+
+    #[test]
+    fn test_event_track() {
+        let hook = MonitorEvent::Hook(HookEvent { sid: "fedcba9876543210".to_string(), evt: "working".to_string(), profile: None, timestamp: 0, pid: None });
+        assert_eq!(event_track(&hook), "session fedcba98");
+        assert_eq!(event_name(&hook), "Hook");
+        assert_eq!(event_time(&hook), Some(0));
+    }
+}
@@ -3,6 +3,8 @@
 // This module contains all shared data structures and type aliases
 // used throughout the application.
 
+use crate::config::NotificationOverride;
+use crate::monitor::container::ContainerInfo;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::sync::{Arc, Mutex};
@@ -10,17 +12,64 @@ use std::sync::{Arc, Mutex};
 /// Represents a Claude Code process (miner)
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct Miner {
+    pub session_id: String,
     pub pid: u32,
     pub cpu_usage: f32,
+    pub energy_impact: Option<f32>,  // macOS Energy Impact (only when sampling is enabled)
     pub memory: u64,
     pub status: String,
     pub has_terminal: bool,
     pub name: String,
+    pub pinned: bool,
+    pub archived: bool,
+    pub project_path: Option<String>,
+    pub profile: Option<String>,
+    pub kind: String,  // Process type tag ("claude", "aider", ...)
+    pub read_only: bool,  // Owned by another user or inside a container (can't be killed)
+    pub container: Option<ContainerInfo>,  // Docker container / WSL distro hosting the process
+    pub activity: Option<String>,  // What a working session does: "writing" or "running_tools"
+    pub needs_attention: bool,  // Blocked on a permission prompt
+    pub idle_secs: Option<u64>,  // Seconds since the last CPU, log, hook or network activity
+    pub started_at: u64,  // Unix timestamp the session's process started
+    pub status_since: u64,  // Unix timestamp the current status was entered
+    pub total_working_seconds: u64,  // Time spent working, including the ongoing stretch
+    pub notification_override: Option<NotificationOverride>,  // Muted or always notified (session flag)
+}
+
+/// Status of a session as shown to the user (serialized as "working", "resting", ...)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SessionStatus {
+    Working,
+    Resting,
+    Zombie,
+    WaitingApproval,  // Hook sessions, set by the Notification hook
+    Detached,
+    #[default]
+    Unknown,
+}
+
+impl SessionStatus {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            SessionStatus::Working => "working",
+            SessionStatus::Resting => "resting",
+            SessionStatus::Zombie => "zombie",
+            SessionStatus::WaitingApproval => "waiting_approval",
+            SessionStatus::Detached => "detached",
+            SessionStatus::Unknown => "unknown",
+        }
+    }
+}
+
+impl std::fmt::Display for SessionStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
 }
 
 /// Working state of a Claude Code session
-#[derive(Debug, Clone, Copy, Serialize)]
-#[allow(dead_code)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
 pub enum WorkingState {
     ActivelyWorking,      // Tool execution detected
     GeneratingResponse,   // Stream only (text generation)
@@ -28,6 +77,22 @@ pub enum WorkingState {
     Unknown,              // Cannot determine
 }
 
+impl WorkingState {
+    /// Evidence of work in progress (tools or text generation)
+    pub fn is_active(self) -> bool {
+        matches!(self, WorkingState::ActivelyWorking | WorkingState::GeneratingResponse)
+    }
+
+    /// Sub-status shown for working miners
+    pub fn activity(self) -> Option<&'static str> {
+        match self {
+            WorkingState::ActivelyWorking => Some("running_tools"),
+            WorkingState::GeneratingResponse => Some("writing"),
+            WorkingState::Idle | WorkingState::Unknown => None,
+        }
+    }
+}
+
 // Type aliases for shared state (kept for future use)
 #[allow(dead_code)]
 pub type CpuCache = Arc<Mutex<HashMap<u32, f32>>>;
@@ -0,0 +1,459 @@
+// Updater
+//
+// In-app updates from GitHub releases:
+// 1. check_for_updates: latest release vs the running version (semver)
+// 2. install_update: download the platform's bundle in the background ("update-progress"
+//    events), verify it, stage it and emit "update-ready"
+// 3. restart_to_update: apply the staged bundle and relaunch
+//
+// Verification (the bundler's minisign updater isn't used, so it is done here):
+// - Every platform: the minisign signature published with the release (<asset>.sig, made with
+//   `tauri signer sign`) must verify against the public key built into the app
+//   (CLAUDEMINER_UPDATE_PUBKEY at build time); releases without one are refused, and builds
+//   without a key can't install updates
+// - macOS: codesign signature of the extracted .app must be valid and from our team
+// - Windows: Authenticode signature of the installer must be valid and from our publisher
+// Downloads go through curl, like the automation webhooks.
+//
+
+use once_cell::sync::{Lazy, OnceCell};
+use base64::Engine;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+use std::thread;
+use std::time::Duration;
+use crate::event::schema::{self, EventPayload};
+
+const LATEST_RELEASE_URL: &str = "https://api.github.com/repos/JUKI-J/claudeminer/releases/latest";
+#[cfg(target_os = "macos")]
+const TEAM_ID: &str = "JJX75F53MA";
+#[cfg(target_os = "windows")]
+const PUBLISHER: &str = "JUKI JUNG";  // CN of the Authenticode signing certificate
+
+/// Release signing key (tauri signer format: base64 of the minisign .pub file)
+const UPDATE_PUBKEY: Option<&str> = option_env!("CLAUDEMINER_UPDATE_PUBKEY");
+const PROGRESS_INTERVAL: Duration = Duration::from_millis(250);
+
+/// Release assets for this platform, in order of preference
+#[cfg(target_os = "macos")]
+const ASSET_SUFFIXES: &[&str] = &[".app.tar.gz"];
+#[cfg(target_os = "windows")]
+const ASSET_SUFFIXES: &[&str] = &[".msi", "-setup.exe"];
+#[cfg(not(any(target_os = "macos", target_os = "windows")))]
+const ASSET_SUFFIXES: &[&str] = &[".AppImage"];
+
+#[derive(Debug, Clone, Deserialize)]
+struct GithubRelease {
+    tag_name: String,
+    #[serde(default)]
+    body: Option<String>,
+    #[serde(default)]
+    published_at: Option<String>,
+    #[serde(default)]
+    assets: Vec<GithubAsset>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct GithubAsset {
+    name: String,
+    browser_download_url: String,
+    #[serde(default)]
+    size: u64,
+}
+
+/// Result of check_for_updates
+#[derive(Debug, Clone, Serialize)]
+pub struct UpdateInfo {
+    pub current_version: String,
+    pub latest_version: String,
+    pub available: bool,
+    pub notes: Option<String>,
+    pub published_at: Option<String>,
+    pub asset: Option<String>,  // None = no bundle for this platform in the release
+}
+
+/// Payload of "update-progress"
+#[derive(Debug, Clone, Serialize)]
+pub struct UpdateProgress {
+    pub downloaded: u64,
+    pub total: u64,
+}
+
+/// A verified bundle waiting for restart_to_update
+#[derive(Debug, Clone, Serialize)]
+pub struct StagedUpdate {
+    pub version: String,
+    pub path: String,
+}
+
+static APP_HANDLE: OnceCell<tauri::AppHandle> = OnceCell::new();
+static STAGED: Lazy<Mutex<Option<StagedUpdate>>> = Lazy::new(|| Mutex::new(None));
+static DOWNLOADING: AtomicBool = AtomicBool::new(false);
+
+/// Initialize with the AppHandle (called once during app setup)
+pub fn init(app_handle: tauri::AppHandle) {
+    if APP_HANDLE.set(app_handle).is_err() {
+        eprintln!("[Updater] Warning: AppHandle already initialized");
+    }
+}
+
+fn current_version() -> String {
+    crate::app_info::VERSION.to_string()
+}
+
+/// "v1.2.0" -> 1.2.0
+fn parse_version(tag: &str) -> Option<semver::Version> {
+    semver::Version::parse(tag.trim().trim_start_matches('v')).ok()
+}
+
+fn fetch_latest_release() -> Result<GithubRelease, String> {
+    let output = Command::new("curl")
+        .args(["-sSfL", "-m", "15", "-H", "Accept: application/vnd.github+json", "-A", "ClaudeMiner-Updater"])
+        .arg(LATEST_RELEASE_URL)
+        .output()
+        .map_err(|e| format!("Failed to run curl: {}", e))?;
+    if !output.status.success() {
+        return Err(format!("Release check failed: {}", String::from_utf8_lossy(&output.stderr).trim()));
+    }
+    serde_json::from_slice(&output.stdout).map_err(|e| format!("Unexpected release format: {}", e))
+}
+
+fn platform_asset(release: &GithubRelease) -> Option<&GithubAsset> {
+    ASSET_SUFFIXES.iter()
+        .find_map(|suffix| release.assets.iter().find(|a| a.name.ends_with(suffix)))
+}
+
+/// Compare the latest GitHub release with the running version
+pub fn check_for_updates() -> Result<UpdateInfo, String> {
+    let release = fetch_latest_release()?;
+    let current = current_version();
+    let available = match (parse_version(&release.tag_name), parse_version(&current)) {
+        (Some(latest), Some(running)) => latest > running,
+        _ => false,
+    };
+
+    println!("[Updater] Current {}, latest {} (update available: {})", current, release.tag_name, available);
+    Ok(UpdateInfo {
+        current_version: current,
+        latest_version: release.tag_name.trim_start_matches('v').to_string(),
+        available,
+        notes: release.body.clone(),
+        published_at: release.published_at.clone(),
+        asset: platform_asset(&release).map(|a| a.name.clone()),
+    })
+}
+
+/// Download, verify and stage the latest release in the background
+/// Emits "update-progress" while downloading and "update-ready" / "update-failed" at the end
+pub fn install_update() -> Result<(), String> {
+    if DOWNLOADING.swap(true, Ordering::SeqCst) {
+        return Err("An update is already being downloaded".to_string());
+    }
+
+    thread::spawn(|| {
+        let result = download_and_stage();
+        DOWNLOADING.store(false, Ordering::SeqCst);
+        match result {
+            Ok(staged) => {
+                println!("[Updater] ✅ {} staged at {}", staged.version, staged.path);
+                emit(EventPayload::UpdateReady(&staged));
+                *STAGED.lock().unwrap() = Some(staged);
+            }
+            Err(e) => {
+                eprintln!("[Updater] ❌ {}", e);
+                emit(EventPayload::UpdateFailed { error: &e });
+            }
+        }
+    });
+    Ok(())
+}
+
+fn emit(payload: EventPayload) {
+    if let Some(handle) = APP_HANDLE.get() {
+        let event = payload.name();
+        if let Err(e) = schema::emit(handle, payload) {
+            eprintln!("[Updater] Failed to emit {}: {}", event, e);
+        }
+    }
+}
+
+fn updates_dir() -> PathBuf {
+    crate::config::store::get_config_path()
+        .parent()
+        .map(|dir| dir.join("updates"))
+        .unwrap_or_else(|| std::env::temp_dir().join("claudeminer-updates"))
+}
+
+fn download_and_stage() -> Result<StagedUpdate, String> {
+    let release = fetch_latest_release()?;
+    let version = release.tag_name.trim_start_matches('v').to_string();
+    let asset = platform_asset(&release)
+        .ok_or_else(|| format!("Release {} has no bundle for this platform", release.tag_name))?;
+    let pubkey = UPDATE_PUBKEY
+        .ok_or("This build has no update signing key; download the release manually")?;
+    let signature = release_signature(&release, asset)?;
+
+    let dir = updates_dir().join(&version);
+    fs::create_dir_all(&dir).map_err(|e| format!("Failed to create {}: {}", dir.display(), e))?;
+    let download = dir.join(&asset.name);
+
+    println!("[Updater] Downloading {} ({} bytes)", asset.name, asset.size);
+    download_with_progress(&asset.browser_download_url, &download, asset.size)?;
+
+    if let Err(e) = verify_minisign(&download, &signature, pubkey) {
+        let _ = fs::remove_file(&download);
+        return Err(format!("Signature check failed for {}: {}", asset.name, e));
+    }
+
+    let bundle = prepare_bundle(&download, &dir)?;
+    verify_signature(&bundle)?;
+
+    Ok(StagedUpdate { version, path: bundle.display().to_string() })
+}
+
+/// Detached signature published with the release: "<asset>.sig"
+fn release_signature(release: &GithubRelease, asset: &GithubAsset) -> Result<String, String> {
+    let signature_asset = release.assets.iter()
+        .find(|a| a.name == format!("{}.sig", asset.name))
+        .ok_or_else(|| format!("Release {} publishes no signature for {}", release.tag_name, asset.name))?;
+
+    let output = Command::new("curl")
+        .args(["-sSfL", "-m", "15", "-A", "ClaudeMiner-Updater"])
+        .arg(&signature_asset.browser_download_url)
+        .output()
+        .map_err(|e| format!("Failed to run curl: {}", e))?;
+    if !output.status.success() {
+        return Err(format!("Failed to download {}", signature_asset.name));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+/// Minisign text (key or signature file), either as is or base64-encoded as tauri signer writes it
+fn minisign_text(encoded: &str) -> Result<String, String> {
+    let encoded = encoded.trim();
+    if encoded.starts_with("untrusted comment:") {
+        return Ok(encoded.to_string());
+    }
+    let bytes = base64::engine::general_purpose::STANDARD.decode(encoded)
+        .map_err(|e| format!("invalid base64: {}", e))?;
+    String::from_utf8(bytes).map_err(|_| "not UTF-8".to_string())
+}
+
+/// Check `path` against a detached minisign signature made with the key `pubkey`
+fn verify_minisign(path: &Path, signature: &str, pubkey: &str) -> Result<(), String> {
+    use minisign_verify::{PublicKey, Signature};
+    use std::io::Read;
+
+    let pubkey = PublicKey::decode(&minisign_text(pubkey)?).map_err(|e| format!("invalid public key: {}", e))?;
+    let signature = Signature::decode(&minisign_text(signature)?).map_err(|e| format!("invalid signature: {}", e))?;
+    let mut verifier = pubkey.verify_stream(&signature).map_err(|e| e.to_string())?;
+
+    let mut file = fs::File::open(path).map_err(|e| format!("Failed to open {}: {}", path.display(), e))?;
+    let mut buf = vec![0u8; 64 * 1024];
+    loop {
+        let read = file.read(&mut buf).map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+        if read == 0 {
+            break;
+        }
+        verifier.update(&buf[..read]);
+    }
+    verifier.finalize().map_err(|e| e.to_string())
+}
+
+/// curl into `path`, reporting the file size as it grows
+fn download_with_progress(url: &str, path: &Path, total: u64) -> Result<(), String> {
+    let mut child = Command::new("curl")
+        .args(["-sSfL", "-A", "ClaudeMiner-Updater", "-o"])
+        .arg(path)
+        .arg(url)
+        .spawn()
+        .map_err(|e| format!("Failed to run curl: {}", e))?;
+
+    loop {
+        let downloaded = fs::metadata(path).map(|m| m.len()).unwrap_or(0);
+        emit(EventPayload::UpdateProgress(&UpdateProgress { downloaded, total }));
+
+        match child.try_wait() {
+            Ok(Some(status)) if status.success() => return Ok(()),
+            Ok(Some(status)) => return Err(format!("Download failed (curl exit {})", status)),
+            Ok(None) => thread::sleep(PROGRESS_INTERVAL),
+            Err(e) => return Err(format!("Download failed: {}", e)),
+        }
+    }
+}
+
+/// The thing to install: the extracted .app on macOS, the downloaded file elsewhere
+fn prepare_bundle(download: &Path, dir: &Path) -> Result<PathBuf, String> {
+    #[cfg(target_os = "macos")]
+    {
+        let status = Command::new("tar")
+            .arg("-xzf").arg(download)
+            .arg("-C").arg(dir)
+            .status()
+            .map_err(|e| format!("Failed to run tar: {}", e))?;
+        if !status.success() {
+            return Err(format!("Failed to extract {}", download.display()));
+        }
+        fs::read_dir(dir)
+            .map_err(|e| format!("Failed to read {}: {}", dir.display(), e))?
+            .flatten()
+            .map(|entry| entry.path())
+            .find(|path| path.extension().is_some_and(|ext| ext == "app"))
+            .ok_or_else(|| format!("No .app in {}", download.display()))
+    }
+
+    #[cfg(not(target_os = "macos"))]
+    {
+        let _ = dir;
+        Ok(download.to_path_buf())
+    }
+}
+
+fn verify_signature(bundle: &Path) -> Result<(), String> {
+    #[cfg(target_os = "macos")]
+    {
+        // Valid, and signed with a Developer ID certificate of our team
+        let requirement = format!(
+            "=anchor apple generic and certificate 1[field.1.2.840.113635.100.6.2.6] exists \
+             and certificate leaf[field.1.2.840.113635.100.6.1.13] exists and certificate leaf[subject.OU] = \"{}\"",
+            TEAM_ID
+        );
+        let verify = Command::new("codesign")
+            .args(["--verify", "--deep", "--strict", "-R", &requirement])
+            .arg(bundle)
+            .output()
+            .map_err(|e| format!("Failed to run codesign: {}", e))?;
+        if !verify.status.success() {
+            return Err(format!("Update is not signed by the ClaudeMiner developer: {}", String::from_utf8_lossy(&verify.stderr).trim()));
+        }
+        Ok(())
+    }
+
+    #[cfg(target_os = "windows")]
+    {
+        let script = format!(
+            "$s = Get-AuthenticodeSignature -LiteralPath '{}'; $s.Status; $s.SignerCertificate.GetNameInfo('SimpleName', $false)",
+            bundle.display().to_string().replace('\'', "''")
+        );
+        let output = Command::new("powershell")
+            .args(["-NoProfile", "-Command", &script])
+            .output()
+            .map_err(|e| format!("Failed to run powershell: {}", e))?;
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let mut lines = stdout.lines().map(str::trim);
+        let (status, signer) = (lines.next().unwrap_or(""), lines.next().unwrap_or(""));
+        if status != "Valid" {
+            return Err(format!("Invalid Authenticode signature ({})", status));
+        }
+        if signer != PUBLISHER {
+            return Err(format!("Update is signed by {:?}, not the ClaudeMiner developer", signer));
+        }
+        Ok(())
+    }
+
+    #[cfg(not(any(target_os = "macos", target_os = "windows")))]
+    {
+        // AppImages aren't code signed; the release signature is the only check
+        println!("[Updater] No code signature to verify for {}", bundle.display());
+        Ok(())
+    }
+}
+
+/// The update downloaded so far, if any
+pub fn staged() -> Option<StagedUpdate> {
+    STAGED.lock().unwrap().clone()
+}
+
+/// Apply the staged update and relaunch (the current process exits)
+pub fn restart_to_update() -> Result<(), String> {
+    let staged = staged().ok_or("No update has been downloaded")?;
+    let bundle = PathBuf::from(&staged.path);
+    let exe = std::env::current_exe().map_err(|e| format!("Failed to locate executable: {}", e))?;
+    println!("[Updater] Installing {} and restarting", staged.version);
+
+    #[cfg(target_os = "macos")]
+    {
+        // Swap the bundles after we exit, then reopen the app
+        let app_path = exe.ancestors().nth(3).ok_or("Failed to find app bundle")?;
+        let script = format!(
+            "while kill -0 {pid} 2>/dev/null; do sleep 0.2; done; \
+             rm -rf \"$1.old\" && mv \"$1\" \"$1.old\" && mv \"$2\" \"$1\" && rm -rf \"$1.old\"; open \"$1\"",
+            pid = std::process::id()
+        );
+        Command::new("sh")
+            .args(["-c", &script, "claudeminer-update"])
+            .arg(app_path)
+            .arg(&bundle)
+            .spawn()
+            .map_err(|e| format!("Failed to start installer: {}", e))?;
+    }
+
+    #[cfg(target_os = "windows")]
+    {
+        let _ = &exe;
+        let mut installer = if bundle.extension().is_some_and(|ext| ext == "msi") {
+            let mut msiexec = Command::new("msiexec");
+            msiexec.arg("/i").arg(&bundle).arg("/passive");
+            msiexec
+        } else {
+            let mut setup = Command::new(&bundle);
+            setup.arg("/S");
+            setup
+        };
+        installer.spawn().map_err(|e| format!("Failed to start installer: {}", e))?;
+    }
+
+    #[cfg(not(any(target_os = "macos", target_os = "windows")))]
+    {
+        // Replace the running AppImage ($APPIMAGE) and start the new one
+        let target = std::env::var_os("APPIMAGE").map(PathBuf::from).unwrap_or(exe);
+        let script = format!(
+            "while kill -0 {pid} 2>/dev/null; do sleep 0.2; done; \
+             mv \"$2\" \"$1\" && chmod +x \"$1\"; \"$1\" >/dev/null 2>&1 &",
+            pid = std::process::id()
+        );
+        Command::new("sh")
+            .args(["-c", &script, "claudeminer-update"])
+            .arg(&target)
+            .arg(&bundle)
+            .spawn()
+            .map_err(|e| format!("Failed to start installer: {}", e))?;
+    }
+
+    crate::window_state::save();
+    std::process::exit(0);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Key pair generated for this test only; the signature is over b"ClaudeMiner update bundle"
+    const TEST_PUBKEY: &str = "dW50cnVzdGVkIGNvbW1lbnQ6IG1pbmlzaWduIHB1YmxpYyBrZXk6IDg3QTk0NDREMDNDRjZFRjYKUldUMmJzOERUVVNwaDlINDFNMTRHS2J1QXp3ZWNWUGdZbHExS1QzeGlnZG1JbHcwaUVXWWJIZWQK";
+    const TEST_SIGNATURE: &str = "dW50cnVzdGVkIGNvbW1lbnQ6IHNpZ25hdHVyZSBmcm9tIHRhdXJpIHNlY3JldCBrZXkKUlVUMmJzOERUVVNwaDFiZGNrRWJaam9BU210VCsreEJna1dueXVFRi9vcFhCNmZwVnNNek8zcE1qMGdVaFBuakdBMTY0MlF0Uk5nZzRCNzVyUjYwSzNNNHJJak9GT3JYekF3PQp0cnVzdGVkIGNvbW1lbnQ6IHRpbWVzdGFtcDoxNzYwMDAwMDAwCWZpbGU6Q2xhdWRlTWluZXIuQXBwSW1hZ2UKbXZZYUZIeldQdGN6Z1pWVEd2TFdXVmM3Umw2YjhpVThoU0lWV0w5T3ZsWG8vaHdxeEVPeGF6Yi9wM3VaNXAzWFZ2dThra3BoZmFCejdURjBwOFJIQVE9PQo=";
+
+    #[test]
+    fn test_verify_minisign() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let bundle = dir.path().join("ClaudeMiner.AppImage");
+
+        std::fs::write(&bundle, b"ClaudeMiner update bundle").unwrap();
+        assert_eq!(verify_minisign(&bundle, TEST_SIGNATURE, TEST_PUBKEY), Ok(()));
+
+        // The decoded (plain minisign) forms work too
+        let plain = minisign_text(TEST_SIGNATURE).unwrap();
+        assert!(plain.starts_with("untrusted comment:"));
+        assert_eq!(verify_minisign(&bundle, &plain, &minisign_text(TEST_PUBKEY).unwrap()), Ok(()));
+
+        // Tampered bundle, garbage signature
+        std::fs::write(&bundle, b"ClaudeMiner update bundle!").unwrap();
+        assert!(verify_minisign(&bundle, TEST_SIGNATURE, TEST_PUBKEY).is_err());
+        assert!(verify_minisign(&bundle, "not a signature", TEST_PUBKEY).is_err());
+
+        assert!(parse_version("v1.2.0").unwrap() > parse_version("1.1.1").unwrap());
+    }
+}
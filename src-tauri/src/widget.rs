@@ -0,0 +1,148 @@
+// Mini Widget
+//
+// Compact summary for the small always-on-top widget window (and menu-bar popovers):
+// miner counts, the longest-running working session and the most recent completed task.
+// The widget window is a second, lightweight webview (widget.html) opened from Rust.
+//
+
+use crate::event::is_listed;
+use crate::session::SessionState;
+use crate::status::journal::WorkInterval;
+use crate::status::machine;
+use crate::types::SessionStatus;
+use serde::Serialize;
+use std::collections::HashMap;
+use tauri::{AppHandle, Manager, WindowBuilder, WindowUrl};
+
+const WIDGET_LABEL: &str = "widget";
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct WidgetCounts {
+    pub total: usize,
+    pub working: usize,
+    pub resting: usize,
+    pub zombie: usize,
+    pub needs_attention: usize,
+}
+
+/// Working session that has been working the longest
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct LongestRunning {
+    pub session_id: String,
+    pub project_path: Option<String>,
+    pub working_secs: u64,  // Length of the current working stretch
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct LastCompletion {
+    pub session_id: String,
+    pub project_path: Option<String>,
+    pub finished_at: u64,
+    pub duration_secs: u64,
+}
+
+/// Payload of get_widget_summary
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct WidgetSummary {
+    pub counts: WidgetCounts,
+    pub longest_running: Option<LongestRunning>,
+    pub last_completion: Option<LastCompletion>,
+    pub generated_at: u64,
+}
+
+/// Summarize the visible sessions (same filter as the miner list)
+pub fn summary(sessions: &HashMap<String, SessionState>, last_completion: Option<WorkInterval>, now: u64) -> WidgetSummary {
+    let visible: Vec<(&String, &SessionState)> = sessions.iter()
+        .filter(|(id, s)| is_listed(id, s, false))
+        .collect();
+
+    let count = |status: SessionStatus| visible.iter().filter(|(_, s)| s.current_status == status).count();
+    let counts = WidgetCounts {
+        total: visible.len(),
+        working: count(machine::WORKING),
+        resting: count(machine::RESTING),
+        zombie: count(machine::ZOMBIE),
+        needs_attention: visible.iter().filter(|(_, s)| s.needs_attention).count(),
+    };
+
+    let longest_running = visible.iter()
+        .filter(|(_, s)| s.current_status == machine::WORKING)
+        .min_by_key(|(id, s)| (s.status_since, id.as_str()))
+        .map(|(id, s)| LongestRunning {
+            session_id: id.to_string(),
+            project_path: s.project_path.clone(),
+            working_secs: now.saturating_sub(s.status_since),
+        });
+
+    WidgetSummary {
+        counts,
+        longest_running,
+        last_completion: last_completion.map(|w| LastCompletion {
+            duration_secs: w.end.saturating_sub(w.start),
+            session_id: w.session_id,
+            project_path: w.project_path,
+            finished_at: w.end,
+        }),
+        generated_at: now,
+    }
+}
+
+/// Show the widget window, creating it on first use
+pub fn open_window(app: &AppHandle) -> Result<(), String> {
+    if let Some(window) = app.get_window(WIDGET_LABEL) {
+        window.show().map_err(|e| e.to_string())?;
+        return window.set_focus().map_err(|e| e.to_string());
+    }
+
+    WindowBuilder::new(app, WIDGET_LABEL, WindowUrl::App("widget.html".into()))
+        .title("ClaudeMiner")
+        .inner_size(240.0, 150.0)
+        .resizable(false)
+        .always_on_top(true)
+        .skip_taskbar(true)
+        .build()
+        .map_err(|e| format!("Failed to open widget window: {}", e))?;
+
+    println!("[Widget] Opened widget window");
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn session(id: &str, pid: u32, status: SessionStatus, status_since: u64) -> (String, SessionState) {
+        let mut s = SessionState::new_legacy(pid, id.to_string());
+        s.current_status = status;
+        s.status_since = status_since;
+        (id.to_string(), s)
+    }
+
+    #[test]
+    fn test_summary() {
+        let mut sessions: HashMap<String, SessionState> = [
+            session("a", 1, machine::WORKING, 900),
+            session("b", 2, machine::WORKING, 500),
+            session("c", 3, machine::RESTING, 100),
+            session("d", 4, machine::ZOMBIE, 100),
+            session("hidden", 5, machine::WORKING, 10),
+        ].into_iter().collect();
+        sessions.get_mut("c").unwrap().needs_attention = true;
+        sessions.get_mut("hidden").unwrap().archived = true;
+
+        let done = WorkInterval {
+            session_id: "c".to_string(),
+            project_path: Some("/p".to_string()),
+            start: 200,
+            end: 260,
+            completed: true,
+        };
+        let summary = summary(&sessions, Some(done), 1000);
+
+        assert_eq!(summary.counts, WidgetCounts { total: 4, working: 2, resting: 1, zombie: 1, needs_attention: 1 });
+        let longest = summary.longest_running.unwrap();
+        assert_eq!((longest.session_id.as_str(), longest.working_secs), ("b", 500));
+        let last = summary.last_completion.unwrap();
+        assert_eq!((last.finished_at, last.duration_secs), (260, 60));
+    }
+}
@@ -0,0 +1,119 @@
+// Window State
+//
+// Main window behavior and persistence (config.json "window"):
+// - close-to-tray: closing the window hides it; Quit (tray/menu) still exits
+// - size/position are tracked while the window moves and written to config.json when
+//   it closes or the app exits (not on every Moved/Resized event)
+// - the saved bounds are restored at startup if they still fit on a connected monitor
+//
+
+use crate::config::{self, WindowBounds};
+use once_cell::sync::Lazy;
+use std::sync::Mutex;
+use tauri::{LogicalPosition, LogicalSize, Window, WindowEvent};
+
+/// Bounds seen since the last save
+static LATEST: Lazy<Mutex<Option<WindowBounds>>> = Lazy::new(|| Mutex::new(None));
+
+/// Apply the saved size/position to the main window
+pub fn restore(window: &Window) {
+    let Some(bounds) = config::get().window.bounds else { return };
+    let monitors = monitors(window);
+
+    let _ = window.set_size(LogicalSize::new(bounds.width, bounds.height));
+    if is_on_screen(&bounds, &monitors) {
+        let _ = window.set_position(LogicalPosition::new(bounds.x, bounds.y));
+    } else {
+        println!("[WindowState] Saved position is off-screen, keeping the default");
+    }
+    println!("[WindowState] Restored {}x{} at ({}, {})", bounds.width, bounds.height, bounds.x, bounds.y);
+}
+
+/// Window event handler (registered with Builder::on_window_event)
+pub fn on_window_event(window: &Window, event: &WindowEvent) {
+    if window.label() != "main" {
+        return;
+    }
+
+    match event {
+        WindowEvent::Moved(_) | WindowEvent::Resized(_) => remember(window),
+        WindowEvent::CloseRequested { api, .. } => {
+            remember(window);
+            save();
+            if config::get().window.close_to_tray {
+                api.prevent_close();
+                let _ = window.hide();
+                println!("[WindowState] Hidden to tray");
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Track the current bounds (skipped while minimized/maximized)
+fn remember(window: &Window) {
+    if window.is_minimized().unwrap_or(false) || window.is_maximized().unwrap_or(false) {
+        return;
+    }
+    let Ok(scale) = window.scale_factor() else { return };
+    let (Ok(position), Ok(size)) = (window.outer_position(), window.inner_size()) else { return };
+
+    let position = position.to_logical::<f64>(scale);
+    let size = size.to_logical::<f64>(scale);
+    *LATEST.lock().unwrap() = Some(WindowBounds {
+        x: position.x,
+        y: position.y,
+        width: size.width,
+        height: size.height,
+    });
+}
+
+/// Write the tracked bounds to config.json (no-op when nothing changed)
+pub fn save() {
+    let Some(bounds) = LATEST.lock().unwrap().take() else { return };
+    if config::get().window.bounds == Some(bounds) {
+        return;
+    }
+    if let Err(e) = config::update(|c| c.window.bounds = Some(bounds)) {
+        eprintln!("[WindowState] Failed to save window state: {}", e);
+    }
+}
+
+/// Connected monitors as logical (x, y, width, height)
+pub fn monitors(window: &Window) -> Vec<(f64, f64, f64, f64)> {
+    window.available_monitors().unwrap_or_default()
+        .iter()
+        .map(|m| {
+            let position = m.position().to_logical::<f64>(m.scale_factor());
+            let size = m.size().to_logical::<f64>(m.scale_factor());
+            (position.x, position.y, size.width, size.height)
+        })
+        .collect()
+}
+
+/// Does the window's top-left area land on one of the monitors (x, y, width, height)?
+pub fn is_on_screen(bounds: &WindowBounds, monitors: &[(f64, f64, f64, f64)]) -> bool {
+    // Require a grabbable strip of the title bar to be visible
+    const MARGIN: f64 = 50.0;
+    monitors.iter().any(|(x, y, width, height)| {
+        bounds.x + MARGIN > *x && bounds.x + MARGIN < x + width
+            && bounds.y >= *y && bounds.y + MARGIN < y + height
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_on_screen() {
+        let monitors = [(0.0, 0.0, 1440.0, 900.0), (1440.0, 0.0, 1920.0, 1080.0)];
+        let at = |x: f64, y: f64| WindowBounds { x, y, width: 800.0, height: 600.0 };
+
+        assert!(is_on_screen(&at(100.0, 100.0), &monitors));
+        assert!(is_on_screen(&at(2000.0, 500.0), &monitors));
+        assert!(!is_on_screen(&at(4000.0, 100.0), &monitors));  // Monitor unplugged
+        assert!(!is_on_screen(&at(100.0, -200.0), &monitors));   // Title bar above the screen
+        assert!(!is_on_screen(&at(100.0, 100.0), &[]));
+    }
+}
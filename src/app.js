@@ -27,6 +27,7 @@ function initializeAPIs() {
 
 // State management
 let previousMiners = new Map();
+let sessionStates = new Map(); // session_id -> full session state, kept current by session-diff
 let notificationsEnabled = localStorage.getItem('notificationsEnabled') === 'true';
 let isLoading = false;
 let errorCount = 0;
@@ -284,6 +285,29 @@ function showNotificationPermissionReminder() {
     }, 10000);
 }
 
+// Kill a miner and the processes it spawned; a session in the middle of a task
+// asks for confirmation first
+// Returns false if the user cancelled, throws the error message on failure
+async function killMiner(pid) {
+    try {
+        await invoke('kill_miner', { pid, tree: true });
+        return true;
+    } catch (error) {
+        if (error?.code !== 'session_busy') {
+            throw error?.message ?? error;
+        }
+        if (!await showConfirmDialog(t('confirmKillBusy', { pid }))) {
+            return false;
+        }
+    }
+    try {
+        await invoke('kill_miner', { pid, force: true, tree: true });
+        return true;
+    } catch (error) {
+        throw error?.message ?? error;
+    }
+}
+
 // Custom confirm dialog (web-style)
 function showConfirmDialog(message) {
     return new Promise((resolve) => {
@@ -420,6 +444,80 @@ function showSuccessToast(message) {
     }, 3000);
 }
 
+// Show an in-app toast with the buttons of an actionable notification
+// (system notifications can't carry buttons in Tauri v1; the tray menu offers the same
+// actions while the window is hidden)
+function showActionToast(notification) {
+    const toast = document.createElement('div');
+    toast.className = 'action-toast';
+
+    const text = document.createElement('div');
+    text.className = 'action-toast-text';
+    text.innerHTML = `<strong></strong><div></div>`;
+    text.querySelector('strong').textContent = notification.title;
+    text.querySelector('div').textContent = notification.body;
+    toast.appendChild(text);
+
+    const dismiss = () => {
+        toast.style.animation = 'slideOutRight 0.3s ease-in';
+        setTimeout(() => toast.remove(), 300);
+    };
+
+    for (const action of notification.actions) {
+        const button = document.createElement('button');
+        button.className = `action-toast-btn action-${action.id}`;
+        button.textContent = t(action.id === 'kill' ? 'killNow' : 'focusTerminal');
+        button.addEventListener('click', async () => {
+            try {
+                if (action.id === 'focus') {
+                    await invoke('focus_session_terminal', { sessionId: notification.session_id });
+                } else if (action.id === 'kill') {
+                    await killMiner(notification.pid);
+                    await updateMiners();
+                }
+            } catch (error) {
+                showError(`${action.label}: ${error}`);
+            }
+            dismiss();
+        });
+        toast.appendChild(button);
+    }
+
+    document.body.appendChild(toast);
+    setTimeout(dismiss, 10000);
+}
+
+// Handle a claudeminer:// link (session/<id> focuses its card, kill/<pid> asks first)
+async function handleDeepLink(link) {
+    console.log('🔗 Deep link:', link);
+
+    if (link.action === 'session') {
+        const card = [...document.querySelectorAll('.miner-card')]
+            .find(c => (c.dataset.sessionId || '').startsWith(link.session_id));
+        if (!card) {
+            showError(t('sessionNotFound', { id: link.session_id }));
+            return;
+        }
+        card.scrollIntoView({ behavior: 'smooth', block: 'center' });
+        card.classList.add('deep-link-focus');
+        setTimeout(() => card.classList.remove('deep-link-focus'), 3000);
+    } else if (link.action === 'kill') {
+        const confirmed = await showConfirmDialog(t('confirmKill', { pid: link.pid }));
+        if (!confirmed) {
+            return;
+        }
+        try {
+            if (!await killMiner(link.pid)) {
+                return;
+            }
+            showSuccessToast(t('processTerminated', { pid: link.pid }));
+            await updateMiners();
+        } catch (error) {
+            showError(`${t('errorKillingProcess')}: ${error}`);
+        }
+    }
+}
+
 // Add CSS animations for reminder and dialogs
 const style = document.createElement('style');
 style.textContent = `
@@ -486,22 +584,62 @@ function getMinerIcon(status) {
             return '⛏️';
         case 'resting':
             return '😴';
+        case 'detached':
+            return '🔌';
         case 'zombie':
             return '🧟';
+        case 'waiting_approval':
+            return '✋';
         default:
             return '👷';
     }
 }
 
+// Compact idle duration: 45s, 12m, 3h 5m, 2d
+function formatIdle(secs) {
+    if (secs < 60) return `${secs}s`;
+    if (secs < 3600) return `${Math.floor(secs / 60)}m`;
+    if (secs < 86400) {
+        const minutes = Math.floor((secs % 3600) / 60);
+        return minutes ? `${Math.floor(secs / 3600)}h ${minutes}m` : `${Math.floor(secs / 3600)}h`;
+    }
+    return `${Math.floor(secs / 86400)}d`;
+}
+
 // Create miner card element
 function createMinerCard(miner) {
     const card = document.createElement('div');
     card.className = `miner-card ${miner.status}`;
+    if (miner.read_only) {
+        card.classList.add('read-only');
+    }
+    // Blocked on a permission prompt
+    if (miner.needs_attention) {
+        card.classList.add('needs-attention');
+    }
     card.dataset.pid = miner.pid;
+    card.dataset.sessionId = miner.session_id;
 
     const badge = document.createElement('div');
     badge.className = 'miner-badge';
     badge.textContent = `#${miner.pid}`;
+    // Tag sessions from non-default Claude profiles (work/personal configs)
+    if (miner.profile && miner.profile !== 'default') {
+        badge.textContent += ` · ${miner.profile}`;
+    }
+    // Sessions inside Docker containers / WSL distros
+    if (miner.container) {
+        badge.textContent += ` · 🐳 ${miner.container.name}`;
+    }
+    if (miner.needs_attention) {
+        badge.textContent += ` · ✋ ${t('needsAttention')}`;
+    }
+    // Per-session/project notification override
+    if (miner.notification_override === 'mute') {
+        badge.textContent += ' · 🔇';
+    } else if (miner.notification_override === 'always') {
+        badge.textContent += ' · 🔔';
+    }
 
     const icon = document.createElement('div');
     icon.className = 'miner-icon';
@@ -517,16 +655,41 @@ function createMinerCard(miner) {
     const memory = document.createElement('span');
     memory.textContent = `${t('memory')}: ${(miner.memory / 1024 / 1024).toFixed(0)} MB`;
 
+    // What a working miner is doing: writing a response or running tools
+    if (miner.activity) {
+        const activity = document.createElement('span');
+        activity.className = 'miner-activity';
+        activity.textContent = miner.activity === 'writing' ? t('activityWriting') : t('activityRunningTools');
+        info.appendChild(activity);
+        info.appendChild(document.createElement('br'));
+    }
+
     info.appendChild(cpu);
     info.appendChild(document.createElement('br'));
     info.appendChild(memory);
 
+    // How long a miner has been idle ("resting for 3h" vs "resting for 30s")
+    if (miner.status !== 'working' && miner.idle_secs != null) {
+        const idle = document.createElement('span');
+        idle.textContent = `${t('idleFor')}: ${formatIdle(miner.idle_secs)}`;
+        info.appendChild(document.createElement('br'));
+        info.appendChild(idle);
+    }
+
+    // Energy impact is only sampled on macOS when enabled in config
+    if (miner.energy_impact != null) {
+        const energy = document.createElement('span');
+        energy.textContent = `${t('energy')}: ${miner.energy_impact.toFixed(1)}`;
+        info.appendChild(document.createElement('br'));
+        info.appendChild(energy);
+    }
+
     card.appendChild(badge);
     card.appendChild(icon);
     card.appendChild(info);
 
-    // Add kill button for zombie processes
-    if (miner.status === 'zombie' || !miner.has_terminal) {
+    // Add kill button for zombie processes (not for other users' sessions)
+    if (!miner.read_only && (miner.status === 'zombie' || !miner.has_terminal)) {
         const killBtn = document.createElement('button');
         killBtn.className = 'kill-button';
         killBtn.textContent = t('killProcess');
@@ -540,7 +703,9 @@ function createMinerCard(miner) {
             if (confirmed) {
                 console.log('[Kill Button] Confirmed, killing PID:', miner.pid);
                 try {
-                    await invoke('kill_miner', { pid: miner.pid });
+                    if (!await killMiner(miner.pid)) {
+                        return;
+                    }
                     console.log('[Kill Button] Successfully killed PID:', miner.pid);
 
                     // Show success message
@@ -580,7 +745,7 @@ function createMinerCard(miner) {
     return card;
 }
 
-// Update the UI with current miners
+// Fetch miners on demand (initial load and after user actions)
 async function updateMiners() {
     if (isLoading) {
         return;
@@ -591,108 +756,101 @@ async function updateMiners() {
     try {
         const miners = await invoke('get_miners');
         errorCount = 0; // Reset error count on success
+        await renderMiners(miners);
+    } catch (error) {
+        console.error('Failed to update miners:', error);
+        errorCount++;
 
-        // Counters
-        let workingCount = 0;
-        let restingCount = 0;
-        let zombieCount = 0;
-
-        // Update metaverse world with miners
-        if (metaverseWorld) {
-            metaverseWorld.updateMiners(miners, handleMinerClick);
-        }
-
-        // Process miners for notifications and counting
-        miners.forEach(miner => {
-            // Check for state changes (for notifications)
-            const prevMiner = previousMiners.get(miner.pid);
-
-            if (prevMiner) {
-                // Working -> Resting (task completed!)
-                if (prevMiner.status === 'working' && miner.status === 'resting') {
-                    console.log(`🎯 Hook Event Detected: PID ${miner.pid} → resting (Stop event)`);
-                    if (notificationsEnabled) {
-                        sendNotification({
-                            title: t('taskCompleted'),
-                            body: t('taskCompletedBody', { pid: miner.pid })
-                        });
-                    }
-                }
+        if (errorCount >= MAX_ERROR_COUNT) {
+            showError(t('errorFetchingProcesses'));
+        }
+    } finally {
+        isLoading = false;
+    }
+}
 
-                // Resting -> Working (task started!)
-                if (prevMiner.status === 'resting' && miner.status === 'working') {
-                    console.log(`🎯 Hook Event Detected: PID ${miner.pid} → working (UserPromptSubmit event)`);
-                }
+// Update the UI with a miner list (from get_miners or the miners-updated event)
+async function renderMiners(miners) {
+    // Counters
+    let workingCount = 0;
+    let restingCount = 0;
+    let zombieCount = 0;
 
-                // Normal -> Zombie (terminal closed)
-                if (prevMiner.has_terminal && !miner.has_terminal) {
-                    console.log(`⚠️ State Change: PID ${miner.pid} → zombie (terminal closed)`);
-                    if (notificationsEnabled) {
-                        sendNotification({
-                            title: t('zombieDetected'),
-                            body: t('zombieDetectedBody', { pid: miner.pid })
-                        });
-                    }
-                }
-            }
+    // Update metaverse world with miners
+    if (metaverseWorld) {
+        metaverseWorld.updateMiners(miners, handleMinerClick);
+    }
 
-            // New miner detected
-            if (!prevMiner && notificationsEnabled && miners.length > previousMiners.size) {
-                sendNotification({
-                    title: t('newMiner'),
-                    body: t('newMinerBody', { pid: miner.pid })
-                });
-            }
+    // Process miners for notifications and counting
+    miners.forEach(miner => {
+        // Check for state changes (for notifications)
+        const prevMiner = previousMiners.get(miner.pid);
 
-            // Count by type
-            if (!miner.has_terminal) {
-                zombieCount++;
-            } else if (miner.status === 'working') {
-                workingCount++;
-            } else {
-                restingCount++;
+        if (prevMiner) {
+            // Working -> Resting (task completed!)
+            if (prevMiner.status === 'working' && miner.status === 'resting') {
+                console.log(`🎯 Hook Event Detected: PID ${miner.pid} → resting (Stop event)`);
+                if (notificationsEnabled) {
+                    sendNotification({
+                        title: t('taskCompleted'),
+                        body: t('taskCompletedBody', { pid: miner.pid })
+                    });
+                }
             }
 
-            // Update previous state
-            previousMiners.set(miner.pid, miner);
-        });
+            // Resting -> Working (task started!)
+            if (prevMiner.status === 'resting' && miner.status === 'working') {
+                console.log(`🎯 Hook Event Detected: PID ${miner.pid} → working (UserPromptSubmit event)`);
+            }
 
-        // Remove deleted miners from previous state
-        const currentPids = new Set(miners.map(m => m.pid));
-        for (const pid of previousMiners.keys()) {
-            if (!currentPids.has(pid)) {
-                previousMiners.delete(pid);
+            // Normal -> Zombie (terminal closed)
+            if (prevMiner.has_terminal && !miner.has_terminal) {
+                console.log(`⚠️ State Change: PID ${miner.pid} → zombie (terminal closed)`);
+                if (notificationsEnabled) {
+                    sendNotification({
+                        title: t('zombieDetected'),
+                        body: t('zombieDetectedBody', { pid: miner.pid })
+                    });
+                }
             }
         }
 
-        // Update stats
-        document.getElementById('total-count').textContent = miners.length;
-        document.getElementById('working-count').textContent = workingCount;
-        document.getElementById('resting-count').textContent = restingCount;
-        document.getElementById('zombie-count').textContent = zombieCount;
-
-        // Update system tray with all counts
-        try {
-            await invoke('update_tray_menu', {
-                total: miners.length,
-                working: workingCount,
-                resting: restingCount,
-                zombie: zombieCount
+        // New miner detected
+        if (!prevMiner && notificationsEnabled && miners.length > previousMiners.size) {
+            sendNotification({
+                title: t('newMiner'),
+                body: t('newMinerBody', { pid: miner.pid })
             });
-        } catch (error) {
-            console.error('Failed to update tray menu:', error);
         }
 
-    } catch (error) {
-        console.error('Failed to update miners:', error);
-        errorCount++;
+        // Count by type
+        if (!miner.has_terminal) {
+            zombieCount++;
+        } else if (miner.status === 'working') {
+            workingCount++;
+        } else {
+            restingCount++;
+        }
 
-        if (errorCount >= MAX_ERROR_COUNT) {
-            showError(t('errorFetchingProcesses'));
+        // Update previous state
+        previousMiners.set(miner.pid, miner);
+    });
+
+    // Remove deleted miners from previous state
+    const currentPids = new Set(miners.map(m => m.pid));
+    for (const pid of previousMiners.keys()) {
+        if (!currentPids.has(pid)) {
+            previousMiners.delete(pid);
         }
-    } finally {
-        isLoading = false;
     }
+
+    // Update stats
+    document.getElementById('total-count').textContent = miners.length;
+    document.getElementById('working-count').textContent = workingCount;
+    document.getElementById('resting-count').textContent = restingCount;
+    document.getElementById('zombie-count').textContent = zombieCount;
+    // Blocked on a permission prompt (also counted under their status)
+    document.getElementById('attention-count').textContent = miners.filter(m => m.needs_attention).length;
 }
 
 // Setup settings modal
@@ -703,6 +861,13 @@ function setupSettings() {
     const closeBtn = document.getElementById('close-modal');
     const notificationsToggle = document.getElementById('notifications-toggle');
     const languageSelect = document.getElementById('language-select');
+    const autostartToggle = document.getElementById('autostart-toggle');
+    const closeToTrayToggle = document.getElementById('close-to-tray-toggle');
+    const overlayToggle = document.getElementById('overlay-toggle');
+    const overlayClickThroughToggle = document.getElementById('overlay-click-through-toggle');
+    const hooksToggle = document.getElementById('hooks-toggle');
+    const doctorBtn = document.getElementById('doctor-btn');
+    const updateBtn = document.getElementById('update-btn');
 
     // Test notification button
     testNotificationBtn.addEventListener('click', async () => {
@@ -727,6 +892,21 @@ function setupSettings() {
         // Load current settings
         notificationsToggle.checked = notificationsEnabled;
         languageSelect.value = getCurrentLanguage();
+        invoke('get_autostart')
+            .then(enabled => { autostartToggle.checked = enabled; })
+            .catch(error => console.error('Failed to read autostart:', error));
+        invoke('get_window_state')
+            .then(state => { closeToTrayToggle.checked = state.close_to_tray; })
+            .catch(error => console.error('Failed to read window state:', error));
+        invoke('get_overlay_state')
+            .then(state => {
+                overlayToggle.checked = state.visible;
+                overlayClickThroughToggle.checked = state.click_through;
+            })
+            .catch(error => console.error('Failed to read overlay state:', error));
+        invoke('get_hooks_enabled')
+            .then(enabled => { hooksToggle.checked = enabled; })
+            .catch(error => console.error('Failed to read hooks setting:', error));
     });
 
     // Close modal
@@ -751,12 +931,179 @@ function setupSettings() {
         localStorage.setItem('notificationsEnabled', notificationsEnabled.toString());
     });
 
+    // Handle autostart toggle
+    autostartToggle.addEventListener('change', async (e) => {
+        try {
+            await invoke('set_autostart', { enabled: e.target.checked });
+        } catch (error) {
+            e.target.checked = !e.target.checked;
+            showError(`${t('launchAtLogin')}: ${error}`);
+        }
+    });
+
+    // Handle close-to-tray toggle
+    closeToTrayToggle.addEventListener('change', async (e) => {
+        try {
+            await invoke('set_window_behavior', { closeToTray: e.target.checked });
+        } catch (error) {
+            e.target.checked = !e.target.checked;
+            showError(`${t('closeToTray')}: ${error}`);
+        }
+    });
+
+    // Handle overlay toggle (the tray can toggle it too, so use the returned state)
+    overlayToggle.addEventListener('change', async (e) => {
+        try {
+            e.target.checked = await invoke('toggle_overlay');
+        } catch (error) {
+            e.target.checked = !e.target.checked;
+            showError(`${t('statusOverlay')}: ${error}`);
+        }
+    });
+
+    // Handle overlay click-through toggle
+    overlayClickThroughToggle.addEventListener('change', async (e) => {
+        try {
+            await invoke('set_overlay_click_through', { enabled: e.target.checked });
+        } catch (error) {
+            e.target.checked = !e.target.checked;
+            showError(`${t('overlayClickThrough')}: ${error}`);
+        }
+    });
+
+    // Handle hooks toggle (adds/removes them in settings.json)
+    hooksToggle.addEventListener('change', async (e) => {
+        try {
+            await invoke('set_hooks_enabled', { enabled: e.target.checked });
+        } catch (error) {
+            e.target.checked = !e.target.checked;
+            showError(`${t('claudeHooks')}: ${error}`);
+        }
+    });
+
+    // Run diagnostics
+    doctorBtn.addEventListener('click', () => runDoctor());
+
+    // Check for updates
+    updateBtn.addEventListener('click', () => checkForUpdates());
+
     // Handle language change
     languageSelect.addEventListener('change', (e) => {
         setLanguage(e.target.value);
     });
 }
 
+// Run the environment checks and render them with their fix-it buttons
+async function runDoctor() {
+    const results = document.getElementById('doctor-results');
+    results.textContent = '…';
+
+    let report;
+    try {
+        report = await invoke('run_doctor');
+    } catch (error) {
+        results.textContent = `${error}`;
+        return;
+    }
+
+    const icons = { ok: '✅', warning: '⚠️', error: '❌' };
+    results.textContent = '';
+    for (const check of report.checks) {
+        const row = document.createElement('div');
+        row.className = `doctor-check ${check.status}`;
+
+        const text = document.createElement('span');
+        text.textContent = `${icons[check.status]} ${check.label}: ${check.detail}`;
+        row.appendChild(text);
+
+        if (check.fix) {
+            const fixBtn = document.createElement('button');
+            fixBtn.className = 'doctor-fix';
+            fixBtn.textContent = check.fix.label;
+            fixBtn.addEventListener('click', async () => {
+                try {
+                    await invoke('run_doctor_fix', { actionId: check.fix.id });
+                } catch (error) {
+                    showError(`${check.fix.label}: ${error}`);
+                }
+                await runDoctor();
+            });
+            row.appendChild(fixBtn);
+        }
+        results.appendChild(row);
+    }
+}
+
+// Check GitHub for a newer release and offer to download it
+async function checkForUpdates() {
+    const status = document.getElementById('update-status');
+    status.textContent = '…';
+
+    const staged = await invoke('get_staged_update').catch(() => null);
+    if (staged) {
+        showUpdateReady(staged);
+        return;
+    }
+
+    let info;
+    try {
+        info = await invoke('check_for_updates');
+    } catch (error) {
+        status.textContent = `${error}`;
+        return;
+    }
+
+    if (!info.available) {
+        status.textContent = `✅ ${info.current_version} is the latest version`;
+        return;
+    }
+    if (!info.asset) {
+        status.textContent = `${info.latest_version} is available, but has no download for this platform`;
+        return;
+    }
+
+    status.textContent = `${info.latest_version} is available (current: ${info.current_version})`;
+    const downloadBtn = document.createElement('button');
+    downloadBtn.className = 'doctor-fix';
+    downloadBtn.textContent = 'Download';
+    downloadBtn.addEventListener('click', async () => {
+        try {
+            await invoke('install_update');
+            status.textContent = 'Downloading…';
+        } catch (error) {
+            showError(`Update: ${error}`);
+        }
+    });
+    status.appendChild(downloadBtn);
+}
+
+// Download progress ("update-progress")
+function showUpdateProgress({ downloaded, total }) {
+    const status = document.getElementById('update-status');
+    const mb = (bytes) => (bytes / 1048576).toFixed(1);
+    status.textContent = total > 0
+        ? `Downloading… ${Math.min(100, Math.round(downloaded / total * 100))}% (${mb(downloaded)} / ${mb(total)} MB)`
+        : `Downloading… ${mb(downloaded)} MB`;
+}
+
+// Verified update staged ("update-ready")
+function showUpdateReady(staged) {
+    const status = document.getElementById('update-status');
+    status.textContent = `${staged.version} is ready to install`;
+
+    const restartBtn = document.createElement('button');
+    restartBtn.className = 'doctor-fix';
+    restartBtn.textContent = 'Restart to Update';
+    restartBtn.addEventListener('click', async () => {
+        try {
+            await invoke('restart_to_update');
+        } catch (error) {
+            showError(`Update: ${error}`);
+        }
+    });
+    status.appendChild(restartBtn);
+}
+
 // Handle miner click in metaverse world
 async function handleMinerClick(minerEntity) {
     const pid = minerEntity.pid;
@@ -767,11 +1114,13 @@ async function handleMinerClick(minerEntity) {
     if (!miner) return;
 
     // Check if it's a zombie (either no terminal or zombie status)
-    if (!miner.has_terminal || miner.status === 'zombie') {
+    if (!miner.read_only && (!miner.has_terminal || miner.status === 'zombie')) {
         const confirmMsg = `${t('confirmKill')}${pid}?`;
         if (confirm(confirmMsg)) {
             try {
-                await invoke('kill_miner', { pid });
+                if (!await killMiner(pid)) {
+                    return;
+                }
                 if (notificationsEnabled) {
                     sendNotification({
                         title: t('title'),
@@ -817,43 +1166,109 @@ async function handleMinerClick(minerEntity) {
 let refreshInterval = null;
 const REFRESH_INTERVAL_MS = 3000; // 3 seconds
 
-function restartRefreshInterval() {
+async function restartRefreshInterval() {
     // Clear existing interval if any
     if (refreshInterval) {
         clearInterval(refreshInterval);
+        refreshInterval = null;
     }
 
-    // Set up new interval to refresh every 3 seconds
-    refreshInterval = setInterval(() => {
-        updateMiners();
-    }, REFRESH_INTERVAL_MS);
+    // Ask the backend to push "miners-updated" every 3 seconds (and on change)
+    try {
+        const intervalMs = await invoke('subscribe_miners', { intervalMs: REFRESH_INTERVAL_MS });
+        console.log(`✅ Subscribed to miner updates (${intervalMs / 1000} second interval)`);
+    } catch (error) {
+        // Older backend: fall back to polling
+        console.error('Failed to subscribe to miner updates, polling instead:', error);
+        refreshInterval = setInterval(() => {
+            updateMiners();
+        }, REFRESH_INTERVAL_MS);
+    }
+}
 
-    console.log(`✅ Auto-refresh started (${REFRESH_INTERVAL_MS / 1000} second interval)`);
+// Fetch the full state of every session (at startup, or when a diff can't be applied)
+async function resyncSessionStates() {
+    try {
+        const sessions = await invoke('get_session_states');
+        sessionStates = new Map(sessions.map(session => [session.session_id, session]));
+    } catch (error) {
+        console.error('Failed to load session states:', error);
+    }
+}
+
+// Merge a session-diff into the known session state
+function applySessionDiff(diff) {
+    const session = sessionStates.get(diff.session_id);
+    if (!session) {
+        // Missed the session's full state: start over from the backend's
+        resyncSessionStates();
+        return;
+    }
+    if (diff.changes.current_status && diff.changes.current_status !== session.current_status) {
+        console.log(`🔄 Session ${diff.session_id.slice(0, 8)}: ${session.current_status} → ${diff.changes.current_status}`);
+    }
+    Object.assign(session, diff.changes);
 }
 
 // Setup Tauri event listeners for real-time updates
 async function setupTauriEventListeners() {
     console.log('🎧 Setting up Tauri event listeners...');
 
-    // Listen for session-created events
+    // Listen for pushed miner lists (replaces polling)
+    await listen('miners-updated', (event) => {
+        renderMiners(event.payload.miners);
+    });
+
+    // Listen for session-created events (full state)
     await listen('session-created', (event) => {
         console.log('🌟 New session created:', event.payload);
-        // Immediately update UI
-        updateMiners();
+        const { event: _name, schema_version: _version, ...session } = event.payload;
+        sessionStates.set(session.session_id, session);
+        // UI refresh arrives via miners-updated
     });
 
-    // Listen for session-status-changed events
-    await listen('session-status-changed', (event) => {
-        console.log('🔄 Session status changed:', event.payload);
-        // Immediately update UI
-        updateMiners();
+    // Listen for session-diff events (only the fields that changed)
+    await listen('session-diff', (event) => {
+        applySessionDiff(event.payload);
+        // UI refresh arrives via miners-updated
     });
 
     // Listen for session-terminated events
     await listen('session-terminated', (event) => {
         console.log('💀 Session terminated:', event.payload);
-        // Immediately update UI
-        updateMiners();
+        sessionStates.delete(event.payload.session_id);
+        // UI refresh arrives via miners-updated
+    });
+
+    // Listen for notifications that offer actions (focus terminal / kill)
+    await listen('notification-actions', (event) => {
+        if (event.payload.actions.length > 0) {
+            showActionToast(event.payload);
+        }
+    });
+
+    // Listen for claudeminer:// links opened while the app runs
+    await listen('deep-link', (event) => {
+        handleDeepLink(event.payload);
+    });
+
+    // Listen for panics in backend threads (report saved; see get_last_crash)
+    await listen('app-crash', (event) => {
+        showError(`Internal error in ${event.payload.thread}: ${event.payload.message}`);
+    });
+
+    // Listen for background update download progress
+    await listen('update-progress', (event) => {
+        showUpdateProgress(event.payload);
+    });
+
+    await listen('update-ready', (event) => {
+        showUpdateReady(event.payload);
+    });
+
+    await listen('update-failed', (event) => {
+        document.getElementById('update-status').textContent = '';
+        showError(`Update: ${event.payload.error}`);
     });
 
     console.log('✅ Tauri event listeners setup complete');
@@ -886,14 +1301,21 @@ async function init() {
 
     // Setup Tauri event listeners for real-time updates
     await setupTauriEventListeners();
+    await resyncSessionStates();
 
     await updateMiners();
 
     // Setup settings UI
     setupSettings();
 
-    // Auto-refresh (backup polling for stability)
-    restartRefreshInterval();
+    // Auto-refresh (backend push, polling fallback)
+    await restartRefreshInterval();
+
+    // claudeminer:// link the app was launched with
+    const pendingLink = await invoke('take_pending_deep_link').catch(() => null);
+    if (pendingLink) {
+        await handleDeepLink(pendingLink);
+    }
 
     console.log('✅ ClaudeMiner Ready');
 }
@@ -24,13 +24,27 @@ const translations = {
         // Miner card
         cpu: "CPU",
         memory: "MEM",
+        energy: "Energy",
+        activityWriting: "✍️ Writing",
+        activityRunningTools: "🔧 Running tools",
+        needsAttention: "Needs approval",
+        idleFor: "Idle",
+        focusTerminal: "Focus terminal",
+        killNow: "Kill now",
+        sessionNotFound: "Session {id} not found",
         killProcess: "Kill Process",
         confirmKill: "Kill process #{pid}?",
+        confirmKillBusy: "Process #{pid} is in the middle of a task. Kill it anyway?",
         processCopied: "✓ Copied!",
 
         // Settings
         settings: "Settings",
         enableNotifications: "Enable Notifications",
+        launchAtLogin: "Launch at Login",
+        closeToTray: "Keep Running in Tray",
+        statusOverlay: "Status Overlay",
+        overlayClickThrough: "Overlay Click-Through",
+        claudeHooks: "Claude Code Hooks",
         about: "About",
         version: "ClaudeMiner v1.0.0",
         language: "Language",
@@ -79,13 +93,27 @@ const translations = {
         // Miner card
         cpu: "CPU",
         memory: "메모리",
+        energy: "에너지",
+        activityWriting: "✍️ 작성 중",
+        activityRunningTools: "🔧 도구 실행 중",
+        needsAttention: "승인 필요",
+        idleFor: "유휴",
+        focusTerminal: "터미널로 이동",
+        killNow: "지금 종료",
+        sessionNotFound: "세션 {id}을(를) 찾을 수 없습니다",
         killProcess: "프로세스 종료",
         confirmKill: "프로세스 #{pid}을(를) 종료하시겠습니까?",
+        confirmKillBusy: "프로세스 #{pid}이(가) 작업 중입니다. 그래도 종료하시겠습니까?",
         processCopied: "✓ 복사됨!",
 
         // Settings
         settings: "설정",
         enableNotifications: "알림 활성화",
+        launchAtLogin: "로그인 시 실행",
+        closeToTray: "트레이에서 계속 실행",
+        statusOverlay: "상태 오버레이",
+        overlayClickThrough: "오버레이 클릭 통과",
+        claudeHooks: "Claude Code 훅",
         about: "정보",
         version: "ClaudeMiner v1.0.0",
         language: "언어",
@@ -134,13 +162,27 @@ const translations = {
         // Miner card
         cpu: "CPU",
         memory: "メモリ",
+        energy: "エネルギー",
+        activityWriting: "✍️ 執筆中",
+        activityRunningTools: "🔧 ツール実行中",
+        needsAttention: "承認待ち",
+        idleFor: "アイドル",
+        focusTerminal: "ターミナルを表示",
+        killNow: "今すぐ終了",
+        sessionNotFound: "セッション {id} が見つかりません",
         killProcess: "プロセス終了",
         confirmKill: "プロセス #{pid}を終了しますか？",
+        confirmKillBusy: "プロセス #{pid}は作業中です。それでも終了しますか？",
         processCopied: "✓ コピーしました！",
 
         // Settings
         settings: "設定",
         enableNotifications: "通知を有効化",
+        launchAtLogin: "ログイン時に起動",
+        closeToTray: "トレイで実行し続ける",
+        statusOverlay: "ステータスオーバーレイ",
+        overlayClickThrough: "オーバーレイをクリック透過",
+        claudeHooks: "Claude Code フック",
         about: "情報",
         version: "ClaudeMiner v1.0.0",
         language: "言語",
@@ -189,13 +231,27 @@ const translations = {
         // Miner card
         cpu: "CPU",
         memory: "MEM",
+        energy: "Energía",
+        activityWriting: "✍️ Escribiendo",
+        activityRunningTools: "🔧 Ejecutando herramientas",
+        needsAttention: "Requiere aprobación",
+        idleFor: "Inactivo",
+        focusTerminal: "Ir a la terminal",
+        killNow: "Terminar ahora",
+        sessionNotFound: "Sesión {id} no encontrada",
         killProcess: "Terminar Proceso",
         confirmKill: "¿Terminar proceso #{pid}?",
+        confirmKillBusy: "El proceso #{pid} está en medio de una tarea. ¿Terminarlo de todos modos?",
         processCopied: "✓ ¡Copiado!",
 
         // Settings
         settings: "Configuración",
         enableNotifications: "Activar Notificaciones",
+        launchAtLogin: "Iniciar al arrancar sesión",
+        closeToTray: "Seguir en la bandeja",
+        statusOverlay: "Superposición de estado",
+        overlayClickThrough: "Clics a través de la superposición",
+        claudeHooks: "Hooks de Claude Code",
         about: "Acerca de",
         version: "ClaudeMiner v1.0.0",
         language: "Idioma",
@@ -287,6 +343,7 @@ function updateAllUIText() {
         if (stats[1]) stats[1].textContent = '⛏️ Working';
         if (stats[2]) stats[2].textContent = '😴 Resting';
         if (stats[3]) stats[3].textContent = '🧟 Zombie';
+        if (stats[4]) stats[4].textContent = `✋ ${translate('needsAttention')}`;
 
         // Settings modal - safe access
         const notifLabel = document.querySelectorAll('.setting-item label')[0];
@@ -294,6 +351,21 @@ function updateAllUIText() {
             notifLabel.childNodes[2].textContent = ` ${translate('enableNotifications')}`;
         }
 
+        const autostartLabel = document.querySelector('label[for="autostart-toggle"] span');
+        if (autostartLabel) autostartLabel.textContent = translate('launchAtLogin');
+
+        const closeToTrayLabel = document.querySelector('label[for="close-to-tray-toggle"] span');
+        if (closeToTrayLabel) closeToTrayLabel.textContent = translate('closeToTray');
+
+        const overlayLabel = document.querySelector('label[for="overlay-toggle"] span');
+        if (overlayLabel) overlayLabel.textContent = translate('statusOverlay');
+
+        const clickThroughLabel = document.querySelector('label[for="overlay-click-through-toggle"] span');
+        if (clickThroughLabel) clickThroughLabel.textContent = translate('overlayClickThrough');
+
+        const hooksLabel = document.querySelector('label[for="hooks-toggle"] span');
+        if (hooksLabel) hooksLabel.textContent = translate('claudeHooks');
+
         const langLabel = document.querySelector('label[for="language-select"]');
         if (langLabel) langLabel.textContent = translate('language');
 
@@ -0,0 +1,299 @@
+// Generated by src-tauri/build.rs from src-tauri/src/event/schema.rs. Do not edit.
+
+/** Every payload also carries the schema version it was emitted with */
+export type EventPayload = { schema_version: number } & (
+    /** A session was detected */
+    | ({ event: "session-created" } & SessionState)
+    /** A session ended (process exited, killed or removed) */
+    | ({ event: "session-terminated" } & SessionState)
+    /**
+     * Only the session fields that changed since its last event (session-created or
+     * get_session_states has the full state to apply it to)
+     */
+    | {
+        event: "session-diff";
+        session_id: string;
+        changes: Record<string, unknown>;
+    }
+    /** Blocked on a permission prompt, or the prompt was answered */
+    | {
+        event: "session-needs-attention";
+        session_id: string;
+        pid: number;
+        status: SessionStatus;
+        needs_attention: boolean;
+        started_at: number;
+        status_since: number;
+        total_working_seconds: number;
+    }
+    /** The full miner list (same as get_miners) */
+    | {
+        event: "miners-updated";
+        miners: Miner[];
+    }
+    /** New lines from a tailed session log */
+    | ({ event: "session-log-lines" } & LogLines)
+    /** Focus mode started, ended or queued a completion */
+    | ({ event: "focus-changed" } & FocusStatus)
+    /** A notification with buttons, rendered as an in-app toast */
+    | {
+        event: "notification-actions";
+        kind: NotificationKind;
+        session_id: string | null;
+        pid: number | null;
+        title: string;
+        body: string;
+        actions: NotificationAction[];
+    }
+    /** claudeminer:// link opened while the app runs */
+    | ({ event: "deep-link" } & DeepLink)
+    /** A backend thread panicked (report saved, see get_last_crash) */
+    | ({ event: "app-crash" } & CrashReport)
+    | ({ event: "update-progress" } & UpdateProgress)
+    /** Update downloaded and verified; restart_to_update installs it */
+    | ({ event: "update-ready" } & StagedUpdate)
+    | {
+        event: "update-failed";
+        error: string;
+    }
+);
+
+/** Tauri event names */
+export type EventName = EventPayload["event"];
+
+/** A verified bundle waiting for restart_to_update */
+export interface StagedUpdate {
+    version: string;
+    path: string;
+}
+
+/** Payload of "update-progress" */
+export interface UpdateProgress {
+    downloaded: number;
+    total: number;
+}
+
+export interface CrashReport {
+    thread: string;
+    message: string;
+    location: string | null;
+    backtrace: string;
+    timestamp: string;
+    version: string;
+}
+
+export type DeepLink =
+    | {
+        action: "session";
+        session_id: string;
+    }
+    | {
+        action: "kill";
+        pid: number;
+    };
+
+/**
+ * Button offered with a notification, handled by the frontend with existing commands
+ * ("focus" -> focus_session_terminal, "kill" -> kill_miner)
+ */
+export interface NotificationAction {
+    id: string;
+    label: string;
+}
+
+/** Notification types (all but Rule/HooksRepaired/HooksStale/FocusDigest/Test can be toggled in config.json "notifications") */
+export type NotificationKind =
+    | "task_completion"
+    | "session_created"
+    | "zombie_killed"
+    | "zombie_detected"
+    | "approval_pending"
+    | "needs_attention"
+    | "long_task"
+    | "milestone"
+    | "rule"
+    | "hooks_repaired"
+    | "hooks_stale"
+    | "focus_digest"
+    | "test";
+
+/** Current focus state (get_focus_status, "focus-changed") */
+export interface FocusStatus {
+    active: boolean;
+    started_at: number | null;
+    ends_at: number | null;
+    queued: number;
+}
+
+/** Payload of "session-log-lines" */
+export interface LogLines {
+    session_id: string;
+    source: LogSource;
+    lines: string[];
+}
+
+/** Which of a session's files to read */
+export type LogSource =
+    | "debug"
+    | "transcript";
+
+/** Represents a Claude Code process (miner) */
+export interface Miner {
+    session_id: string;
+    pid: number;
+    cpu_usage: number;
+    energy_impact: number | null;
+    memory: number;
+    status: string;
+    has_terminal: boolean;
+    name: string;
+    pinned: boolean;
+    archived: boolean;
+    project_path: string | null;
+    profile: string | null;
+    kind: string;
+    read_only: boolean;
+    container: ContainerInfo | null;
+    activity: string | null;
+    needs_attention: boolean;
+    idle_secs: number | null;
+    started_at: number;
+    status_since: number;
+    total_working_seconds: number;
+    notification_override: NotificationOverride | null;
+}
+
+/**
+ * Mute a project's notifications, or deliver them regardless of the per-event
+ * toggles, quiet hours and focus mode
+ */
+export type NotificationOverride =
+    | "mute"
+    | "always";
+
+/** Container or WSL distro hosting a miner process */
+export interface ContainerInfo {
+    runtime: ContainerRuntime;
+    name: string;
+}
+
+export type ContainerRuntime =
+    | "docker"
+    | "wsl";
+
+/** Status of a session as shown to the user (serialized as "working", "resting", ...) */
+export type SessionStatus =
+    | "working"
+    | "resting"
+    | "zombie"
+    | "waiting_approval"
+    | "detached"
+    | "unknown";
+
+/** Session state aggregated from all events */
+export interface SessionState {
+    pid: number;
+    session_id: string;
+    session_type: SessionType;
+    last_log_event: LogEvent | null;
+    last_cpu_event: CpuEvent | null;
+    last_network_event: NetworkEvent | null;
+    current_status: SessionStatus;
+    has_terminal: boolean;
+    last_update: number;
+    last_active_timestamp: number | null;
+    pinned: boolean;
+    ignored: boolean;
+    archived: boolean;
+    notification_override: NotificationOverride | null;
+    project_path: string | null;
+    tty: string | null;
+    multiplexer: MultiplexerInfo | null;
+    token_usage: TokenUsage;
+    profile: string | null;
+    kind: string;
+    container: ContainerInfo | null;
+    last_hook_timestamp: number;
+    last_hook_at: number;
+    hooks_stale: boolean;
+    needs_attention: boolean;
+    generation: number;
+    started_at: number;
+    status_since: number;
+    total_working_seconds: number;
+}
+
+/** Cumulative token usage seen in a session's transcript */
+export interface TokenUsage {
+    input_tokens: number;
+    output_tokens: number;
+}
+
+/** tmux/screen session hosting a Claude process */
+export interface MultiplexerInfo {
+    kind: MultiplexerKind;
+    session_name: string;
+    window_name: string | null;
+    attached: boolean;
+}
+
+export type MultiplexerKind =
+    | "tmux"
+    | "screen";
+
+/** Network activity of a miner process */
+export interface NetworkEvent {
+    pid: number;
+    timestamp: number;
+    connections: number;
+    bytes: NetBytes | null;
+}
+
+/** Cumulative network bytes of a process */
+export interface NetBytes {
+    bytes_in: number;
+    bytes_out: number;
+}
+
+/** CPU usage change event */
+export interface CpuEvent {
+    pid: number;
+    timestamp: number;
+    cpu_percent: number;
+    kind: string;
+    uid: number | null;
+    container: ContainerInfo | null;
+}
+
+/** Log file change event */
+export interface LogEvent {
+    session_id: string;
+    pid: number | null;
+    timestamp: number;
+    state: WorkingState;
+    has_approval_pending: boolean;
+    file_mtime: number;
+    transcript: TranscriptStats | null;
+    profile: string | null;
+}
+
+/** Message-level details from new transcript records (~/.claude/projects) */
+export interface TranscriptStats {
+    last_role: string | null;
+    tool_calls: string[];
+    input_tokens: number;
+    output_tokens: number;
+    cwd: string | null;
+}
+
+/** Working state of a Claude Code session */
+export type WorkingState =
+    | "ActivelyWorking"
+    | "GeneratingResponse"
+    | "Idle"
+    | "Unknown";
+
+/** Session type: Legacy (pre-app start) or Hook (post-app start) */
+export type SessionType =
+    | "Legacy"
+    | "Hook";